@@ -0,0 +1,7 @@
+use soroban_sdk::contracterror;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    TokenNotFound = 1,
+}
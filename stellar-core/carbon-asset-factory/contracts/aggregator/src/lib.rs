@@ -0,0 +1,110 @@
+#![no_std]
+
+pub mod errors;
+pub mod types;
+
+use soroban_sdk::{
+    contract, contractimpl, Address, Env, IntoVal, InvokeError, Symbol, TryFromVal, Val, Vec,
+};
+
+use crate::errors::ContractError;
+use crate::types::{AssetStatus, AuditEvent, CarbonAssetMetadata, MethodologyMeta, TokenOverview};
+
+// Number of most recent audit events included in an overview.
+pub const RECENT_AUDIT_EVENTS: u32 = 10;
+
+// ========================================================================
+// Contract
+// ========================================================================
+
+// Read-only fan-out over the core contracts. Holds no state; every address
+// is passed per call.
+#[contract]
+pub struct Aggregator;
+
+#[contractimpl]
+impl Aggregator {
+    pub fn token_overview(
+        env: Env,
+        carbon_asset: Address,
+        methodology_lib: Address,
+        audit: Address,
+        token_id: u32,
+    ) -> Result<TokenOverview, ContractError> {
+        let metadata: CarbonAssetMetadata =
+            Self::try_call(&env, &carbon_asset, "get_metadata", (token_id,))
+                .ok_or(ContractError::TokenNotFound)?;
+        // Burned credits keep their metadata and status but have no owner
+        let owner: Option<Address> = Self::try_call(&env, &carbon_asset, "owner_of", (token_id,));
+        let status: AssetStatus = Self::call(&env, &carbon_asset, "get_status", (token_id,));
+        let quality_score: i128 = Self::call(&env, &carbon_asset, "get_quality_score", (token_id,));
+
+        let methodology_id = metadata.methodology_id;
+        let methodology: Option<MethodologyMeta> = Self::try_call(
+            &env,
+            &methodology_lib,
+            "get_methodology_meta",
+            (methodology_id,),
+        );
+        let methodology_valid: bool = Self::call(
+            &env,
+            &methodology_lib,
+            "is_valid_methodology",
+            (methodology_id,),
+        );
+        let methodology_deprecated: bool =
+            Self::call(&env, &methodology_lib, "is_deprecated", (methodology_id,));
+
+        let events: Vec<AuditEvent> = Self::call(
+            &env,
+            &audit,
+            "get_events_by_entity",
+            (metadata.project_id.clone(),),
+        );
+        let audit_event_count = events.len();
+        let recent_audit_events =
+            events.slice(audit_event_count.saturating_sub(RECENT_AUDIT_EVENTS)..);
+
+        Ok(TokenOverview {
+            token_id,
+            owner,
+            status,
+            metadata,
+            quality_score,
+            methodology_name: methodology.as_ref().map(|meta| meta.name.clone()),
+            methodology_version: methodology.map(|meta| meta.version),
+            methodology_valid,
+            methodology_deprecated,
+            audit_event_count,
+            recent_audit_events,
+        })
+    }
+}
+
+impl Aggregator {
+    fn call<T: TryFromVal<Env, Val>>(
+        env: &Env,
+        contract: &Address,
+        function: &str,
+        args: impl IntoVal<Env, Vec<Val>>,
+    ) -> T {
+        env.invoke_contract(contract, &Symbol::new(env, function), args.into_val(env))
+    }
+
+    // None if the call fails, e.g. with a "not found" contract error.
+    fn try_call<T: TryFromVal<Env, Val>>(
+        env: &Env,
+        contract: &Address,
+        function: &str,
+        args: impl IntoVal<Env, Vec<Val>>,
+    ) -> Option<T> {
+        match env.try_invoke_contract::<T, InvokeError>(
+            contract,
+            &Symbol::new(env, function),
+            args.into_val(env),
+        ) {
+            Ok(Ok(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+
+/// Mirror of the CarbonAsset status returned by `get_status`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AssetStatus {
+    Issued,
+    Listed,
+    Locked,
+    Retired,
+    Invalidated,
+}
+
+/// Mirror of the CarbonAsset metadata returned by `get_metadata`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CarbonAssetMetadata {
+    pub project_id: String,
+    pub vintage_year: u64,
+    pub methodology_id: u32,
+    pub geo_hash: BytesN<32>,
+}
+
+/// Mirror of the Methodology Library metadata returned by
+/// `get_methodology_meta`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MethodologyMeta {
+    pub name: String,
+    pub version: String,
+    pub registry: String,
+    pub registry_link: String,
+    pub issuing_authority: Address,
+    pub ipfs_cid: Option<String>,
+}
+
+/// Mirror of the Audit Trail event returned by `get_events_by_entity`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEvent {
+    pub event_id: BytesN<32>,
+    pub timestamp: u64,
+    pub event_type: String,
+    pub emitting_contract: Address,
+    pub primary_entity_id: String,
+    pub secondary_entity_id: Option<String>,
+    pub event_data: String,
+    pub tx_hash: BytesN<32>,
+    pub seq: u64,
+    pub ledger_seq: u32,
+}
+
+// Everything a front-end shows for one credit, read in a single call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenOverview {
+    pub token_id: u32,
+    // None once the credit has been burned
+    pub owner: Option<Address>,
+    pub status: AssetStatus,
+    pub metadata: CarbonAssetMetadata,
+    pub quality_score: i128,
+    // None if the Methodology Library does not know the methodology
+    pub methodology_name: Option<String>,
+    pub methodology_version: Option<String>,
+    pub methodology_valid: bool,
+    pub methodology_deprecated: bool,
+    // Audit history is indexed by project; these are the project's events
+    pub audit_event_count: u32,
+    pub recent_audit_events: Vec<AuditEvent>,
+}
@@ -0,0 +1,299 @@
+#![cfg(test)]
+
+use aggregator::errors::ContractError;
+use aggregator::types::{AssetStatus, AuditEvent, CarbonAssetMetadata, MethodologyMeta};
+use aggregator::{Aggregator, AggregatorClient, RECENT_AUDIT_EVENTS};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Vec,
+};
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MockError {
+    NotFound = 1,
+}
+
+#[contracttype]
+enum MockKey {
+    Owner(u32),
+    Metadata(u32),
+    Status(u32),
+    QualityScore(u32),
+    Methodology(u32),
+    Authority,
+    Deprecated(u32),
+    Events(String),
+}
+
+/// Stand-in for the Carbon Asset; the test stores each token's state
+/// directly. A token without an owner reads as burned.
+#[contract]
+pub struct MockCarbonAsset;
+
+#[contractimpl]
+impl MockCarbonAsset {
+    pub fn set_token(
+        env: Env,
+        token_id: u32,
+        owner: Option<Address>,
+        metadata: CarbonAssetMetadata,
+        status: AssetStatus,
+        quality_score: i128,
+    ) {
+        let storage = env.storage().persistent();
+        match owner {
+            Some(owner) => storage.set(&MockKey::Owner(token_id), &owner),
+            None => storage.remove(&MockKey::Owner(token_id)),
+        }
+        storage.set(&MockKey::Metadata(token_id), &metadata);
+        storage.set(&MockKey::Status(token_id), &status);
+        storage.set(&MockKey::QualityScore(token_id), &quality_score);
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, MockError> {
+        let key = MockKey::Owner(token_id);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(MockError::NotFound)
+    }
+
+    pub fn get_metadata(env: Env, token_id: u32) -> Result<CarbonAssetMetadata, MockError> {
+        let key = MockKey::Metadata(token_id);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(MockError::NotFound)
+    }
+
+    pub fn get_status(env: Env, token_id: u32) -> AssetStatus {
+        let key = MockKey::Status(token_id);
+        env.storage().persistent().get(&key).unwrap()
+    }
+
+    pub fn get_quality_score(env: Env, token_id: u32) -> i128 {
+        let key = MockKey::QualityScore(token_id);
+        env.storage().persistent().get(&key).unwrap()
+    }
+}
+
+/// Stand-in for the Methodology Library. A methodology is valid while its
+/// issuing authority is the one set with `set_authority`.
+#[contract]
+pub struct MockMethodologyLibrary;
+
+#[contractimpl]
+impl MockMethodologyLibrary {
+    pub fn set_methodology(env: Env, token_id: u32, meta: MethodologyMeta) {
+        let key = MockKey::Methodology(token_id);
+        env.storage().persistent().set(&key, &meta);
+    }
+
+    pub fn set_authority(env: Env, authority: Address) {
+        env.storage()
+            .persistent()
+            .set(&MockKey::Authority, &authority);
+    }
+
+    pub fn deprecate(env: Env, token_id: u32) {
+        let key = MockKey::Deprecated(token_id);
+        env.storage().persistent().set(&key, &true);
+    }
+
+    pub fn get_methodology_meta(env: Env, token_id: u32) -> Result<MethodologyMeta, MockError> {
+        let key = MockKey::Methodology(token_id);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(MockError::NotFound)
+    }
+
+    pub fn is_valid_methodology(env: Env, token_id: u32) -> bool {
+        let authority: Option<Address> = env.storage().persistent().get(&MockKey::Authority);
+        match Self::get_methodology_meta(env, token_id) {
+            Ok(meta) => authority == Some(meta.issuing_authority),
+            Err(_) => false,
+        }
+    }
+
+    pub fn is_deprecated(env: Env, token_id: u32) -> bool {
+        let key = MockKey::Deprecated(token_id);
+        env.storage().persistent().has(&key)
+    }
+}
+
+/// Stand-in for the Audit Trail: events are appended per entity with
+/// consecutive sequence numbers.
+#[contract]
+pub struct MockAuditTrail;
+
+#[contractimpl]
+impl MockAuditTrail {
+    pub fn record(env: Env, entity_id: String, event_type: String) {
+        let mut events = Self::get_events_by_entity(env.clone(), entity_id.clone());
+        let seq = events.len() as u64 + 1;
+        events.push_back(AuditEvent {
+            event_id: BytesN::from_array(&env, &[seq as u8; 32]),
+            timestamp: seq,
+            event_type,
+            emitting_contract: env.current_contract_address(),
+            primary_entity_id: entity_id.clone(),
+            secondary_entity_id: None,
+            event_data: String::from_str(&env, ""),
+            tx_hash: BytesN::from_array(&env, &[seq as u8; 32]),
+            seq,
+            ledger_seq: 1,
+        });
+        env.storage()
+            .persistent()
+            .set(&MockKey::Events(entity_id), &events);
+    }
+
+    pub fn get_events_by_entity(env: Env, entity_id: String) -> Vec<AuditEvent> {
+        env.storage()
+            .persistent()
+            .get(&MockKey::Events(entity_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    authority: Address,
+    carbon: MockCarbonAssetClient<'a>,
+    methodology: MockMethodologyLibraryClient<'a>,
+    audit: MockAuditTrailClient<'a>,
+    aggregator: AggregatorClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    let authority = Address::generate(&env);
+    let carbon = MockCarbonAssetClient::new(&env, &env.register(MockCarbonAsset, ()));
+    let methodology =
+        MockMethodologyLibraryClient::new(&env, &env.register(MockMethodologyLibrary, ()));
+    let audit = MockAuditTrailClient::new(&env, &env.register(MockAuditTrail, ()));
+    let aggregator = AggregatorClient::new(&env, &env.register(Aggregator, ()));
+    methodology.set_authority(&authority);
+
+    Setup {
+        env,
+        authority,
+        carbon,
+        methodology,
+        audit,
+        aggregator,
+    }
+}
+
+fn metadata(env: &Env, methodology_id: u32) -> CarbonAssetMetadata {
+    CarbonAssetMetadata {
+        project_id: String::from_str(env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id,
+        geo_hash: BytesN::from_array(env, &[7u8; 32]),
+    }
+}
+
+fn methodology_meta(env: &Env, issuing_authority: &Address) -> MethodologyMeta {
+    MethodologyMeta {
+        name: String::from_str(env, "VM0042"),
+        version: String::from_str(env, "1.0"),
+        registry: String::from_str(env, "VCS"),
+        registry_link: String::from_str(env, "https://registry.verra.org"),
+        issuing_authority: issuing_authority.clone(),
+        ipfs_cid: None,
+    }
+}
+
+fn overview(s: &Setup, token_id: u32) -> aggregator::types::TokenOverview {
+    s.aggregator.token_overview(
+        &s.carbon.address,
+        &s.methodology.address,
+        &s.audit.address,
+        &token_id,
+    )
+}
+
+#[test]
+fn test_token_overview_combines_reads() {
+    let s = setup();
+    let owner = Address::generate(&s.env);
+    let meta = metadata(&s.env, 3);
+    s.carbon
+        .set_token(&1, &Some(owner.clone()), &meta, &AssetStatus::Listed, &85);
+    s.methodology
+        .set_methodology(&3, &methodology_meta(&s.env, &s.authority));
+    for i in 0..RECENT_AUDIT_EVENTS + 2 {
+        let event_type = if i == 0 { "minted" } else { "updated" };
+        s.audit
+            .record(&meta.project_id, &String::from_str(&s.env, event_type));
+    }
+
+    let view = overview(&s, 1);
+    assert_eq!(view.token_id, 1);
+    assert_eq!(view.owner, Some(owner));
+    assert_eq!(view.status, AssetStatus::Listed);
+    assert_eq!(view.metadata, meta);
+    assert_eq!(view.quality_score, 85);
+    assert_eq!(
+        view.methodology_name,
+        Some(String::from_str(&s.env, "VM0042"))
+    );
+    assert_eq!(
+        view.methodology_version,
+        Some(String::from_str(&s.env, "1.0"))
+    );
+    assert!(view.methodology_valid);
+    assert!(!view.methodology_deprecated);
+
+    // Only the most recent events are returned, oldest first
+    assert_eq!(view.audit_event_count, RECENT_AUDIT_EVENTS + 2);
+    assert_eq!(view.recent_audit_events.len(), RECENT_AUDIT_EVENTS);
+    assert_eq!(view.recent_audit_events.get(0).unwrap().seq, 3);
+    assert_eq!(
+        view.recent_audit_events.last().unwrap().seq,
+        RECENT_AUDIT_EVENTS as u64 + 2
+    );
+}
+
+#[test]
+fn test_burned_token_with_unknown_methodology() {
+    let s = setup();
+    let meta = metadata(&s.env, 9);
+    s.carbon
+        .set_token(&2, &None, &meta, &AssetStatus::Retired, &100);
+
+    let view = overview(&s, 2);
+    assert_eq!(view.owner, None);
+    assert_eq!(view.status, AssetStatus::Retired);
+    assert_eq!(view.methodology_name, None);
+    assert!(!view.methodology_valid);
+    assert_eq!(view.audit_event_count, 0);
+    assert_eq!(view.recent_audit_events.len(), 0);
+
+    // Known but deprecated, and issued by an authority no longer trusted
+    let other_authority = Address::generate(&s.env);
+    s.methodology
+        .set_methodology(&9, &methodology_meta(&s.env, &other_authority));
+    s.methodology.deprecate(&9);
+    let view = overview(&s, 2);
+    assert!(view.methodology_name.is_some());
+    assert!(!view.methodology_valid);
+    assert!(view.methodology_deprecated);
+}
+
+#[test]
+fn test_unknown_token() {
+    let s = setup();
+    assert_eq!(
+        s.aggregator.try_token_overview(
+            &s.carbon.address,
+            &s.methodology.address,
+            &s.audit.address,
+            &42,
+        ),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+}
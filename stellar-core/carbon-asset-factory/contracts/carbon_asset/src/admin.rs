@@ -0,0 +1,329 @@
+use soroban_sdk::{contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::errors::ContractError;
+use crate::events::{
+    IssuanceKeyUpdatedEvent, MintFeeSetEvent, MintLockUpdatedEvent, RetirementTrackerUpdatedEvent,
+};
+use crate::storage::DataKey;
+use crate::types::MintFee;
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// ========================================================================
+// Admin Configuration
+// ========================================================================
+
+// Privileged mutators for instance-level settings. Every entry point here
+// requires the admin's authorization.
+#[contractimpl]
+impl CarbonAsset {
+    // Nominate `new_admin`. The role moves only when they call
+    // `accept_admin`.
+    pub fn propose_admin(
+        env: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        Ok(access_control::propose_admin(&env, &new_admin)?)
+    }
+
+    pub fn accept_admin(env: Env) -> Result<(), ContractError> {
+        access_control::accept_admin(&env)?;
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, caller: Address) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        Ok(access_control::cancel_admin_transfer(&env)?)
+    }
+
+    pub fn set_retirement_tracker(
+        env: Env,
+        caller: Address,
+        retirement_tracker: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        let old_tracker = Self::get_retirement_tracker(env.clone())?;
+        // Only the tracker holding escrowed credits can release or burn them
+        if retirement_tracker != old_tracker && Self::escrowed_count(env.clone()) > 0 {
+            return Err(ContractError::EscrowOutstanding);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RetirementTracker, &retirement_tracker);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        RetirementTrackerUpdatedEvent {
+            sequence: next_sequence,
+            old_tracker,
+            new_tracker: retirement_tracker,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Register another address whose incoming credits are retired on
+    // receipt, e.g. a sink for a different jurisdiction. The retirement
+    // tracker is always a sink and is changed with `set_retirement_tracker`.
+    pub fn add_retire_sink(env: Env, caller: Address, sink: Address) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        if Self::is_retire_sink(env.clone(), sink.clone())? {
+            return Ok(());
+        }
+
+        let mut sinks: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RetireSinks)
+            .unwrap_or(Vec::new(&env));
+        sinks.push_back(sink.clone());
+        env.storage().instance().set(&DataKey::RetireSinks, &sinks);
+        Self::publish_retire_sink_update(&env, sink, true, caller);
+        Ok(())
+    }
+
+    pub fn remove_retire_sink(
+        env: Env,
+        caller: Address,
+        sink: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        let mut sinks: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RetireSinks)
+            .unwrap_or(Vec::new(&env));
+        let index = sinks
+            .first_index_of(&sink)
+            .ok_or(ContractError::RetireSinkNotFound)?;
+        sinks.remove(index);
+        env.storage().instance().set(&DataKey::RetireSinks, &sinks);
+        Self::publish_retire_sink_update(&env, sink, false, caller);
+        Ok(())
+    }
+
+    pub fn set_regulatory_check(
+        env: Env,
+        caller: Address,
+        regulatory_check: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RegulatoryCheck, &regulatory_check);
+        Ok(())
+    }
+
+    pub fn set_verification_registry(
+        env: Env,
+        caller: Address,
+        verification_registry: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::VerificationRegistry, &verification_registry);
+        Ok(())
+    }
+
+    // Approve `marketplace` to list credits and settle sales. Several
+    // marketplaces may be approved at once; each keeps its own listed
+    // amounts.
+    pub fn add_marketplace(
+        env: Env,
+        caller: Address,
+        marketplace: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        if Self::is_marketplace(env.clone(), marketplace.clone()) {
+            return Ok(());
+        }
+
+        let mut marketplaces = Self::get_marketplaces(env.clone());
+        marketplaces.push_back(marketplace.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Marketplaces, &marketplaces);
+        Self::publish_marketplace_update(&env, marketplace, true, caller);
+        Ok(())
+    }
+
+    // A removed marketplace can no longer list or settle sales, but can
+    // still delist what it holds listed.
+    pub fn remove_marketplace(
+        env: Env,
+        caller: Address,
+        marketplace: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        let mut marketplaces = Self::get_marketplaces(env.clone());
+        let index = marketplaces
+            .first_index_of(&marketplace)
+            .ok_or(ContractError::MarketplaceNotFound)?;
+        marketplaces.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Marketplaces, &marketplaces);
+        Self::publish_marketplace_update(&env, marketplace, false, caller);
+        Ok(())
+    }
+
+    pub fn set_methodology_library(
+        env: Env,
+        caller: Address,
+        methodology_library: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MethodologyLibrary, &methodology_library);
+        Ok(())
+    }
+
+    // Consult `circuit_breaker` before minting, transfers, retirement burns
+    // and listings. Halted calls fail with `SystemHalted`.
+    pub fn set_circuit_breaker(
+        env: Env,
+        caller: Address,
+        circuit_breaker: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CircuitBreaker, &circuit_breaker);
+        Ok(())
+    }
+
+    pub fn set_oracle(env: Env, caller: Address, oracle: Address) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        Ok(())
+    }
+
+    // Charge `amount` of `fee_token` per credit to whoever mints, paid to
+    // `treasury` before the credits are created. An amount of 0 turns the
+    // fee off.
+    pub fn set_mint_fee(
+        env: Env,
+        caller: Address,
+        fee_token: Address,
+        amount: i128,
+        treasury: Address,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        if amount < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let fee = MintFee {
+            fee_token: fee_token.clone(),
+            amount,
+            treasury: treasury.clone(),
+        };
+        env.storage().instance().set(&DataKey::MintFee, &fee);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        MintFeeSetEvent {
+            sequence: next_sequence,
+            fee_token,
+            amount,
+            treasury,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Accept vouchers signed by `issuance_key` in `mint_with_voucher`. The
+    // vouchers carry the admin's authority, so a methodology with its own
+    // issuer cannot be minted this way. `None` stops voucher minting.
+    pub fn set_issuance_key(
+        env: Env,
+        caller: Address,
+        issuance_key: Option<BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        match &issuance_key {
+            Some(key) => env.storage().instance().set(&DataKey::IssuanceKey, key),
+            None => env.storage().instance().remove(&DataKey::IssuanceKey),
+        }
+
+        IssuanceKeyUpdatedEvent {
+            issuance_key,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Block transfers of each token for `mint_lock_seconds` after its mint,
+    // to discourage wash trading of new credits. 0 turns the lock off. The
+    // period is read at transfer time, so a change applies to every token.
+    pub fn set_mint_lock_seconds(
+        env: Env,
+        caller: Address,
+        mint_lock_seconds: u64,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MintLockSeconds, &mint_lock_seconds);
+
+        MintLockUpdatedEvent {
+            mint_lock_seconds,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn set_host_jurisdiction(
+        env: Env,
+        caller: Address,
+        host_jurisdiction: String,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HostJurisdiction, &host_jurisdiction);
+        Ok(())
+    }
+
+    pub fn set_name(env: Env, caller: Address, name: String) -> Result<(), ContractError> {
+        let field = Symbol::new(&env, "name");
+        Self::update_token_metadata(env, caller, DataKey::Name, field, name)
+    }
+
+    pub fn set_symbol(env: Env, caller: Address, symbol: String) -> Result<(), ContractError> {
+        let field = Symbol::new(&env, "symbol");
+        Self::update_token_metadata(env, caller, DataKey::Symbol, field, symbol)
+    }
+}
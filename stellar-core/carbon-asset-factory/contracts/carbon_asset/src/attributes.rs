@@ -0,0 +1,53 @@
+use soroban_sdk::{contractimpl, Address, Env, Map, String};
+
+use crate::errors::ContractError;
+use crate::events::AttributeUpdatedEvent;
+use crate::storage::DataKey;
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// ========================================================================
+// Token Attributes
+// ========================================================================
+
+// Free-form per-token fields such as a registry URL or SDG codes. They are
+// kept apart from `CarbonAssetMetadata`, which stays fixed after minting.
+#[contractimpl]
+impl CarbonAsset {
+    // Set or overwrite `key` on `token_id`. Admin-only.
+    pub fn set_attribute(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        key: String,
+        value: String,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        Self::get_status(env.clone(), token_id)?;
+
+        let mut attributes = Self::get_all_attributes(env.clone(), token_id);
+        attributes.set(key.clone(), value.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenAttributes(token_id), &attributes);
+
+        AttributeUpdatedEvent {
+            token_id,
+            key,
+            value,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_attribute(env: Env, token_id: u32, key: String) -> Option<String> {
+        Self::get_all_attributes(env, token_id).get(key)
+    }
+
+    pub fn get_all_attributes(env: Env, token_id: u32) -> Map<String, String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenAttributes(token_id))
+            .unwrap_or_else(|| Map::new(&env))
+    }
+}
@@ -1,4 +1,5 @@
-use soroban_sdk::contracterror;
+use access_control::AccessError;
+use soroban_sdk::{contracterror, Env, Symbol};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[contracterror]
@@ -15,4 +16,104 @@ pub enum ContractError {
     RegulatoryNotSet = 10,
     HostJurisdictionNotSet = 11,
     TokenAlreadyBurned = 12,
+    InvalidAmount = 13,
+    SerialCountMismatch = 14,
+    BatchTooLarge = 15,
+    TokenFrozen = 16,
+    VerificationRegistryNotSet = 17,
+    NotVerified = 18,
+    ExceedsVerifiedAmount = 19,
+    MarketplaceNotApproved = 20,
+    NoPendingAdmin = 21,
+    UnknownRole = 22,
+    ContractPaused = 23,
+    ReentrantTransfer = 24,
+    DuplicateToken = 25,
+    SystemHalted = 26,
+    SerialAlreadyBridged = 27,
+    SupplyOverflow = 28,
+    InvalidRoyalty = 29,
+    RetireSinkNotFound = 30,
+    MintFeeFailed = 31,
+    MarketplaceNotFound = 32,
+    InsufficientFreeBalance = 33,
+    InvalidNonce = 34,
+    MigrationNotFound = 35,
+    MigrationExists = 36,
+    MigrationClaimed = 37,
+    ProjectMismatch = 38,
+    VoucherExpired = 39,
+    VoucherUsed = 40,
+    VoucherMismatch = 41,
+    TokenCoolingOff = 42,
+    ReentrantCallback = 43,
+    InvalidRegion = 44,
+    EscrowOutstanding = 45,
+    TokenListed = 46,
+}
+
+impl ContractError {
+    // The variant name as a symbol, for read-only checks that report why a
+    // call would fail instead of failing.
+    pub fn reason(&self, env: &Env) -> Symbol {
+        let name = match self {
+            ContractError::AlreadyInitialized => "AlreadyInitialized",
+            ContractError::NotInitialized => "NotInitialized",
+            ContractError::NotAuthorized => "NotAuthorized",
+            ContractError::TokenNotFound => "TokenNotFound",
+            ContractError::NotOwner => "NotOwner",
+            ContractError::TransferNotAllowed => "TransferNotAllowed",
+            ContractError::StatusFrozen => "StatusFrozen",
+            ContractError::InvalidStatusTransition => "InvalidStatusTransition",
+            ContractError::ComplianceFailed => "ComplianceFailed",
+            ContractError::RegulatoryNotSet => "RegulatoryNotSet",
+            ContractError::HostJurisdictionNotSet => "HostJurisdictionNotSet",
+            ContractError::TokenAlreadyBurned => "TokenAlreadyBurned",
+            ContractError::InvalidAmount => "InvalidAmount",
+            ContractError::SerialCountMismatch => "SerialCountMismatch",
+            ContractError::BatchTooLarge => "BatchTooLarge",
+            ContractError::TokenFrozen => "TokenFrozen",
+            ContractError::VerificationRegistryNotSet => "VerificationRegistryNotSet",
+            ContractError::NotVerified => "NotVerified",
+            ContractError::ExceedsVerifiedAmount => "ExceedsVerifiedAmount",
+            ContractError::MarketplaceNotApproved => "MarketplaceNotApproved",
+            ContractError::NoPendingAdmin => "NoPendingAdmin",
+            ContractError::UnknownRole => "UnknownRole",
+            ContractError::ContractPaused => "ContractPaused",
+            ContractError::ReentrantTransfer => "ReentrantTransfer",
+            ContractError::DuplicateToken => "DuplicateToken",
+            ContractError::SystemHalted => "SystemHalted",
+            ContractError::SerialAlreadyBridged => "SerialAlreadyBridged",
+            ContractError::SupplyOverflow => "SupplyOverflow",
+            ContractError::InvalidRoyalty => "InvalidRoyalty",
+            ContractError::RetireSinkNotFound => "RetireSinkNotFound",
+            ContractError::MintFeeFailed => "MintFeeFailed",
+            ContractError::MarketplaceNotFound => "MarketplaceNotFound",
+            ContractError::InsufficientFreeBalance => "InsufficientFreeBalance",
+            ContractError::InvalidNonce => "InvalidNonce",
+            ContractError::MigrationNotFound => "MigrationNotFound",
+            ContractError::MigrationExists => "MigrationExists",
+            ContractError::MigrationClaimed => "MigrationClaimed",
+            ContractError::ProjectMismatch => "ProjectMismatch",
+            ContractError::VoucherExpired => "VoucherExpired",
+            ContractError::VoucherUsed => "VoucherUsed",
+            ContractError::VoucherMismatch => "VoucherMismatch",
+            ContractError::TokenCoolingOff => "TokenCoolingOff",
+            ContractError::ReentrantCallback => "ReentrantCallback",
+            ContractError::InvalidRegion => "InvalidRegion",
+            ContractError::EscrowOutstanding => "EscrowOutstanding",
+            ContractError::TokenListed => "TokenListed",
+        };
+        Symbol::new(env, name)
+    }
+}
+
+impl From<AccessError> for ContractError {
+    fn from(err: AccessError) -> Self {
+        match err {
+            AccessError::NotInitialized => ContractError::NotInitialized,
+            AccessError::NotAuthorized => ContractError::NotAuthorized,
+            AccessError::NoPendingAdmin => ContractError::NoPendingAdmin,
+        }
+    }
 }
@@ -1,6 +1,11 @@
-use soroban_sdk::{contractevent, Address, String};
+use soroban_sdk::{contractevent, Address, BytesN, Map, String, Symbol, Vec};
 
-use crate::types::AssetStatus;
+use crate::types::{AssetStatus, ContractConfig, StatusReason};
+
+#[contractevent(topics = ["initialized"])]
+pub struct InitializedEvent {
+    pub config: ContractConfig,
+}
 
 #[contractevent]
 pub struct MintEvent {
@@ -10,14 +15,133 @@ pub struct MintEvent {
     pub project_id: String,
     pub vintage_year: u64,
     pub methodology_id: u32,
+    // Origin registry for bridged credits, `None` for native issuance
+    pub origin_registry: Option<Symbol>,
+}
+
+#[contractevent]
+pub struct BatchMintEvent {
+    pub sequence: u64,
+    pub owner: Address,
+    pub first_token_id: u32,
+    pub amount: u32,
+    pub serial_start: u64,
+    pub serial_count: u64,
+}
+
+#[contractevent]
+pub struct DistributedMintEvent {
+    pub sequence: u64,
+    pub first_token_id: u32,
+    pub recipients: Vec<(Address, u32)>,
+    pub total: u32,
+}
+
+// Emitted by `mint_with_sig` after the mint events. `issuer` is the address
+// `signer` is registered to; `relayer` submitted the call.
+#[contractevent]
+pub struct SignedMintEvent {
+    pub sequence: u64,
+    pub issuer: Address,
+    pub signer: BytesN<32>,
+    pub relayer: Address,
+    pub nonce: u64,
+    pub first_token_id: u32,
+    pub amount: u32,
+}
+
+#[contractevent]
+pub struct VoucherMintEvent {
+    pub sequence: u64,
+    pub operator: Address,
+    pub to: Address,
+    pub nonce: u64,
+    pub first_token_id: u32,
+    pub amount: u32,
+}
+
+#[contractevent(topics = ["metadata_updated"])]
+pub struct MetadataUpdatedEvent {
+    pub sequence: u64,
+    #[topic]
+    pub field: Symbol,
+    pub old_value: String,
+    pub new_value: String,
+    pub updated_by: Address,
+}
+
+#[contractevent]
+pub struct RetirementTrackerUpdatedEvent {
+    pub sequence: u64,
+    pub old_tracker: Address,
+    pub new_tracker: Address,
+    pub updated_by: Address,
 }
 
+#[contractevent]
+pub struct RetireSinkUpdatedEvent {
+    pub sequence: u64,
+    pub sink: Address,
+    pub added: bool,
+    pub updated_by: Address,
+}
+
+#[contractevent]
+pub struct MarketplaceUpdatedEvent {
+    pub sequence: u64,
+    pub marketplace: Address,
+    pub added: bool,
+    pub updated_by: Address,
+}
+
+// Emitted per token by `list_tokens`, `delist_tokens` and marketplace sales.
+// `listed` is 1 while `marketplace` holds `token_id` listed for the owner.
+#[contractevent]
+pub struct ListedAmountUpdatedEvent {
+    pub sequence: u64,
+    pub marketplace: Address,
+    pub owner: Address,
+    pub token_id: u32,
+    pub listed: i128,
+}
+
+// Emitted by `retire_direct`. `cert_id` is the tracker's certificate when a
+// beneficiary was named.
+#[contractevent]
+pub struct DirectRetirementEvent {
+    pub sequence: u64,
+    pub token_id: u32,
+    pub from: Address,
+    pub beneficiary: Option<String>,
+    pub cert_id: Option<BytesN<32>>,
+}
+
+// Emitted when a credit sent to a retire sink is retired on receipt.
+#[contractevent]
+pub struct AutoRetireEvent {
+    pub sequence: u64,
+    pub token_id: u32,
+    pub from: Address,
+    pub sink: Address,
+}
+
+// `rule_version` is the regulatory rule set version the transfer was
+// screened under, or None when `check_transfer` did not run.
 #[contractevent]
 pub struct TransferEvent {
     pub sequence: u64,
     pub token_id: u32,
     pub from: Address,
     pub to: Address,
+    pub rule_version: Option<u64>,
+}
+
+#[contractevent]
+pub struct MultiTransferEvent {
+    pub sequence: u64,
+    pub from: Address,
+    pub to: Address,
+    pub token_ids: Vec<u32>,
 }
 
 #[contractevent]
@@ -27,6 +151,151 @@ pub struct StatusChangeEvent {
     pub old_status: Option<AssetStatus>,
     pub new_status: AssetStatus,
     pub changed_by: Address,
+    pub reason: Option<StatusReason>,
+    pub note: Option<String>,
+}
+
+#[contractevent]
+pub struct ListPriceSetEvent {
+    pub sequence: u64,
+    pub token_id: u32,
+    pub price: i128,
+    pub listed_by: Address,
+}
+
+#[contractevent]
+pub struct RoyaltySetEvent {
+    pub token_id: u32,
+    pub developer: Address,
+    pub bps: u32,
+}
+
+// Emitted by `transfer_with_sale_price` when a royalty is paid.
+#[contractevent]
+pub struct RoyaltyPaidEvent {
+    pub sequence: u64,
+    pub token_id: u32,
+    pub developer: Address,
+    pub payer: Address,
+    pub payment_token: Address,
+    pub sale_price: i128,
+    pub royalty: i128,
+}
+
+#[contractevent]
+pub struct MintFeeSetEvent {
+    pub sequence: u64,
+    pub fee_token: Address,
+    pub amount: i128,
+    pub treasury: Address,
+    pub updated_by: Address,
+}
+
+// Emitted by the mint paths when the mint fee is collected.
+#[contractevent]
+pub struct MintFeePaidEvent {
+    pub sequence: u64,
+    pub payer: Address,
+    pub fee_token: Address,
+    pub fee: i128,
+    pub credits: u32,
+    pub treasury: Address,
+}
+
+#[contractevent]
+pub struct TokenFreezeEvent {
+    pub sequence: u64,
+    pub token_id: u32,
+    pub frozen: bool,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct RoleUpdatedEvent {
+    #[topic]
+    pub role: Symbol,
+    pub account: Address,
+    pub granted: bool,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct MethodologyIssuerUpdatedEvent {
+    #[topic]
+    pub methodology_id: u32,
+    pub issuer: Option<Address>,
+    pub changed_by: Address,
+}
+
+#[contractevent(topics = ["transfer_summary"])]
+pub struct TransferSummaryEvent {
+    pub sequence: u64,
+    pub ledger: u32,
+    pub count: u32,
+    pub amounts: Map<u32, i128>,
+}
+
+#[contractevent]
+pub struct SummaryModeUpdatedEvent {
+    pub enabled: bool,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct BalanceObserverUpdatedEvent {
+    pub observer: Option<Address>,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct MintLockUpdatedEvent {
+    pub mint_lock_seconds: u64,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct TokenMigratedEvent {
+    pub sequence: u64,
+    pub old_token_id: u32,
+    pub new_token_id: u32,
+    pub migrated_by: Address,
+}
+
+#[contractevent]
+pub struct MigrationClaimedEvent {
+    pub sequence: u64,
+    pub old_token_id: u32,
+    pub new_token_id: u32,
+    pub holder: Address,
+}
+
+#[contractevent]
+pub struct MintSignerUpdatedEvent {
+    #[topic]
+    pub signer: BytesN<32>,
+    pub issuer: Option<Address>,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct IssuanceKeyUpdatedEvent {
+    pub issuance_key: Option<BytesN<32>>,
+    pub changed_by: Address,
+}
+
+#[contractevent]
+pub struct AttributeUpdatedEvent {
+    #[topic]
+    pub token_id: u32,
+    pub key: String,
+    pub value: String,
+    pub updated_by: Address,
+}
+
+#[contractevent]
+pub struct PauseEvent {
+    pub paused: bool,
+    pub changed_by: Address,
 }
 
 #[contractevent]
@@ -56,6 +325,19 @@ pub struct Sep41TransferEvent {
     pub amount: i128,
 }
 
+// Emitted by `transfer_from` after the transfer events, so indexers can
+// tell who moved the credits on the owner's behalf.
+#[contractevent]
+pub struct TransferFromEvent {
+    pub sequence: u64,
+    pub spender: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub token_ids: Vec<u32>,
+    pub remaining_allowance: i128,
+}
+
 #[contractevent]
 pub struct Sep41BurnEvent {
     pub sequence: u64,
@@ -1,21 +1,72 @@
 #![no_std]
 
-mod errors;
+mod admin;
+mod attributes;
+pub mod errors;
 mod events;
+mod migration;
+mod observer;
+mod regions;
+pub mod roles;
 mod storage;
-mod types;
+mod summary;
+pub mod types;
+pub mod views;
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, token, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, String,
+    Symbol, Val, Vec,
+};
 
 use crate::errors::ContractError;
 use crate::events::{
-    ApproveEvent, MintEvent, QualityScoreUpdatedEvent, Sep41BurnEvent, Sep41TransferEvent,
-    StatusChangeEvent, TransferEvent,
+    ApproveEvent, AutoRetireEvent, BatchMintEvent, DirectRetirementEvent, DistributedMintEvent,
+    InitializedEvent, ListPriceSetEvent, ListedAmountUpdatedEvent, MarketplaceUpdatedEvent,
+    MetadataUpdatedEvent, MintEvent, MintFeePaidEvent, MultiTransferEvent,
+    QualityScoreUpdatedEvent, RetireSinkUpdatedEvent, RoyaltyPaidEvent, RoyaltySetEvent,
+    Sep41BurnEvent, Sep41TransferEvent, SignedMintEvent, StatusChangeEvent, TokenFreezeEvent,
+    TransferEvent, TransferFromEvent, VoucherMintEvent,
+};
+use crate::storage::{DataKey, ExtendedKey};
+use crate::types::{
+    AllowanceData, AssetStatus, BurnReceipt, CarbonAssetMetadata, MintAuthorization, MintFee,
+    MintSimulation, MintVoucher, OperationType, OriginInfo, RoyaltyInfo, SerialRange, StatusReason,
+    TransferCheck, TransferDecision, TransferSimulation, ValidationResult,
 };
-use crate::storage::DataKey;
-use crate::types::{AllowanceData, AssetStatus, CarbonAssetMetadata, OperationType, ValidationResult};
+
+// Each minted credit writes its own ledger entries, so batches are kept well
+// inside the per-transaction write limit.
+const MAX_BATCH_MINT: u32 = 5;
+
+// A serial range is stored once for its whole `mint_batch` call, but every
+// credit is still minted as its own token. Six credits already exceed the
+// network's per-transaction footprint limit, so a registry block larger than
+// five is minted over several calls.
+const MAX_SERIAL_BATCH: u32 = 5;
+
+// The methodology, vintage and registry indexes are split into segments of
+// this many ids, so a mint rewrites a bounded entry however many tokens
+// share the index.
+const TOKEN_INDEX_SEGMENT: u32 = 256;
+
+// Batch start ids are indexed in buckets of this many token ids, so the index
+// entry a lookup reads stays small however many batches are minted.
+const SERIAL_BUCKET_SPAN: u32 = 64;
+
+// A moved credit rewrites its owner, index and possibly status entries, plus
+// the index entry of the token that takes its slot, and a sale also pays the
+// token's royalty. Five tokens, each with a royalty, fit the network's
+// per-transaction footprint limit in one `transfer_multi`, `list_tokens` or
+// `transfer_with_sale_price` call.
+const MAX_MULTI_TRANSFER: u32 = 5;
+
+// Quality score at which `portfolio_value` counts a credit at full price.
+pub const QUALITY_SCORE_SCALE: i128 = 100;
+
+// Largest royalty `set_royalty` accepts, in basis points of the sale price.
+pub const MAX_ROYALTY_BPS: u32 = 500;
 
 // ========================================================================
 // Contract
@@ -38,13 +89,13 @@ impl CarbonAsset {
         retirement_tracker: Address,
         host_jurisdiction: String,
     ) -> Result<(), ContractError> {
-        if env.storage().instance().has(&DataKey::Admin) {
+        if access_control::has_admin(&env) {
             return Err(ContractError::AlreadyInitialized);
         }
 
         admin.require_auth();
 
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        access_control::set_admin(&env, &admin);
         env.storage().instance().set(&DataKey::Name, &name);
         env.storage().instance().set(&DataKey::Symbol, &symbol);
         env.storage().instance().set(&DataKey::Decimals, &0u32);
@@ -57,6 +108,11 @@ impl CarbonAsset {
         env.storage().instance().set(&DataKey::NextTokenId, &1u32);
         env.storage().instance().set(&DataKey::EventSequence, &0u64);
 
+        InitializedEvent {
+            config: Self::get_config(env.clone())?,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -70,77 +126,407 @@ impl CarbonAsset {
         owner: Address,
         metadata: CarbonAssetMetadata,
     ) -> Result<u32, ContractError> {
-        caller.require_auth();
-        let admin = Self::get_admin(env.clone())?;
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
+        Self::require_issuer(&env, &caller, metadata.methodology_id)?;
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+
+        Self::reserve_verified_supply(env.clone(), &metadata, 1)?;
+        Self::report_methodology_usage(&env, &metadata, 1);
+        Self::charge_mint_fee(&env, &caller, 1)?;
+        Self::mint_internal(env, caller, owner, metadata, None)
+    }
+
+    // Mint one credit bridged from an external registry, recording where it
+    // came from. `serial_hash` identifies the retired or locked serial range
+    // at the origin registry and can be bridged only once. Origin info is
+    // written here and nowhere else, so it cannot change after minting.
+    pub fn mint_bridged(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        metadata: CarbonAssetMetadata,
+        registry: Symbol,
+        serial_hash: BytesN<32>,
+        bridge_tx: BytesN<32>,
+    ) -> Result<u32, ContractError> {
+        Self::require_role(&env, roles::BRIDGER, &caller)?;
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+
+        let serial_key = DataKey::BridgedSerial(serial_hash.clone());
+        if env.storage().persistent().has(&serial_key) {
+            return Err(ContractError::SerialAlreadyBridged);
         }
 
-        let token_id: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::NextTokenId)
-            .ok_or(ContractError::NotInitialized)?;
+        Self::reserve_verified_supply(env.clone(), &metadata, 1)?;
+        Self::report_methodology_usage(&env, &metadata, 1);
+        Self::charge_mint_fee(&env, &caller, 1)?;
 
-        env.storage()
-            .instance()
-            .set(&DataKey::NextTokenId, &(token_id + 1));
+        let origin = OriginInfo {
+            registry,
+            serial_hash,
+            bridged_at: env.ledger().timestamp(),
+            bridge_tx,
+        };
+        let token_id = Self::mint_internal(env.clone(), caller, owner, metadata, Some(origin))?;
+        env.storage().persistent().set(&serial_key, &token_id);
+        Ok(token_id)
+    }
 
+    // Mint `amount` whole-unit credits carrying the registry serial range
+    // [serial_start, serial_start + serial_count). Each token is one credit and
+    // receives the next serial number in the range. The batch's tokens have
+    // consecutive ids, so the range is recorded once under the first of them.
+    pub fn mint_batch(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        metadata: CarbonAssetMetadata,
+        amount: u32,
+        serial_start: u64,
+        serial_count: u64,
+    ) -> Result<Vec<u32>, ContractError> {
+        Self::require_issuer(&env, &caller, metadata.methodology_id)?;
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+
+        if amount == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if serial_count != amount as u64 || serial_start.checked_add(serial_count).is_none() {
+            return Err(ContractError::SerialCountMismatch);
+        }
+        if amount > MAX_SERIAL_BATCH {
+            return Err(ContractError::BatchTooLarge);
+        }
+        Self::reserve_verified_supply(env.clone(), &metadata, amount)?;
+        Self::report_methodology_usage(&env, &metadata, amount);
+        Self::charge_mint_fee(&env, &caller, amount)?;
+
+        let mut token_ids = Vec::new(&env);
+        for _ in 0..amount {
+            let token_id = Self::mint_internal(
+                env.clone(),
+                caller.clone(),
+                owner.clone(),
+                metadata.clone(),
+                None,
+            )?;
+            token_ids.push_back(token_id);
+        }
+        let first_token_id = token_ids.get(0).unwrap();
+        let range = SerialRange {
+            serial_start,
+            serial_count,
+        };
         env.storage()
             .persistent()
-            .set(&DataKey::Owner(token_id), &owner);
-        Self::add_token_to_owner(env.clone(), owner.clone(), token_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Metadata(token_id), &metadata);
+            .set(&DataKey::SerialRange(first_token_id), &range);
+        Self::append_token_index(
+            &env,
+            &ExtendedKey::SerialBatches(first_token_id / SERIAL_BUCKET_SPAN),
+            first_token_id,
+        );
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
         env.storage()
-            .persistent()
-            .set(&DataKey::Status(token_id), &AssetStatus::Issued);
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        BatchMintEvent {
+            sequence: next_sequence,
+            owner,
+            first_token_id,
+            amount,
+            serial_start,
+            serial_count,
+        }
+        .publish(&env);
+
+        Ok(token_ids)
+    }
+
+    // Primary issuance to several initial holders: mint `amount` credits with
+    // the same metadata for each `(holder, amount)`. Amounts are validated and
+    // supply reserved for the whole distribution before anything is minted.
+    // The total counts against the same per-call limit as `mint_batch`.
+    pub fn mint_distributed(
+        env: Env,
+        caller: Address,
+        metadata: CarbonAssetMetadata,
+        recipients: Vec<(Address, u32)>,
+    ) -> Result<Vec<u32>, ContractError> {
+        Self::require_issuer(&env, &caller, metadata.methodology_id)?;
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+
+        let mut total: u32 = 0;
+        for (_, amount) in recipients.iter() {
+            if amount == 0 {
+                return Err(ContractError::InvalidAmount);
+            }
+            total = total.saturating_add(amount);
+        }
+        if total == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if total > MAX_BATCH_MINT {
+            return Err(ContractError::BatchTooLarge);
+        }
+        Self::reserve_verified_supply(env.clone(), &metadata, total)?;
+        Self::report_methodology_usage(&env, &metadata, total);
+        Self::charge_mint_fee(&env, &caller, total)?;
+
+        let mut token_ids = Vec::new(&env);
+        for (owner, amount) in recipients.iter() {
+            for _ in 0..amount {
+                let token_id = Self::mint_internal(
+                    env.clone(),
+                    caller.clone(),
+                    owner.clone(),
+                    metadata.clone(),
+                    None,
+                )?;
+                token_ids.push_back(token_id);
+            }
+        }
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
         env.storage()
-            .persistent()
-            .set(&DataKey::QualityScore(token_id), &0i128);
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        DistributedMintEvent {
+            sequence: next_sequence,
+            first_token_id: token_ids.get(0).unwrap(),
+            recipients,
+            total,
+        }
+        .publish(&env);
+
+        Ok(token_ids)
+    }
+
+    // Mint `amount` credits to `owner` on the strength of an ed25519
+    // signature by `signer` over the `MintAuthorization` for this call, so a
+    // relayer can submit issuance it has no authority over. The key must be
+    // registered with `set_mint_signer`, and its issuer must be allowed to
+    // mint the methodology, as with `mint`. `nonce` must be the key's
+    // current nonce and is used up, so each signature mints once. The
+    // relayer pays the mint fee. A bad signature traps.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_with_sig(
+        env: Env,
+        relayer: Address,
+        owner: Address,
+        metadata: CarbonAssetMetadata,
+        amount: u32,
+        nonce: u64,
+        signer: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<Vec<u32>, ContractError> {
+        Self::require_initialized(&env)?;
+        relayer.require_auth();
+        let issuer = Self::get_mint_signer(env.clone(), signer.clone())
+            .ok_or(ContractError::NotAuthorized)?;
+        if !Self::can_issue(&env, &issuer, metadata.methodology_id) {
+            return Err(ContractError::NotAuthorized);
+        }
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+
+        if amount == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if amount > MAX_BATCH_MINT {
+            return Err(ContractError::BatchTooLarge);
+        }
+        if nonce != Self::get_mint_nonce(env.clone(), signer.clone()) {
+            return Err(ContractError::InvalidNonce);
+        }
+        let authorization = MintAuthorization {
+            contract: env.current_contract_address(),
+            owner: owner.clone(),
+            metadata: metadata.clone(),
+            amount,
+            nonce,
+        };
+        env.crypto()
+            .ed25519_verify(&signer, &authorization.to_xdr(&env), &signature);
         env.storage()
             .persistent()
-            .set(&DataKey::Burned(token_id), &false);
+            .set(&DataKey::MintNonce(signer.clone()), &(nonce + 1));
+
+        Self::reserve_verified_supply(env.clone(), &metadata, amount)?;
+        Self::report_methodology_usage(&env, &metadata, amount);
+        Self::charge_mint_fee(&env, &relayer, amount)?;
+
+        let mut token_ids = Vec::new(&env);
+        for _ in 0..amount {
+            let token_id = Self::mint_internal(
+                env.clone(),
+                issuer.clone(),
+                owner.clone(),
+                metadata.clone(),
+                None,
+            )?;
+            token_ids.push_back(token_id);
+        }
 
         let sequence: u64 = env
             .storage()
             .instance()
             .get(&DataKey::EventSequence)
             .unwrap_or(0u64);
-        let mint_sequence = sequence + 1;
+        let next_sequence = sequence + 1;
         env.storage()
             .instance()
-            .set(&DataKey::EventSequence, &mint_sequence);
-        MintEvent {
-            sequence: mint_sequence,
-            token_id,
-            owner: owner.clone(),
-            project_id: metadata.project_id.clone(),
-            vintage_year: metadata.vintage_year,
-            methodology_id: metadata.methodology_id,
+            .set(&DataKey::EventSequence, &next_sequence);
+        SignedMintEvent {
+            sequence: next_sequence,
+            issuer,
+            signer,
+            relayer,
+            nonce,
+            first_token_id: token_ids.get(0).unwrap(),
+            amount,
         }
         .publish(&env);
 
+        Ok(token_ids)
+    }
+
+    // Redeem a treasury voucher: mint `voucher.amount` credits to
+    // `voucher.to` under `metadata`, whose hash the voucher fixes. The
+    // voucher must be signed by the key set with `set_issuance_key`, must
+    // not have expired, and its nonce must be unused. The operator needs no
+    // role, so an operational key can only mint what was signed for. The
+    // operator pays the mint fee. A bad signature traps.
+    pub fn mint_with_voucher(
+        env: Env,
+        operator: Address,
+        voucher: MintVoucher,
+        metadata: CarbonAssetMetadata,
+        signature: BytesN<64>,
+    ) -> Result<Vec<u32>, ContractError> {
+        Self::require_initialized(&env)?;
+        operator.require_auth();
+        let issuance_key =
+            Self::get_issuance_key(env.clone()).ok_or(ContractError::NotAuthorized)?;
+        let admin = Self::get_admin(env.clone())?;
+        if !Self::can_issue(&env, &admin, metadata.methodology_id) {
+            return Err(ContractError::NotAuthorized);
+        }
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+
+        let amount = voucher.amount;
+        if amount == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if amount > MAX_BATCH_MINT {
+            return Err(ContractError::BatchTooLarge);
+        }
+        if Self::is_voucher_used(env.clone(), voucher.nonce) {
+            return Err(ContractError::VoucherUsed);
+        }
+        if env.ledger().timestamp() > voucher.expires_at {
+            return Err(ContractError::VoucherExpired);
+        }
+        let metadata_hash: BytesN<32> = env.crypto().sha256(&metadata.clone().to_xdr(&env)).into();
+        if voucher.contract != env.current_contract_address()
+            || voucher.metadata_hash != metadata_hash
+        {
+            return Err(ContractError::VoucherMismatch);
+        }
+        env.crypto()
+            .ed25519_verify(&issuance_key, &voucher.clone().to_xdr(&env), &signature);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VoucherUsed(voucher.nonce), &true);
+
+        Self::reserve_verified_supply(env.clone(), &metadata, amount)?;
+        Self::report_methodology_usage(&env, &metadata, amount);
+        Self::charge_mint_fee(&env, &operator, amount)?;
+
+        let mut token_ids = Vec::new(&env);
+        for _ in 0..amount {
+            let token_id = Self::mint_internal(
+                env.clone(),
+                admin.clone(),
+                voucher.to.clone(),
+                metadata.clone(),
+                None,
+            )?;
+            token_ids.push_back(token_id);
+        }
+
         let sequence: u64 = env
             .storage()
             .instance()
             .get(&DataKey::EventSequence)
             .unwrap_or(0u64);
-        let status_sequence = sequence + 1;
+        let next_sequence = sequence + 1;
         env.storage()
             .instance()
-            .set(&DataKey::EventSequence, &status_sequence);
-        StatusChangeEvent {
-            sequence: status_sequence,
-            token_id,
-            old_status: None,
-            new_status: AssetStatus::Issued,
-            changed_by: caller,
+            .set(&DataKey::EventSequence, &next_sequence);
+        VoucherMintEvent {
+            sequence: next_sequence,
+            operator,
+            to: voucher.to,
+            nonce: voucher.nonce,
+            first_token_id: token_ids.get(0).unwrap(),
+            amount,
         }
         .publish(&env);
 
-        Ok(token_id)
+        Ok(token_ids)
+    }
+
+    // Preview of minting `amount` credits of `metadata`: the token ids they
+    // would receive and the mint fee, or the error minting would fail with.
+    // Applies the pause, circuit breaker, batch size and verified supply
+    // checks of the mint paths, but not the caller's role or balance. Writes
+    // nothing.
+    pub fn simulate_mint(env: Env, metadata: CarbonAssetMetadata, amount: u32) -> MintSimulation {
+        let result = Self::require_initialized(&env)
+            .and_then(|_| Self::require_not_paused(&env))
+            .and_then(|_| Self::require_not_halted(&env, roles::MINTING))
+            .and_then(|_| {
+                if amount == 0 {
+                    return Err(ContractError::InvalidAmount);
+                }
+                if amount > MAX_BATCH_MINT {
+                    return Err(ContractError::BatchTooLarge);
+                }
+                Self::check_verified_supply(&env, &metadata, amount)?;
+                let fee = Self::mint_fee_due(&env, amount)?.map_or(0, |(_, fee)| fee);
+                Ok((Self::next_token_ids(&env, amount)?, fee))
+            });
+        match result {
+            Ok((token_ids, fee)) => MintSimulation {
+                to_recipient: amount,
+                to_buffer: 0,
+                fee,
+                token_ids,
+                failure: None,
+            },
+            Err(err) => MintSimulation {
+                to_recipient: 0,
+                to_buffer: 0,
+                fee: 0,
+                token_ids: Vec::new(&env),
+                failure: Some(err.reason(&env)),
+            },
+        }
     }
 
     // ====================================================================
@@ -167,6 +553,7 @@ impl CarbonAsset {
         amount: i128,
         live_until_ledger: u32,
     ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
         from.require_auth();
 
         let current_ledger = env.ledger().sequence();
@@ -202,13 +589,17 @@ impl CarbonAsset {
         Ok(())
     }
 
-    pub fn balance(env: Env, owner: Address) -> i128 {
-        Self::balance_of(env, owner)
+    pub fn balance(env: Env, owner: Address) -> Result<i128, ContractError> {
+        Self::require_initialized(&env)?;
+        Ok(Self::balance_of(env, owner))
     }
 
     pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
         from.require_auth();
-        Self::transfer_amount_internal(env, from, to, amount)
+        Self::require_not_halted(&env, roles::TRANSFERS)?;
+        Self::transfer_amount_internal(env, from, to, amount)?;
+        Ok(())
     }
 
     pub fn transfer_from(
@@ -218,18 +609,171 @@ impl CarbonAsset {
         to: Address,
         amount: i128,
     ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
         spender.require_auth();
+        Self::require_not_halted(&env, roles::TRANSFERS)?;
+        Self::transfer_from_internal(env, spender, from, to, amount)
+    }
 
-        let allowance = Self::spend_allowance(env.clone(), from.clone(), spender.clone(), amount)?;
-        let key = DataKey::Allowance(from.clone(), spender);
-        env.storage().persistent().set(&key, &allowance);
+    // Move the listed tokens from `from` to `to` in one call. Every token is
+    // checked and cleared by the regulatory contract before any of them
+    // moves, so a single failure leaves all of them in place. Tokens sent
+    // to the retirement tracker are retired one by one, as with `transfer`.
+    pub fn transfer_multi(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_ids: Vec<u32>,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        from.require_auth();
+        Self::require_not_halted(&env, roles::TRANSFERS)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        if token_ids.len() > MAX_MULTI_TRANSFER {
+            return Err(ContractError::BatchTooLarge);
+        }
+
+        // Every check runs against the same rule set, so one version covers
+        // all of the tokens
+        let mut rule_version = None;
+        for (i, token_id) in token_ids.iter().enumerate() {
+            if token_ids.first_index_of(token_id) != Some(i as u32) {
+                return Err(ContractError::DuplicateToken);
+            }
+            Self::check_transferable(&env, &from, token_id)?;
+            // Self-transfers skip the compliance hook, as with `transfer`
+            if from != to {
+                Self::lock_token(&env, token_id)?;
+                let (allowed, version) =
+                    Self::run_compliance_check(env.clone(), from.clone(), to.clone(), token_id)?;
+                if !allowed {
+                    return Err(ContractError::ComplianceFailed);
+                }
+                rule_version = version;
+            }
+        }
+        Self::require_free_balance(&env, &from, &to, token_ids.len() as i128)?;
+        if from != to {
+            for token_id in token_ids.iter() {
+                let (from, to) = (from.clone(), to.clone());
+                Self::apply_token_transfer(env.clone(), from, to, token_id, rule_version)?;
+            }
+        }
+        if Self::is_summary_mode(env.clone()) {
+            return Ok(());
+        }
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        MultiTransferEvent {
+            sequence: next_sequence,
+            from,
+            to,
+            token_ids,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // Whether `transfer(from, to, amount)` would succeed right now, and if
+    // not, which error it would return. Nothing is written and no auth is
+    // required. With `check_compliance` the regulatory contract is queried
+    // for each credit that would move; otherwise only local state is checked.
+    pub fn can_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        check_compliance: bool,
+    ) -> TransferCheck {
+        let result = Self::check_amount_transfer(&env, &from, &to, amount, check_compliance);
+        Self::transfer_check(&env, result.map(|_| ()))
+    }
+
+    // Preview of `transfer(from, to, amount)`: the credits that would move
+    // and whether the recipient retires them on receipt. Runs the same
+    // checks as `can_transfer` with compliance; writes nothing and needs no
+    // auth.
+    pub fn simulate_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> TransferSimulation {
+        let result = Self::check_amount_transfer(&env, &from, &to, amount, true)
+            .and_then(|token_ids| Ok((token_ids, Self::is_retire_sink(env.clone(), to.clone())?)));
+        match result {
+            Ok((token_ids, retire_sink)) => TransferSimulation {
+                net_to_recipient: amount,
+                fee: 0,
+                royalty: 0,
+                token_ids,
+                // Self-transfers move nothing, so nothing is retired
+                would_auto_retire: retire_sink && from != to,
+                failure: None,
+            },
+            Err(err) => Self::failed_simulation(&env, err),
+        }
+    }
+
+    // Preview of `transfer_with_sale_price` with the same arguments, less
+    // the payment token: the royalty the buyer would pay in total. Runs the
+    // sale's checks with compliance, but cannot tell whether the buyer's
+    // payment would succeed. Writes nothing and needs no auth.
+    pub fn simulate_sale(
+        env: Env,
+        marketplace: Address,
+        from: Address,
+        to: Address,
+        token_ids: Vec<u32>,
+        price_per_unit: i128,
+    ) -> TransferSimulation {
+        let result = Self::check_sale(&env, &marketplace, &from, &to, &token_ids, price_per_unit)
+            .and_then(|royalty| Ok((royalty, Self::is_retire_sink(env.clone(), to.clone())?)));
+        match result {
+            Ok((royalty, retire_sink)) => TransferSimulation {
+                net_to_recipient: token_ids.len() as i128,
+                fee: 0,
+                royalty,
+                token_ids,
+                would_auto_retire: retire_sink,
+                failure: None,
+            },
+            Err(err) => Self::failed_simulation(&env, err),
+        }
+    }
 
-        Self::transfer_amount_internal(env, from, to, amount)
+    // Same as `can_transfer` for moving one specific token, as with
+    // `transfer_multi`.
+    pub fn can_transfer_token(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        check_compliance: bool,
+    ) -> TransferCheck {
+        let result = Self::require_initialized(&env)
+            .and_then(|_| Self::require_not_halted(&env, roles::TRANSFERS))
+            .and_then(|_| Self::check_token_transfer(&env, &from, &to, token_id, check_compliance))
+            .and_then(|_| Self::require_free_balance(&env, &from, &to, 1));
+        Self::transfer_check(&env, result)
     }
 
     pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
         let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
         retirement_tracker.require_auth();
+        Self::require_not_halted(&env, roles::RETIREMENTS)?;
 
         Self::burn_amount_internal(env, from, amount)
     }
@@ -245,6 +789,7 @@ impl CarbonAsset {
             return Err(ContractError::NotAuthorized);
         }
         retirement_tracker.require_auth();
+        Self::require_not_halted(&env, roles::RETIREMENTS)?;
 
         Self::burn_amount_internal(env, from, amount)
     }
@@ -254,9 +799,11 @@ impl CarbonAsset {
     // ====================================================================
 
     // C-01 extension: burn a specific token_id (used by RetirementTracker for 1:1 retirement).
-    pub fn burn_token(env: Env, token_id: u32, from: Address) -> Result<(), ContractError> {
+    // Returns the id of the stored `BurnReceipt`.
+    pub fn burn_token(env: Env, token_id: u32, from: Address) -> Result<BytesN<32>, ContractError> {
         let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
         retirement_tracker.require_auth();
+        Self::require_not_halted(&env, roles::RETIREMENTS)?;
 
         let owner = Self::owner_of(env.clone(), token_id)?;
         if owner != from {
@@ -272,6 +819,11 @@ impl CarbonAsset {
             return Err(ContractError::TokenAlreadyBurned);
         }
 
+        Self::require_not_paused(&env)?;
+        if Self::is_token_frozen(env.clone(), token_id) {
+            return Err(ContractError::TokenFrozen);
+        }
+
         let status = Self::get_status(env.clone(), token_id)?;
         if status == AssetStatus::Invalidated {
             return Err(ContractError::StatusFrozen);
@@ -285,42 +837,210 @@ impl CarbonAsset {
             )?;
         }
 
-        Self::remove_token_from_owner(env.clone(), from, token_id)?;
+        Self::remove_token_from_owner(env.clone(), from.clone(), token_id)?;
         env.storage()
             .persistent()
             .set(&DataKey::Burned(token_id), &true);
         env.storage()
             .persistent()
             .remove(&DataKey::Owner(token_id));
+        Self::add_retired_supply(&env);
 
-        Ok(())
+        Ok(Self::record_burn_receipt(&env, from, token_id))
     }
 
-    // ====================================================================
-    // Compliance Hook
-    // ====================================================================
-
-    pub fn before_transfer(
+    // Retire and burn `from`'s credit in one call, without escrowing it at
+    // the tracker. `amount` must be 1, as every token is one credit. The
+    // token must be one `from` could send to the tracker: the status, freeze,
+    // cooling-off and compliance checks are those of a transfer. With a
+    // `beneficiary` the tracker records the retirement and issues a
+    // certificate under the registered `reason` code, whose id is returned.
+    pub fn retire_direct(
         env: Env,
         from: Address,
-        to: Address,
+        amount: i128,
         token_id: u32,
-    ) -> Result<bool, ContractError> {
-        let _status = Self::get_status(env.clone(), token_id)?;
-
-        let regulatory_contract: Option<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::RegulatoryCheck);
+        beneficiary: Option<String>,
+        reason: Symbol,
+    ) -> Result<Option<BytesN<32>>, ContractError> {
+        Self::require_initialized(&env)?;
+        from.require_auth();
+        Self::require_not_halted(&env, roles::RETIREMENTS)?;
+        if amount != 1 {
+            return Err(ContractError::InvalidAmount);
+        }
 
-        if regulatory_contract.is_none() {
-            return Ok(true);
+        Self::check_transferable(&env, &from, token_id)?;
+        let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
+        Self::require_free_balance(&env, &from, &retirement_tracker, 1)?;
+        let sink = retirement_tracker.clone();
+        if !Self::before_transfer(env.clone(), from.clone(), sink, token_id)? {
+            return Err(ContractError::ComplianceFailed);
         }
 
-        let host_jurisdiction: Option<String> = env
-            .storage()
-            .instance()
-            .get(&DataKey::HostJurisdiction);
+        Self::set_status_internal(env.clone(), token_id, AssetStatus::Retired, from.clone())?;
+        Self::remove_token_from_owner(env.clone(), from.clone(), token_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Burned(token_id), &true);
+        env.storage().persistent().remove(&DataKey::Owner(token_id));
+        Self::add_retired_supply(&env);
+        Self::record_burn_receipt(&env, from.clone(), token_id);
+
+        // The tracker cannot call back into this contract mid-call, so it
+        // is handed the metadata rather than fetching it
+        let cert_id = match beneficiary.clone() {
+            Some(beneficiary) => {
+                let metadata = Self::get_metadata(env.clone(), token_id)?;
+                let symbol = Symbol::new(&env, "record_direct_retirement");
+                let mut args = Vec::new(&env);
+                args.push_back(from.into_val(&env));
+                args.push_back(token_id.into_val(&env));
+                args.push_back(metadata.into_val(&env));
+                args.push_back(beneficiary.into_val(&env));
+                args.push_back(reason.into_val(&env));
+                let cert_id: BytesN<32> = env.invoke_contract(&retirement_tracker, &symbol, args);
+                Some(cert_id)
+            }
+            None => None,
+        };
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        DirectRetirementEvent {
+            sequence: next_sequence,
+            token_id,
+            from,
+            beneficiary,
+            cert_id: cert_id.clone(),
+        }
+        .publish(&env);
+        Ok(cert_id)
+    }
+
+    // Escrow a token inside the RetirementTracker while a retirement request is pending.
+    pub fn escrow_for_retirement(
+        env: Env,
+        token_id: u32,
+        from: Address,
+    ) -> Result<(), ContractError> {
+        let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
+        retirement_tracker.require_auth();
+        Self::require_not_halted(&env, roles::RETIREMENTS)?;
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if owner != from {
+            return Err(ContractError::NotOwner);
+        }
+
+        if Self::is_burned(env.clone(), token_id)? {
+            return Err(ContractError::TokenAlreadyBurned);
+        }
+
+        Self::require_not_paused(&env)?;
+        if Self::is_token_frozen(env.clone(), token_id) {
+            return Err(ContractError::TokenFrozen);
+        }
+
+        let status = Self::get_status(env.clone(), token_id)?;
+        if status != AssetStatus::Issued && status != AssetStatus::Listed {
+            return Err(ContractError::TransferNotAllowed);
+        }
+        if Self::listed_amount(env.clone(), from.clone(), token_id) > 0 {
+            return Err(ContractError::TokenListed);
+        }
+        Self::require_free_balance(&env, &from, &retirement_tracker, 1)?;
+
+        Self::lock_token(&env, token_id)?;
+        let escrow = retirement_tracker.clone();
+        if !Self::before_transfer(env.clone(), from.clone(), escrow, token_id)? {
+            return Err(ContractError::ComplianceFailed);
+        }
+
+        Self::remove_token_from_owner(env.clone(), from, token_id)?;
+        Self::add_token_to_owner(env.clone(), retirement_tracker.clone(), token_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owner(token_id), &retirement_tracker);
+
+        Self::set_status_internal(
+            env.clone(),
+            token_id,
+            AssetStatus::Locked,
+            retirement_tracker,
+        )?;
+        Self::mark_escrowed(&env, token_id);
+        Self::unlock_token(&env, token_id);
+        Ok(())
+    }
+
+    // Return an escrowed token to its holder when a retirement request is cancelled.
+    pub fn release_from_escrow(env: Env, token_id: u32, to: Address) -> Result<(), ContractError> {
+        let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
+        retirement_tracker.require_auth();
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if owner != retirement_tracker {
+            return Err(ContractError::NotOwner);
+        }
+
+        let status = Self::get_status(env.clone(), token_id)?;
+        if status != AssetStatus::Locked {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        Self::remove_token_from_owner(env.clone(), retirement_tracker.clone(), token_id)?;
+        Self::add_token_to_owner(env.clone(), to.clone(), token_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owner(token_id), &to);
+
+        Self::set_status_internal(env, token_id, AssetStatus::Issued, retirement_tracker)
+    }
+
+    // ====================================================================
+    // Compliance Hook
+    // ====================================================================
+
+    pub fn before_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<bool, ContractError> {
+        Ok(Self::run_compliance_check(env, from, to, token_id)?.0)
+    }
+
+    // The verdict of `before_transfer`, with the rule version reported by
+    // `check_transfer` when it ran.
+    fn run_compliance_check(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<(bool, Option<u64>), ContractError> {
+        let _status = Self::get_status(env.clone(), token_id)?;
+
+        let regulatory_contract: Option<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RegulatoryCheck);
+
+        if regulatory_contract.is_none() {
+            return Ok((true, None));
+        }
+
+        let host_jurisdiction: Option<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::HostJurisdiction);
 
         let host_jurisdiction = match host_jurisdiction {
             Some(value) => value,
@@ -328,8 +1048,7 @@ impl CarbonAsset {
         };
 
         let contract = regulatory_contract.unwrap();
-        let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
-        let operation = if to == retirement_tracker {
+        let operation = if Self::is_retire_sink(env.clone(), to.clone())? {
             OperationType::RETIREMENT
         } else {
             OperationType::TRANSFER
@@ -342,8 +1061,25 @@ impl CarbonAsset {
         args.push_back(host_jurisdiction.into_val(&env));
 
         let result: ValidationResult = env.invoke_contract(&contract, &symbol, args);
+        if !result.is_compliant || result.requires_authorization {
+            return Ok((false, None));
+        }
+        if operation != OperationType::TRANSFER {
+            return Ok((true, None));
+        }
+
+        // Corridor, KYC and daily volume screening; each credit counts as one unit
+        let metadata = Self::get_metadata(env.clone(), token_id)?;
+        let symbol = Symbol::new(&env, "check_transfer");
+        let mut args = Vec::new(&env);
+        args.push_back(from.into_val(&env));
+        args.push_back(to.into_val(&env));
+        args.push_back(1i128.into_val(&env));
+        args.push_back(token_id.into_val(&env));
+        args.push_back(metadata.project_id.into_val(&env));
 
-        Ok(result.is_compliant && !result.requires_authorization)
+        let decision: TransferDecision = env.invoke_contract(&contract, &symbol, args);
+        Ok((decision.allowed, Some(decision.rule_version)))
     }
 
     // ====================================================================
@@ -355,12 +1091,10 @@ impl CarbonAsset {
         caller: Address,
         token_id: u32,
         new_status: AssetStatus,
+        reason: StatusReason,
+        note: Option<String>,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
-        let admin = Self::get_admin(env.clone())?;
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
-        }
+        access_control::require_admin(&env, &caller)?;
 
         let current = Self::get_status(env.clone(), token_id)?;
         if current == AssetStatus::Retired || current == AssetStatus::Invalidated {
@@ -371,24 +1105,331 @@ impl CarbonAsset {
             return Err(ContractError::InvalidStatusTransition);
         }
 
-        Self::set_status_internal(env, token_id, new_status, caller)
+        Self::set_status_with_reason(env, token_id, new_status, caller, Some(reason), note)
+    }
+
+    pub fn invalidate(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        reason: StatusReason,
+        note: Option<String>,
+    ) -> Result<(), ContractError> {
+        let status = AssetStatus::Invalidated;
+        Self::set_status(env, caller, token_id, status, reason, note)
+    }
+
+    // Return a Locked token to Issued once the verification registry has an
+    // attestation covering its project and vintage. Callable by anyone.
+    pub fn unlock_verified(env: Env, token_id: u32) -> Result<(), ContractError> {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::VerificationRegistry)
+            .ok_or(ContractError::VerificationRegistryNotSet)?;
+
+        let status = Self::get_status(env.clone(), token_id)?;
+        if status != AssetStatus::Locked {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        // Tokens escrowed for retirement are also Locked; only the tracker releases them
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if owner == Self::get_retirement_tracker(env.clone())? {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        let metadata = Self::get_metadata(env.clone(), token_id)?;
+        let symbol = Symbol::new(&env, "is_verified");
+        let mut args = Vec::new(&env);
+        args.push_back(metadata.project_id.into_val(&env));
+        args.push_back(metadata.vintage_year.into_val(&env));
+        let verified: bool = env.invoke_contract(&registry, &symbol, args);
+        if !verified {
+            return Err(ContractError::NotVerified);
+        }
+
+        Self::set_status_internal(env, token_id, AssetStatus::Issued, registry)
     }
 
     // ====================================================================
-    // Quality Score Updates
+    // Marketplace Hooks
     // ====================================================================
 
-    pub fn set_oracle(env: Env, caller: Address, oracle: Address) -> Result<(), ContractError> {
-        caller.require_auth();
-        let admin = Self::get_admin(env.clone())?;
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
+    // Mark a token as offered for sale. Only an approved marketplace may
+    // list or delist. The status is informational: what an owner cannot
+    // move is tracked by `list_tokens`.
+    pub fn list(env: Env, marketplace: Address, token_id: u32) -> Result<(), ContractError> {
+        Self::require_marketplace(&env, &marketplace)?;
+        Self::require_not_halted(&env, roles::LISTINGS)?;
+
+        if Self::get_status(env.clone(), token_id)? != AssetStatus::Issued {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+        Self::set_status_internal(env, token_id, AssetStatus::Listed, marketplace)
+    }
+
+    pub fn delist(env: Env, marketplace: Address, token_id: u32) -> Result<(), ContractError> {
+        Self::require_marketplace(&env, &marketplace)?;
+
+        if Self::get_status(env.clone(), token_id)? != AssetStatus::Listed {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+        Self::set_status_internal(env, token_id, AssetStatus::Issued, marketplace)
+    }
+
+    // Record `token_ids`, which `owner` must hold and could transfer, as
+    // listed on `marketplace`. A token is listed on one marketplace at a
+    // time, and a listed token cannot be transferred, escrowed or picked for
+    // an amount transfer until it is delisted or sold.
+    pub fn list_tokens(
+        env: Env,
+        marketplace: Address,
+        owner: Address,
+        token_ids: Vec<u32>,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        Self::require_marketplace(&env, &marketplace)?;
+        Self::require_not_halted(&env, roles::LISTINGS)?;
+        Self::check_token_set(&token_ids)?;
+
+        for token_id in token_ids.iter() {
+            Self::check_transferable(&env, &owner, token_id)?;
+            Self::adjust_listed_amount(&env, &marketplace, &owner, token_id, 1);
+        }
+        Ok(())
+    }
+
+    // Release `token_ids` from what `marketplace` holds listed for `owner`.
+    // A marketplace that has since been removed may still delist.
+    pub fn delist_tokens(
+        env: Env,
+        marketplace: Address,
+        owner: Address,
+        token_ids: Vec<u32>,
+    ) -> Result<(), ContractError> {
+        marketplace.require_auth();
+        Self::check_token_set(&token_ids)?;
+
+        for token_id in token_ids.iter() {
+            let listed = Self::listed_on(env.clone(), owner.clone(), token_id, marketplace.clone());
+            if listed == 0 {
+                return Err(ContractError::InvalidAmount);
+            }
+            Self::adjust_listed_amount(&env, &marketplace, &owner, token_id, -1);
+        }
+        Ok(())
+    }
+
+    // List a token with an asking price for deployments without a
+    // marketplace contract. Callable by the admin or the holder; calling it
+    // again on a token listed this way updates the price. The price is
+    // cleared whenever the token leaves Listed.
+    pub fn list_with_price(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        price: i128,
+    ) -> Result<(), ContractError> {
+        Self::require_holder_or_admin(&env, &caller, token_id)?;
+        Self::require_not_halted(&env, roles::LISTINGS)?;
+        if price <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let price_key = DataKey::ListPrice(token_id);
+        match Self::get_status(env.clone(), token_id)? {
+            AssetStatus::Issued => Self::set_status_internal(
+                env.clone(),
+                token_id,
+                AssetStatus::Listed,
+                caller.clone(),
+            )?,
+            // Marketplace listings are managed by the marketplace
+            AssetStatus::Listed if env.storage().persistent().has(&price_key) => {}
+            _ => return Err(ContractError::InvalidStatusTransition),
+        }
+        env.storage().persistent().set(&price_key, &price);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        ListPriceSetEvent {
+            sequence: next_sequence,
+            token_id,
+            price,
+            listed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Withdraw a listing made with `list_with_price`.
+    pub fn unlist(env: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        Self::require_holder_or_admin(&env, &caller, token_id)?;
+        if Self::get_list_price(env.clone(), token_id).is_none() {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+        Self::set_status_internal(env, token_id, AssetStatus::Issued, caller)
+    }
+
+    // Settle a marketplace sale of `token_ids` from `from` to `to` at
+    // `price_per_unit` in `payment_token`. Exactly those tokens move. Any of
+    // them listed on `marketplace` is released first; a token listed on
+    // another marketplace fails the sale with `TokenListed`. For every token
+    // with a royalty, the royalty on `price_per_unit` is pulled from `to`
+    // through its payment token allowance to this contract and paid to the
+    // developer. Spends `from`'s allowance to the marketplace. Only an
+    // approved marketplace may call this; a failed payment or transfer
+    // reverts the whole sale.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_with_sale_price(
+        env: Env,
+        marketplace: Address,
+        from: Address,
+        to: Address,
+        token_ids: Vec<u32>,
+        price_per_unit: i128,
+        payment_token: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        Self::require_marketplace(&env, &marketplace)?;
+        Self::require_not_halted(&env, roles::TRANSFERS)?;
+        Self::check_token_set(&token_ids)?;
+
+        for token_id in token_ids.iter() {
+            if Self::listed_on(env.clone(), from.clone(), token_id, marketplace.clone()) > 0 {
+                Self::adjust_listed_amount(&env, &marketplace, &from, token_id, -1);
+            }
+        }
+
+        for token_id in token_ids.iter() {
+            let Some((developer, royalty)) = Self::royalty_due(&env, token_id, price_per_unit)?
+            else {
+                continue;
+            };
+            let payment = token::TokenClient::new(&env, &payment_token);
+            payment.transfer_from(&env.current_contract_address(), &to, &developer, &royalty);
+
+            let sequence: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::EventSequence)
+                .unwrap_or(0u64);
+            let next_sequence = sequence + 1;
+            env.storage()
+                .instance()
+                .set(&DataKey::EventSequence, &next_sequence);
+            RoyaltyPaidEvent {
+                sequence: next_sequence,
+                token_id,
+                developer,
+                payer: to.clone(),
+                payment_token: payment_token.clone(),
+                sale_price: price_per_unit,
+                royalty,
+            }
+            .publish(&env);
+        }
+
+        Self::transfer_sale_internal(env, marketplace, from, to, token_ids)
+    }
+
+    pub fn get_list_price(env: Env, token_id: u32) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ListPrice(token_id))
+    }
+
+    // ====================================================================
+    // Royalties
+    // ====================================================================
+
+    // Pay `developer` `bps` basis points of every sale of `token_id` settled
+    // through `transfer_with_sale_price`. Zero removes the royalty; plain
+    // transfers never pay one.
+    pub fn set_royalty(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        developer: Address,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        Self::get_status(env.clone(), token_id)?;
+        if bps > MAX_ROYALTY_BPS {
+            return Err(ContractError::InvalidRoyalty);
+        }
+
+        let key = DataKey::Royalty(token_id);
+        if bps == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            let royalty = RoyaltyInfo {
+                developer: developer.clone(),
+                bps,
+            };
+            env.storage().persistent().set(&key, &royalty);
         }
 
-        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        RoyaltySetEvent {
+            token_id,
+            developer,
+            bps,
+        }
+        .publish(&env);
         Ok(())
     }
 
+    // The developer and royalty owed on a sale of `token_id` at
+    // `sale_price`, rounded down; `None` when the token has no royalty.
+    pub fn get_royalty_info(
+        env: Env,
+        token_id: u32,
+        sale_price: i128,
+    ) -> Result<Option<(Address, i128)>, ContractError> {
+        if sale_price < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let Some(royalty) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, RoyaltyInfo>(&DataKey::Royalty(token_id))
+        else {
+            return Ok(None);
+        };
+
+        let amount = sale_price
+            .checked_mul(royalty.bps as i128)
+            .ok_or(ContractError::InvalidAmount)?
+            / 10_000;
+        Ok(Some((royalty.developer, amount)))
+    }
+
+    // ====================================================================
+    // Token Freeze
+    // ====================================================================
+
+    // Block transfers and retirement of a single token without touching its
+    // lifecycle status, e.g. while one project's credits are investigated.
+    pub fn freeze_token(env: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        Self::set_token_frozen(env, caller, token_id, true)
+    }
+
+    pub fn unfreeze_token(env: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        Self::set_token_frozen(env, caller, token_id, false)
+    }
+
+    // ====================================================================
+    // Quality Score Updates
+    // ====================================================================
+
     pub fn update_quality_score(
         env: Env,
         caller: Address,
@@ -439,228 +1480,962 @@ impl CarbonAsset {
     }
 
     // ====================================================================
-    // Admin Configuration
+    // Portfolio Valuation
     // ====================================================================
 
-    pub fn set_retirement_tracker(
+    // Sum the value of the tokens in `token_ids` that `holder` owns, pricing
+    // each credit with `price_oracle.get_price(project_id, vintage_year)`.
+    // Retired, invalidated and burned tokens are worth nothing. With
+    // `quality_weighted`, each price is scaled by the token's quality score,
+    // where QUALITY_SCORE_SCALE is full price.
+    pub fn portfolio_value(
         env: Env,
-        caller: Address,
-        retirement_tracker: Address,
-    ) -> Result<(), ContractError> {
-        caller.require_auth();
-        let admin = Self::get_admin(env.clone())?;
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
+        holder: Address,
+        token_ids: Vec<u32>,
+        price_oracle: Address,
+        quality_weighted: bool,
+    ) -> Result<i128, ContractError> {
+        let mut total: i128 = 0;
+        for token_id in token_ids.iter() {
+            // A burned token has no owner, so `owner_of` would fail
+            if Self::is_burned(env.clone(), token_id)?
+                || Self::owner_of(env.clone(), token_id)? != holder
+            {
+                continue;
+            }
+            let status = Self::get_status(env.clone(), token_id)?;
+            if status == AssetStatus::Retired || status == AssetStatus::Invalidated {
+                continue;
+            }
+
+            let metadata = Self::get_metadata(env.clone(), token_id)?;
+            let mut value = Self::call_price_oracle(&env, &price_oracle, metadata);
+            if quality_weighted {
+                let score = Self::get_quality_score(env.clone(), token_id)?;
+                value = value
+                    .checked_mul(score)
+                    .ok_or(ContractError::InvalidAmount)?
+                    / QUALITY_SCORE_SCALE;
+            }
+            total = total
+                .checked_add(value)
+                .ok_or(ContractError::InvalidAmount)?;
+        }
+        Ok(total)
+    }
+
+    // Count how many of `token_ids` are in each status, for portfolio
+    // summaries. Statuses with no tokens are left out, and an id listed
+    // twice is counted twice.
+    pub fn status_breakdown(
+        env: Env,
+        token_ids: Vec<u32>,
+    ) -> Result<Map<AssetStatus, u32>, ContractError> {
+        let mut counts: Map<AssetStatus, u32> = Map::new(&env);
+        for token_id in token_ids.iter() {
+            let status = Self::get_status(env.clone(), token_id)?;
+            let count = counts.get(status.clone()).unwrap_or(0);
+            counts.set(status, count + 1);
+        }
+        Ok(counts)
+    }
+
+    fn set_token_frozen(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        frozen: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_role(&env, roles::FREEZER, &caller)?;
+
+        Self::get_status(env.clone(), token_id)?;
+        if Self::is_token_frozen(env.clone(), token_id) == frozen {
+            return Ok(());
+        }
+
+        let key = DataKey::FrozenToken(token_id);
+        if frozen {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
         }
 
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
         env.storage()
             .instance()
-            .set(&DataKey::RetirementTracker, &retirement_tracker);
+            .set(&DataKey::EventSequence, &next_sequence);
+        TokenFreezeEvent {
+            sequence: next_sequence,
+            token_id,
+            frozen,
+            changed_by: caller,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
-    pub fn set_regulatory_check(
+    fn update_token_metadata(
         env: Env,
         caller: Address,
-        regulatory_check: Address,
+        key: DataKey,
+        field: Symbol,
+        value: String,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
-        let admin = Self::get_admin(env.clone())?;
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
-        }
+        access_control::require_admin(&env, &caller)?;
 
+        let old_value: String = env
+            .storage()
+            .instance()
+            .get(&key)
+            .ok_or(ContractError::NotInitialized)?;
+        env.storage().instance().set(&key, &value);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
         env.storage()
             .instance()
-            .set(&DataKey::RegulatoryCheck, &regulatory_check);
+            .set(&DataKey::EventSequence, &next_sequence);
+        MetadataUpdatedEvent {
+            sequence: next_sequence,
+            field,
+            old_value,
+            new_value: value,
+            updated_by: caller,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
-    pub fn set_host_jurisdiction(
+    // Count `amount` new credits against the (project, vintage) supply.
+    fn reserve_verified_supply(
         env: Env,
-        caller: Address,
-        host_jurisdiction: String,
+        metadata: &CarbonAssetMetadata,
+        amount: u32,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
-        let admin = Self::get_admin(env.clone())?;
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
+        let new_total = Self::check_verified_supply(&env, metadata, amount)?;
+        let key = DataKey::MintedSupply(metadata.project_id.clone(), metadata.vintage_year);
+        env.storage().persistent().set(&key, &new_total);
+        Ok(())
+    }
+
+    // The (project, vintage) supply after minting `amount` more. With a
+    // verification registry configured, the vintage must be attested and the
+    // running total may not exceed the attested amount.
+    fn check_verified_supply(
+        env: &Env,
+        metadata: &CarbonAssetMetadata,
+        amount: u32,
+    ) -> Result<i128, ContractError> {
+        let project_id = metadata.project_id.clone();
+        let vintage_year = metadata.vintage_year;
+        let minted = Self::get_minted_supply(env.clone(), project_id.clone(), vintage_year);
+        let new_total = minted
+            .checked_add(amount as i128)
+            .ok_or(ContractError::SupplyOverflow)?;
+
+        if let Some(registry) = Self::get_verification_registry(env.clone()) {
+            let mut args = Vec::new(env);
+            args.push_back(project_id.into_val(env));
+            args.push_back(vintage_year.into_val(env));
+
+            let symbol = Symbol::new(env, "is_verified");
+            let verified: bool = env.invoke_contract(&registry, &symbol, args.clone());
+            if !verified {
+                return Err(ContractError::NotVerified);
+            }
+
+            let symbol = Symbol::new(env, "get_verified_amount");
+            let verified_amount: i128 = env.invoke_contract(&registry, &symbol, args);
+            if new_total > verified_amount {
+                return Err(ContractError::ExceedsVerifiedAmount);
+            }
+        }
+        Ok(new_total)
+    }
+
+    // The ids the next `amount` mints will receive.
+    fn next_token_ids(env: &Env, amount: u32) -> Result<Vec<u32>, ContractError> {
+        let first: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .ok_or(ContractError::NotInitialized)?;
+        first
+            .checked_add(amount)
+            .ok_or(ContractError::SupplyOverflow)?;
+
+        let mut token_ids = Vec::new(env);
+        for token_id in first..first + amount {
+            token_ids.push_back(token_id);
+        }
+        Ok(token_ids)
+    }
+
+    // Token ids under `index_key` are kept in mint order.
+    fn append_token_index<K: IntoVal<Env, Val>>(env: &Env, index_key: &K, token_id: u32) {
+        let mut token_ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(index_key)
+            .unwrap_or(Vec::new(env));
+        token_ids.push_back(token_id);
+        env.storage().persistent().set(index_key, &token_ids);
+    }
+
+    fn token_index_page<K: IntoVal<Env, Val>>(
+        env: &Env,
+        index_key: &K,
+        start: u32,
+        limit: u32,
+    ) -> Vec<u32> {
+        let token_ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(index_key)
+            .unwrap_or(Vec::new(env));
+        let end = start.saturating_add(limit).min(token_ids.len());
+        if start >= end {
+            return Vec::new(env);
+        }
+        token_ids.slice(start..end)
+    }
+
+    // Segment 0 is the index's original entry, so indexes written before
+    // segmentation remain readable.
+    fn index_segment(env: &Env, index_key: &DataKey, segment: u32) -> Vec<u32> {
+        let storage = env.storage().persistent();
+        let segment_ids = if segment == 0 {
+            storage.get(index_key)
+        } else {
+            storage.get(&ExtendedKey::IndexSegment(index_key.clone(), segment))
+        };
+        segment_ids.unwrap_or(Vec::new(env))
+    }
+
+    fn set_index_segment(env: &Env, index_key: &DataKey, segment: u32, segment_ids: &Vec<u32>) {
+        let storage = env.storage().persistent();
+        if segment == 0 {
+            storage.set(index_key, segment_ids);
+        } else {
+            storage.set(
+                &ExtendedKey::IndexSegment(index_key.clone(), segment),
+                segment_ids,
+            );
         }
+    }
 
+    // Number of ids held by each segment of `index_key`, so that a page can
+    // skip to its first segment without loading the ones before it. An index
+    // written before segmentation is its one original entry.
+    fn index_segment_lens(env: &Env, index_key: &DataKey) -> Vec<u32> {
         env.storage()
+            .persistent()
+            .get(&ExtendedKey::IndexSegmentLens(index_key.clone()))
+            .unwrap_or_else(|| vec![env, Self::index_segment(env, index_key, 0).len()])
+    }
+
+    // Token ids under `index_key` are kept in mint order, a new segment
+    // starting once the last one holds `TOKEN_INDEX_SEGMENT` ids.
+    fn append_segmented_index(env: &Env, index_key: &DataKey, token_id: u32) {
+        let mut lens = Self::index_segment_lens(env, index_key);
+        let mut segment = lens.len() - 1;
+        let mut segment_ids = Self::index_segment(env, index_key, segment);
+        if segment_ids.len() >= TOKEN_INDEX_SEGMENT {
+            segment += 1;
+            segment_ids = Vec::new(env);
+            lens.push_back(0);
+        }
+        segment_ids.push_back(token_id);
+        lens.set(segment, segment_ids.len());
+        Self::set_index_segment(env, index_key, segment, &segment_ids);
+        env.storage()
+            .persistent()
+            .set(&ExtendedKey::IndexSegmentLens(index_key.clone()), &lens);
+    }
+
+    fn segmented_index_page(env: &Env, index_key: &DataKey, start: u32, limit: u32) -> Vec<u32> {
+        let mut page = Vec::new(env);
+        let mut skip = start;
+        for (segment, len) in Self::index_segment_lens(env, index_key).iter().enumerate() {
+            if page.len() >= limit {
+                break;
+            }
+            if skip >= len {
+                skip -= len;
+                continue;
+            }
+            let segment_ids = Self::index_segment(env, index_key, segment as u32);
+            let end = skip.saturating_add(limit - page.len()).min(segment_ids.len());
+            page.append(&segment_ids.slice(skip..end));
+            skip = 0;
+        }
+        page
+    }
+
+    // The last serial batch in `bucket` starting at or before `token_id`.
+    fn serial_batch_at_or_before(env: &Env, bucket: u32, token_id: u32) -> Option<u32> {
+        let batches: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&ExtendedKey::SerialBatches(bucket))?;
+        match batches.binary_search(token_id) {
+            Ok(index) => batches.get(index),
+            Err(0) => None,
+            Err(index) => batches.get(index - 1),
+        }
+    }
+
+    // Report issuance to the methodology library, when one is configured, so
+    // it can count projects and credits per methodology. This contract must
+    // be one of the library's reporters or minting fails.
+    fn report_methodology_usage(env: &Env, metadata: &CarbonAssetMetadata, amount: u32) {
+        let Some(library) = Self::get_methodology_library(env.clone()) else {
+            return;
+        };
+        let symbol = Symbol::new(env, "register_project_usage");
+        let mut args = Vec::new(env);
+        args.push_back(env.current_contract_address().into_val(env));
+        args.push_back(metadata.methodology_id.into_val(env));
+        args.push_back(metadata.project_id.into_val(env));
+        args.push_back((amount as i128).into_val(env));
+        env.invoke_contract::<()>(&library, &symbol, args);
+    }
+
+    // The mint fee configuration and the fee owed for minting `credits`;
+    // `None` when no fee is charged. Shared with `simulate_mint`.
+    fn mint_fee_due(env: &Env, credits: u32) -> Result<Option<(MintFee, i128)>, ContractError> {
+        let Some(mint_fee) = Self::get_mint_fee(env.clone()) else {
+            return Ok(None);
+        };
+        if mint_fee.amount == 0 {
+            return Ok(None);
+        }
+        let fee = mint_fee
+            .amount
+            .checked_mul(credits as i128)
+            .ok_or(ContractError::InvalidAmount)?;
+        Ok(Some((mint_fee, fee)))
+    }
+
+    // The developer and a non-zero royalty owed on a sale of `token_id` at
+    // `sale_price`. Shared with `simulate_sale`.
+    fn royalty_due(
+        env: &Env,
+        token_id: u32,
+        sale_price: i128,
+    ) -> Result<Option<(Address, i128)>, ContractError> {
+        Ok(Self::get_royalty_info(env.clone(), token_id, sale_price)?
+            .filter(|(_, royalty)| *royalty > 0))
+    }
+
+    // Collect the mint fee for `credits` from `payer`. A failed payment,
+    // e.g. for lack of balance, fails the mint with `MintFeeFailed`.
+    fn charge_mint_fee(env: &Env, payer: &Address, credits: u32) -> Result<(), ContractError> {
+        let Some((mint_fee, fee)) = Self::mint_fee_due(env, credits)? else {
+            return Ok(());
+        };
+
+        let fee_token = token::TokenClient::new(env, &mint_fee.fee_token);
+        match fee_token.try_transfer(payer, &mint_fee.treasury, &fee) {
+            Ok(Ok(())) => {}
+            _ => return Err(ContractError::MintFeeFailed),
+        }
+
+        let sequence: u64 = env
+            .storage()
             .instance()
-            .set(&DataKey::HostJurisdiction, &host_jurisdiction);
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        MintFeePaidEvent {
+            sequence: next_sequence,
+            payer: payer.clone(),
+            fee_token: mint_fee.fee_token,
+            fee,
+            credits,
+            treasury: mint_fee.treasury,
+        }
+        .publish(env);
         Ok(())
     }
 
-    // ====================================================================
-    // Getters
-    // ====================================================================
+    fn mint_internal(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        metadata: CarbonAssetMetadata,
+        origin: Option<OriginInfo>,
+    ) -> Result<u32, ContractError> {
+        let token_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .ok_or(ContractError::NotInitialized)?;
 
-    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        let next_token_id = token_id
+            .checked_add(1)
+            .ok_or(ContractError::SupplyOverflow)?;
         env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(ContractError::NotInitialized)
-    }
+            .set(&DataKey::NextTokenId, &next_token_id);
 
-    pub fn get_name(env: Env) -> Result<String, ContractError> {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owner(token_id), &owner);
+        Self::add_token_to_owner(env.clone(), owner.clone(), token_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Metadata(token_id), &metadata);
+        Self::append_segmented_index(
+            &env,
+            &DataKey::MethodologyTokens(metadata.methodology_id),
+            token_id,
+        );
+        Self::append_segmented_index(
+            &env,
+            &DataKey::VintageTokens(metadata.vintage_year),
+            token_id,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Status(token_id), &AssetStatus::Issued);
+        env.storage()
+            .persistent()
+            .set(&DataKey::QualityScore(token_id), &0i128);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Burned(token_id), &false);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MintedAt(token_id), &env.ledger().timestamp());
+
+        let origin_registry = origin.as_ref().map(|origin| origin.registry.clone());
+        if let Some(origin) = origin {
+            let index_key = DataKey::RegistryTokens(origin.registry.clone());
+            Self::append_segmented_index(&env, &index_key, token_id);
+            env.storage()
+                .persistent()
+                .set(&DataKey::OriginInfo(token_id), &origin);
+        }
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let mint_sequence = sequence + 1;
         env.storage()
             .instance()
-            .get(&DataKey::Name)
-            .ok_or(ContractError::NotInitialized)
+            .set(&DataKey::EventSequence, &mint_sequence);
+        MintEvent {
+            sequence: mint_sequence,
+            token_id,
+            owner: owner.clone(),
+            project_id: metadata.project_id.clone(),
+            vintage_year: metadata.vintage_year,
+            methodology_id: metadata.methodology_id,
+            origin_registry,
+        }
+        .publish(&env);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let status_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &status_sequence);
+        StatusChangeEvent {
+            sequence: status_sequence,
+            token_id,
+            old_status: None,
+            new_status: AssetStatus::Issued,
+            changed_by: caller,
+            reason: None,
+            note: None,
+        }
+        .publish(&env);
+
+        Ok(token_id)
+    }
+
+    fn transfer_token_internal(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        require_auth: bool,
+    ) -> Result<(), ContractError> {
+        if require_auth {
+            from.require_auth();
+        }
+
+        Self::check_transferable(&env, &from, token_id)?;
+        Self::lock_token(&env, token_id)?;
+        let (allowed, rule_version) =
+            Self::run_compliance_check(env.clone(), from.clone(), to.clone(), token_id)?;
+        if !allowed {
+            return Err(ContractError::ComplianceFailed);
+        }
+
+        Self::apply_token_transfer(env, from, to, token_id, rule_version)
+    }
+
+    fn check_transferable(env: &Env, from: &Address, token_id: u32) -> Result<(), ContractError> {
+        Self::check_token_state(env, from, token_id)?;
+        if Self::listed_amount(env.clone(), from.clone(), token_id) > 0 {
+            return Err(ContractError::TokenListed);
+        }
+        Ok(())
+    }
+
+    // Everything `check_transferable` checks except listings.
+    fn check_token_state(env: &Env, from: &Address, token_id: u32) -> Result<(), ContractError> {
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if owner != *from {
+            return Err(ContractError::NotOwner);
+        }
+
+        let burned = Self::is_burned(env.clone(), token_id)?;
+        if burned {
+            return Err(ContractError::TokenAlreadyBurned);
+        }
+
+        Self::require_not_paused(env)?;
+        if Self::is_token_frozen(env.clone(), token_id) {
+            return Err(ContractError::TokenFrozen);
+        }
+        if Self::is_cooling_off(env, token_id) {
+            return Err(ContractError::TokenCoolingOff);
+        }
+
+        let status = Self::get_status(env.clone(), token_id)?;
+        if status != AssetStatus::Issued && status != AssetStatus::Listed {
+            return Err(ContractError::TransferNotAllowed);
+        }
+        Ok(())
+    }
+
+    // True while `token_id` is within `mint_lock_seconds` of its mint.
+    fn is_cooling_off(env: &Env, token_id: u32) -> bool {
+        let lock = Self::get_mint_lock_seconds(env.clone());
+        match Self::get_minted_at(env.clone(), token_id) {
+            Some(minted_at) if lock > 0 => {
+                env.ledger().timestamp().saturating_sub(minted_at) < lock
+            }
+            _ => false,
+        }
+    }
+
+    // Mirrors the checks in `transfer_amount_internal`, in the same order.
+    // Returns the tokens the transfer would move.
+    fn check_amount_transfer(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+        check_compliance: bool,
+    ) -> Result<Vec<u32>, ContractError> {
+        Self::require_initialized(env)?;
+        Self::require_not_halted(env, roles::TRANSFERS)?;
+        if amount <= 0 {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        let token_ids = Self::collect_transferable_tokens(env.clone(), from.clone(), amount)?;
+        if from == to {
+            Self::require_not_paused(env)?;
+            return Ok(token_ids);
+        }
+        Self::require_free_balance(env, from, to, amount)?;
+        for token_id in token_ids.iter() {
+            Self::check_token_transfer(env, from, to, token_id, check_compliance)?;
+        }
+        Ok(token_ids)
+    }
+
+    // Mirrors the checks in `transfer_with_sale_price` and
+    // `transfer_sale_internal`, in the same order. Returns the royalty owed
+    // on the sale.
+    fn check_sale(
+        env: &Env,
+        marketplace: &Address,
+        from: &Address,
+        to: &Address,
+        token_ids: &Vec<u32>,
+        price_per_unit: i128,
+    ) -> Result<i128, ContractError> {
+        Self::require_initialized(env)?;
+        Self::require_marketplace(env, marketplace)?;
+        Self::require_not_halted(env, roles::TRANSFERS)?;
+        Self::check_token_set(token_ids)?;
+
+        // The sale releases whatever `marketplace` has listed first
+        let mut released = 0;
+        for token_id in token_ids.iter() {
+            released += Self::listed_on(env.clone(), from.clone(), token_id, marketplace.clone());
+        }
+        let mut royalty: i128 = 0;
+        for token_id in token_ids.iter() {
+            if let Some((_, due)) = Self::royalty_due(env, token_id, price_per_unit)? {
+                royalty = royalty
+                    .checked_add(due)
+                    .ok_or(ContractError::InvalidAmount)?;
+            }
+        }
+
+        let amount = token_ids.len() as i128;
+        Self::spend_allowance(env.clone(), from.clone(), marketplace.clone(), amount)?;
+        let free = Self::free_balance(env.clone(), from.clone()) + released;
+        if from != to && amount > free {
+            return Err(ContractError::InsufficientFreeBalance);
+        }
+        for token_id in token_ids.iter() {
+            Self::check_token_state(env, from, token_id)?;
+            let listed = Self::listed_amount(env.clone(), from.clone(), token_id)
+                - Self::listed_on(env.clone(), from.clone(), token_id, marketplace.clone());
+            if listed > 0 {
+                return Err(ContractError::TokenListed);
+            }
+            if from != to
+                && !Self::before_transfer(env.clone(), from.clone(), to.clone(), token_id)?
+            {
+                return Err(ContractError::ComplianceFailed);
+            }
+        }
+        Ok(royalty)
     }
 
-    pub fn get_symbol(env: Env) -> Result<String, ContractError> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Symbol)
-            .ok_or(ContractError::NotInitialized)
+    // A batch of token ids handled in one call: not empty, within
+    // `MAX_MULTI_TRANSFER` and without repeats.
+    fn check_token_set(token_ids: &Vec<u32>) -> Result<(), ContractError> {
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        if token_ids.len() > MAX_MULTI_TRANSFER {
+            return Err(ContractError::BatchTooLarge);
+        }
+        for (i, token_id) in token_ids.iter().enumerate() {
+            if token_ids.first_index_of(token_id) != Some(i as u32) {
+                return Err(ContractError::DuplicateToken);
+            }
+        }
+        Ok(())
     }
 
-    pub fn decimals(env: Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Decimals)
-            .unwrap_or(0u32)
+    fn check_token_transfer(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        token_id: u32,
+        check_compliance: bool,
+    ) -> Result<(), ContractError> {
+        Self::check_transferable(env, from, token_id)?;
+        // Self-transfers never reach the compliance hook
+        if check_compliance
+            && from != to
+            && !Self::before_transfer(env.clone(), from.clone(), to.clone(), token_id)?
+        {
+            return Err(ContractError::ComplianceFailed);
+        }
+        Ok(())
     }
 
-    pub fn name(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Name)
-            .unwrap()
+    fn failed_simulation(env: &Env, err: ContractError) -> TransferSimulation {
+        TransferSimulation {
+            net_to_recipient: 0,
+            fee: 0,
+            royalty: 0,
+            token_ids: Vec::new(env),
+            would_auto_retire: false,
+            failure: Some(err.reason(env)),
+        }
     }
 
-    pub fn symbol(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Symbol)
-            .unwrap()
+    fn transfer_check(env: &Env, result: Result<(), ContractError>) -> TransferCheck {
+        match result {
+            Ok(()) => TransferCheck {
+                allowed: true,
+                reason: Symbol::new(env, "Allowed"),
+            },
+            Err(err) => TransferCheck {
+                allowed: false,
+                reason: err.reason(env),
+            },
+        }
     }
 
-    pub fn get_retirement_tracker(env: Env) -> Result<Address, ContractError> {
+    // Move a token that has passed `check_transferable` and the compliance
+    // hook, then release its transfer lock.
+    fn apply_token_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        rule_version: Option<u64>,
+    ) -> Result<(), ContractError> {
+        Self::remove_token_from_owner(env.clone(), from.clone(), token_id)?;
+        Self::add_token_to_owner(env.clone(), to.clone(), token_id)?;
         env.storage()
-            .instance()
-            .get(&DataKey::RetirementTracker)
-            .ok_or(ContractError::NotInitialized)
-    }
+            .persistent()
+            .set(&DataKey::Owner(token_id), &to);
 
-    pub fn get_regulatory_check(env: Env) -> Option<Address> {
-        env.storage().instance().get(&DataKey::RegulatoryCheck)
-    }
+        if Self::is_summary_mode(env.clone()) {
+            Self::record_summary_transfer(&env, &from, &to, token_id);
+        } else {
+            let sequence: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::EventSequence)
+                .unwrap_or(0u64);
+            let next_sequence = sequence + 1;
+            env.storage()
+                .instance()
+                .set(&DataKey::EventSequence, &next_sequence);
+            TransferEvent {
+                sequence: next_sequence,
+                token_id,
+                from: from.clone(),
+                to: to.clone(),
+                rule_version,
+            }
+            .publish(&env);
+        }
 
-    pub fn get_host_jurisdiction(env: Env) -> Option<String> {
-        env.storage().instance().get(&DataKey::HostJurisdiction)
-    }
+        if Self::is_retire_sink(env.clone(), to.clone())? {
+            Self::set_status_internal(env.clone(), token_id, AssetStatus::Retired, from.clone())?;
+
+            let sequence: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::EventSequence)
+                .unwrap_or(0u64);
+            let next_sequence = sequence + 1;
+            env.storage()
+                .instance()
+                .set(&DataKey::EventSequence, &next_sequence);
+            AutoRetireEvent {
+                sequence: next_sequence,
+                token_id,
+                from,
+                sink: to,
+            }
+            .publish(&env);
+        } else {
+            Self::record_transfer_volume(env.clone(), from);
+        }
 
-    pub fn get_oracle(env: Env) -> Option<Address> {
-        env.storage().instance().get(&DataKey::Oracle)
+        Self::unlock_token(&env, token_id);
+        Ok(())
     }
 
-    pub fn get_event_sequence(env: Env) -> u64 {
-        env.storage()
+    fn publish_retire_sink_update(env: &Env, sink: Address, added: bool, updated_by: Address) {
+        let sequence: u64 = env
+            .storage()
             .instance()
             .get(&DataKey::EventSequence)
-            .unwrap_or(0u64)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        RetireSinkUpdatedEvent {
+            sequence: next_sequence,
+            sink,
+            added,
+            updated_by,
+        }
+        .publish(env);
     }
 
-    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, ContractError> {
-        let burned: bool = env
+    fn publish_marketplace_update(
+        env: &Env,
+        marketplace: Address,
+        added: bool,
+        updated_by: Address,
+    ) {
+        let sequence: u64 = env
             .storage()
-            .persistent()
-            .get(&DataKey::Burned(token_id))
-            .unwrap_or(false);
-        if burned {
-            return Err(ContractError::TokenNotFound);
-        }
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
         env.storage()
-            .persistent()
-            .get(&DataKey::Owner(token_id))
-            .ok_or(ContractError::TokenNotFound)
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        MarketplaceUpdatedEvent {
+            sequence: next_sequence,
+            marketplace,
+            added,
+            updated_by,
+        }
+        .publish(env);
     }
 
-    pub fn get_metadata(env: Env, token_id: u32) -> Result<CarbonAssetMetadata, ContractError> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Metadata(token_id))
-            .ok_or(ContractError::TokenNotFound)
+    fn require_marketplace(env: &Env, marketplace: &Address) -> Result<(), ContractError> {
+        marketplace.require_auth();
+        if !Self::is_marketplace(env.clone(), marketplace.clone()) {
+            return Err(ContractError::MarketplaceNotApproved);
+        }
+        Ok(())
     }
 
-    pub fn get_status(env: Env, token_id: u32) -> Result<AssetStatus, ContractError> {
+    // Apply `delta` to the marketplace's listed amount and to the owner's
+    // per-token and overall totals, dropping entries that reach zero.
+    fn adjust_listed_amount(
+        env: &Env,
+        marketplace: &Address,
+        owner: &Address,
+        token_id: u32,
+        delta: i128,
+    ) {
+        let venue_key = DataKey::ListedAmount(owner.clone(), token_id, marketplace.clone());
+        let listed = Self::add_to_listed(env, &venue_key, delta);
+        Self::add_to_listed(env, &DataKey::ListedByToken(owner.clone(), token_id), delta);
+        Self::add_to_listed(env, &DataKey::ListedTotal(owner.clone()), delta);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
         env.storage()
-            .persistent()
-            .get(&DataKey::Status(token_id))
-            .ok_or(ContractError::TokenNotFound)
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        ListedAmountUpdatedEvent {
+            sequence: next_sequence,
+            marketplace: marketplace.clone(),
+            owner: owner.clone(),
+            token_id,
+            listed,
+        }
+        .publish(env);
     }
 
-    pub fn get_quality_score(env: Env, token_id: u32) -> Result<i128, ContractError> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::QualityScore(token_id))
-            .ok_or(ContractError::TokenNotFound)
+    fn add_to_listed(env: &Env, key: &DataKey, delta: i128) -> i128 {
+        let amount = env.storage().persistent().get(key).unwrap_or(0i128) + delta;
+        if amount == 0 {
+            env.storage().persistent().remove(key);
+        } else {
+            env.storage().persistent().set(key, &amount);
+        }
+        amount
     }
 
-    pub fn balance_of(env: Env, owner: Address) -> i128 {
-        let tokens: Vec<u32> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(owner))
-            .unwrap_or(Vec::new(&env));
-        tokens.len() as i128
+    // Moving `amount` credits out of `from` must leave at least their listed
+    // total behind. Self-transfers move nothing.
+    fn require_free_balance(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if from != to && amount > Self::free_balance(env.clone(), from.clone()) {
+            return Err(ContractError::InsufficientFreeBalance);
+        }
+        Ok(())
     }
 
-    pub fn tokens_of_owner(env: Env, owner: Address) -> Vec<u32> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(owner))
-            .unwrap_or(Vec::new(&env))
+    fn call_price_oracle(env: &Env, price_oracle: &Address, metadata: CarbonAssetMetadata) -> i128 {
+        let symbol = Symbol::new(env, "get_price");
+        let mut args = Vec::new(env);
+        args.push_back(metadata.project_id.into_val(env));
+        args.push_back(metadata.vintage_year.into_val(env));
+        env.invoke_contract(price_oracle, &symbol, args)
     }
 
-    pub fn is_burned(env: Env, token_id: u32) -> Result<bool, ContractError> {
-        Self::get_status(env.clone(), token_id)?;
-        Ok(env
-            .storage()
-            .persistent()
-            .get(&DataKey::Burned(token_id))
-            .unwrap_or(false))
+    /// Report a completed transfer to the regulatory check so it counts
+    /// against the sender's daily volume limit.
+    fn record_transfer_volume(env: Env, from: Address) {
+        let regulatory_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::RegulatoryCheck);
+
+        if let Some(contract) = regulatory_contract {
+            let symbol = Symbol::new(&env, "record_transfer");
+            let mut args = Vec::new(&env);
+            args.push_back(from.into_val(&env));
+            args.push_back(1i128.into_val(&env));
+            env.invoke_contract::<()>(&contract, &symbol, args);
+        }
     }
 
-    fn transfer_token_internal(
+    // Returns the tokens that moved, in the order they were picked.
+    fn transfer_amount_internal(
         env: Env,
         from: Address,
         to: Address,
-        token_id: u32,
-        require_auth: bool,
-    ) -> Result<(), ContractError> {
-        if require_auth {
-            from.require_auth();
+        amount: i128,
+    ) -> Result<Vec<u32>, ContractError> {
+        if amount <= 0 {
+            return Err(ContractError::InvalidStatusTransition);
         }
 
-        let owner = Self::owner_of(env.clone(), token_id)?;
-        if owner != from {
-            return Err(ContractError::NotOwner);
+        let token_ids = Self::collect_transferable_tokens(env.clone(), from.clone(), amount)?;
+        if from == to {
+            // No net change: skip the ownership writes and compliance hook,
+            // but still emit the transfer event for audit consistency.
+            Self::require_not_paused(&env)?;
+        } else {
+            Self::require_free_balance(&env, &from, &to, amount)?;
+            for i in 0..token_ids.len() {
+                let token_id = token_ids.get(i).unwrap();
+                Self::transfer_token_internal(
+                    env.clone(),
+                    from.clone(),
+                    to.clone(),
+                    token_id,
+                    false,
+                )?;
+            }
         }
-
-        let burned = Self::is_burned(env.clone(), token_id)?;
-        if burned {
-            return Err(ContractError::TokenAlreadyBurned);
+        if Self::is_summary_mode(env.clone()) {
+            return Ok(token_ids);
         }
 
-        let status = Self::get_status(env.clone(), token_id)?;
-        if status != AssetStatus::Issued && status != AssetStatus::Listed {
-            return Err(ContractError::TransferNotAllowed);
-        }
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        Sep41TransferEvent { sequence: next_sequence, from, to, amount }.publish(&env);
 
-        if !Self::before_transfer(env.clone(), from.clone(), to.clone(), token_id)? {
-            return Err(ContractError::ComplianceFailed);
-        }
+        Ok(token_ids)
+    }
 
-        Self::remove_token_from_owner(env.clone(), from.clone(), token_id)?;
-        Self::add_token_to_owner(env.clone(), to.clone(), token_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Owner(token_id), &to);
+    fn transfer_from_internal(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        let allowance = Self::spend_allowance(env.clone(), from.clone(), spender.clone(), amount)?;
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().persistent().set(&key, &allowance);
+
+        let token_ids =
+            Self::transfer_amount_internal(env.clone(), from.clone(), to.clone(), amount)?;
 
         let sequence: u64 = env
             .storage()
@@ -671,35 +2446,44 @@ impl CarbonAsset {
         env.storage()
             .instance()
             .set(&DataKey::EventSequence, &next_sequence);
-        TransferEvent {
+        TransferFromEvent {
             sequence: next_sequence,
-            token_id,
-            from: from.clone(),
-            to: to.clone(),
+            spender,
+            from,
+            to,
+            amount,
+            token_ids,
+            remaining_allowance: allowance.amount,
         }
         .publish(&env);
 
-        let retirement_tracker = Self::get_retirement_tracker(env.clone())?;
-        if to == retirement_tracker {
-            Self::set_status_internal(env, token_id, AssetStatus::Retired, from)?;
-        }
-
         Ok(())
     }
 
-    fn transfer_amount_internal(
+    // `transfer_from_internal` for a sale of exactly `token_ids`. Tokens
+    // flipped to Listed for the sale go back to Issued before they move.
+    fn transfer_sale_internal(
         env: Env,
+        spender: Address,
         from: Address,
         to: Address,
-        amount: i128,
+        token_ids: Vec<u32>,
     ) -> Result<(), ContractError> {
-        if amount <= 0 {
-            return Err(ContractError::InvalidStatusTransition);
-        }
+        let amount = token_ids.len() as i128;
+        let allowance = Self::spend_allowance(env.clone(), from.clone(), spender.clone(), amount)?;
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().persistent().set(&key, &allowance);
 
-        let token_ids = Self::collect_transferable_tokens(env.clone(), from.clone(), amount)?;
-        for i in 0..token_ids.len() {
-            let token_id = token_ids.get(i).unwrap();
+        Self::require_free_balance(&env, &from, &to, amount)?;
+        for token_id in token_ids.iter() {
+            if Self::get_status(env.clone(), token_id)? == AssetStatus::Listed {
+                Self::set_status_internal(
+                    env.clone(),
+                    token_id,
+                    AssetStatus::Issued,
+                    spender.clone(),
+                )?;
+            }
             Self::transfer_token_internal(env.clone(), from.clone(), to.clone(), token_id, false)?;
         }
 
@@ -712,7 +2496,16 @@ impl CarbonAsset {
         env.storage()
             .instance()
             .set(&DataKey::EventSequence, &next_sequence);
-        Sep41TransferEvent { sequence: next_sequence, from, to, amount }.publish(&env);
+        TransferFromEvent {
+            sequence: next_sequence,
+            spender,
+            from,
+            to,
+            amount,
+            token_ids,
+            remaining_allowance: allowance.amount,
+        }
+        .publish(&env);
 
         Ok(())
     }
@@ -746,6 +2539,62 @@ impl CarbonAsset {
         Ok(())
     }
 
+    // The receipt id hashes the burner, amount, token and ledger time. A
+    // token burns once, so ids never collide.
+    fn record_burn_receipt(env: &Env, from: Address, token_id: u32) -> BytesN<32> {
+        let receipt = BurnReceipt {
+            from,
+            token_id,
+            amount: 1,
+            burned_at: env.ledger().timestamp(),
+        };
+        let mut payload = receipt.from.clone().to_xdr(env);
+        payload.append(&Bytes::from_array(env, &receipt.amount.to_be_bytes()));
+        payload.append(&Bytes::from_array(env, &token_id.to_be_bytes()));
+        payload.append(&Bytes::from_array(env, &receipt.burned_at.to_be_bytes()));
+        let receipt_id: BytesN<32> = env.crypto().sha256(&payload).into();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BurnReceipt(receipt_id.clone()), &receipt);
+        receipt_id
+    }
+
+    fn mark_escrowed(env: &Env, token_id: u32) {
+        env.storage()
+            .persistent()
+            .set(&ExtendedKey::Escrowed(token_id), &true);
+        let count = Self::escrowed_count(env.clone());
+        env.storage()
+            .instance()
+            .set(&ExtendedKey::EscrowedCount, &(count + 1));
+    }
+
+    // A token leaves escrow whenever it stops being Locked: released,
+    // burned, or given another status by the admin.
+    fn clear_escrowed(env: &Env, token_id: u32) {
+        let key = ExtendedKey::Escrowed(token_id);
+        if !env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage().persistent().remove(&key);
+        let count = Self::escrowed_count(env.clone());
+        env.storage()
+            .instance()
+            .set(&ExtendedKey::EscrowedCount, &(count - 1));
+    }
+
+    fn add_retired_supply(env: &Env) {
+        let retired: i128 = env
+            .storage()
+            .instance()
+            .get(&ExtendedKey::RetiredSupply)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&ExtendedKey::RetiredSupply, &(retired + 1));
+    }
+
     fn collect_transferable_tokens(
         env: Env,
         owner: Address,
@@ -754,14 +2603,28 @@ impl CarbonAsset {
         let tokens: Vec<u32> = env
             .storage()
             .persistent()
-            .get(&DataKey::OwnerTokens(owner))
+            .get(&DataKey::OwnerTokens(owner.clone()))
             .unwrap_or(Vec::new(&env));
+        Self::pick_transferable_tokens(&env, &owner, &tokens, amount)
+    }
 
-        let mut transferable = Vec::new(&env);
+    // The first `amount` of `owner`'s `tokens` that may move, in order.
+    // Listed tokens are passed over.
+    fn pick_transferable_tokens(
+        env: &Env,
+        owner: &Address,
+        tokens: &Vec<u32>,
+        amount: i128,
+    ) -> Result<Vec<u32>, ContractError> {
+        let mut transferable = Vec::new(env);
         for i in 0..tokens.len() {
             let token_id = tokens.get(i).unwrap();
             let burned = Self::is_burned(env.clone(), token_id)?;
-            if burned {
+            if burned
+                || Self::is_token_frozen(env.clone(), token_id)
+                || Self::is_cooling_off(env, token_id)
+                || Self::listed_amount(env.clone(), owner.clone(), token_id) > 0
+            {
                 continue;
             }
             let status = Self::get_status(env.clone(), token_id)?;
@@ -818,20 +2681,72 @@ impl CarbonAsset {
     // Internal Helpers
     // ====================================================================
 
+    // Held from the compliance call until the move is recorded, so a
+    // regulatory contract cannot re-enter and move the same token mid-flight.
+    // The host already rejects contract re-entry; this keeps the guarantee
+    // local to the contract. A failed transfer rolls the flag back with the
+    // rest of its writes.
+    fn lock_token(env: &Env, token_id: u32) -> Result<(), ContractError> {
+        let key = DataKey::TransferLock(token_id);
+        if env.storage().instance().has(&key) {
+            return Err(ContractError::ReentrantTransfer);
+        }
+        env.storage().instance().set(&key, &true);
+        Ok(())
+    }
+
+    fn unlock_token(env: &Env, token_id: u32) {
+        env.storage()
+            .instance()
+            .remove(&DataKey::TransferLock(token_id));
+    }
+
+    // Entry points that would otherwise fail on missing configuration or
+    // silently write state call this first.
+    pub(crate) fn require_initialized(env: &Env) -> Result<(), ContractError> {
+        if !access_control::has_admin(env) {
+            return Err(ContractError::NotInitialized);
+        }
+        Ok(())
+    }
+
     fn set_status_internal(
         env: Env,
         token_id: u32,
         new_status: AssetStatus,
         changed_by: Address,
+    ) -> Result<(), ContractError> {
+        Self::set_status_with_reason(env, token_id, new_status, changed_by, None, None)
+    }
+
+    fn set_status_with_reason(
+        env: Env,
+        token_id: u32,
+        new_status: AssetStatus,
+        changed_by: Address,
+        reason: Option<StatusReason>,
+        note: Option<String>,
     ) -> Result<(), ContractError> {
         let current = Self::get_status(env.clone(), token_id)?;
         if current == new_status {
             return Ok(());
         }
+        if current == AssetStatus::Locked {
+            Self::clear_escrowed(&env, token_id);
+        }
 
         env.storage()
             .persistent()
             .set(&DataKey::Status(token_id), &new_status);
+        let price_key = DataKey::ListPrice(token_id);
+        if new_status != AssetStatus::Listed && env.storage().persistent().has(&price_key) {
+            env.storage().persistent().remove(&price_key);
+        }
+        if let Some(reason) = reason.clone() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::LastStatusReason(token_id), &reason);
+        }
 
         let sequence: u64 = env
             .storage()
@@ -848,13 +2763,29 @@ impl CarbonAsset {
             old_status: Some(current),
             new_status,
             changed_by,
+            reason,
+            note,
         }
         .publish(&env);
 
         Ok(())
     }
 
-    fn add_token_to_owner(env: Env, owner: Address, token_id: u32) {
+    fn require_holder_or_admin(
+        env: &Env,
+        caller: &Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if Self::owner_of(env.clone(), token_id)? == *caller
+            || Self::get_admin(env.clone())? == *caller
+        {
+            return Ok(());
+        }
+        Err(ContractError::NotAuthorized)
+    }
+
+    fn add_token_to_owner(env: Env, owner: Address, token_id: u32) -> Result<(), ContractError> {
         let mut tokens: Vec<u32> = env
             .storage()
             .persistent()
@@ -865,10 +2796,11 @@ impl CarbonAsset {
         let index = tokens.len() - 1;
         env.storage()
             .persistent()
-            .set(&DataKey::OwnerTokens(owner), &tokens);
+            .set(&DataKey::OwnerTokens(owner.clone()), &tokens);
         env.storage()
             .persistent()
             .set(&DataKey::TokenIndex(token_id), &index);
+        Self::notify_balance_change(&env, &owner, token_id)
     }
 
     fn remove_token_from_owner(
@@ -888,7 +2820,7 @@ impl CarbonAsset {
             .get(&DataKey::TokenIndex(token_id))
             .ok_or(ContractError::TokenNotFound)?;
 
-        if tokens.len() == 0 {
+        if tokens.is_empty() {
             return Err(ContractError::TokenNotFound);
         }
 
@@ -903,19 +2835,21 @@ impl CarbonAsset {
         }
 
         tokens.pop_back();
-        if tokens.len() == 0 {
-            env.storage().persistent().remove(&DataKey::OwnerTokens(owner));
+        if tokens.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::OwnerTokens(owner.clone()));
         } else {
             env.storage()
                 .persistent()
-                .set(&DataKey::OwnerTokens(owner), &tokens);
+                .set(&DataKey::OwnerTokens(owner.clone()), &tokens);
         }
 
         env.storage()
             .persistent()
             .remove(&DataKey::TokenIndex(token_id));
 
-        Ok(())
+        Self::notify_balance_change(&env, &owner, token_id)
     }
 
     fn spend_allowance(
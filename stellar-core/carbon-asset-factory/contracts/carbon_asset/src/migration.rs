@@ -0,0 +1,163 @@
+use soroban_sdk::{contractimpl, Address, Env};
+
+use crate::errors::ContractError;
+use crate::events::{MigrationClaimedEvent, TokenMigratedEvent};
+use crate::storage::DataKey;
+use crate::types::{AssetStatus, TokenMigration};
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// ========================================================================
+// Token Migration
+// ========================================================================
+
+// Replaces a credit invalidated over a registry data error with one
+// reissued under corrected metadata. The admin mints the replacement to
+// themselves and registers it with `migrate_token`; the holder of the old
+// credit then swaps it with `claim_migration`.
+#[contractimpl]
+impl CarbonAsset {
+    // Point the Invalidated `old_token_id` at `new_token_id`, an Issued
+    // credit of the same project held by the admin. The new credit is held
+    // by this contract until claimed.
+    pub fn migrate_token(
+        env: Env,
+        caller: Address,
+        old_token_id: u32,
+        new_token_id: u32,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        let migration_key = DataKey::Migration(old_token_id);
+        if env.storage().persistent().has(&migration_key) {
+            return Err(ContractError::MigrationExists);
+        }
+
+        if Self::get_status(env.clone(), old_token_id)? != AssetStatus::Invalidated {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+        if Self::get_status(env.clone(), new_token_id)? != AssetStatus::Issued {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+        if Self::owner_of(env.clone(), new_token_id)? != caller {
+            return Err(ContractError::NotOwner);
+        }
+        let old_metadata = Self::get_metadata(env.clone(), old_token_id)?;
+        let new_metadata = Self::get_metadata(env.clone(), new_token_id)?;
+        if old_metadata.project_id != new_metadata.project_id {
+            return Err(ContractError::ProjectMismatch);
+        }
+
+        let custody = env.current_contract_address();
+        Self::move_token(&env, &caller, &custody, new_token_id)?;
+
+        let migration = TokenMigration {
+            old_token_id,
+            new_token_id,
+            migrated_by: caller.clone(),
+            migrated_at: env.ledger().timestamp(),
+            claimed_by: None,
+        };
+        env.storage().persistent().set(&migration_key, &migration);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        TokenMigratedEvent {
+            sequence: next_sequence,
+            old_token_id,
+            new_token_id,
+            migrated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Swap `holder`'s migrated credit for its replacement, 1:1. The old
+    // credit is burned and the migration marked claimed, so it can only be
+    // claimed once. A frozen old credit cannot be claimed until unfrozen.
+    pub fn claim_migration(
+        env: Env,
+        holder: Address,
+        old_token_id: u32,
+    ) -> Result<u32, ContractError> {
+        holder.require_auth();
+        Self::require_not_paused(&env)?;
+        let migration_key = DataKey::Migration(old_token_id);
+        let mut migration: TokenMigration = env
+            .storage()
+            .persistent()
+            .get(&migration_key)
+            .ok_or(ContractError::MigrationNotFound)?;
+        if migration.claimed_by.is_some() {
+            return Err(ContractError::MigrationClaimed);
+        }
+        if Self::owner_of(env.clone(), old_token_id)? != holder {
+            return Err(ContractError::NotOwner);
+        }
+        if Self::is_token_frozen(env.clone(), old_token_id) {
+            return Err(ContractError::TokenFrozen);
+        }
+
+        Self::remove_token_from_owner(env.clone(), holder.clone(), old_token_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Burned(old_token_id), &true);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Owner(old_token_id));
+
+        let custody = env.current_contract_address();
+        let new_token_id = migration.new_token_id;
+        Self::move_token(&env, &custody, &holder, new_token_id)?;
+
+        migration.claimed_by = Some(holder.clone());
+        env.storage().persistent().set(&migration_key, &migration);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        MigrationClaimedEvent {
+            sequence: next_sequence,
+            old_token_id,
+            new_token_id,
+            holder,
+        }
+        .publish(&env);
+        Ok(new_token_id)
+    }
+
+    pub fn get_migration(env: Env, old_token_id: u32) -> Option<TokenMigration> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Migration(old_token_id))
+    }
+}
+
+impl CarbonAsset {
+    // Ownership writes only: migrations bypass the transfer checks and the
+    // compliance hook, as escrow releases do.
+    fn move_token(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
+        Self::remove_token_from_owner(env.clone(), from.clone(), token_id)?;
+        Self::add_token_to_owner(env.clone(), to.clone(), token_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owner(token_id), to);
+        Ok(())
+    }
+}
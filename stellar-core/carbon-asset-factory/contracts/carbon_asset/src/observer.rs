@@ -0,0 +1,82 @@
+use soroban_sdk::{contractimpl, Address, Env, IntoVal, Symbol, Vec};
+
+use crate::errors::ContractError;
+use crate::events::BalanceObserverUpdatedEvent;
+use crate::storage::ExtendedKey;
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// ========================================================================
+// Balance Observer
+// ========================================================================
+
+// Integrations such as an external ledger mirror can follow balances without
+// parsing events. Once an observer is set, every change to an address's
+// balance (mints, transfers, escrow moves, burns and retirements) calls its
+// `on_balance_change(address, token_id, new_balance)` within the same
+// invocation. A failing observer fails the change with it, so the mirror
+// never misses one; the admin can unset it.
+#[contractimpl]
+impl CarbonAsset {
+    // `None` stops the callbacks.
+    pub fn set_balance_observer(
+        env: Env,
+        caller: Address,
+        observer: Option<Address>,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        match &observer {
+            Some(observer) => env
+                .storage()
+                .instance()
+                .set(&ExtendedKey::BalanceObserver, observer),
+            None => env
+                .storage()
+                .instance()
+                .remove(&ExtendedKey::BalanceObserver),
+        }
+
+        BalanceObserverUpdatedEvent {
+            observer,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_balance_observer(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ExtendedKey::BalanceObserver)
+    }
+}
+
+impl CarbonAsset {
+    // Report `owner`'s balance to the observer, if any, after `token_id`
+    // moved in or out. The lock is held across the callback, like the
+    // transfer lock, so an observer cannot change balances from inside it.
+    // The host already rejects contract re-entry; this keeps the guarantee
+    // local to the contract.
+    pub(crate) fn notify_balance_change(
+        env: &Env,
+        owner: &Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
+        let Some(observer) = Self::get_balance_observer(env.clone()) else {
+            return Ok(());
+        };
+        if env.storage().instance().has(&ExtendedKey::ObserverLock) {
+            return Err(ContractError::ReentrantCallback);
+        }
+        let new_balance = Self::balance_of(env.clone(), owner.clone());
+
+        env.storage()
+            .instance()
+            .set(&ExtendedKey::ObserverLock, &true);
+        let mut args = Vec::new(env);
+        args.push_back(owner.into_val(env));
+        args.push_back(token_id.into_val(env));
+        args.push_back(new_balance.into_val(env));
+        env.invoke_contract::<()>(&observer, &Symbol::new(env, "on_balance_change"), args);
+        env.storage().instance().remove(&ExtendedKey::ObserverLock);
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use soroban_sdk::{contractimpl, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::errors::ContractError;
+use crate::roles;
+use crate::storage::ExtendedKey;
+use crate::types::ExtendedMetadata;
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// ========================================================================
+// Regions
+// ========================================================================
+
+// A coarse ISO 3166 alpha-2 country tag buyers can filter by, since
+// `geo_hash` is opaque. It is kept apart from `CarbonAssetMetadata` and, like
+// origin info, written only at mint.
+#[contractimpl]
+impl CarbonAsset {
+    // `mint` with an optional region tag. A region that is not two
+    // upper-case letters fails with `InvalidRegion`.
+    pub fn mint_extended(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        extended: ExtendedMetadata,
+    ) -> Result<u32, ContractError> {
+        let metadata = extended.metadata;
+        Self::require_issuer(&env, &caller, metadata.methodology_id)?;
+        Self::require_not_paused(&env)?;
+        Self::require_not_halted(&env, roles::MINTING)?;
+        if let Some(region) = &extended.region {
+            if !Self::is_region_code(&env, region) {
+                return Err(ContractError::InvalidRegion);
+            }
+        }
+
+        Self::reserve_verified_supply(env.clone(), &metadata, 1)?;
+        Self::report_methodology_usage(&env, &metadata, 1);
+        Self::charge_mint_fee(&env, &caller, 1)?;
+        let token_id = Self::mint_internal(env.clone(), caller, owner, metadata, None)?;
+        if let Some(region) = extended.region {
+            env.storage()
+                .persistent()
+                .set(&ExtendedKey::Region(token_id), &region);
+            Self::append_token_index(&env, &ExtendedKey::RegionIndex(region), token_id);
+        }
+        Ok(token_id)
+    }
+
+    pub fn get_region(env: Env, token_id: u32) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&ExtendedKey::Region(token_id))
+    }
+
+    // Tokens tagged with `region`, in mint order.
+    pub fn get_tokens_by_region(env: Env, region: Symbol, start: u32, limit: u32) -> Vec<u32> {
+        Self::token_index_page(&env, &ExtendedKey::RegionIndex(region), start, limit)
+    }
+
+    // Whether `geojson_hash` matches the token's `geo_hash`, so a buyer can
+    // check a project boundary they were given. False for unknown tokens.
+    pub fn verify_geolocation(env: Env, token_id: u32, geojson_hash: BytesN<32>) -> bool {
+        Self::get_metadata(env, token_id).is_ok_and(|metadata| metadata.geo_hash == geojson_hash)
+    }
+}
+
+impl CarbonAsset {
+    // Symbols carry no string accessor on-chain, so the code is read from
+    // the XDR: the type tag, the length, then the characters.
+    fn is_region_code(env: &Env, region: &Symbol) -> bool {
+        let xdr = region.clone().to_xdr(env);
+        xdr.len() == 12
+            && xdr.get(7) == Some(2)
+            && xdr.slice(8..10).iter().all(|c| c.is_ascii_uppercase())
+    }
+}
@@ -0,0 +1,261 @@
+use soroban_sdk::{contractimpl, symbol_short, Address, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::errors::ContractError;
+use crate::events::{
+    MethodologyIssuerUpdatedEvent, MintSignerUpdatedEvent, PauseEvent, RoleUpdatedEvent,
+};
+use crate::storage::DataKey;
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// May call `mint`, `mint_batch` and `mint_distributed`, except for
+// methodologies with a delegated issuer.
+pub const MINTER: Symbol = symbol_short!("MINTER");
+// May call `pause` and `unpause`.
+pub const PAUSER: Symbol = symbol_short!("PAUSER");
+// May call `freeze_token` and `unfreeze_token`.
+pub const FREEZER: Symbol = symbol_short!("FREEZER");
+// May call `mint_bridged`.
+pub const BRIDGER: Symbol = symbol_short!("BRIDGER");
+
+// Circuit breaker subsystems checked by the guarded entry points.
+pub(crate) const MINTING: &str = "minting";
+pub(crate) const TRANSFERS: &str = "transfers";
+pub(crate) const RETIREMENTS: &str = "retirements";
+pub(crate) const LISTINGS: &str = "listings";
+
+// ========================================================================
+// Roles
+// ========================================================================
+
+#[contractimpl]
+impl CarbonAsset {
+    // Grant `role` to `account`. Admin-only; the admin implicitly holds every
+    // role and does not need grants.
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+    ) -> Result<(), ContractError> {
+        Self::set_role(env, caller, role, account, true)
+    }
+
+    pub fn revoke_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+    ) -> Result<(), ContractError> {
+        Self::set_role(env, caller, role, account, false)
+    }
+
+    pub fn has_role(env: Env, role: Symbol, account: Address) -> bool {
+        if access_control::get_admin(&env).is_ok_and(|admin| admin == account) {
+            return true;
+        }
+        env.storage()
+            .persistent()
+            .has(&DataKey::Role(role, account))
+    }
+
+    // ====================================================================
+    // Delegated Issuers
+    // ====================================================================
+
+    // Let only `issuer` mint credits of `methodology_id`, in place of the
+    // admin and `MINTER` holders. `None` restores role-based minting.
+    pub fn set_methodology_issuer(
+        env: Env,
+        caller: Address,
+        methodology_id: u32,
+        issuer: Option<Address>,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        let key = DataKey::MethodologyIssuer(methodology_id);
+        match &issuer {
+            Some(issuer) => env.storage().persistent().set(&key, issuer),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        MethodologyIssuerUpdatedEvent {
+            methodology_id,
+            issuer,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_methodology_issuer(env: Env, methodology_id: u32) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MethodologyIssuer(methodology_id))
+    }
+
+    // Accept ed25519 signatures by `signer` as `issuer`'s authorization for
+    // `mint_with_sig`. The key carries no authority of its own: mints are
+    // checked against what `issuer` may mint at the time. `None` retires
+    // the key.
+    pub fn set_mint_signer(
+        env: Env,
+        caller: Address,
+        signer: BytesN<32>,
+        issuer: Option<Address>,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        let key = DataKey::MintSigner(signer.clone());
+        match &issuer {
+            Some(issuer) => env.storage().persistent().set(&key, issuer),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        MintSignerUpdatedEvent {
+            signer,
+            issuer,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_mint_signer(env: Env, signer: BytesN<32>) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::MintSigner(signer))
+    }
+
+    // The nonce the next authorization signed by `signer` must carry.
+    pub fn get_mint_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MintNonce(signer))
+            .unwrap_or(0)
+    }
+
+    // ====================================================================
+    // Pause
+    // ====================================================================
+
+    // Halt minting, transfers, escrow and burns for every token until
+    // `unpause`. Per-token freezes are unaffected.
+    pub fn pause(env: Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    pub fn unpause(env: Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+}
+
+impl CarbonAsset {
+    // `caller` must authorize the call and hold `role`.
+    pub(crate) fn require_role(
+        env: &Env,
+        role: Symbol,
+        caller: &Address,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(env)?;
+        caller.require_auth();
+        if !Self::has_role(env.clone(), role, caller.clone()) {
+            return Err(ContractError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    // Minting credits of `methodology_id` takes its delegated issuer when
+    // one is set, and the `MINTER` role otherwise.
+    pub(crate) fn require_issuer(
+        env: &Env,
+        caller: &Address,
+        methodology_id: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(env)?;
+        caller.require_auth();
+        if !Self::can_issue(env, caller, methodology_id) {
+            return Err(ContractError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    // The authority check of `require_issuer`, without requiring `account`'s
+    // signature.
+    pub(crate) fn can_issue(env: &Env, account: &Address, methodology_id: u32) -> bool {
+        match Self::get_methodology_issuer(env.clone(), methodology_id) {
+            Some(issuer) => *account == issuer,
+            None => Self::has_role(env.clone(), MINTER, account.clone()),
+        }
+    }
+
+    pub(crate) fn require_not_paused(env: &Env) -> Result<(), ContractError> {
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::ContractPaused);
+        }
+        Ok(())
+    }
+
+    // Ask the configured circuit breaker whether `subsystem` is halted.
+    // Entry points call this once, before any per-credit work, so a batch
+    // makes a single query.
+    pub(crate) fn require_not_halted(env: &Env, subsystem: &str) -> Result<(), ContractError> {
+        let Some(breaker) = Self::get_circuit_breaker(env.clone()) else {
+            return Ok(());
+        };
+
+        let mut args = Vec::new(env);
+        args.push_back(Symbol::new(env, subsystem).into_val(env));
+        let halted: bool = env.invoke_contract(&breaker, &Symbol::new(env, "is_halted"), args);
+        if halted {
+            return Err(ContractError::SystemHalted);
+        }
+        Ok(())
+    }
+
+    fn set_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+        granted: bool,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+        if role != MINTER && role != PAUSER && role != FREEZER && role != BRIDGER {
+            return Err(ContractError::UnknownRole);
+        }
+
+        let key = DataKey::Role(role.clone(), account.clone());
+        if granted {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        RoleUpdatedEvent {
+            role,
+            account,
+            granted,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    fn set_paused(env: Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        Self::require_role(&env, PAUSER, &caller)?;
+
+        env.storage().instance().set(&DataKey::Paused, &paused);
+
+        PauseEvent {
+            paused,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
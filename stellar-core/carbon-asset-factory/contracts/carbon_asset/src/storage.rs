@@ -1,18 +1,23 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol};
 
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Admin,
     Name,
     Symbol,
     Decimals,
     NextTokenId,
     EventSequence,
     RetirementTracker,
+    RetireSinks,
     RegulatoryCheck,
     HostJurisdiction,
+    VerificationRegistry,
+    Marketplaces,
+    MethodologyLibrary,
     Oracle,
+    CircuitBreaker,
+    Paused,
     Owner(u32),
     OwnerTokens(Address),
     TokenIndex(u32),
@@ -21,4 +26,51 @@ pub enum DataKey {
     Status(u32),
     QualityScore(u32),
     Burned(u32),
+    SerialRange(u32),
+    FrozenToken(u32),
+    LastStatusReason(u32),
+    MintedSupply(String, u64),
+    Role(Symbol, Address),
+    TokenAttributes(u32),
+    TransferLock(u32),
+    ListPrice(u32),
+    OriginInfo(u32),
+    RegistryTokens(Symbol),
+    BridgedSerial(BytesN<32>),
+    Royalty(u32),
+    MethodologyTokens(u32),
+    VintageTokens(u64),
+    BurnReceipt(BytesN<32>),
+    MethodologyIssuer(u32),
+    MintFee,
+    ListedAmount(Address, u32, Address),
+    ListedByToken(Address, u32),
+    ListedTotal(Address),
+    MintSigner(BytesN<32>),
+    MintNonce(BytesN<32>),
+    Migration(u32),
+    IssuanceKey,
+    VoucherUsed(u64),
+    MintLockSeconds,
+    MintedAt(u32),
+}
+
+// `DataKey` is at the 50 variant limit of a contract type, so keys added
+// since live here.
+#[derive(Clone)]
+#[contracttype]
+pub enum ExtendedKey {
+    SummaryMode,
+    TransferSummary,
+    SummaryStats(Address),
+    RetiredSupply,
+    BalanceObserver,
+    ObserverLock,
+    Region(u32),
+    RegionIndex(Symbol),
+    SerialBatches(u32),
+    Escrowed(u32),
+    EscrowedCount,
+    IndexSegment(DataKey, u32),
+    IndexSegmentLens(DataKey),
 }
@@ -0,0 +1,141 @@
+use soroban_sdk::{contractimpl, Address, Env, Map};
+
+use crate::errors::ContractError;
+use crate::events::{SummaryModeUpdatedEvent, TransferSummaryEvent};
+use crate::storage::{DataKey, ExtendedKey};
+use crate::types::{SummaryStats, TransferSummary};
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient};
+
+// ========================================================================
+// Transfer Summaries
+// ========================================================================
+
+// High-volume deployments can trade per-transfer events for one summary per
+// ledger. While summary mode is on, transfers publish no TransferEvent,
+// MultiTransferEvent or SEP-41 transfer event; they are counted into the
+// ledger's pending summary, which `flush_summary` publishes as a single
+// `TransferSummaryEvent`. Ownership is unchanged, and each address's
+// summarized volume is kept in `get_summary_stats`.
+#[contractimpl]
+impl CarbonAsset {
+    // Turning summary mode off flushes the pending summary, so transfers
+    // after the call emit their own events again and none are left unreported.
+    pub fn set_summary_mode(env: Env, caller: Address, enabled: bool) -> Result<(), ContractError> {
+        access_control::require_admin(&env, &caller)?;
+
+        if !enabled {
+            Self::flush_summary(env.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&ExtendedKey::SummaryMode, &enabled);
+
+        SummaryModeUpdatedEvent {
+            enabled,
+            changed_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Publish the pending summary, if any, and clear it. Anyone may call
+    // this; a second call in the same ledger finds nothing pending and
+    // returns false without emitting anything.
+    pub fn flush_summary(env: Env) -> bool {
+        let summary: TransferSummary = match env
+            .storage()
+            .persistent()
+            .get(&ExtendedKey::TransferSummary)
+        {
+            Some(summary) => summary,
+            None => return false,
+        };
+        env.storage()
+            .persistent()
+            .remove(&ExtendedKey::TransferSummary);
+
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64);
+        let next_sequence = sequence + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSequence, &next_sequence);
+        TransferSummaryEvent {
+            sequence: next_sequence,
+            ledger: summary.ledger,
+            count: summary.count,
+            amounts: summary.amounts,
+        }
+        .publish(&env);
+        true
+    }
+
+    pub fn is_summary_mode(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&ExtendedKey::SummaryMode)
+            .unwrap_or(false)
+    }
+
+    // Transfers recorded since the last flush, or None when nothing is
+    // pending.
+    pub fn get_transfer_summary(env: Env) -> Option<TransferSummary> {
+        env.storage()
+            .persistent()
+            .get(&ExtendedKey::TransferSummary)
+    }
+
+    // Credits `account` has sent and received while summary mode was on.
+    pub fn get_summary_stats(env: Env, account: Address) -> SummaryStats {
+        env.storage()
+            .persistent()
+            .get(&ExtendedKey::SummaryStats(account))
+            .unwrap_or_default()
+    }
+}
+
+impl CarbonAsset {
+    // Count a moved credit into the current ledger's summary in place of
+    // its TransferEvent. A summary left pending from an earlier ledger is
+    // flushed first, so each summary covers exactly one ledger.
+    pub(crate) fn record_summary_transfer(env: &Env, from: &Address, to: &Address, token_id: u32) {
+        let ledger = env.ledger().sequence();
+        let pending: Option<TransferSummary> = env
+            .storage()
+            .persistent()
+            .get(&ExtendedKey::TransferSummary);
+        let mut summary = match pending {
+            Some(summary) if summary.ledger == ledger => summary,
+            stale => {
+                if stale.is_some() {
+                    Self::flush_summary(env.clone());
+                }
+                TransferSummary {
+                    ledger,
+                    count: 0,
+                    amounts: Map::new(env),
+                }
+            }
+        };
+        summary.count += 1;
+        let moved = summary.amounts.get(token_id).unwrap_or(0);
+        summary.amounts.set(token_id, moved + 1);
+        env.storage()
+            .persistent()
+            .set(&ExtendedKey::TransferSummary, &summary);
+
+        let mut sender = Self::get_summary_stats(env.clone(), from.clone());
+        sender.sent += 1;
+        env.storage()
+            .persistent()
+            .set(&ExtendedKey::SummaryStats(from.clone()), &sender);
+        let mut recipient = Self::get_summary_stats(env.clone(), to.clone());
+        recipient.received += 1;
+        env.storage()
+            .persistent()
+            .set(&ExtendedKey::SummaryStats(to.clone()), &recipient);
+    }
+}
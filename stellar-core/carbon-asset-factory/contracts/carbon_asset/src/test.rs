@@ -1,9 +1,160 @@
 #![cfg(test)]
+extern crate std;
 
-use super::{CarbonAsset, CarbonAssetClient};
-use crate::types::{AssetStatus, CarbonAssetMetadata};
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, BytesN, Env, String};
+use super::{
+    CarbonAsset, CarbonAssetClient, MAX_MULTI_TRANSFER, MAX_SERIAL_BATCH, SERIAL_BUCKET_SPAN,
+    TOKEN_INDEX_SEGMENT,
+};
+use crate::errors::ContractError;
+use crate::events::{
+    ApproveEvent, AutoRetireEvent, MintEvent, TransferEvent, TransferFromEvent,
+    TransferSummaryEvent,
+};
+use crate::roles::{BRIDGER, FREEZER, MINTER, PAUSER};
+use crate::storage::{DataKey, ExtendedKey};
+use crate::types::{
+    AssetStatus, BurnReceipt, CarbonAssetMetadata, ContractConfig, ExtendedMetadata,
+    MintAuthorization, MintFee, MintVoucher, OperationType, OriginInfo, StatusReason, SummaryStats,
+    TransferCheck, TransferDecision, ValidationResult,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, vec, Address, Bytes, BytesN, Env, Event, IntoVal, String,
+    Symbol,
+};
+
+// Regulatory check that clears the jurisdiction test but screens out every
+// transfer, as a blocked corridor or sanctions hit would.
+#[contract]
+struct RejectingRegulator;
+
+#[contractimpl]
+impl RejectingRegulator {
+    pub fn validate_transaction(
+        _env: Env,
+        _from: Address,
+        _to: Address,
+        _operation: OperationType,
+        _host_jurisdiction: String,
+    ) -> ValidationResult {
+        ValidationResult {
+            is_compliant: true,
+            rule_id: None,
+            requires_authorization: false,
+            authority_address: None,
+            error_message: None,
+        }
+    }
+
+    pub fn check_transfer(
+        _env: Env,
+        _from: Address,
+        _to: Address,
+        _amount: i128,
+        _token_id: u32,
+        _project_id: String,
+    ) -> TransferDecision {
+        TransferDecision {
+            allowed: false,
+            rule: symbol_short!("corridor"),
+            corridor_from: None,
+            corridor_to: None,
+            rule_version: 1,
+        }
+    }
+}
+
+// Regulatory check that refuses every operation by an account it has
+// frozen, as the compliance engine does.
+#[contract]
+struct FreezingRegulator;
+
+#[contractimpl]
+impl FreezingRegulator {
+    pub fn freeze(env: Env, account: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("frozen"), &account);
+    }
+
+    pub fn validate_transaction(
+        env: Env,
+        from: Address,
+        _to: Address,
+        _operation: OperationType,
+        _host_jurisdiction: String,
+    ) -> ValidationResult {
+        let frozen: Option<Address> = env.storage().instance().get(&symbol_short!("frozen"));
+        ValidationResult {
+            is_compliant: frozen != Some(from),
+            rule_id: None,
+            requires_authorization: false,
+            authority_address: None,
+            error_message: None,
+        }
+    }
+}
+
+// Verification registry that attests every vintage for three credits.
+#[contract]
+struct ThreeCreditRegistry;
+
+#[contractimpl]
+impl ThreeCreditRegistry {
+    pub fn is_verified(_env: Env, _project_id: String, _vintage_year: u64) -> bool {
+        true
+    }
+
+    pub fn get_verified_amount(_env: Env, _project_id: String, _vintage_year: u64) -> i128 {
+        3
+    }
+}
+
+// Retirement tracker that certifies direct retirements, deriving the
+// certificate id from the token and vintage it was handed.
+#[contract]
+struct CertifyingTracker;
+
+#[contractimpl]
+impl CertifyingTracker {
+    pub fn record_direct_retirement(
+        env: Env,
+        _holder: Address,
+        token_id: u32,
+        metadata: CarbonAssetMetadata,
+        _beneficiary: String,
+        _reason_code: Symbol,
+    ) -> BytesN<32> {
+        let mut cert_id = [token_id as u8; 32];
+        cert_id[31] = (metadata.vintage_year % 256) as u8;
+        BytesN::from_array(&env, &cert_id)
+    }
+}
+
+// Balance observer that records every callback it receives, in order.
+#[contract]
+struct RecordingObserver;
+
+#[contractimpl]
+impl RecordingObserver {
+    pub fn on_balance_change(env: Env, address: Address, token_id: u32, new_balance: i128) {
+        let mut calls = Self::calls(env.clone());
+        calls.push_back((address, token_id, new_balance));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("calls"), &calls);
+    }
+
+    pub fn calls(env: Env) -> soroban_sdk::Vec<(Address, u32, i128)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("calls"))
+            .unwrap_or(vec![&env])
+    }
+}
 
 fn setup_env() -> (Env, Address, Address, Address) {
     let env = Env::default();
@@ -47,6 +198,39 @@ fn test_mint_and_transfer_token() {
     assert_eq!(client.owner_of(&token_id), buyer);
 }
 
+#[test]
+fn test_mint_assigns_unique_token_ids() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    // Identical metadata still yields a distinct token per mint
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let other_owner = Address::generate(&env);
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &other_owner, &meta);
+    let third = client.mint(&admin, &owner, &meta);
+    assert_eq!((first, second, third), (1, 2, 3));
+
+    assert_eq!(client.owner_of(&second), other_owner);
+    assert_eq!(client.tokens_of_owner(&owner).len(), 2);
+    assert_eq!(client.balance(&owner), 2);
+    assert_eq!(client.balance(&other_owner), 1);
+}
+
 #[test]
 fn test_transfer_amount_and_allowance() {
     let (env, admin, retirement_tracker, owner) = setup_env();
@@ -84,6 +268,139 @@ fn test_transfer_amount_and_allowance() {
     assert_eq!(client.balance(&recipient), 1);
 }
 
+#[test]
+fn test_allowance_events_name_the_spender() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[9u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    client.mint(&admin, &owner, &meta);
+
+    let spender = Address::generate(&env);
+    let live_until = env.ledger().sequence() + 100;
+    client.approve(&owner, &spender, &2, &live_until);
+    // Read events before any further call replaces them
+    let events = env.events().all();
+    let approve = ApproveEvent {
+        sequence: client.get_event_sequence(),
+        from: owner.clone(),
+        spender: spender.clone(),
+        amount: 2,
+        live_until_ledger: live_until,
+    };
+    assert_eq!(events.events(), [approve.to_xdr(&env, &contract_id)]);
+
+    let recipient = Address::generate(&env);
+    client.transfer_from(&spender, &owner, &recipient, &1);
+    let events = env.events().all();
+    // Per-token transfer, SEP-41 transfer, then the spender event
+    assert_eq!(events.events().len(), 3);
+    let transfer_from = TransferFromEvent {
+        sequence: client.get_event_sequence(),
+        spender: spender.clone(),
+        from: owner.clone(),
+        to: recipient.clone(),
+        amount: 1,
+        token_ids: vec![&env, first],
+        remaining_allowance: 1,
+    };
+    assert_eq!(events.events()[2], transfer_from.to_xdr(&env, &contract_id));
+    assert_eq!(client.allowance(&owner, &spender), 1);
+    assert_eq!(client.owner_of(&first), recipient);
+}
+
+#[test]
+fn test_standard_token_client_moves_credits() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let token = TokenClient::new(&env, &contract_id);
+    let recipient = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..3 {
+        client.mint(&admin, &owner, &meta);
+    }
+
+    assert_eq!(token.name(), String::from_str(&env, "Carbon Asset"));
+    assert_eq!(token.symbol(), String::from_str(&env, "C01"));
+    assert_eq!(token.decimals(), 0);
+    assert_eq!(token.balance(&owner), 3);
+
+    // The standard interface moves whole credits; the contract picks which
+    token.transfer(&owner, &recipient, &1);
+    assert_eq!(token.balance(&owner), 2);
+    assert_eq!(token.balance(&recipient), 1);
+    assert_eq!(client.tokens_of_owner(&recipient).len(), 1);
+
+    token.approve(&owner, &spender, &2, &(env.ledger().sequence() + 100));
+    assert_eq!(token.allowance(&owner, &spender), 2);
+    token.transfer_from(&spender, &owner, &recipient, &1);
+    assert_eq!(token.allowance(&owner, &spender), 1);
+    assert_eq!(token.balance(&recipient), 2);
+
+    // Frozen credits count toward the balance but can't be moved
+    let remaining = client.tokens_of_owner(&owner).get(0).unwrap();
+    client.freeze_token(&admin, &remaining);
+    assert_eq!(token.balance(&owner), 1);
+    assert!(token.try_transfer(&owner, &recipient, &1).is_err());
+}
+
+#[test]
+fn test_format_amount_splits_at_stored_decimals() {
+    let (env, admin, retirement_tracker, _) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    assert_eq!(client.format_amount(&15_000_000), (15_000_000, 0));
+
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Decimals, &7u32);
+    });
+    assert_eq!(client.format_amount(&15_000_000), (1, 5_000_000));
+    assert_eq!(client.format_amount(&9_999_999), (0, 9_999_999));
+    assert_eq!(client.format_amount(&0), (0, 0));
+    assert_eq!(
+        client.try_format_amount(&-1),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
 #[test]
 fn test_transfer_to_retirement_tracker_sets_status() {
     let (env, admin, retirement_tracker, owner) = setup_env();
@@ -111,7 +428,97 @@ fn test_transfer_to_retirement_tracker_sets_status() {
 }
 
 #[test]
-fn test_event_sequence_persistence_in_storage() {
+fn test_rotated_retirement_tracker_takes_over_auto_retire() {
+    let (env, admin, old_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let new_tracker = Address::generate(&env);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &old_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-2"),
+        vintage_year: 1704067200,
+        methodology_id: 2,
+        geo_hash: BytesN::from_array(&env, &[3u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+
+    assert_eq!(
+        client.try_set_retirement_tracker(&owner, &new_tracker),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    let sequence = client.get_event_sequence();
+    client.set_retirement_tracker(&admin, &new_tracker);
+    assert_eq!(env.events().all().events().len(), 1);
+    assert_eq!(client.get_event_sequence(), sequence + 1);
+    assert_eq!(client.get_retirement_tracker(), new_tracker);
+
+    // The old tracker is now an ordinary holder
+    client.transfer_multi(&owner, &old_tracker, &vec![&env, first]);
+    assert_eq!(client.get_status(&first), AssetStatus::Issued);
+
+    client.transfer_multi(&owner, &new_tracker, &vec![&env, second]);
+    assert_eq!(client.get_status(&second), AssetStatus::Retired);
+}
+
+#[test]
+fn test_retirement_tracker_kept_while_credits_are_escrowed() {
+    let (env, admin, old_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let new_tracker = Address::generate(&env);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &old_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-2"),
+        vintage_year: 1704067200,
+        methodology_id: 2,
+        geo_hash: BytesN::from_array(&env, &[3u8; 32]),
+    };
+    let released = client.mint(&admin, &owner, &meta);
+    let burned = client.mint(&admin, &owner, &meta);
+    client.escrow_for_retirement(&released, &owner);
+    client.escrow_for_retirement(&burned, &owner);
+    assert_eq!(client.escrowed_count(), 2);
+
+    assert_eq!(
+        client.try_set_retirement_tracker(&admin, &new_tracker),
+        Err(Ok(ContractError::EscrowOutstanding))
+    );
+    // Re-setting the current tracker strands nothing
+    client.set_retirement_tracker(&admin, &old_tracker);
+
+    // Releasing or burning a credit takes it out of escrow
+    client.release_from_escrow(&released, &owner);
+    assert_eq!(client.escrowed_count(), 1);
+    assert_eq!(
+        client.try_set_retirement_tracker(&admin, &new_tracker),
+        Err(Ok(ContractError::EscrowOutstanding))
+    );
+    client.burn_token(&burned, &old_tracker);
+    assert_eq!(client.escrowed_count(), 0);
+
+    client.set_retirement_tracker(&admin, &new_tracker);
+    assert_eq!(client.get_retirement_tracker(), new_tracker);
+}
+
+#[test]
+fn test_transfer_gated_by_status() {
     let (env, admin, retirement_tracker, owner) = setup_env();
     let contract_id = env.register(CarbonAsset, ());
     let client = CarbonAssetClient::new(&env, &contract_id);
@@ -124,16 +531,3528 @@ fn test_event_sequence_persistence_in_storage() {
         &String::from_str(&env, "US"),
     );
 
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let cases = [
+        (AssetStatus::Issued, true),
+        (AssetStatus::Listed, true),
+        (AssetStatus::Locked, false),
+        (AssetStatus::Retired, false),
+        (AssetStatus::Invalidated, false),
+    ];
+    for (status, transferable) in cases {
+        // One token per holder, so the amount transfer can only pick this one
+        let holder = Address::generate(&env);
+        let token_id = client.mint(&admin, &holder, &meta);
+        if status != AssetStatus::Issued {
+            client.set_status(&admin, &token_id, &status, &StatusReason::Manual, &None);
+        }
+
+        let result = client.try_transfer(&holder, &owner, &1);
+        if transferable {
+            assert_eq!(result, Ok(Ok(())));
+            assert_eq!(client.owner_of(&token_id), owner);
+        } else {
+            assert_eq!(result, Err(Ok(ContractError::TransferNotAllowed)));
+            assert_eq!(client.owner_of(&token_id), holder);
+        }
+    }
+}
+
+#[test]
+fn test_new_tokens_cannot_move_during_mint_lock() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
     let meta = CarbonAssetMetadata {
         project_id: String::from_str(&env, "PROJ-1"),
         vintage_year: 1704067200,
         methodology_id: 1,
         geo_hash: BytesN::from_array(&env, &[7u8; 32]),
     };
+    env.ledger().set_timestamp(10_000);
+    let old_token = client.mint(&admin, &owner, &meta);
+    client.set_mint_lock_seconds(&admin, &3_600);
+    env.ledger().set_timestamp(12_000);
+    let new_token = client.mint(&admin, &owner, &meta);
+    assert_eq!(client.get_minted_at(&new_token), Some(12_000));
+
+    // Neither token has cleared its hold yet
+    let recipient = Address::generate(&env);
+    assert_eq!(
+        client.try_transfer(&owner, &recipient, &1),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+    assert_eq!(
+        client.try_transfer_multi(&owner, &recipient, &vec![&env, new_token]),
+        Err(Ok(ContractError::TokenCoolingOff))
+    );
+
+    // The first token's hold ends 3600s after its mint
+    env.ledger().set_timestamp(13_600);
+    client.transfer(&owner, &recipient, &1);
+    assert_eq!(client.owner_of(&old_token), recipient);
+    assert_eq!(
+        client.try_transfer(&owner, &recipient, &1),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+
+    env.ledger().set_timestamp(15_600);
+    client.transfer(&owner, &recipient, &1);
+    assert_eq!(client.balance(&recipient), 2);
 
+    // With the lock off, fresh tokens move at once
+    client.set_mint_lock_seconds(&admin, &0);
     client.mint(&admin, &owner, &meta);
+    client.transfer(&owner, &recipient, &1);
+    assert_eq!(client.balance(&recipient), 3);
+}
 
-    // Check sequence incremented to 2 (MintEvent + StatusChangeEvent)
+#[test]
+fn test_summary_mode_batches_transfer_events_per_ledger() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    client.mint(&admin, &owner, &meta);
+    client.mint(&admin, &owner, &meta);
+    let recipient = Address::generate(&env);
+    client.set_summary_mode(&admin, &true);
+    assert!(client.is_summary_mode());
+
+    // Transfers move credits but publish nothing and use no sequence numbers
     let sequence = client.get_event_sequence();
-    assert_eq!(sequence, 2);
+    client.transfer(&owner, &recipient, &2);
+    assert_eq!(env.events().all().events().len(), 0);
+    client.transfer_multi(&recipient, &owner, &vec![&env, first]);
+    assert_eq!(env.events().all().events().len(), 0);
+    assert_eq!(client.get_event_sequence(), sequence);
+    assert_eq!(client.balance(&recipient), 1);
+
+    let summary = client.get_transfer_summary().unwrap();
+    assert_eq!(summary.count, 3);
+    assert_eq!(summary.amounts.get(first), Some(2));
+    assert_eq!(summary.amounts.get(first + 1), Some(1));
+    assert_eq!(
+        client.get_summary_stats(&owner),
+        SummaryStats {
+            sent: 2,
+            received: 1,
+        }
+    );
+    assert_eq!(
+        client.get_summary_stats(&recipient),
+        SummaryStats {
+            sent: 1,
+            received: 2,
+        }
+    );
+
+    // One event per flush; a second flush in the same ledger is a no-op
+    assert!(client.flush_summary());
+    let flushed = TransferSummaryEvent {
+        sequence: sequence + 1,
+        ledger: summary.ledger,
+        count: 3,
+        amounts: summary.amounts,
+    };
+    assert_eq!(
+        env.events().all().events(),
+        [flushed.to_xdr(&env, &contract_id)]
+    );
+    assert!(!client.flush_summary());
+    assert_eq!(env.events().all().events().len(), 0);
+    assert_eq!(client.get_transfer_summary(), None);
+
+    // Disabling summary mode restores per-transfer events at once
+    client.set_summary_mode(&admin, &false);
+    client.transfer_multi(&owner, &recipient, &vec![&env, first]);
+    let events = env.events().all();
+    assert_eq!(events.events().len(), 2);
+    let transfer = TransferEvent {
+        sequence: sequence + 2,
+        token_id: first,
+        from: owner.clone(),
+        to: recipient.clone(),
+        rule_version: None,
+    };
+    assert_eq!(events.events()[0], transfer.to_xdr(&env, &contract_id));
+}
+
+#[test]
+fn test_self_transfer_leaves_balance_unchanged() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    client.mint(&admin, &owner, &meta);
+    client.mint(&admin, &owner, &meta);
+    let tokens = client.tokens_of_owner(&owner);
+    let sequence = client.get_event_sequence();
+
+    client.transfer(&owner, &owner, &2);
+    assert_eq!(env.events().all().events().len(), 1);
+    assert_eq!(client.get_event_sequence(), sequence + 1);
+    assert_eq!(client.balance(&owner), 2);
+    assert_eq!(client.tokens_of_owner(&owner), tokens);
+
+    // Balance and pause checks still apply
+    assert_eq!(
+        client.try_transfer(&owner, &owner, &3),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+    client.pause(&admin);
+    assert_eq!(
+        client.try_transfer(&owner, &owner, &1),
+        Err(Ok(ContractError::ContractPaused))
+    );
+}
+
+#[test]
+fn test_transfer_multi_is_all_or_nothing() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let other = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+    let foreign = client.mint(&admin, &other, &meta);
+    let fourth = client.mint(&admin, &owner, &meta);
+
+    // The third token belongs to someone else, so nothing moves
+    let result = client.try_transfer_multi(
+        &owner,
+        &recipient,
+        &vec![&env, first, second, foreign, fourth],
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    assert_eq!(client.balance(&owner), 3);
+    assert_eq!(client.balance(&recipient), 0);
+    assert_eq!(client.owner_of(&first), owner);
+    assert_eq!(client.owner_of(&second), owner);
+
+    assert_eq!(
+        client.try_transfer_multi(&owner, &recipient, &vec![&env]),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_multi(&owner, &recipient, &vec![&env, first, first]),
+        Err(Ok(ContractError::DuplicateToken))
+    );
+    // A full batch passes the size check and fails on the first foreign
+    // token; one more is refused outright
+    let mut too_many = vec![&env, foreign];
+    for token_id in 100..99 + MAX_MULTI_TRANSFER {
+        too_many.push_back(token_id);
+    }
+    assert_eq!(
+        client.try_transfer_multi(&owner, &recipient, &too_many),
+        Err(Ok(ContractError::NotOwner))
+    );
+    too_many.push_back(99 + MAX_MULTI_TRANSFER);
+    assert_eq!(
+        client.try_transfer_multi(&owner, &recipient, &too_many),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+
+    let sequence = client.get_event_sequence();
+    client.transfer_multi(&owner, &recipient, &vec![&env, first, fourth]);
+    // One transfer event per token plus the aggregate
+    assert_eq!(env.events().all().events().len(), 3);
+    assert_eq!(client.get_event_sequence(), sequence + 3);
+    assert_eq!(client.balance(&owner), 1);
+    assert_eq!(client.balance(&recipient), 2);
+    assert_eq!(client.owner_of(&first), recipient);
+    assert_eq!(client.owner_of(&fourth), recipient);
+
+    client.transfer_multi(&recipient, &retirement_tracker, &vec![&env, first, fourth]);
+    assert_eq!(client.get_status(&first), AssetStatus::Retired);
+    assert_eq!(client.get_status(&fourth), AssetStatus::Retired);
+    assert_eq!(client.balance(&retirement_tracker), 2);
+}
+
+// Full batches, each token with a royalty and interleaved with tokens the
+// owner keeps, are listed, sold and moved within the network's default
+// per-transaction limits.
+#[test]
+fn test_full_batches_fit_network_limits() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let marketplace = Address::generate(&env);
+    client.add_marketplace(&admin, &marketplace);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let developer = Address::generate(&env);
+    let mut sold = vec![&env];
+    let mut moved = vec![&env];
+    for i in 0..4 * MAX_MULTI_TRANSFER {
+        let token_id = client.mint(&admin, &owner, &meta);
+        client.set_royalty(&admin, &token_id, &developer, &500);
+        match i % 4 {
+            0 => sold.push_back(token_id),
+            2 => moved.push_back(token_id),
+            _ => {}
+        }
+    }
+
+    let buyer = Address::generate(&env);
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &1_000_000);
+    let live_until = env.ledger().sequence() + 100;
+    TokenClient::new(&env, &payment_id).approve(&buyer, &contract_id, &1_000_000, &live_until);
+    client.approve(
+        &owner,
+        &marketplace,
+        &(MAX_MULTI_TRANSFER as i128),
+        &live_until,
+    );
+
+    client.list_tokens(&marketplace, &owner, &sold);
+    client.transfer_with_sale_price(&marketplace, &owner, &buyer, &sold, &1_000, &payment_id);
+    client.transfer_multi(&owner, &buyer, &moved);
+
+    assert_eq!(client.balance(&buyer), 2 * MAX_MULTI_TRANSFER as i128);
+    assert_eq!(
+        TokenClient::new(&env, &payment_id).balance(&developer),
+        50 * MAX_MULTI_TRANSFER as i128
+    );
+}
+
+#[test]
+fn test_can_transfer_reports_failure_reason() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let recipient = Address::generate(&env);
+    let reason = |check: crate::types::TransferCheck| {
+        assert_eq!(check.allowed, check.reason == Symbol::new(&env, "Allowed"));
+        check.reason
+    };
+
+    assert_eq!(
+        reason(client.can_transfer(&owner, &recipient, &1, &false)),
+        Symbol::new(&env, "NotInitialized")
+    );
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+    let foreign = client.mint(&admin, &recipient, &meta);
+
+    assert_eq!(
+        reason(client.can_transfer(&owner, &recipient, &1, &true)),
+        Symbol::new(&env, "Allowed")
+    );
+    assert_eq!(
+        reason(client.can_transfer(&owner, &recipient, &0, &false)),
+        Symbol::new(&env, "InvalidStatusTransition")
+    );
+    assert_eq!(
+        reason(client.can_transfer(&owner, &recipient, &2, &false)),
+        Symbol::new(&env, "TransferNotAllowed")
+    );
+    assert_eq!(
+        reason(client.can_transfer_token(&owner, &recipient, &foreign, &false)),
+        Symbol::new(&env, "NotOwner")
+    );
+    assert_eq!(
+        reason(client.can_transfer_token(&owner, &recipient, &99, &false)),
+        Symbol::new(&env, "TokenNotFound")
+    );
+
+    client.freeze_token(&admin, &token_id);
+    assert_eq!(
+        reason(client.can_transfer_token(&owner, &recipient, &token_id, &false)),
+        Symbol::new(&env, "TokenFrozen")
+    );
+    // Frozen credits are skipped when picking credits for an amount
+    assert_eq!(
+        reason(client.can_transfer(&owner, &recipient, &1, &false)),
+        Symbol::new(&env, "TransferNotAllowed")
+    );
+    client.unfreeze_token(&admin, &token_id);
+
+    client.pause(&admin);
+    assert_eq!(
+        reason(client.can_transfer(&owner, &recipient, &1, &false)),
+        Symbol::new(&env, "ContractPaused")
+    );
+    client.unpause(&admin);
+
+    // Each check matches the error the transfer itself returns
+    client.transfer(&owner, &retirement_tracker, &1);
+    assert_eq!(
+        reason(client.can_transfer_token(&retirement_tracker, &recipient, &token_id, &false)),
+        Symbol::new(&env, "TransferNotAllowed")
+    );
+    assert_eq!(
+        client.try_transfer_multi(&retirement_tracker, &recipient, &vec![&env, token_id]),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+}
+
+#[test]
+fn test_burn_token_requires_retirement_tracker() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+
+    // The owner's signature alone is not enough
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &owner,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "burn_token",
+                args: (token_id, owner.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_burn_token(&token_id, &owner);
+    assert!(result.is_err());
+    assert!(!client.is_burned(&token_id));
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_burn_token(&token_id, &admin),
+        Err(Ok(ContractError::NotOwner))
+    );
+
+    client.burn_token(&token_id, &owner);
+    assert_eq!(env.auths()[0].0, retirement_tracker);
+    assert!(client.is_burned(&token_id));
+    assert_eq!(client.get_status(&token_id), AssetStatus::Retired);
+    assert_eq!(
+        client.try_burn_token(&token_id, &owner),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_burn_token_returns_retrievable_receipt() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+
+    env.ledger().set_timestamp(1_700_000_000);
+    let receipt = client.burn_token(&first, &owner);
+    assert_eq!(
+        client.get_burn_receipt(&receipt),
+        Some(BurnReceipt {
+            from: owner.clone(),
+            token_id: first,
+            amount: 1,
+            burned_at: 1_700_000_000,
+        })
+    );
+
+    // Same burner and time, different token: a distinct receipt
+    let other = client.burn_token(&second, &owner);
+    assert_ne!(other, receipt);
+    assert_eq!(client.get_burn_receipt(&other).unwrap().token_id, second);
+    assert_eq!(
+        client.get_burn_receipt(&BytesN::from_array(&env, &[0u8; 32])),
+        None
+    );
+}
+
+#[test]
+fn test_balance_observer_is_called_on_every_balance_change() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let observer = env.register(RecordingObserver, ());
+    let recorder = RecordingObserverClient::new(&env, &observer);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+
+    // Unset, balance changes go unreported
+    let first = client.mint(&admin, &owner, &meta);
+    assert_eq!(client.get_balance_observer(), None);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_balance_observer(&stranger, &Some(observer.clone())),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.set_balance_observer(&admin, &Some(observer.clone()));
+    assert_eq!(client.get_balance_observer(), Some(observer.clone()));
+
+    let second = client.mint(&admin, &owner, &meta);
+    let buyer = Address::generate(&env);
+    client.transfer(&owner, &buyer, &1);
+    let moved = client.tokens_of_owner(&buyer).get(0).unwrap();
+    client.retire_direct(&buyer, &1, &moved, &None, &symbol_short!("OFFSET"));
+    assert_eq!(
+        recorder.calls(),
+        vec![
+            &env,
+            (owner.clone(), second, 2),
+            (owner.clone(), moved, 1),
+            (buyer.clone(), moved, 1),
+            (buyer.clone(), moved, 0),
+        ]
+    );
+
+    client.set_balance_observer(&admin, &None);
+    let kept = if moved == first { second } else { first };
+    client.transfer(&owner, &buyer, &1);
+    assert_eq!(client.owner_of(&kept), buyer);
+    assert_eq!(recorder.calls().len(), 4);
+}
+
+#[test]
+fn test_metadata_hash_matches_for_identical_metadata() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+    let other = client.mint(
+        &admin,
+        &owner,
+        &CarbonAssetMetadata {
+            vintage_year: 2025,
+            ..meta.clone()
+        },
+    );
+
+    let hash = client.get_metadata_hash(&first).unwrap();
+    assert_eq!(client.get_metadata_hash(&second), Some(hash.clone()));
+    assert_ne!(client.get_metadata_hash(&other), Some(hash.clone()));
+    assert_eq!(client.get_metadata_hash(&(other + 1)), None);
+
+    // An external system can reproduce it from the metadata alone
+    let expected: BytesN<32> = env.crypto().sha256(&meta.to_xdr(&env)).into();
+    assert_eq!(hash, expected);
+}
+
+#[test]
+fn test_region_tags_are_indexed_at_mint() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let extended = |region: Option<Symbol>| ExtendedMetadata {
+        metadata: meta.clone(),
+        region,
+    };
+    let kenya = symbol_short!("KE");
+    let brazil = symbol_short!("BR");
+
+    let untagged = client.mint(&admin, &owner, &meta);
+    let first = client.mint_extended(&admin, &owner, &extended(Some(kenya.clone())));
+    let second = client.mint_extended(&admin, &owner, &extended(Some(brazil.clone())));
+    let third = client.mint_extended(&admin, &owner, &extended(Some(kenya.clone())));
+    let no_region = client.mint_extended(&admin, &owner, &extended(None));
+
+    assert_eq!(client.get_region(&untagged), None);
+    assert_eq!(client.get_region(&no_region), None);
+    assert_eq!(client.get_region(&second), Some(brazil.clone()));
+    assert_eq!(client.get_metadata(&first).geo_hash, meta.geo_hash);
+    assert_eq!(
+        client.get_tokens_by_region(&kenya, &0, &10),
+        vec![&env, first, third]
+    );
+    assert_eq!(
+        client.get_tokens_by_region(&kenya, &1, &10),
+        vec![&env, third]
+    );
+    assert_eq!(
+        client.get_tokens_by_region(&brazil, &0, &10),
+        vec![&env, second]
+    );
+    assert_eq!(
+        client
+            .get_tokens_by_region(&symbol_short!("US"), &0, &10)
+            .len(),
+        0
+    );
+
+    // Only upper-case alpha-2 codes are accepted
+    for region in ["KEN", "ke", "K", "K1"] {
+        assert_eq!(
+            client.try_mint_extended(&admin, &owner, &extended(Some(Symbol::new(&env, region)))),
+            Err(Ok(ContractError::InvalidRegion))
+        );
+    }
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_mint_extended(&stranger, &owner, &extended(Some(kenya))),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(client.balance(&owner), 5);
+}
+
+#[test]
+fn test_verify_geolocation_compares_against_geo_hash() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let geojson = Bytes::from_slice(&env, br#"{"type":"Polygon","coordinates":[]}"#);
+    let geo_hash: BytesN<32> = env.crypto().sha256(&geojson).into();
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: geo_hash.clone(),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+
+    assert!(client.verify_geolocation(&token_id, &geo_hash));
+    let other = Bytes::from_slice(&env, br#"{"type":"Point","coordinates":[]}"#);
+    let other_hash: BytesN<32> = env.crypto().sha256(&other).into();
+    assert!(!client.verify_geolocation(&token_id, &other_hash));
+    assert!(!client.verify_geolocation(&(token_id + 1), &geo_hash));
+}
+
+#[test]
+fn test_retire_direct_burns_without_escrow() {
+    let (env, admin, _, owner) = setup_env();
+    let retirement_tracker = env.register(CertifyingTracker, ());
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+    let reason = symbol_short!("OFFSET");
+
+    // A token is one credit
+    assert_eq!(
+        client.try_retire_direct(&owner, &2, &first, &None, &reason),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_retire_direct(&stranger, &1, &first, &None, &reason),
+        Err(Ok(ContractError::NotOwner))
+    );
+
+    // Without a beneficiary the tracker is not involved
+    assert_eq!(
+        client.retire_direct(&owner, &1, &first, &None, &reason),
+        None
+    );
+    assert_eq!(client.balance(&owner), 1);
+    assert_eq!(client.balance(&retirement_tracker), 0);
+    assert_eq!(client.retired_supply(), 1);
+    assert_eq!(client.get_status(&first), AssetStatus::Retired);
+    assert!(client.is_burned(&first));
+    assert_eq!(
+        client.try_retire_direct(&owner, &1, &first, &None, &reason),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+
+    // With one, the tracker certifies the retirement from the passed metadata
+    let beneficiary = Some(String::from_str(&env, "Acme Corp"));
+    let cert_id = client.retire_direct(&owner, &1, &second, &beneficiary, &reason);
+    let mut expected = [second as u8; 32];
+    expected[31] = (2024 % 256) as u8;
+    assert_eq!(cert_id, Some(BytesN::from_array(&env, &expected)));
+    assert_eq!(client.balance(&owner), 0);
+    assert_eq!(client.retired_supply(), 2);
+}
+
+#[test]
+fn test_retire_direct_runs_transfer_checks() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let reason = symbol_short!("OFFSET");
+    assert_eq!(
+        client.try_retire_direct(&owner, &1, &1, &None, &reason),
+        Err(Ok(ContractError::NotInitialized))
+    );
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let locked = client.mint(&admin, &owner, &meta);
+    let frozen = client.mint(&admin, &owner, &meta);
+    let held = client.mint(&admin, &owner, &meta);
+
+    // Locked credits, e.g. pending verification, cannot be retired
+    client.set_status(
+        &admin,
+        &locked,
+        &AssetStatus::Locked,
+        &StatusReason::PendingVerification,
+        &None,
+    );
+    assert_eq!(
+        client.try_retire_direct(&owner, &1, &locked, &None, &reason),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+    client.freeze_token(&admin, &frozen);
+    assert_eq!(
+        client.try_retire_direct(&owner, &1, &frozen, &None, &reason),
+        Err(Ok(ContractError::TokenFrozen))
+    );
+
+    // Nor can a holder the regulatory check has frozen
+    let regulator = env.register(FreezingRegulator, ());
+    FreezingRegulatorClient::new(&env, &regulator).freeze(&owner);
+    client.set_regulatory_check(&admin, &regulator);
+    assert_eq!(
+        client.try_retire_direct(&owner, &1, &held, &None, &reason),
+        Err(Ok(ContractError::ComplianceFailed))
+    );
+
+    assert_eq!(client.balance(&owner), 3);
+    assert_eq!(client.retired_supply(), 0);
+    assert_eq!(client.get_status(&locked), AssetStatus::Locked);
+    assert!(!client.is_burned(&held));
+}
+
+#[test]
+fn test_event_sequence_persistence_in_storage() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+
+    client.mint(&admin, &owner, &meta);
+
+    // Check sequence incremented to 2 (MintEvent + StatusChangeEvent)
+    let sequence = client.get_event_sequence();
+    assert_eq!(sequence, 2);
+}
+
+#[test]
+fn test_mint_batch_requires_matching_serial_count() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[3u8; 32]),
+    };
+
+    let amount = MAX_SERIAL_BATCH;
+    let count = amount as u64;
+    let short = client.try_mint_batch(&admin, &owner, &meta, &amount, &5_000_000, &(count - 1));
+    assert_eq!(short, Err(Ok(ContractError::SerialCountMismatch)));
+    let long = client.try_mint_batch(&admin, &owner, &meta, &amount, &5_000_000, &(count + 1));
+    assert_eq!(long, Err(Ok(ContractError::SerialCountMismatch)));
+    assert_eq!(client.balance(&owner), 0);
+
+    // A full batch mints one token per credit within the network's limits
+    let token_ids = client.mint_batch(&admin, &owner, &meta, &amount, &5_000_000, &count);
+    assert_eq!(token_ids.len(), amount);
+    assert_eq!(client.balance(&owner), amount as i128);
+
+    let first = client.get_serial_range(&token_ids.get(0).unwrap()).unwrap();
+    assert_eq!(first.serial_start, 5_000_000);
+    assert_eq!(first.serial_count, 1);
+    let last = client
+        .get_serial_range(&token_ids.get(amount - 1).unwrap())
+        .unwrap();
+    assert_eq!(last.serial_start, 5_000_000 + count - 1);
+
+    let too_large = client.try_mint_batch(
+        &admin,
+        &owner,
+        &meta,
+        &(amount + 1),
+        &6_000_000,
+        &(count + 1),
+    );
+    assert_eq!(too_large, Err(Ok(ContractError::BatchTooLarge)));
+
+    // Ranges of later batches and tokens minted in between stay apart
+    let single = client.mint(&admin, &owner, &meta);
+    assert_eq!(client.get_serial_range(&single), None);
+    let next = client.mint_batch(&admin, &owner, &meta, &2, &7_000_000, &2);
+    let second = client.get_serial_range(&next.get(1).unwrap()).unwrap();
+    assert_eq!(second.serial_start, 7_000_001);
+    assert_eq!(client.get_serial_range(&(next.get(1).unwrap() + 1)), None);
+
+    // A batch that starts just before a bucket boundary is still found from
+    // tokens past it
+    while client.mint(&admin, &owner, &meta) < SERIAL_BUCKET_SPAN - 3 {}
+    let straddling = client.mint_batch(&admin, &owner, &meta, &amount, &8_000_000, &count);
+    assert_eq!(straddling.get(0).unwrap(), SERIAL_BUCKET_SPAN - 2);
+    let past = client
+        .get_serial_range(&straddling.get(amount - 1).unwrap())
+        .unwrap();
+    assert_eq!(past.serial_start, 8_000_000 + count - 1);
+    assert_eq!(client.get_serial_range(&(SERIAL_BUCKET_SPAN - 3)), None);
+}
+
+#[test]
+fn test_mint_bridged_records_immutable_origin() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let bridger = Address::generate(&env);
+    client.grant_role(&admin, &BRIDGER, &bridger);
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2021,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[3u8; 32]),
+    };
+    let verra = symbol_short!("VERRA");
+    let serial_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let bridge_tx = BytesN::from_array(&env, &[2u8; 32]);
+
+    let native = client.mint(&admin, &owner, &meta);
+    assert_eq!(
+        client.try_mint_bridged(&admin, &owner, &meta, &verra, &serial_hash, &bridge_tx),
+        Ok(Ok(native + 1))
+    );
+    // Only the bridged mint event names an origin registry
+    let events = env.events().all();
+    let bridged = native + 1;
+    let mint_event = MintEvent {
+        sequence: client.get_event_sequence() - 1,
+        token_id: bridged,
+        owner: owner.clone(),
+        project_id: meta.project_id.clone(),
+        vintage_year: 2021,
+        methodology_id: 1,
+        origin_registry: Some(verra.clone()),
+    };
+    assert_eq!(events.events()[0], mint_event.to_xdr(&env, &contract_id));
+
+    let minter = Address::generate(&env);
+    client.grant_role(&admin, &MINTER, &minter);
+    assert_eq!(
+        client.try_mint_bridged(&minter, &owner, &meta, &verra, &serial_hash, &bridge_tx),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    assert_eq!(client.get_origin_info(&native), None);
+    let origin = OriginInfo {
+        registry: verra.clone(),
+        serial_hash: serial_hash.clone(),
+        bridged_at: 1_700_000_000,
+        bridge_tx,
+    };
+    assert_eq!(client.get_origin_info(&bridged), Some(origin.clone()));
+    assert_eq!(
+        client.get_tokens_by_registry(&verra, &0, &10),
+        vec![&env, bridged]
+    );
+    assert_eq!(client.get_tokens_by_registry(&verra, &1, &10).len(), 0);
+    assert_eq!(
+        client
+            .get_tokens_by_registry(&symbol_short!("GOLD"), &0, &10)
+            .len(),
+        0
+    );
+
+    // The same origin serials cannot be bridged twice, and the first
+    // token's origin is untouched by the attempt
+    env.ledger().set_timestamp(1_700_086_400);
+    let other_tx = BytesN::from_array(&env, &[9u8; 32]);
+    assert_eq!(
+        client.try_mint_bridged(&bridger, &owner, &meta, &verra, &serial_hash, &other_tx),
+        Err(Ok(ContractError::SerialAlreadyBridged))
+    );
+    assert_eq!(client.get_origin_info(&bridged), Some(origin));
+    assert_eq!(client.balance(&owner), 2);
+}
+
+#[test]
+fn test_tokens_by_methodology_pages_in_mint_order() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let mut meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 7,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    meta.methodology_id = 8;
+    let other = client.mint(&admin, &owner, &meta);
+    meta.methodology_id = 7;
+    let second = client.mint(&admin, &owner, &meta);
+
+    assert_eq!(
+        client.get_tokens_by_methodology(&7, &0, &10),
+        vec![&env, first, second]
+    );
+    assert_eq!(
+        client.get_tokens_by_methodology(&7, &1, &1),
+        vec![&env, second]
+    );
+    assert_eq!(client.get_tokens_by_methodology(&7, &2, &10).len(), 0);
+    assert_eq!(
+        client.get_tokens_by_methodology(&8, &0, &10),
+        vec![&env, other]
+    );
+    assert_eq!(client.get_tokens_by_methodology(&9, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_tokens_by_vintage_pages_in_mint_order() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let mut meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2023,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let older = client.mint(&admin, &owner, &meta);
+    meta.vintage_year = 2024;
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+
+    assert_eq!(
+        client.get_tokens_by_vintage(&2024, &0, &10),
+        vec![&env, first, second]
+    );
+    assert_eq!(
+        client.get_tokens_by_vintage(&2024, &1, &5),
+        vec![&env, second]
+    );
+    assert_eq!(
+        client.get_tokens_by_vintage(&2023, &0, &10),
+        vec![&env, older]
+    );
+    assert_eq!(client.get_tokens_by_vintage(&2022, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_token_index_rolls_over_into_segments() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    // A full index written before segmentation, without segment lengths
+    let index_key = DataKey::MethodologyTokens(7);
+    let mut legacy = vec![&env];
+    for i in 0..TOKEN_INDEX_SEGMENT {
+        legacy.push_back(10_000 + i);
+    }
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&index_key, &legacy);
+    });
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 7,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+
+    // New ids go to a second segment and the original entry stays as it was
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get(&index_key), Some(legacy.clone()));
+        assert_eq!(
+            storage.get(&ExtendedKey::IndexSegment(index_key.clone(), 1)),
+            Some(vec![&env, first, second])
+        );
+        assert_eq!(
+            storage.get(&ExtendedKey::IndexSegmentLens(index_key.clone())),
+            Some(vec![&env, TOKEN_INDEX_SEGMENT, 2])
+        );
+    });
+
+    // Pages read across the segment boundary
+    let last_legacy = 10_000 + TOKEN_INDEX_SEGMENT - 1;
+    assert_eq!(
+        client.get_tokens_by_methodology(&7, &(TOKEN_INDEX_SEGMENT - 1), &10),
+        vec![&env, last_legacy, first, second]
+    );
+    assert_eq!(
+        client.get_tokens_by_methodology(&7, &TOKEN_INDEX_SEGMENT, &1),
+        vec![&env, first]
+    );
+    assert_eq!(client.get_tokens_by_methodology(&7, &0, &3).len(), 3);
+    assert_eq!(
+        client
+            .get_tokens_by_methodology(&7, &(TOKEN_INDEX_SEGMENT + 2), &10)
+            .len(),
+        0
+    );
+    assert_eq!(
+        client.get_tokens_by_vintage(&2024, &0, &10),
+        vec![&env, first, second]
+    );
+}
+#[test]
+fn test_mint_distributed_credits_each_recipient() {
+    let (env, admin, retirement_tracker, _owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+
+    assert_eq!(
+        client.try_mint_distributed(&admin, &meta, &vec![&env, (a.clone(), 2), (b.clone(), 0)]),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_mint_distributed(&admin, &meta, &vec![&env, (a.clone(), 3), (b.clone(), 3)]),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+    assert_eq!(
+        client.get_minted_supply(&meta.project_id, &meta.vintage_year),
+        0
+    );
+
+    let token_ids = client.mint_distributed(
+        &admin,
+        &meta,
+        &vec![&env, (a.clone(), 1), (b.clone(), 2), (c.clone(), 2)],
+    );
+    assert_eq!(token_ids, vec![&env, 1, 2, 3, 4, 5]);
+    assert_eq!(client.balance(&a), 1);
+    assert_eq!(client.balance(&b), 2);
+    assert_eq!(client.balance(&c), 2);
+    assert_eq!(client.tokens_of_owner(&c), vec![&env, 4, 5]);
+    assert_eq!(
+        client.get_minted_supply(&meta.project_id, &meta.vintage_year),
+        5
+    );
+}
+
+#[test]
+fn test_admin_can_update_name_and_symbol() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    client.set_symbol(&admin, &String::from_str(&env, "CSC"));
+    assert_eq!(client.symbol(), String::from_str(&env, "CSC"));
+    assert_eq!(client.get_symbol(), String::from_str(&env, "CSC"));
+
+    client.set_name(&admin, &String::from_str(&env, "CarbonScribe Credit"));
+    assert_eq!(client.name(), String::from_str(&env, "CarbonScribe Credit"));
+    assert_eq!(client.get_event_sequence(), 2);
+
+    let result = client.try_set_symbol(&owner, &String::from_str(&env, "XXX"));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    assert_eq!(client.symbol(), String::from_str(&env, "CSC"));
+}
+
+#[test]
+fn test_token_attributes() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+
+    let registry_url = String::from_str(&env, "registry_url");
+    let sdg_codes = String::from_str(&env, "sdg_codes");
+    assert_eq!(client.get_attribute(&token_id, &registry_url), None);
+
+    client.set_attribute(
+        &admin,
+        &token_id,
+        &registry_url,
+        &String::from_str(&env, "https://registry.example/PROJ-1"),
+    );
+    client.set_attribute(
+        &admin,
+        &token_id,
+        &sdg_codes,
+        &String::from_str(&env, "7,13"),
+    );
+
+    assert_eq!(
+        client.get_attribute(&token_id, &sdg_codes),
+        Some(String::from_str(&env, "7,13"))
+    );
+    let attributes = client.get_all_attributes(&token_id);
+    assert_eq!(attributes.len(), 2);
+    assert_eq!(
+        attributes.get(registry_url.clone()),
+        Some(String::from_str(&env, "https://registry.example/PROJ-1"))
+    );
+    assert_eq!(client.get_metadata(&token_id).project_id, meta.project_id);
+
+    assert_eq!(
+        client.try_set_attribute(&owner, &token_id, &sdg_codes, &String::from_str(&env, "1")),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_set_attribute(&admin, &99, &sdg_codes, &String::from_str(&env, "1")),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let (env, admin, retirement_tracker, new_admin) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(ContractError::NoPendingAdmin))
+    );
+    assert_eq!(
+        client.try_propose_admin(&new_admin, &new_admin),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    let result = client.try_set_symbol(&admin, &String::from_str(&env, "XXX"));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_symbol(&new_admin, &String::from_str(&env, "CSC"));
+}
+
+#[test]
+fn test_minter_can_mint_but_not_pause() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let minter = Address::generate(&env);
+    assert_eq!(
+        client.try_mint(&minter, &owner, &meta),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    // The admin holds every role without grants
+    assert!(client.has_role(&MINTER, &admin));
+    assert!(client.has_role(&PAUSER, &admin));
+    assert!(client.has_role(&FREEZER, &admin));
+
+    client.grant_role(&admin, &MINTER, &minter);
+    assert!(client.has_role(&MINTER, &minter));
+    assert!(!client.has_role(&PAUSER, &minter));
+    let token_id = client.mint(&minter, &owner, &meta);
+    assert_eq!(client.owner_of(&token_id), owner);
+
+    assert_eq!(
+        client.try_pause(&minter),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_freeze_token(&minter, &token_id),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_grant_role(&minter, &PAUSER, &minter),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_grant_role(&admin, &symbol_short!("BURNER"), &minter),
+        Err(Ok(ContractError::UnknownRole))
+    );
+
+    client.revoke_role(&admin, &MINTER, &minter);
+    assert_eq!(
+        client.try_mint(&minter, &owner, &meta),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_methodology_issuer_replaces_minter_role() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let mut meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 7,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let issuer = Address::generate(&env);
+    let minter = Address::generate(&env);
+    client.grant_role(&admin, &MINTER, &minter);
+
+    assert_eq!(
+        client.try_set_methodology_issuer(&minter, &7, &Some(issuer.clone())),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.set_methodology_issuer(&admin, &7, &Some(issuer.clone()));
+    assert_eq!(client.get_methodology_issuer(&7), Some(issuer.clone()));
+
+    // Only the delegated issuer mints this methodology, without a role grant
+    let token_id = client.mint(&issuer, &owner, &meta);
+    assert_eq!(env.auths()[0].0, issuer);
+    assert_eq!(client.owner_of(&token_id), owner);
+    assert_eq!(
+        client
+            .mint_batch(&issuer, &owner, &meta, &2, &100, &2)
+            .len(),
+        2
+    );
+    assert_eq!(
+        client.try_mint(&admin, &owner, &meta),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_mint(&minter, &owner, &meta),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    let recipients = vec![&env, (owner.clone(), 1u32)];
+    assert_eq!(
+        client.try_mint_distributed(&minter, &meta, &recipients),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    // Other methodologies keep role-based minting
+    meta.methodology_id = 8;
+    assert_eq!(
+        client.try_mint(&issuer, &owner, &meta),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.mint(&minter, &owner, &meta);
+
+    meta.methodology_id = 7;
+    client.set_methodology_issuer(&admin, &7, &None);
+    assert_eq!(client.get_methodology_issuer(&7), None);
+    client.mint(&minter, &owner, &meta);
+    assert_eq!(
+        client.try_mint(&issuer, &owner, &meta),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_pause_halts_minting_and_transfers() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+
+    let pauser = Address::generate(&env);
+    client.grant_role(&admin, &PAUSER, &pauser);
+    client.pause(&pauser);
+    assert!(client.is_paused());
+
+    let recipient = Address::generate(&env);
+    assert_eq!(
+        client.try_transfer(&owner, &recipient, &1),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    assert_eq!(
+        client.try_mint(&admin, &owner, &meta),
+        Err(Ok(ContractError::ContractPaused))
+    );
+
+    client.unpause(&pauser);
+    assert!(!client.is_paused());
+    client.transfer(&owner, &recipient, &1);
+    assert_eq!(client.owner_of(&token_id), recipient);
+}
+
+#[test]
+fn test_freeze_token_blocks_transfers_without_status_change() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let frozen = client.mint(&admin, &owner, &meta);
+    let free = client.mint(&admin, &owner, &meta);
+
+    assert_eq!(
+        client.try_freeze_token(&owner, &frozen),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.freeze_token(&admin, &frozen);
+    assert!(client.is_token_frozen(&frozen));
+    assert!(!client.is_token_frozen(&free));
+    assert_eq!(client.get_status(&frozen), AssetStatus::Issued);
+
+    // Amount transfers skip the frozen token and fail once only it remains
+    let buyer = Address::generate(&env);
+    client.transfer(&owner, &buyer, &1);
+    assert_eq!(client.owner_of(&free), buyer);
+    assert_eq!(
+        client.try_transfer(&owner, &buyer, &1),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+
+    let spender = Address::generate(&env);
+    client.approve(&owner, &spender, &1, &1000);
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &buyer, &1),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+    assert_eq!(
+        client.try_transfer(&owner, &retirement_tracker, &1),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+
+    client.unfreeze_token(&admin, &frozen);
+    assert!(!client.is_token_frozen(&frozen));
+    client.transfer_from(&spender, &owner, &buyer, &1);
+    assert_eq!(client.owner_of(&frozen), buyer);
+    assert_eq!(client.get_status(&frozen), AssetStatus::Issued);
+}
+
+#[test]
+fn test_freeze_is_orthogonal_to_every_status() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let statuses = [
+        AssetStatus::Issued,
+        AssetStatus::Listed,
+        AssetStatus::Locked,
+        AssetStatus::Retired,
+        AssetStatus::Invalidated,
+    ];
+    for status in statuses {
+        let token_id = client.mint(&admin, &owner, &meta);
+        if status != AssetStatus::Issued {
+            client.set_status(&admin, &token_id, &status, &StatusReason::Manual, &None);
+        }
+
+        // Freezing and unfreezing never touch the status
+        client.freeze_token(&admin, &token_id);
+        assert!(client.is_token_frozen(&token_id));
+        assert_eq!(client.get_status(&token_id), status);
+        client.unfreeze_token(&admin, &token_id);
+        assert!(!client.is_token_frozen(&token_id));
+        assert_eq!(client.get_status(&token_id), status);
+    }
+
+    // Status changes leave the flag in place, and retirement is blocked
+    // until the token is unfrozen
+    let token_id = client.mint(&admin, &owner, &meta);
+    client.freeze_token(&admin, &token_id);
+    client.set_status(
+        &admin,
+        &token_id,
+        &AssetStatus::Locked,
+        &StatusReason::PendingVerification,
+        &None,
+    );
+    assert!(client.is_token_frozen(&token_id));
+    assert_eq!(
+        client.try_burn_token(&token_id, &owner),
+        Err(Ok(ContractError::TokenFrozen))
+    );
+    client.unfreeze_token(&admin, &token_id);
+    client.burn_token(&token_id, &owner);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Retired);
+    assert!(!client.is_token_frozen(&token_id));
+}
+
+#[test]
+fn test_status_changes_record_reason() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+    assert_eq!(client.get_last_status_reason(&token_id), None);
+
+    client.set_status(
+        &admin,
+        &token_id,
+        &AssetStatus::Locked,
+        &StatusReason::PendingVerification,
+        &Some(String::from_str(&env, "Q3 monitoring report")),
+    );
+    assert_eq!(client.get_status(&token_id), AssetStatus::Locked);
+    assert_eq!(
+        client.get_last_status_reason(&token_id),
+        Some(StatusReason::PendingVerification)
+    );
+
+    assert_eq!(
+        client.try_invalidate(&owner, &token_id, &StatusReason::Fraud, &None),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.invalidate(&admin, &token_id, &StatusReason::Fraud, &None);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Invalidated);
+    assert_eq!(
+        client.get_last_status_reason(&token_id),
+        Some(StatusReason::Fraud)
+    );
+
+    assert_eq!(
+        client.try_set_status(
+            &admin,
+            &token_id,
+            &AssetStatus::Listed,
+            &StatusReason::Manual,
+            &None
+        ),
+        Err(Ok(ContractError::StatusFrozen))
+    );
+    assert_eq!(
+        client.get_last_status_reason(&token_id),
+        Some(StatusReason::Fraud)
+    );
+}
+
+#[test]
+fn test_invalidated_token_migrates_to_replacement() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let old_token = client.mint(&admin, &owner, &meta);
+    let corrected = CarbonAssetMetadata {
+        geo_hash: BytesN::from_array(&env, &[8u8; 32]),
+        ..meta.clone()
+    };
+    let new_token = client.mint(&admin, &admin, &corrected);
+    let other_project = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-2"),
+        ..meta
+    };
+    let other_token = client.mint(&admin, &admin, &other_project);
+
+    assert_eq!(
+        client.try_migrate_token(&admin, &old_token, &new_token),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+    client.invalidate(&admin, &old_token, &StatusReason::Fraud, &None);
+    assert_eq!(
+        client.try_migrate_token(&owner, &old_token, &new_token),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_migrate_token(&admin, &old_token, &other_token),
+        Err(Ok(ContractError::ProjectMismatch))
+    );
+    assert_eq!(
+        client.try_claim_migration(&owner, &old_token),
+        Err(Ok(ContractError::MigrationNotFound))
+    );
+
+    client.migrate_token(&admin, &old_token, &new_token);
+    assert_eq!(client.owner_of(&new_token), contract_id);
+    assert_eq!(
+        client.try_migrate_token(&admin, &old_token, &other_token),
+        Err(Ok(ContractError::MigrationExists))
+    );
+    let migration = client.get_migration(&old_token).unwrap();
+    assert_eq!(migration.new_token_id, new_token);
+    assert_eq!(migration.claimed_by, None);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_claim_migration(&stranger, &old_token),
+        Err(Ok(ContractError::NotOwner))
+    );
+
+    assert_eq!(client.claim_migration(&owner, &old_token), new_token);
+    assert!(client.is_burned(&old_token));
+    assert_eq!(client.owner_of(&new_token), owner);
+    assert_eq!(client.balance_of(&owner), 1);
+    assert_eq!(
+        client.get_migration(&old_token).unwrap().claimed_by,
+        Some(owner.clone())
+    );
+    assert_eq!(
+        client.try_claim_migration(&owner, &old_token),
+        Err(Ok(ContractError::MigrationClaimed))
+    );
+}
+
+#[test]
+fn test_list_and_delist_require_marketplace() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+
+    let marketplace = Address::generate(&env);
+    assert_eq!(
+        client.try_list(&marketplace, &token_id),
+        Err(Ok(ContractError::MarketplaceNotApproved))
+    );
+
+    client.add_marketplace(&admin, &marketplace);
+    assert_eq!(client.get_marketplaces(), vec![&env, marketplace.clone()]);
+
+    client.list(&marketplace, &token_id);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Listed);
+    assert_eq!(
+        client.try_list(&marketplace, &token_id),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+
+    client.delist(&marketplace, &token_id);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(
+        client.try_delist(&marketplace, &token_id),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+
+    client.remove_marketplace(&admin, &marketplace);
+    assert!(!client.is_marketplace(&marketplace));
+    assert_eq!(
+        client.try_remove_marketplace(&admin, &marketplace),
+        Err(Ok(ContractError::MarketplaceNotFound))
+    );
+}
+
+#[test]
+fn test_list_with_price_without_marketplace() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+    let stranger = Address::generate(&env);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+    let marketplace_token = client.mint(&admin, &owner, &meta);
+
+    assert_eq!(
+        client.try_list_with_price(&stranger, &token_id, &100),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_list_with_price(&owner, &token_id, &0),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+
+    client.list_with_price(&owner, &token_id, &100);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Listed);
+    assert_eq!(client.get_list_price(&token_id), Some(100));
+
+    // Relisting updates the price; the admin may also set it
+    client.list_with_price(&admin, &token_id, &120);
+    assert_eq!(client.get_list_price(&token_id), Some(120));
+
+    client.unlist(&owner, &token_id);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(client.get_list_price(&token_id), None);
+    assert_eq!(
+        client.try_unlist(&owner, &token_id),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+
+    // Marketplace listings can't be priced or withdrawn this way
+    let marketplace = Address::generate(&env);
+    client.add_marketplace(&admin, &marketplace);
+    client.list(&marketplace, &marketplace_token);
+    assert_eq!(
+        client.try_list_with_price(&owner, &marketplace_token, &100),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+    assert_eq!(
+        client.try_unlist(&owner, &marketplace_token),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+
+    // Any status change away from Listed clears the price
+    client.list_with_price(&owner, &token_id, &90);
+    client.transfer_multi(&owner, &retirement_tracker, &vec![&env, token_id]);
+    assert_eq!(client.get_status(&token_id), AssetStatus::Retired);
+    assert_eq!(client.get_list_price(&token_id), None);
+}
+
+#[test]
+fn test_status_breakdown_tallies_mixed_tokens() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let mut token_ids = vec![&env];
+    for _ in 0..5 {
+        token_ids.push_back(client.mint(&admin, &owner, &meta));
+    }
+    client.list_with_price(&owner, &token_ids.get(1).unwrap(), &100);
+    client.burn_token(&token_ids.get(2).unwrap(), &owner);
+    client.invalidate(
+        &admin,
+        &token_ids.get(3).unwrap(),
+        &StatusReason::Fraud,
+        &None,
+    );
+
+    let breakdown = client.status_breakdown(&token_ids);
+    assert_eq!(breakdown.len(), 4);
+    assert_eq!(breakdown.get(AssetStatus::Issued), Some(2));
+    assert_eq!(breakdown.get(AssetStatus::Listed), Some(1));
+    assert_eq!(breakdown.get(AssetStatus::Retired), Some(1));
+    assert_eq!(breakdown.get(AssetStatus::Invalidated), Some(1));
+    assert_eq!(breakdown.get(AssetStatus::Locked), None);
+
+    assert_eq!(client.status_breakdown(&vec![&env]).len(), 0);
+    assert_eq!(
+        client.try_status_breakdown(&vec![&env, 1, 99]),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_calls_before_initialize_fail_with_not_initialized() {
+    let (env, admin, _retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let buyer = Address::generate(&env);
+
+    assert!(!client.is_initialized());
+    assert_eq!(
+        client.try_mint(&admin, &owner, &meta),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_transfer(&owner, &buyer, &1),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_approve(&owner, &buyer, &1, &100),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_balance(&owner),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(client.try_name(), Err(Ok(ContractError::NotInitialized)));
+    assert_eq!(
+        client.try_pause(&admin),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_set_oracle(&admin, &buyer),
+        Err(Ok(ContractError::NotInitialized))
+    );
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    assert!(client.is_initialized());
+    assert_eq!(client.balance(&owner), 0);
+}
+
+#[test]
+fn test_get_config_reflects_every_setter() {
+    let (env, admin, retirement_tracker, _owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_get_config(),
+        Err(Ok(ContractError::NotInitialized))
+    );
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    assert_eq!(env.events().all().events().len(), 1);
+
+    let initial = ContractConfig {
+        admin: admin.clone(),
+        pending_admin: None,
+        name: String::from_str(&env, "Carbon Asset"),
+        symbol: String::from_str(&env, "C01"),
+        decimals: 0,
+        retirement_tracker,
+        host_jurisdiction: Some(String::from_str(&env, "US")),
+        regulatory_check: None,
+        verification_registry: None,
+        marketplaces: vec![&env],
+        methodology_library: None,
+        oracle: None,
+        circuit_breaker: None,
+        fee_token: None,
+        mint_fee: 0,
+        fee_treasury: None,
+        issuance_key: None,
+        mint_lock_seconds: 0,
+        summary_mode: false,
+        balance_observer: None,
+        paused: false,
+    };
+    assert_eq!(client.get_config(), initial);
+
+    let new_admin = Address::generate(&env);
+    let tracker = Address::generate(&env);
+    let regulatory_check = Address::generate(&env);
+    let registry = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    let library = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let circuit_breaker = Address::generate(&env);
+    let fee_token = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let issuance_key = BytesN::from_array(&env, &[9u8; 32]);
+    let observer = Address::generate(&env);
+
+    client.propose_admin(&admin, &new_admin);
+    client.set_name(&admin, &String::from_str(&env, "Renamed"));
+    client.set_symbol(&admin, &String::from_str(&env, "C02"));
+    client.set_retirement_tracker(&admin, &tracker);
+    client.set_host_jurisdiction(&admin, &String::from_str(&env, "KE"));
+    client.set_regulatory_check(&admin, &regulatory_check);
+    client.set_verification_registry(&admin, &registry);
+    client.add_marketplace(&admin, &marketplace);
+    client.set_methodology_library(&admin, &library);
+    client.set_oracle(&admin, &oracle);
+    client.set_circuit_breaker(&admin, &circuit_breaker);
+    client.set_mint_fee(&admin, &fee_token, &5, &treasury);
+    client.set_issuance_key(&admin, &Some(issuance_key.clone()));
+    client.set_mint_lock_seconds(&admin, &3_600);
+    client.set_summary_mode(&admin, &true);
+    client.set_balance_observer(&admin, &Some(observer.clone()));
+    client.pause(&admin);
+
+    assert_eq!(
+        client.get_config(),
+        ContractConfig {
+            admin,
+            pending_admin: Some(new_admin),
+            name: String::from_str(&env, "Renamed"),
+            symbol: String::from_str(&env, "C02"),
+            decimals: 0,
+            retirement_tracker: tracker,
+            host_jurisdiction: Some(String::from_str(&env, "KE")),
+            regulatory_check: Some(regulatory_check),
+            verification_registry: Some(registry),
+            marketplaces: vec![&env, marketplace],
+            methodology_library: Some(library),
+            oracle: Some(oracle),
+            circuit_breaker: Some(circuit_breaker),
+            fee_token: Some(fee_token),
+            mint_fee: 5,
+            fee_treasury: Some(treasury),
+            issuance_key: Some(issuance_key),
+            mint_lock_seconds: 3_600,
+            summary_mode: true,
+            balance_observer: Some(observer),
+            paused: true,
+        }
+    );
+}
+
+// Deterministic xorshift generator so failing sequences can be replayed
+// from the seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+// Every credit ever minted is either held by someone (the retirement
+// tracker included) or burned, and every live token is indexed under its
+// owner.
+fn assert_supply_conserved(
+    client: &CarbonAssetClient,
+    holders: &[Address],
+    meta: &CarbonAssetMetadata,
+    seed: u64,
+    step: u32,
+) {
+    let minted = client.get_minted_supply(&meta.project_id, &meta.vintage_year);
+    let held: i128 = holders.iter().map(|h| client.balance(h)).sum();
+
+    let mut burned = 0i128;
+    for token_id in 1..=minted as u32 {
+        if client.is_burned(&token_id) {
+            burned += 1;
+            continue;
+        }
+        let owner = client.owner_of(&token_id);
+        assert!(
+            client.tokens_of_owner(&owner).contains(token_id),
+            "seed {seed} step {step}: token {token_id} missing from owner index"
+        );
+    }
+
+    assert_eq!(
+        minted,
+        held + burned,
+        "seed {seed} step {step}: supply not conserved"
+    );
+}
+
+#[test]
+fn test_random_operations_conserve_supply() {
+    for seed in [0x9e37_79b9_7f4a_7c15u64, 0xdead_beef, 42] {
+        let (env, admin, retirement_tracker, _owner) = setup_env();
+        let contract_id = env.register(CarbonAsset, ());
+        let client = CarbonAssetClient::new(&env, &contract_id);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Carbon Asset"),
+            &String::from_str(&env, "C01"),
+            &retirement_tracker,
+            &String::from_str(&env, "US"),
+        );
+
+        let meta = CarbonAssetMetadata {
+            project_id: String::from_str(&env, "PROJ-1"),
+            vintage_year: 1704067200,
+            methodology_id: 1,
+            geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+        };
+        let holders = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+            retirement_tracker.clone(),
+        ];
+
+        let mut rng = Rng(seed);
+        for step in 0..80 {
+            let from = holders[rng.below(holders.len() as u64) as usize].clone();
+            let to = holders[rng.below(holders.len() as u64) as usize].clone();
+            let amount = rng.below(3) as i128 + 1;
+
+            // Operations may legitimately fail (empty balance, retired
+            // tokens, self-transfer); the invariant must hold either way.
+            match rng.below(5) {
+                0 => {
+                    let _ = client.try_mint(&admin, &from, &meta);
+                }
+                1 => {
+                    let _ = client.try_transfer(&from, &to, &amount);
+                }
+                2 => {
+                    let _ = client.try_transfer(&from, &retirement_tracker, &amount);
+                }
+                3 => {
+                    let _ = client.try_burn(&retirement_tracker, &amount);
+                }
+                _ => {
+                    let minted = client.get_minted_supply(&meta.project_id, &meta.vintage_year);
+                    if minted > 0 {
+                        let token_id = rng.below(minted as u64) as u32 + 1;
+                        let _ = client.try_burn_token(&token_id, &retirement_tracker);
+                    }
+                }
+            }
+
+            assert_supply_conserved(&client, &holders, &meta, seed, step);
+        }
+    }
+}
+
+#[test]
+fn test_mint_rejects_counter_overflow() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+
+    // Supply already at the top of i128 for this vintage
+    let supply_key = DataKey::MintedSupply(meta.project_id.clone(), meta.vintage_year);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&supply_key, &i128::MAX);
+    });
+    assert_eq!(
+        client.try_mint(&admin, &owner, &meta),
+        Err(Ok(ContractError::SupplyOverflow))
+    );
+    assert_eq!(
+        client.get_minted_supply(&meta.project_id, &meta.vintage_year),
+        i128::MAX
+    );
+    assert_eq!(client.balance(&owner), 0);
+
+    // Token ids exhausted
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&supply_key);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTokenId, &u32::MAX);
+    });
+    assert_eq!(
+        client.try_mint(&admin, &owner, &meta),
+        Err(Ok(ContractError::SupplyOverflow))
+    );
+    assert_eq!(client.balance(&owner), 0);
+}
+
+#[test]
+fn test_sale_price_transfer_pays_developer_royalty() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let marketplace = Address::generate(&env);
+    client.add_marketplace(&admin, &marketplace);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let royalty_token = client.mint(&admin, &owner, &meta);
+    let plain_token = client.mint(&admin, &owner, &meta);
+
+    let developer = Address::generate(&env);
+    assert_eq!(
+        client.try_set_royalty(&admin, &royalty_token, &developer, &501),
+        Err(Ok(ContractError::InvalidRoyalty))
+    );
+    assert_eq!(
+        client.try_set_royalty(&admin, &99, &developer, &100),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+    client.set_royalty(&admin, &royalty_token, &developer, &250);
+
+    // 2.5%, rounded down
+    assert_eq!(
+        client.get_royalty_info(&royalty_token, &1_000),
+        Some((developer.clone(), 25))
+    );
+    assert_eq!(
+        client.get_royalty_info(&royalty_token, &39),
+        Some((developer.clone(), 0))
+    );
+    assert_eq!(client.get_royalty_info(&plain_token, &1_000), None);
+
+    let buyer = Address::generate(&env);
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let payment = TokenClient::new(&env, &payment_id);
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &1_000);
+    let live_until = env.ledger().sequence() + 100;
+    payment.approve(&buyer, &contract_id, &100, &live_until);
+    client.approve(&owner, &marketplace, &2, &live_until);
+
+    client.transfer_with_sale_price(
+        &marketplace,
+        &owner,
+        &buyer,
+        &vec![&env, royalty_token],
+        &1_000,
+        &payment_id,
+    );
+    assert_eq!(payment.balance(&developer), 25);
+    assert_eq!(payment.balance(&buyer), 975);
+    assert_eq!(client.owner_of(&royalty_token), buyer);
+    assert_eq!(client.allowance(&owner, &marketplace), 1);
+
+    // A zero-royalty token moves without touching the payment token
+    let other_buyer = Address::generate(&env);
+    client.transfer_with_sale_price(
+        &marketplace,
+        &owner,
+        &other_buyer,
+        &vec![&env, plain_token],
+        &1_000,
+        &payment_id,
+    );
+    assert_eq!(client.owner_of(&plain_token), other_buyer);
+    assert_eq!(payment.balance(&developer), 25);
+    assert_eq!(payment.balance(&other_buyer), 0);
+
+    // The buyer's remaining allowance of 75 cannot cover a 500 royalty, so
+    // the credit stays with the seller
+    client.transfer(&other_buyer, &owner, &1);
+    client.set_royalty(&admin, &plain_token, &developer, &500);
+    client.approve(&owner, &marketplace, &1, &live_until);
+    assert!(client
+        .try_transfer_with_sale_price(
+            &marketplace,
+            &owner,
+            &buyer,
+            &vec![&env, plain_token],
+            &10_000,
+            &payment_id
+        )
+        .is_err());
+    assert_eq!(client.owner_of(&plain_token), owner);
+    assert_eq!(client.allowance(&owner, &marketplace), 1);
+    assert_eq!(payment.balance(&developer), 25);
+}
+
+#[test]
+fn test_sale_price_transfer_moves_exactly_the_named_tokens() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let marketplace = Address::generate(&env);
+    client.add_marketplace(&admin, &marketplace);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let first = client.mint(&admin, &owner, &meta);
+    let second = client.mint(&admin, &owner, &meta);
+    let third = client.mint(&admin, &owner, &meta);
+    let fourth = client.mint(&admin, &owner, &meta);
+
+    let developer = Address::generate(&env);
+    client.set_royalty(&admin, &third, &developer, &500);
+    client.set_royalty(&admin, &fourth, &developer, &250);
+    let buyer = Address::generate(&env);
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let payment = TokenClient::new(&env, &payment_id);
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &1_000);
+    let live_until = env.ledger().sequence() + 100;
+    payment.approve(&buyer, &contract_id, &1_000, &live_until);
+    client.approve(&owner, &marketplace, &3, &live_until);
+
+    // The named tokens move even though the seller holds others before
+    // them, and each pays its own royalty on the unit price
+    client.list_tokens(&marketplace, &owner, &vec![&env, third, fourth]);
+    client.transfer_with_sale_price(
+        &marketplace,
+        &owner,
+        &buyer,
+        &vec![&env, fourth, third],
+        &1_000,
+        &payment_id,
+    );
+    assert_eq!(client.owner_of(&third), buyer);
+    assert_eq!(client.owner_of(&fourth), buyer);
+    assert_eq!(client.owner_of(&first), owner);
+    assert_eq!(client.owner_of(&second), owner);
+    assert_eq!(payment.balance(&developer), 50 + 25);
+    assert_eq!(client.listed_on(&owner, &third, &marketplace), 0);
+    assert_eq!(client.free_balance(&owner), 2);
+    assert_eq!(client.allowance(&owner, &marketplace), 1);
+
+    // The seller must still hold every token, and each must be movable
+    client.approve(&owner, &marketplace, &2, &live_until);
+    assert_eq!(
+        client.try_transfer_with_sale_price(
+            &marketplace,
+            &owner,
+            &buyer,
+            &vec![&env, first, third],
+            &1_000,
+            &payment_id
+        ),
+        Err(Ok(ContractError::NotOwner))
+    );
+    client.freeze_token(&admin, &second);
+    assert_eq!(
+        client.try_transfer_with_sale_price(
+            &marketplace,
+            &owner,
+            &buyer,
+            &vec![&env, second],
+            &1_000,
+            &payment_id
+        ),
+        Err(Ok(ContractError::TokenFrozen))
+    );
+    assert_eq!(
+        client.try_transfer_with_sale_price(
+            &marketplace,
+            &owner,
+            &buyer,
+            &vec![&env, first, first],
+            &1_000,
+            &payment_id
+        ),
+        Err(Ok(ContractError::DuplicateToken))
+    );
+    assert_eq!(
+        client.try_transfer_with_sale_price(
+            &marketplace,
+            &owner,
+            &buyer,
+            &vec![&env],
+            &1_000,
+            &payment_id
+        ),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.owner_of(&first), owner);
+    assert_eq!(client.owner_of(&second), owner);
+    assert_eq!(payment.balance(&developer), 75);
+}
+
+#[test]
+fn test_listed_tokens_are_reserved_per_marketplace() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let unapproved = Address::generate(&env);
+    client.add_marketplace(&admin, &first);
+    client.add_marketplace(&admin, &second);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+    let other_token = client.mint(&admin, &owner, &meta);
+    let free_token = client.mint(&admin, &owner, &meta);
+    let recipient = Address::generate(&env);
+
+    assert_eq!(
+        client.try_list_tokens(&unapproved, &owner, &vec![&env, token_id]),
+        Err(Ok(ContractError::MarketplaceNotApproved))
+    );
+    assert_eq!(
+        client.try_list_tokens(&first, &recipient, &vec![&env, token_id]),
+        Err(Ok(ContractError::NotOwner))
+    );
+
+    client.list_tokens(&first, &owner, &vec![&env, token_id, other_token]);
+    assert_eq!(client.listed_on(&owner, &token_id, &first), 1);
+    assert_eq!(client.free_balance(&owner), 1);
+
+    // The same token cannot be listed again, here or on another marketplace
+    assert_eq!(
+        client.try_list_tokens(&second, &owner, &vec![&env, free_token, token_id]),
+        Err(Ok(ContractError::TokenListed))
+    );
+    assert_eq!(
+        client.try_list_tokens(&first, &owner, &vec![&env, token_id]),
+        Err(Ok(ContractError::TokenListed))
+    );
+    client.list_tokens(&second, &owner, &vec![&env, free_token]);
+    assert_eq!(client.listed_amount(&owner, &free_token), 1);
+    assert_eq!(client.listed_on(&owner, &free_token, &second), 1);
+
+    // Listed tokens move neither by id nor by amount
+    assert_eq!(
+        client.try_transfer(&owner, &recipient, &1),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+    assert_eq!(
+        client.try_transfer_multi(&owner, &recipient, &vec![&env, token_id]),
+        Err(Ok(ContractError::TokenListed))
+    );
+    assert_eq!(
+        client.try_escrow_for_retirement(&token_id, &owner),
+        Err(Ok(ContractError::TokenListed))
+    );
+    assert_eq!(
+        client.can_transfer_token(&owner, &recipient, &token_id, &false),
+        TransferCheck {
+            allowed: false,
+            reason: Symbol::new(&env, "TokenListed"),
+        }
+    );
+
+    // Each marketplace can only release what it listed
+    assert_eq!(
+        client.try_delist_tokens(&second, &owner, &vec![&env, token_id]),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.remove_marketplace(&admin, &first);
+    client.delist_tokens(&first, &owner, &vec![&env, other_token]);
+    assert_eq!(client.listed_on(&owner, &other_token, &first), 0);
+    assert_eq!(client.listed_amount(&owner, &token_id), 1);
+
+    // Delisting makes the credit transferable again, and an amount transfer
+    // passes over the tokens still listed
+    client.transfer(&owner, &recipient, &1);
+    assert_eq!(client.owner_of(&other_token), recipient);
+    assert_eq!(client.balance(&owner), 2);
+    assert_eq!(client.free_balance(&owner), 0);
+
+    // A sale releases the selling marketplace's listing, but not another's
+    let live_until = env.ledger().sequence() + 100;
+    client.approve(&owner, &second, &2, &live_until);
+    let payment_token = Address::generate(&env);
+    assert_eq!(
+        client.try_transfer_with_sale_price(
+            &second,
+            &owner,
+            &recipient,
+            &vec![&env, token_id],
+            &10,
+            &payment_token,
+        ),
+        Err(Ok(ContractError::InsufficientFreeBalance))
+    );
+    client.transfer_with_sale_price(
+        &second,
+        &owner,
+        &recipient,
+        &vec![&env, free_token],
+        &10,
+        &payment_token,
+    );
+    assert_eq!(client.owner_of(&free_token), recipient);
+    assert_eq!(client.listed_on(&owner, &free_token, &second), 0);
+    assert_eq!(client.listed_amount(&owner, &token_id), 1);
+    assert_eq!(client.free_balance(&owner), 0);
+}
+
+// The signature `mint_with_sig` expects from `key` for these parameters.
+fn sign_mint(
+    env: &Env,
+    key: &SigningKey,
+    contract: &Address,
+    owner: &Address,
+    metadata: &CarbonAssetMetadata,
+    amount: u32,
+    nonce: u64,
+) -> BytesN<64> {
+    let authorization = MintAuthorization {
+        contract: contract.clone(),
+        owner: owner.clone(),
+        metadata: metadata.clone(),
+        amount,
+        nonce,
+    };
+    let message: std::vec::Vec<u8> = authorization.to_xdr(env).iter().collect();
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_mint_with_sig_checks_signer_and_nonce() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let key = SigningKey::from_bytes(&[3u8; 32]);
+    let signer = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let issuer = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let signature = sign_mint(&env, &key, &contract_id, &owner, &meta, 2, 0);
+
+    // The key must be registered, and its issuer able to mint
+    assert_eq!(
+        client.try_mint_with_sig(&relayer, &owner, &meta, &2, &0, &signer, &signature),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.set_mint_signer(&admin, &signer, &Some(issuer.clone()));
+    assert_eq!(client.get_mint_signer(&signer), Some(issuer.clone()));
+    assert_eq!(
+        client.try_mint_with_sig(&relayer, &owner, &meta, &2, &0, &signer, &signature),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.grant_role(&admin, &MINTER, &issuer);
+
+    let token_ids = client.mint_with_sig(&relayer, &owner, &meta, &2, &0, &signer, &signature);
+    assert_eq!(token_ids, vec![&env, 1, 2]);
+    assert_eq!(client.balance(&owner), 2);
+    assert_eq!(client.get_mint_nonce(&signer), 1);
+
+    // Replaying the same authorization fails on the used nonce
+    assert_eq!(
+        client.try_mint_with_sig(&relayer, &owner, &meta, &2, &0, &signer, &signature),
+        Err(Ok(ContractError::InvalidNonce))
+    );
+
+    // A signature over different parameters does not verify
+    let signature = sign_mint(&env, &key, &contract_id, &owner, &meta, 1, 1);
+    assert!(client
+        .try_mint_with_sig(&relayer, &owner, &meta, &2, &1, &signer, &signature)
+        .is_err());
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_mint_with_sig(&relayer, &stranger, &meta, &1, &1, &signer, &signature)
+        .is_err());
+    assert_eq!(client.balance(&owner), 2);
+    assert_eq!(client.get_mint_nonce(&signer), 1);
+
+    client.mint_with_sig(&relayer, &owner, &meta, &1, &1, &signer, &signature);
+    assert_eq!(client.balance(&owner), 3);
+
+    // Retiring the key stops further mints
+    client.set_mint_signer(&admin, &signer, &None);
+    let signature = sign_mint(&env, &key, &contract_id, &owner, &meta, 1, 2);
+    assert_eq!(
+        client.try_mint_with_sig(&relayer, &owner, &meta, &1, &2, &signer, &signature),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+}
+
+fn sign_voucher(env: &Env, key: &SigningKey, voucher: &MintVoucher) -> BytesN<64> {
+    let message: std::vec::Vec<u8> = voucher.clone().to_xdr(env).iter().collect();
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_mint_with_voucher_checks_key_nonce_and_expiry() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let key = SigningKey::from_bytes(&[5u8; 32]);
+    let operator = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+    let voucher = MintVoucher {
+        contract: contract_id.clone(),
+        to: owner.clone(),
+        amount: 2,
+        metadata_hash: env.crypto().sha256(&meta.clone().to_xdr(&env)).into(),
+        nonce: 7,
+        expires_at: 2_000,
+    };
+    let signature = sign_voucher(&env, &key, &voucher);
+
+    // Nothing is accepted until an issuance key is registered
+    assert_eq!(
+        client.try_mint_with_voucher(&operator, &voucher, &meta, &signature),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.set_issuance_key(
+        &admin,
+        &Some(BytesN::from_array(&env, &key.verifying_key().to_bytes())),
+    );
+
+    // Metadata other than what was signed for is rejected
+    let other_meta = CarbonAssetMetadata {
+        vintage_year: 1735689600,
+        ..meta.clone()
+    };
+    assert_eq!(
+        client.try_mint_with_voucher(&operator, &voucher, &other_meta, &signature),
+        Err(Ok(ContractError::VoucherMismatch))
+    );
+
+    // A signature from another key, or over other terms, does not verify
+    let forged = sign_voucher(&env, &SigningKey::from_bytes(&[6u8; 32]), &voucher);
+    assert!(client
+        .try_mint_with_voucher(&operator, &voucher, &meta, &forged)
+        .is_err());
+    let inflated = MintVoucher {
+        amount: 20,
+        ..voucher.clone()
+    };
+    assert!(client
+        .try_mint_with_voucher(&operator, &inflated, &meta, &signature)
+        .is_err());
+    assert!(!client.is_voucher_used(&7));
+
+    let token_ids = client.mint_with_voucher(&operator, &voucher, &meta, &signature);
+    assert_eq!(token_ids, vec![&env, 1, 2]);
+    assert_eq!(client.balance(&owner), 2);
+    assert!(client.is_voucher_used(&7));
+
+    // Each nonce is redeemed once
+    assert_eq!(
+        client.try_mint_with_voucher(&operator, &voucher, &meta, &signature),
+        Err(Ok(ContractError::VoucherUsed))
+    );
+
+    // Vouchers cannot be redeemed after they expire
+    let late = MintVoucher {
+        nonce: 8,
+        ..voucher
+    };
+    let signature = sign_voucher(&env, &key, &late);
+    env.ledger().set_timestamp(2_001);
+    assert_eq!(
+        client.try_mint_with_voucher(&operator, &late, &meta, &signature),
+        Err(Ok(ContractError::VoucherExpired))
+    );
+    assert_eq!(client.balance(&owner), 2);
+}
+
+#[test]
+fn test_mint_fee_is_paid_before_minting() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let treasury = Address::generate(&env);
+    let fee_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let fee_token = TokenClient::new(&env, &fee_id);
+    StellarAssetClient::new(&env, &fee_id).mint(&admin, &25);
+
+    assert_eq!(client.get_mint_fee(), None);
+    assert_eq!(
+        client.try_set_mint_fee(&owner, &fee_id, &10, &treasury),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_set_mint_fee(&admin, &fee_id, &-1, &treasury),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.set_mint_fee(&admin, &fee_id, &10, &treasury);
+    assert_eq!(
+        client.get_mint_fee(),
+        Some(MintFee {
+            fee_token: fee_id.clone(),
+            amount: 10,
+            treasury: treasury.clone(),
+        })
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+    assert_eq!(client.owner_of(&token_id), owner);
+    assert_eq!(fee_token.balance(&treasury), 10);
+    assert_eq!(fee_token.balance(&admin), 15);
+
+    // The fee is charged per credit: 15 covers one credit but not two
+    assert_eq!(
+        client.try_mint_batch(&admin, &owner, &meta, &2, &100, &2),
+        Err(Ok(ContractError::MintFeeFailed))
+    );
+    assert_eq!(client.balance(&owner), 1);
+    assert_eq!(client.get_minted_supply(&meta.project_id, &2024), 1);
+    assert_eq!(fee_token.balance(&admin), 15);
+
+    // An issuer with no balance cannot mint at all
+    let minter = Address::generate(&env);
+    client.grant_role(&admin, &MINTER, &minter);
+    assert_eq!(
+        client.try_mint(&minter, &owner, &meta),
+        Err(Ok(ContractError::MintFeeFailed))
+    );
+
+    // A zero fee turns collection off
+    client.set_mint_fee(&admin, &fee_id, &0, &treasury);
+    client.mint(&minter, &owner, &meta);
+    assert_eq!(client.balance(&owner), 2);
+    assert_eq!(fee_token.balance(&treasury), 10);
+}
+
+#[test]
+fn test_transfers_to_any_retire_sink_auto_retire() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..3 {
+        client.mint(&admin, &owner, &meta);
+    }
+
+    let eu_sink = Address::generate(&env);
+    let uk_sink = Address::generate(&env);
+    client.add_retire_sink(&admin, &eu_sink);
+    client.add_retire_sink(&admin, &uk_sink);
+    assert_eq!(
+        client.get_retire_sinks(),
+        vec![
+            &env,
+            retirement_tracker.clone(),
+            eu_sink.clone(),
+            uk_sink.clone()
+        ]
+    );
+
+    client.transfer(&owner, &eu_sink, &1);
+    let first = client.tokens_of_owner(&eu_sink).get(0).unwrap();
+    assert_eq!(client.get_status(&first), AssetStatus::Retired);
+
+    client.transfer(&owner, &uk_sink, &1);
+    let events = env.events().all();
+    let second = client.tokens_of_owner(&uk_sink).get(0).unwrap();
+    // The SEP-41 transfer event follows the retirement
+    let auto_retire = AutoRetireEvent {
+        sequence: client.get_event_sequence() - 1,
+        token_id: second,
+        from: owner.clone(),
+        sink: uk_sink.clone(),
+    };
+    assert!(events
+        .events()
+        .contains(&auto_retire.to_xdr(&env, &contract_id)));
+    assert_eq!(client.get_status(&second), AssetStatus::Retired);
+
+    // A removed sink receives credits like any other holder
+    client.remove_retire_sink(&admin, &uk_sink);
+    assert!(!client.is_retire_sink(&uk_sink));
+    assert!(client.is_retire_sink(&retirement_tracker));
+    assert_eq!(
+        client.try_remove_retire_sink(&admin, &uk_sink),
+        Err(Ok(ContractError::RetireSinkNotFound))
+    );
+    client.transfer(&owner, &uk_sink, &1);
+    assert!(client.tokens_of_owner(&owner).is_empty());
+    let third = client.tokens_of_owner(&uk_sink).get(1).unwrap();
+    assert_eq!(client.get_status(&third), AssetStatus::Issued);
+}
+
+#[test]
+fn test_regulatory_rejection_is_reported_to_the_caller() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let regulator = env.register(RejectingRegulator, ());
+    client.set_regulatory_check(&admin, &regulator);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = client.mint(&admin, &owner, &meta);
+    let buyer = Address::generate(&env);
+
+    // The rejection comes back as an error, not a trap, and nothing moves
+    assert_eq!(
+        client.try_transfer(&owner, &buyer, &1),
+        Err(Ok(ContractError::ComplianceFailed))
+    );
+    assert_eq!(
+        client.try_transfer_multi(&owner, &buyer, &vec![&env, token_id]),
+        Err(Ok(ContractError::ComplianceFailed))
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+
+    let rejected = TransferCheck {
+        allowed: false,
+        reason: Symbol::new(&env, "ComplianceFailed"),
+    };
+    assert_eq!(client.can_transfer(&owner, &buyer, &1, &true), rejected);
+    assert_eq!(
+        client.can_transfer_token(&owner, &buyer, &token_id, &true),
+        rejected
+    );
+}
+
+#[test]
+fn test_simulate_transfer_matches_transfer() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..3 {
+        client.mint(&admin, &owner, &meta);
+    }
+
+    // Plain transfer
+    let buyer = Address::generate(&env);
+    let simulation = client.simulate_transfer(&owner, &buyer, &2);
+    assert_eq!(simulation.failure, None);
+    assert!(!simulation.would_auto_retire);
+    assert_eq!((simulation.fee, simulation.royalty), (0, 0));
+    let before = client.balance(&buyer);
+    client.transfer(&owner, &buyer, &2);
+    assert_eq!(client.balance(&buyer) - before, simulation.net_to_recipient);
+    assert_eq!(client.tokens_of_owner(&buyer), simulation.token_ids);
+
+    // Transfer into a retire sink
+    let simulation = client.simulate_transfer(&owner, &retirement_tracker, &1);
+    assert_eq!(simulation.failure, None);
+    assert!(simulation.would_auto_retire);
+    client.transfer(&owner, &retirement_tracker, &1);
+    let retired = simulation.token_ids.get(0).unwrap();
+    assert_eq!(client.owner_of(&retired), retirement_tracker);
+    assert_eq!(client.get_status(&retired), AssetStatus::Retired);
+
+    // Failures name the error and leave balances alone
+    let simulation = client.simulate_transfer(&buyer, &owner, &3);
+    assert_eq!(
+        simulation.failure,
+        Some(Symbol::new(&env, "TransferNotAllowed"))
+    );
+    assert_eq!(simulation.net_to_recipient, 0);
+    assert!(simulation.token_ids.is_empty());
+    assert_eq!(
+        client.try_transfer(&buyer, &owner, &3),
+        Err(Ok(ContractError::TransferNotAllowed))
+    );
+    assert_eq!(client.balance(&buyer), 2);
+}
+
+#[test]
+fn test_simulate_sale_matches_sale() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+    let marketplace = Address::generate(&env);
+    let other_marketplace = Address::generate(&env);
+    client.add_marketplace(&admin, &marketplace);
+    client.add_marketplace(&admin, &other_marketplace);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for serial_start in [0, 2, 4, 6, 8] {
+        client.mint_batch(&admin, &owner, &meta, &2, &serial_start, &2);
+    }
+
+    let developer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let payment = TokenClient::new(&env, &payment_id);
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &10_000);
+    let live_until = env.ledger().sequence() + 100;
+    payment.approve(&buyer, &contract_id, &10_000, &live_until);
+    client.approve(&owner, &marketplace, &10, &live_until);
+
+    // No royalty, then 2.5% and 5% (rounded down), on sales of one to three
+    // credits, the first of them listed
+    for (amount, bps, price_per_unit) in [(1, 0, 1_000), (2, 250, 999), (3, 500, 2_345)] {
+        let tokens = client.tokens_of_owner(&owner);
+        let mut token_ids = vec![&env];
+        for i in 0..amount {
+            let token_id = tokens.get(i).unwrap();
+            client.set_royalty(&admin, &token_id, &developer, &bps);
+            token_ids.push_back(token_id);
+        }
+        client.list_tokens(&marketplace, &owner, &vec![&env, token_ids.get(0).unwrap()]);
+
+        let simulation =
+            client.simulate_sale(&marketplace, &owner, &buyer, &token_ids, &price_per_unit);
+        assert_eq!(simulation.failure, None);
+        assert_eq!(simulation.fee, 0);
+        assert_eq!(
+            simulation.royalty,
+            amount as i128 * (price_per_unit * bps as i128 / 10_000)
+        );
+        assert_eq!(simulation.token_ids, token_ids);
+
+        let paid_before = payment.balance(&buyer);
+        let royalty_before = payment.balance(&developer);
+        let credits_before = client.balance(&buyer);
+        client.transfer_with_sale_price(
+            &marketplace,
+            &owner,
+            &buyer,
+            &token_ids,
+            &price_per_unit,
+            &payment_id,
+        );
+        assert_eq!(paid_before - payment.balance(&buyer), simulation.royalty);
+        assert_eq!(
+            payment.balance(&developer) - royalty_before,
+            simulation.royalty
+        );
+        assert_eq!(
+            client.balance(&buyer) - credits_before,
+            simulation.net_to_recipient
+        );
+        for moved in simulation.token_ids.iter() {
+            assert_eq!(client.owner_of(&moved), buyer);
+        }
+    }
+
+    // Failures name the error the sale returns
+    let tokens = client.tokens_of_owner(&owner);
+    client.list_tokens(
+        &other_marketplace,
+        &owner,
+        &vec![&env, tokens.get(1).unwrap()],
+    );
+    for (token_ids, error) in [
+        (vec![&env], ContractError::InvalidAmount),
+        (tokens.clone(), ContractError::InsufficientFreeBalance),
+        (
+            vec![&env, tokens.get(0).unwrap(), tokens.get(1).unwrap()],
+            ContractError::TokenListed,
+        ),
+    ] {
+        let simulation = client.simulate_sale(&marketplace, &owner, &buyer, &token_ids, &1_000);
+        assert_eq!(simulation.failure, Some(error.reason(&env)));
+        assert_eq!((simulation.net_to_recipient, simulation.royalty), (0, 0));
+        assert_eq!(
+            client.try_transfer_with_sale_price(
+                &marketplace,
+                &owner,
+                &buyer,
+                &token_ids,
+                &1_000,
+                &payment_id,
+            ),
+            Err(Ok(error))
+        );
+    }
+}
+
+#[test]
+fn test_simulate_mint_matches_mint() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+
+    // No verification registry
+    let simulation = client.simulate_mint(&meta, &2);
+    assert_eq!(simulation.failure, None);
+    assert_eq!(simulation.to_recipient, 2);
+    assert_eq!(
+        client.get_minted_supply(&meta.project_id, &meta.vintage_year),
+        0
+    );
+    assert_eq!((simulation.fee, simulation.to_buffer), (0, 0));
+    let minted = client.mint_batch(&admin, &owner, &meta, &2, &100, &2);
+    assert_eq!(minted, simulation.token_ids);
+    assert_eq!(client.balance(&owner), simulation.to_recipient as i128);
+
+    for (amount, error) in [
+        (0, ContractError::InvalidAmount),
+        (6, ContractError::BatchTooLarge),
+    ] {
+        let simulation = client.simulate_mint(&meta, &amount);
+        assert_eq!(simulation.failure, Some(error.reason(&env)));
+        assert!(simulation.token_ids.is_empty());
+    }
+
+    // Registry attesting three credits, two of them already minted
+    let registry = env.register(ThreeCreditRegistry, ());
+    client.set_verification_registry(&admin, &registry);
+    let simulation = client.simulate_mint(&meta, &2);
+    assert_eq!(
+        simulation.failure,
+        Some(Symbol::new(&env, "ExceedsVerifiedAmount"))
+    );
+    assert_eq!(
+        client.try_mint_batch(&admin, &owner, &meta, &2, &102, &2),
+        Err(Ok(ContractError::ExceedsVerifiedAmount))
+    );
+
+    let simulation = client.simulate_mint(&meta, &1);
+    assert_eq!(simulation.failure, None);
+    let token_id = client.mint(&admin, &owner, &meta);
+    assert_eq!(simulation.token_ids, vec![&env, token_id]);
+}
+
+#[test]
+fn test_simulate_mint_reports_mint_fee() {
+    let (env, admin, retirement_tracker, owner) = setup_env();
+    let contract_id = env.register(CarbonAsset, ());
+    let client = CarbonAssetClient::new(&env, &contract_id);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &retirement_tracker,
+        &String::from_str(&env, "US"),
+    );
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let fee_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let fee_token = TokenClient::new(&env, &fee_id);
+    StellarAssetClient::new(&env, &fee_id).mint(&admin, &1_000);
+    let treasury = Address::generate(&env);
+
+    for (fee, amount) in [(3, 1), (7, 2), (11, 5)] {
+        client.set_mint_fee(&admin, &fee_id, &fee, &treasury);
+        let simulation = client.simulate_mint(&meta, &amount);
+        assert_eq!(simulation.failure, None);
+        assert_eq!(simulation.fee, fee * amount as i128);
+        assert_eq!(simulation.to_buffer, 0);
+
+        let paid_before = fee_token.balance(&admin);
+        let treasury_before = fee_token.balance(&treasury);
+        let balance_before = client.balance(&owner);
+        let serial_start = 10 * fee as u64;
+        let minted = client.mint_batch(
+            &admin,
+            &owner,
+            &meta,
+            &amount,
+            &serial_start,
+            &(amount as u64),
+        );
+        assert_eq!(minted, simulation.token_ids);
+        assert_eq!(paid_before - fee_token.balance(&admin), simulation.fee);
+        assert_eq!(
+            fee_token.balance(&treasury) - treasury_before,
+            simulation.fee
+        );
+        assert_eq!(
+            client.balance(&owner) - balance_before,
+            simulation.to_recipient as i128
+        );
+    }
+}
+
+// Names entry points, failing to compile if one is not exported.
+macro_rules! entry_points {
+    ($($name:ident),* $(,)?) => {
+        &[$({
+            let _ = CarbonAssetClient::$name;
+            stringify!($name)
+        }),*]
+    };
+}
+
+#[test]
+fn test_every_entry_point_is_categorized() {
+    // Read-only: no authorization, no writes
+    let views: &[&str] = entry_points![
+        spec_version,
+        get_admin,
+        get_pending_admin,
+        get_name,
+        get_symbol,
+        decimals,
+        format_amount,
+        name,
+        symbol,
+        is_initialized,
+        get_retirement_tracker,
+        get_retire_sinks,
+        is_retire_sink,
+        get_regulatory_check,
+        get_minted_supply,
+        get_verification_registry,
+        get_marketplaces,
+        is_marketplace,
+        get_methodology_library,
+        get_host_jurisdiction,
+        get_oracle,
+        get_circuit_breaker,
+        get_mint_fee,
+        get_config,
+        get_event_sequence,
+        owner_of,
+        get_metadata,
+        get_metadata_hash,
+        get_status,
+        get_quality_score,
+        is_methodology_deprecated,
+        balance_of,
+        tokens_of_owner,
+        listed_amount,
+        listed_on,
+        free_balance,
+        get_origin_info,
+        get_tokens_by_registry,
+        get_tokens_by_methodology,
+        get_tokens_by_vintage,
+        get_serial_range,
+        get_last_status_reason,
+        get_minted_at,
+        is_token_frozen,
+        get_burn_receipt,
+        is_burned,
+        retired_supply,
+        escrowed_count,
+        allowance,
+        balance,
+        can_transfer,
+        simulate_transfer,
+        simulate_sale,
+        can_transfer_token,
+        simulate_mint,
+        get_list_price,
+        get_royalty_info,
+        portfolio_value,
+        status_breakdown,
+        has_role,
+        get_methodology_issuer,
+        get_mint_signer,
+        get_mint_nonce,
+        get_issuance_key,
+        is_voucher_used,
+        get_mint_lock_seconds,
+        is_summary_mode,
+        get_transfer_summary,
+        get_summary_stats,
+        get_balance_observer,
+        get_region,
+        get_tokens_by_region,
+        verify_geolocation,
+        is_paused,
+        get_attribute,
+        get_all_attributes,
+        get_migration,
+    ];
+    // Admin-only configuration
+    let admin: &[&str] = entry_points![
+        initialize,
+        propose_admin,
+        accept_admin,
+        cancel_admin_transfer,
+        set_retirement_tracker,
+        add_retire_sink,
+        remove_retire_sink,
+        set_regulatory_check,
+        set_verification_registry,
+        add_marketplace,
+        remove_marketplace,
+        set_methodology_library,
+        set_circuit_breaker,
+        set_oracle,
+        set_mint_fee,
+        set_host_jurisdiction,
+        set_name,
+        set_symbol,
+        grant_role,
+        revoke_role,
+        set_methodology_issuer,
+        set_mint_signer,
+        set_issuance_key,
+        set_mint_lock_seconds,
+        set_summary_mode,
+        set_balance_observer,
+    ];
+    // Holder, role and hook calls that change state
+    let mutators: &[&str] = entry_points![
+        mint,
+        mint_bridged,
+        mint_batch,
+        mint_distributed,
+        mint_with_sig,
+        mint_with_voucher,
+        mint_extended,
+        approve,
+        transfer,
+        transfer_from,
+        transfer_multi,
+        burn,
+        burn_from,
+        burn_token,
+        retire_direct,
+        escrow_for_retirement,
+        release_from_escrow,
+        before_transfer,
+        set_status,
+        invalidate,
+        unlock_verified,
+        list,
+        delist,
+        list_tokens,
+        delist_tokens,
+        list_with_price,
+        unlist,
+        transfer_with_sale_price,
+        set_royalty,
+        freeze_token,
+        unfreeze_token,
+        update_quality_score,
+        pause,
+        unpause,
+        set_attribute,
+        migrate_token,
+        claim_migration,
+        flush_summary,
+    ];
+
+    let sources = [
+        include_str!("lib.rs"),
+        include_str!("admin.rs"),
+        include_str!("views.rs"),
+        include_str!("roles.rs"),
+        include_str!("attributes.rs"),
+        include_str!("migration.rs"),
+        include_str!("summary.rs"),
+        include_str!("observer.rs"),
+        include_str!("regions.rs"),
+    ];
+    let mut exported = 0;
+    for source in sources {
+        for line in source.lines() {
+            let Some(signature) = line.strip_prefix("    pub fn ") else {
+                continue;
+            };
+            let name = signature.split('(').next().unwrap();
+            let categories = [views, admin, mutators]
+                .iter()
+                .filter(|category| category.contains(&name))
+                .count();
+            assert_eq!(categories, 1, "{} must be in exactly one category", name);
+            exported += 1;
+        }
+    }
+    assert_eq!(exported, views.len() + admin.len() + mutators.len());
+}
+
+#[test]
+fn test_spec_version_needs_no_initialization() {
+    let env = Env::default();
+    let client = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+
+    assert_eq!(client.spec_version(), crate::views::SPEC_VERSION);
+    assert!(!client.is_initialized());
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, Env, String};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map, String, Symbol, Vec};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -10,6 +10,17 @@ pub enum AssetStatus {
     Invalidated,
 }
 
+// Why an admin changed a token's status; aggregated by analytics.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum StatusReason {
+    PendingVerification,
+    Reversal,
+    Fraud,
+    Expired,
+    Manual,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct CarbonAssetMetadata {
@@ -19,6 +30,112 @@ pub struct CarbonAssetMetadata {
     pub geo_hash: BytesN<32>,
 }
 
+// What `mint_extended` takes: the fixed metadata plus an optional ISO 3166
+// alpha-2 region tag, which is stored next to it rather than inside it.
+#[derive(Clone)]
+#[contracttype]
+pub struct ExtendedMetadata {
+    pub metadata: CarbonAssetMetadata,
+    pub region: Option<Symbol>,
+}
+
+// Set up by `migrate_token`: the holder of the invalidated `old_token_id`
+// may claim `new_token_id` in its place. `claimed_by` is set by the claim.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenMigration {
+    pub old_token_id: u32,
+    pub new_token_id: u32,
+    pub migrated_by: Address,
+    pub migrated_at: u64,
+    pub claimed_by: Option<Address>,
+}
+
+// Transfers made in one ledger while summary mode is on, published as a
+// single `TransferSummaryEvent`. `amounts` holds the credits moved per
+// token id.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferSummary {
+    pub ledger: u32,
+    pub count: u32,
+    pub amounts: Map<u32, i128>,
+}
+
+// Credits an address sent and received while summary mode was on, so the
+// transfers behind the summaries can be accounted for per address.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct SummaryStats {
+    pub sent: i128,
+    pub received: i128,
+}
+
+// What an issuer's key signs to authorize `mint_with_sig`. The contract
+// address keeps a signature from being replayed on another deployment.
+#[derive(Clone)]
+#[contracttype]
+pub struct MintAuthorization {
+    pub contract: Address,
+    pub owner: Address,
+    pub metadata: CarbonAssetMetadata,
+    pub amount: u32,
+    pub nonce: u64,
+}
+
+// A mint pre-authorized offline by the treasury's issuance key, redeemed
+// with `mint_with_voucher`. `metadata_hash` is the SHA-256 of the XDR of
+// the metadata to mint under. Each `nonce` can be redeemed once.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MintVoucher {
+    pub contract: Address,
+    pub to: Address,
+    pub amount: u32,
+    pub metadata_hash: BytesN<32>,
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
+// Registry serial numbers carried by a token; one serial per whole-unit credit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SerialRange {
+    pub serial_start: u64,
+    pub serial_count: u64,
+}
+
+// Provenance of a credit bridged from an external registry such as Verra or
+// Gold Standard. Recorded once by `mint_bridged` and never changed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OriginInfo {
+    pub registry: Symbol,
+    pub serial_hash: BytesN<32>,
+    pub bridged_at: u64,
+    pub bridge_tx: BytesN<32>,
+}
+
+// One credit burned by `burn_token`, stored under the receipt hash it
+// returns.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BurnReceipt {
+    pub from: Address,
+    pub token_id: u32,
+    pub amount: i128,
+    pub burned_at: u64,
+}
+
+// Share of each marketplace sale of a token paid to its project developer,
+// in basis points of the sale price.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RoyaltyInfo {
+    pub developer: Address,
+    pub bps: u32,
+}
+
 // Shared with RegulatoryCheck contract for validation.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -37,6 +154,55 @@ pub struct ValidationResult {
     pub error_message: Option<String>,
 }
 
+// Returned by the RegulatoryCheck contract's `check_transfer`: the verdict,
+// the rule that decided it and the version of the rule set in force.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferDecision {
+    pub allowed: bool,
+    pub rule: Symbol,
+    pub corridor_from: Option<String>,
+    pub corridor_to: Option<String>,
+    pub rule_version: u64,
+}
+
+// Outcome of `can_transfer`; `reason` is `Allowed` or the name of the
+// `ContractError` the transfer would fail with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferCheck {
+    pub allowed: bool,
+    pub reason: Symbol,
+}
+
+// Outcome of `simulate_transfer` and `simulate_sale`. `fee` is always zero,
+// as transfers carry no fee; `royalty` is what the buyer pays the developer,
+// in the sale's payment token. On failure `failure` names the
+// `ContractError` the transfer would return and the other fields are empty.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferSimulation {
+    pub net_to_recipient: i128,
+    pub fee: i128,
+    pub royalty: i128,
+    pub token_ids: Vec<u32>,
+    pub would_auto_retire: bool,
+    pub failure: Option<Symbol>,
+}
+
+// Outcome of `simulate_mint`, in the same shape as `TransferSimulation`.
+// `fee` is the mint fee in the fee token. `to_buffer` is always zero, as
+// minting withholds no credits for a buffer pool.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MintSimulation {
+    pub to_recipient: u32,
+    pub to_buffer: u32,
+    pub fee: i128,
+    pub token_ids: Vec<u32>,
+    pub failure: Option<Symbol>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct AllowanceData {
@@ -44,6 +210,44 @@ pub struct AllowanceData {
     pub live_until_ledger: u32,
 }
 
+// Instance-level configuration in one read, for deployment checks. Optional
+// integrations are None until their setter has been called.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ContractConfig {
+    pub admin: Address,
+    pub pending_admin: Option<Address>,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    pub retirement_tracker: Address,
+    pub host_jurisdiction: Option<String>,
+    pub regulatory_check: Option<Address>,
+    pub verification_registry: Option<Address>,
+    pub marketplaces: Vec<Address>,
+    pub methodology_library: Option<Address>,
+    pub oracle: Option<Address>,
+    pub circuit_breaker: Option<Address>,
+    pub fee_token: Option<Address>,
+    pub mint_fee: i128,
+    pub fee_treasury: Option<Address>,
+    pub issuance_key: Option<BytesN<32>>,
+    pub mint_lock_seconds: u64,
+    pub summary_mode: bool,
+    pub balance_observer: Option<Address>,
+    pub paused: bool,
+}
+
+// Charged to the caller of every mint path, `amount` per credit minted, and
+// paid to `treasury` in `fee_token`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MintFee {
+    pub fee_token: Address,
+    pub amount: i128,
+    pub treasury: Address,
+}
+
 #[allow(dead_code)]
 pub trait CarbonAssetValueOracle {
     fn update_quality_score(
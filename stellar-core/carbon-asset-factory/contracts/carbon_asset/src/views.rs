@@ -0,0 +1,421 @@
+use soroban_sdk::{contractimpl, xdr::ToXdr, Address, BytesN, Env, IntoVal, String, Symbol, Vec};
+
+use crate::errors::ContractError;
+use crate::storage::{DataKey, ExtendedKey};
+use crate::types::{
+    AssetStatus, BurnReceipt, CarbonAssetMetadata, ContractConfig, MintFee, OriginInfo,
+    SerialRange, StatusReason,
+};
+use crate::{CarbonAsset, CarbonAssetArgs, CarbonAssetClient, SERIAL_BUCKET_SPAN};
+
+// Version of the exported interface. Bump it whenever an entry point is
+// added, removed or changes signature.
+pub const SPEC_VERSION: u32 = 16;
+
+// ========================================================================
+// Getters
+// ========================================================================
+
+// Read-only views. None of them require authorization or write state, and
+// none trap on a missing entry: absent settings come back as `None` or
+// `NotInitialized`, unknown tokens as `TokenNotFound`.
+#[contractimpl]
+impl CarbonAsset {
+    pub fn spec_version(_env: Env) -> u32 {
+        SPEC_VERSION
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        Ok(access_control::get_admin(&env)?)
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        access_control::get_pending_admin(&env)
+    }
+
+    pub fn get_name(env: Env) -> Result<String, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Name)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_symbol(env: Env) -> Result<String, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Symbol)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(0u32)
+    }
+
+    // Split `base_units` into whole and fractional parts at the stored
+    // `decimals`, so clients need not do the division themselves. The
+    // fractional part is in base units, e.g. 15000000 at 7 decimals is
+    // (1, 5000000). Negative amounts fail with `InvalidAmount`.
+    pub fn format_amount(env: Env, base_units: i128) -> Result<(i128, u32), ContractError> {
+        if base_units < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let scale = 10i128
+            .checked_pow(Self::decimals(env))
+            .ok_or(ContractError::InvalidAmount)?;
+        let fractional =
+            u32::try_from(base_units % scale).map_err(|_| ContractError::InvalidAmount)?;
+        Ok((base_units / scale, fractional))
+    }
+
+    pub fn name(env: Env) -> Result<String, ContractError> {
+        Self::get_name(env)
+    }
+
+    pub fn symbol(env: Env) -> Result<String, ContractError> {
+        Self::get_symbol(env)
+    }
+
+    pub fn is_initialized(env: Env) -> bool {
+        access_control::has_admin(&env)
+    }
+
+    pub fn get_retirement_tracker(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RetirementTracker)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    // The retirement tracker followed by the sinks added with
+    // `add_retire_sink`.
+    pub fn get_retire_sinks(env: Env) -> Result<Vec<Address>, ContractError> {
+        let mut sinks = Vec::new(&env);
+        sinks.push_back(Self::get_retirement_tracker(env.clone())?);
+        let extra: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RetireSinks)
+            .unwrap_or(Vec::new(&env));
+        sinks.append(&extra);
+        Ok(sinks)
+    }
+
+    pub fn is_retire_sink(env: Env, address: Address) -> Result<bool, ContractError> {
+        if address == Self::get_retirement_tracker(env.clone())? {
+            return Ok(true);
+        }
+        let sinks: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RetireSinks)
+            .unwrap_or(Vec::new(&env));
+        Ok(sinks.contains(&address))
+    }
+
+    pub fn get_regulatory_check(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RegulatoryCheck)
+    }
+
+    pub fn get_minted_supply(env: Env, project_id: String, vintage_year: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MintedSupply(project_id, vintage_year))
+            .unwrap_or(0)
+    }
+
+    pub fn get_verification_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::VerificationRegistry)
+    }
+
+    pub fn get_marketplaces(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Marketplaces)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn is_marketplace(env: Env, address: Address) -> bool {
+        Self::get_marketplaces(env).contains(&address)
+    }
+
+    pub fn get_methodology_library(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::MethodologyLibrary)
+    }
+
+    pub fn get_host_jurisdiction(env: Env) -> Option<String> {
+        env.storage().instance().get(&DataKey::HostJurisdiction)
+    }
+
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Oracle)
+    }
+
+    pub fn get_circuit_breaker(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::CircuitBreaker)
+    }
+
+    pub fn get_mint_fee(env: Env) -> Option<MintFee> {
+        env.storage().instance().get(&DataKey::MintFee)
+    }
+
+    pub fn get_issuance_key(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::IssuanceKey)
+    }
+
+    pub fn is_voucher_used(env: Env, nonce: u64) -> bool {
+        env.storage().persistent().has(&DataKey::VoucherUsed(nonce))
+    }
+
+    pub fn get_mint_lock_seconds(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MintLockSeconds)
+            .unwrap_or(0)
+    }
+
+    // Every instance-level setting in one call. Add new configuration here
+    // when it lands.
+    pub fn get_config(env: Env) -> Result<ContractConfig, ContractError> {
+        let mint_fee = Self::get_mint_fee(env.clone());
+        Ok(ContractConfig {
+            admin: Self::get_admin(env.clone())?,
+            pending_admin: Self::get_pending_admin(env.clone()),
+            name: Self::get_name(env.clone())?,
+            symbol: Self::get_symbol(env.clone())?,
+            decimals: Self::decimals(env.clone()),
+            retirement_tracker: Self::get_retirement_tracker(env.clone())?,
+            host_jurisdiction: Self::get_host_jurisdiction(env.clone()),
+            regulatory_check: Self::get_regulatory_check(env.clone()),
+            verification_registry: Self::get_verification_registry(env.clone()),
+            marketplaces: Self::get_marketplaces(env.clone()),
+            methodology_library: Self::get_methodology_library(env.clone()),
+            oracle: Self::get_oracle(env.clone()),
+            circuit_breaker: Self::get_circuit_breaker(env.clone()),
+            fee_token: mint_fee.as_ref().map(|fee| fee.fee_token.clone()),
+            mint_fee: mint_fee.as_ref().map_or(0, |fee| fee.amount),
+            fee_treasury: mint_fee.map(|fee| fee.treasury),
+            issuance_key: Self::get_issuance_key(env.clone()),
+            mint_lock_seconds: Self::get_mint_lock_seconds(env.clone()),
+            summary_mode: Self::is_summary_mode(env.clone()),
+            balance_observer: Self::get_balance_observer(env.clone()),
+            paused: Self::is_paused(env),
+        })
+    }
+
+    pub fn get_event_sequence(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventSequence)
+            .unwrap_or(0u64)
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, ContractError> {
+        let burned: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Burned(token_id))
+            .unwrap_or(false);
+        if burned {
+            return Err(ContractError::TokenNotFound);
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owner(token_id))
+            .ok_or(ContractError::TokenNotFound)
+    }
+
+    pub fn get_metadata(env: Env, token_id: u32) -> Result<CarbonAssetMetadata, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Metadata(token_id))
+            .ok_or(ContractError::TokenNotFound)
+    }
+
+    // SHA-256 of the XDR of the token's metadata, the same hash a mint
+    // voucher commits to, so other systems can detect drift by comparing 32
+    // bytes. None for unknown tokens.
+    pub fn get_metadata_hash(env: Env, token_id: u32) -> Option<BytesN<32>> {
+        let metadata = Self::get_metadata(env.clone(), token_id).ok()?;
+        Some(env.crypto().sha256(&metadata.to_xdr(&env)).into())
+    }
+
+    pub fn get_status(env: Env, token_id: u32) -> Result<AssetStatus, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Status(token_id))
+            .ok_or(ContractError::TokenNotFound)
+    }
+
+    pub fn get_quality_score(env: Env, token_id: u32) -> Result<i128, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::QualityScore(token_id))
+            .ok_or(ContractError::TokenNotFound)
+    }
+
+    // Advisory only: true when the configured methodology library reports the
+    // token's methodology as deprecated. The token's status is unaffected.
+    // False for unknown tokens or when no library is configured.
+    pub fn is_methodology_deprecated(env: Env, token_id: u32) -> bool {
+        let library: Option<Address> = env.storage().instance().get(&DataKey::MethodologyLibrary);
+        let metadata: Option<CarbonAssetMetadata> =
+            env.storage().persistent().get(&DataKey::Metadata(token_id));
+        match (library, metadata) {
+            (Some(library), Some(metadata)) => {
+                let symbol = Symbol::new(&env, "is_deprecated");
+                let mut args = Vec::new(&env);
+                args.push_back(metadata.methodology_id.into_val(&env));
+                env.invoke_contract(&library, &symbol, args)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> i128 {
+        let tokens: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerTokens(owner))
+            .unwrap_or(Vec::new(&env));
+        tokens.len() as i128
+    }
+
+    pub fn tokens_of_owner(env: Env, owner: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OwnerTokens(owner))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // 1 while `owner` has `token_id` listed on any marketplace, else 0.
+    pub fn listed_amount(env: Env, owner: Address, token_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ListedByToken(owner, token_id))
+            .unwrap_or(0)
+    }
+
+    pub fn listed_on(env: Env, owner: Address, token_id: u32, marketplace: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ListedAmount(owner, token_id, marketplace))
+            .unwrap_or(0)
+    }
+
+    // `owner`'s balance less everything they have listed. Transfers may not
+    // take it below zero.
+    pub fn free_balance(env: Env, owner: Address) -> i128 {
+        let listed: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ListedTotal(owner.clone()))
+            .unwrap_or(0);
+        Self::balance_of(env, owner) - listed
+    }
+
+    pub fn get_origin_info(env: Env, token_id: u32) -> Option<OriginInfo> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OriginInfo(token_id))
+    }
+
+    // Tokens bridged from `registry`, in mint order.
+    pub fn get_tokens_by_registry(env: Env, registry: Symbol, start: u32, limit: u32) -> Vec<u32> {
+        Self::segmented_index_page(&env, &DataKey::RegistryTokens(registry), start, limit)
+    }
+
+    // Tokens minted under `methodology_id`, in mint order.
+    pub fn get_tokens_by_methodology(
+        env: Env,
+        methodology_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Vec<u32> {
+        let index_key = DataKey::MethodologyTokens(methodology_id);
+        Self::segmented_index_page(&env, &index_key, start, limit)
+    }
+
+    // Tokens whose metadata carries `vintage_year`, in mint order. The value
+    // is matched exactly as it was minted.
+    pub fn get_tokens_by_vintage(env: Env, vintage_year: u64, start: u32, limit: u32) -> Vec<u32> {
+        Self::segmented_index_page(&env, &DataKey::VintageTokens(vintage_year), start, limit)
+    }
+
+    // The serial `token_id` carries, found from the range of the
+    // `mint_batch` call that minted it. A batch spans at most
+    // `MAX_SERIAL_BATCH` ids, so it starts in the token's own bucket or at
+    // the end of the one before.
+    pub fn get_serial_range(env: Env, token_id: u32) -> Option<SerialRange> {
+        let bucket = token_id / SERIAL_BUCKET_SPAN;
+        let first_token_id = match Self::serial_batch_at_or_before(&env, bucket, token_id) {
+            Some(first_token_id) => first_token_id,
+            None if bucket > 0 => Self::serial_batch_at_or_before(&env, bucket - 1, token_id)?,
+            None => return None,
+        };
+        let batch: SerialRange = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SerialRange(first_token_id))?;
+        let offset = (token_id - first_token_id) as u64;
+        if offset >= batch.serial_count {
+            return None;
+        }
+        Some(SerialRange {
+            serial_start: batch.serial_start + offset,
+            serial_count: 1,
+        })
+    }
+
+    pub fn get_last_status_reason(env: Env, token_id: u32) -> Option<StatusReason> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LastStatusReason(token_id))
+    }
+
+    // Ledger timestamp of the token's mint. `None` for unknown tokens and
+    // for tokens minted before mint times were recorded.
+    pub fn get_minted_at(env: Env, token_id: u32) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::MintedAt(token_id))
+    }
+
+    pub fn is_token_frozen(env: Env, token_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::FrozenToken(token_id))
+    }
+
+    pub fn get_burn_receipt(env: Env, receipt: BytesN<32>) -> Option<BurnReceipt> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BurnReceipt(receipt))
+    }
+
+    pub fn is_burned(env: Env, token_id: u32) -> Result<bool, ContractError> {
+        Self::get_status(env.clone(), token_id)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Burned(token_id))
+            .unwrap_or(false))
+    }
+
+    // Credits burned on retirement, by the tracker or `retire_direct`.
+    pub fn retired_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&ExtendedKey::RetiredSupply)
+            .unwrap_or(0)
+    }
+
+    // Credits the retirement tracker holds in escrow for pending
+    // retirements. The tracker cannot be replaced while any remain.
+    pub fn escrowed_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ExtendedKey::EscrowedCount)
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,579 @@
+#![cfg(test)]
+
+use carbon_asset::errors::ContractError;
+use carbon_asset::types::{
+    AssetStatus, CarbonAssetMetadata, OperationType, TransferDecision, ValidationResult,
+};
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, Address, BytesN, Env, IntoVal,
+    InvokeError, String, Symbol, Val, Vec,
+};
+
+#[contracttype]
+enum MockKey {
+    Verified(String, u64),
+    Deprecated(u32),
+    Compliant,
+    TransferAllowed,
+    Target,
+    ReentryBlocked,
+    ObserverTarget,
+    ObserverReentryBlocked,
+}
+
+/// Stand-in for the verification registry: the test sets the attested
+/// amount per (project, vintage) directly.
+#[contract]
+pub struct MockVerificationRegistry;
+
+#[contractimpl]
+impl MockVerificationRegistry {
+    pub fn set_verified(env: Env, project_id: String, vintage_year: u64, amount: i128) {
+        let key = MockKey::Verified(project_id, vintage_year);
+        env.storage().persistent().set(&key, &amount);
+    }
+
+    pub fn is_verified(env: Env, project_id: String, vintage_year: u64) -> bool {
+        let key = MockKey::Verified(project_id, vintage_year);
+        env.storage().persistent().has(&key)
+    }
+
+    pub fn get_verified_amount(env: Env, project_id: String, vintage_year: u64) -> i128 {
+        let key = MockKey::Verified(project_id, vintage_year);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+}
+
+/// Fixed prices: 1_000 for PROJ-A credits, 400 for everything else.
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    pub fn get_price(env: Env, project_id: String, _vintage_year: u64) -> i128 {
+        if project_id == String::from_str(&env, "PROJ-A") {
+            1_000
+        } else if project_id == String::from_str(&env, "PROJ-MAX") {
+            i128::MAX
+        } else {
+            400
+        }
+    }
+}
+
+/// Stand-in for the methodology library's deprecation flag.
+#[contract]
+pub struct MockMethodologyLibrary;
+
+#[contractimpl]
+impl MockMethodologyLibrary {
+    pub fn deprecate(env: Env, methodology_id: u32) {
+        let key = MockKey::Deprecated(methodology_id);
+        env.storage().persistent().set(&key, &true);
+    }
+
+    pub fn is_deprecated(env: Env, methodology_id: u32) -> bool {
+        let key = MockKey::Deprecated(methodology_id);
+        env.storage().persistent().has(&key)
+    }
+}
+
+/// Stand-in for the regulatory check contract: the test sets whether
+/// `validate_transaction` and `check_transfer` pass. Both pass by default,
+/// and recorded transfer volume is ignored.
+#[contract]
+pub struct MockRegulatoryCheck;
+
+#[contractimpl]
+impl MockRegulatoryCheck {
+    pub fn set_result(env: Env, compliant: bool, transfer_allowed: bool) {
+        env.storage()
+            .instance()
+            .set(&MockKey::Compliant, &compliant);
+        env.storage()
+            .instance()
+            .set(&MockKey::TransferAllowed, &transfer_allowed);
+    }
+
+    pub fn validate_transaction(
+        env: Env,
+        _from: Address,
+        _to: Address,
+        _operation: OperationType,
+        _host_jurisdiction: String,
+    ) -> ValidationResult {
+        let is_compliant = env
+            .storage()
+            .instance()
+            .get(&MockKey::Compliant)
+            .unwrap_or(true);
+        ValidationResult {
+            is_compliant,
+            rule_id: None,
+            requires_authorization: false,
+            authority_address: None,
+            error_message: None,
+        }
+    }
+
+    pub fn check_transfer(
+        env: Env,
+        _from: Address,
+        _to: Address,
+        _amount: i128,
+        _token_id: u32,
+        _project_id: String,
+    ) -> TransferDecision {
+        let allowed = env
+            .storage()
+            .instance()
+            .get(&MockKey::TransferAllowed)
+            .unwrap_or(true);
+        decision(&env, allowed)
+    }
+
+    pub fn record_transfer(_env: Env, _from: Address, _amount: i128) {}
+}
+
+/// Regulatory check that tries to re-enter `transfer` while approving one,
+/// moving the same credits to `attacker`, and records whether it was stopped.
+#[contract]
+pub struct ReentrantRegulatoryCheck;
+
+#[contractimpl]
+impl ReentrantRegulatoryCheck {
+    pub fn set_target(env: Env, asset: Address, attacker: Address) {
+        env.storage()
+            .instance()
+            .set(&MockKey::Target, &(asset, attacker));
+    }
+
+    pub fn reentry_blocked(env: Env) -> Option<bool> {
+        env.storage().instance().get(&MockKey::ReentryBlocked)
+    }
+
+    pub fn validate_transaction(
+        env: Env,
+        from: Address,
+        _to: Address,
+        _operation: OperationType,
+        _host_jurisdiction: String,
+    ) -> ValidationResult {
+        let (asset, attacker): (Address, Address) =
+            env.storage().instance().get(&MockKey::Target).unwrap();
+        let args: Vec<Val> = (from, attacker, 1i128).into_val(&env);
+        let result = env.try_invoke_contract::<(), InvokeError>(
+            &asset,
+            &Symbol::new(&env, "transfer"),
+            args,
+        );
+        env.storage()
+            .instance()
+            .set(&MockKey::ReentryBlocked, &result.is_err());
+
+        ValidationResult {
+            is_compliant: true,
+            rule_id: None,
+            requires_authorization: false,
+            authority_address: None,
+            error_message: None,
+        }
+    }
+
+    pub fn check_transfer(
+        env: Env,
+        _from: Address,
+        _to: Address,
+        _amount: i128,
+        _token_id: u32,
+        _project_id: String,
+    ) -> TransferDecision {
+        decision(&env, true)
+    }
+
+    pub fn record_transfer(_env: Env, _from: Address, _amount: i128) {}
+}
+
+/// The decision both regulatory mocks return from `check_transfer`.
+fn decision(env: &Env, allowed: bool) -> TransferDecision {
+    TransferDecision {
+        allowed,
+        rule: Symbol::new(env, "default"),
+        corridor_from: None,
+        corridor_to: None,
+        rule_version: 0,
+    }
+}
+
+/// Balance observer that tries to move a credit of the address it is told
+/// about to `attacker`, and records whether it was stopped.
+#[contract]
+pub struct ReentrantObserver;
+
+#[contractimpl]
+impl ReentrantObserver {
+    pub fn set_target(env: Env, asset: Address, attacker: Address) {
+        env.storage()
+            .instance()
+            .set(&MockKey::ObserverTarget, &(asset, attacker));
+    }
+
+    pub fn reentry_blocked(env: Env) -> Option<bool> {
+        env.storage()
+            .instance()
+            .get(&MockKey::ObserverReentryBlocked)
+    }
+
+    pub fn on_balance_change(env: Env, address: Address, _token_id: u32, new_balance: i128) {
+        if new_balance == 0 {
+            return;
+        }
+        let (asset, attacker): (Address, Address) = env
+            .storage()
+            .instance()
+            .get(&MockKey::ObserverTarget)
+            .unwrap();
+        let args: Vec<Val> = (address, attacker, 1i128).into_val(&env);
+        let result = env.try_invoke_contract::<(), InvokeError>(
+            &asset,
+            &Symbol::new(&env, "transfer"),
+            args,
+        );
+        env.storage()
+            .instance()
+            .set(&MockKey::ObserverReentryBlocked, &result.is_err());
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    owner: Address,
+    asset: CarbonAssetClient<'a>,
+    registry: MockVerificationRegistryClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let asset = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let registry_id = env.register(MockVerificationRegistry, ());
+    let registry = MockVerificationRegistryClient::new(&env, &registry_id);
+
+    asset.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    asset.set_verification_registry(&admin, &registry_id);
+
+    Setup {
+        env,
+        admin,
+        owner,
+        asset,
+        registry,
+    }
+}
+
+fn metadata(env: &Env, project_id: &str, vintage_year: u64) -> CarbonAssetMetadata {
+    CarbonAssetMetadata {
+        project_id: String::from_str(env, project_id),
+        vintage_year,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(env, &[7u8; 32]),
+    }
+}
+
+#[test]
+fn test_mint_rejected_without_attestation() {
+    let s = setup();
+    let meta = metadata(&s.env, "PROJ-1", 2024);
+
+    assert_eq!(
+        s.asset.try_mint(&s.admin, &s.owner, &meta),
+        Err(Ok(ContractError::NotVerified))
+    );
+    assert_eq!(
+        s.asset
+            .try_mint_batch(&s.admin, &s.owner, &meta, &2, &100, &2),
+        Err(Ok(ContractError::NotVerified))
+    );
+
+    // An attestation for another vintage does not count
+    s.registry.set_verified(&meta.project_id, &2023, &10);
+    assert_eq!(
+        s.asset.try_mint(&s.admin, &s.owner, &meta),
+        Err(Ok(ContractError::NotVerified))
+    );
+    assert_eq!(s.asset.get_minted_supply(&meta.project_id, &2024), 0);
+    assert_eq!(s.asset.balance(&s.owner), 0);
+}
+
+#[test]
+fn test_mint_capped_at_verified_amount() {
+    let s = setup();
+    let meta = metadata(&s.env, "PROJ-1", 2024);
+    s.registry.set_verified(&meta.project_id, &2024, &4);
+
+    s.asset.mint(&s.admin, &s.owner, &meta);
+    s.asset.mint_batch(&s.admin, &s.owner, &meta, &2, &100, &2);
+    assert_eq!(s.asset.get_minted_supply(&meta.project_id, &2024), 3);
+
+    // A batch that would overshoot is rejected as a whole
+    assert_eq!(
+        s.asset
+            .try_mint_batch(&s.admin, &s.owner, &meta, &2, &102, &2),
+        Err(Ok(ContractError::ExceedsVerifiedAmount))
+    );
+    assert_eq!(s.asset.get_minted_supply(&meta.project_id, &2024), 3);
+
+    s.asset.mint(&s.admin, &s.owner, &meta);
+    assert_eq!(
+        s.asset.try_mint(&s.admin, &s.owner, &meta),
+        Err(Ok(ContractError::ExceedsVerifiedAmount))
+    );
+    assert_eq!(s.asset.get_minted_supply(&meta.project_id, &2024), 4);
+    assert_eq!(s.asset.balance(&s.owner), 4);
+
+    // A further attestation raises the cap
+    s.registry.set_verified(&meta.project_id, &2024, &5);
+    s.asset.mint(&s.admin, &s.owner, &meta);
+    assert_eq!(s.asset.get_minted_supply(&meta.project_id, &2024), 5);
+}
+
+#[test]
+fn test_minted_supply_tracked_per_project_and_vintage() {
+    let s = setup();
+    let proj_a_2024 = metadata(&s.env, "PROJ-A", 2024);
+    let proj_a_2025 = metadata(&s.env, "PROJ-A", 2025);
+    let proj_b_2024 = metadata(&s.env, "PROJ-B", 2024);
+    s.registry.set_verified(&proj_a_2024.project_id, &2024, &1);
+    s.registry.set_verified(&proj_a_2025.project_id, &2025, &1);
+    s.registry.set_verified(&proj_b_2024.project_id, &2024, &1);
+
+    s.asset.mint(&s.admin, &s.owner, &proj_a_2024);
+    s.asset.mint(&s.admin, &s.owner, &proj_a_2025);
+    s.asset.mint(&s.admin, &s.owner, &proj_b_2024);
+
+    assert_eq!(s.asset.get_minted_supply(&proj_a_2024.project_id, &2024), 1);
+    assert_eq!(s.asset.get_minted_supply(&proj_a_2025.project_id, &2025), 1);
+    assert_eq!(s.asset.get_minted_supply(&proj_b_2024.project_id, &2024), 1);
+    assert_eq!(
+        s.asset.try_mint(&s.admin, &s.owner, &proj_a_2024),
+        Err(Ok(ContractError::ExceedsVerifiedAmount))
+    );
+}
+
+#[test]
+fn test_portfolio_value_with_price_oracle() {
+    let s = setup();
+    let oracle = s.env.register(MockPriceOracle, ());
+    let proj_a = metadata(&s.env, "PROJ-A", 2024);
+    let proj_b = metadata(&s.env, "PROJ-B", 2024);
+    s.registry.set_verified(&proj_a.project_id, &2024, &2);
+    s.registry.set_verified(&proj_b.project_id, &2024, &1);
+
+    let a1 = s.asset.mint(&s.admin, &s.owner, &proj_a);
+    let a2 = s.asset.mint(&s.admin, &s.owner, &proj_a);
+    let b1 = s.asset.mint(&s.admin, &s.owner, &proj_b);
+    s.asset.update_quality_score(&s.admin, &a1, &50);
+    s.asset.update_quality_score(&s.admin, &b1, &100);
+
+    let token_ids = Vec::from_array(&s.env, [a1, a2, b1]);
+    assert_eq!(
+        s.asset
+            .portfolio_value(&s.owner, &token_ids, &oracle, &false),
+        2_400
+    );
+    // a1 at half price, a2 unscored, b1 at full price
+    assert_eq!(
+        s.asset
+            .portfolio_value(&s.owner, &token_ids, &oracle, &true),
+        900
+    );
+
+    // Tokens the holder no longer owns are skipped
+    s.asset.transfer(&s.owner, &Address::generate(&s.env), &1);
+    assert_eq!(
+        s.asset
+            .portfolio_value(&s.owner, &token_ids, &oracle, &false),
+        1_400
+    );
+    assert_eq!(
+        s.asset
+            .portfolio_value(&s.owner, &Vec::new(&s.env), &oracle, &false),
+        0
+    );
+}
+
+#[test]
+fn test_portfolio_value_skips_burned_tokens_and_rejects_overflow() {
+    let s = setup();
+    let oracle = s.env.register(MockPriceOracle, ());
+    let proj_a = metadata(&s.env, "PROJ-A", 2024);
+    s.registry.set_verified(&proj_a.project_id, &2024, &2);
+    let kept = s.asset.mint(&s.admin, &s.owner, &proj_a);
+    let retired = s.asset.mint(&s.admin, &s.owner, &proj_a);
+
+    // A burned token is worth nothing rather than failing the whole call
+    s.asset.retire_direct(
+        &s.owner,
+        &1,
+        &retired,
+        &None,
+        &Symbol::new(&s.env, "voluntary"),
+    );
+    assert!(s.asset.is_burned(&retired));
+    let token_ids = Vec::from_array(&s.env, [kept, retired]);
+    assert_eq!(
+        s.asset
+            .portfolio_value(&s.owner, &token_ids, &oracle, &false),
+        1_000
+    );
+    assert_eq!(
+        s.asset
+            .portfolio_value(&s.owner, &token_ids, &oracle, &true),
+        0
+    );
+
+    // Values too large to add up fail instead of wrapping
+    let proj_max = metadata(&s.env, "PROJ-MAX", 2024);
+    s.registry.set_verified(&proj_max.project_id, &2024, &1);
+    let priceless = s.asset.mint(&s.admin, &s.owner, &proj_max);
+    s.asset.update_quality_score(&s.admin, &priceless, &50);
+    let token_ids = Vec::from_array(&s.env, [kept, priceless]);
+    assert_eq!(
+        s.asset
+            .try_portfolio_value(&s.owner, &token_ids, &oracle, &false),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        s.asset
+            .try_portfolio_value(&s.owner, &token_ids, &oracle, &true),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_methodology_deprecation_flags_existing_credits() {
+    let s = setup();
+    let library_id = s.env.register(MockMethodologyLibrary, ());
+    let library = MockMethodologyLibraryClient::new(&s.env, &library_id);
+
+    let meta = metadata(&s.env, "PROJ-A", 2024);
+    let mut other_meta = meta.clone();
+    other_meta.methodology_id = 2;
+    s.registry.set_verified(&meta.project_id, &2024, &2);
+    let token_id = s.asset.mint(&s.admin, &s.owner, &meta);
+    let other_token_id = s.asset.mint(&s.admin, &s.owner, &other_meta);
+
+    // No library configured: nothing is flagged
+    library.deprecate(&1);
+    assert!(!s.asset.is_methodology_deprecated(&token_id));
+
+    s.asset.set_methodology_library(&s.admin, &library_id);
+    assert!(s.asset.is_methodology_deprecated(&token_id));
+    assert!(!s.asset.is_methodology_deprecated(&other_token_id));
+    assert!(!s.asset.is_methodology_deprecated(&99));
+
+    // The flag is advisory; the credit keeps its status and stays transferable
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Issued);
+    s.asset.transfer(&s.owner, &Address::generate(&s.env), &2);
+    assert!(s.asset.is_methodology_deprecated(&token_id));
+}
+
+#[test]
+fn test_regulatory_check_rejects_transfers() {
+    let s = setup();
+    let regulatory_id = s.env.register(MockRegulatoryCheck, ());
+    let regulatory = MockRegulatoryCheckClient::new(&s.env, &regulatory_id);
+    s.asset.set_regulatory_check(&s.admin, &regulatory_id);
+
+    let meta = metadata(&s.env, "PROJ-1", 2024);
+    s.registry.set_verified(&meta.project_id, &2024, &3);
+    let token_id = s.asset.mint(&s.admin, &s.owner, &meta);
+    s.asset.mint(&s.admin, &s.owner, &meta);
+    let buyer = Address::generate(&s.env);
+
+    // Rejected by the jurisdiction rules
+    regulatory.set_result(&false, &true);
+    assert_eq!(
+        s.asset.try_transfer(&s.owner, &buyer, &1),
+        Err(Ok(ContractError::ComplianceFailed))
+    );
+
+    // Rejected by corridor, KYC or volume screening
+    regulatory.set_result(&true, &false);
+    assert_eq!(
+        s.asset.try_transfer(&s.owner, &buyer, &1),
+        Err(Ok(ContractError::ComplianceFailed))
+    );
+
+    // The dry run only consults the regulatory contract when asked to
+    let check = s.asset.can_transfer(&s.owner, &buyer, &1, &true);
+    assert!(!check.allowed);
+    assert_eq!(check.reason, Symbol::new(&s.env, "ComplianceFailed"));
+    let check = s
+        .asset
+        .can_transfer_token(&s.owner, &buyer, &token_id, &true);
+    assert_eq!(check.reason, Symbol::new(&s.env, "ComplianceFailed"));
+    assert!(s.asset.can_transfer(&s.owner, &buyer, &1, &false).allowed);
+    assert_eq!(s.asset.owner_of(&token_id), s.owner);
+    assert_eq!(s.asset.balance(&buyer), 0);
+
+    regulatory.set_result(&true, &true);
+    s.asset.transfer(&s.owner, &buyer, &2);
+    assert_eq!(s.asset.balance(&buyer), 2);
+}
+
+#[test]
+fn test_regulatory_check_cannot_reenter_transfer() {
+    let s = setup();
+    let regulatory_id = s.env.register(ReentrantRegulatoryCheck, ());
+    let regulatory = ReentrantRegulatoryCheckClient::new(&s.env, &regulatory_id);
+    s.asset.set_regulatory_check(&s.admin, &regulatory_id);
+
+    let meta = metadata(&s.env, "PROJ-1", 2024);
+    s.registry.set_verified(&meta.project_id, &2024, &1);
+    let token_id = s.asset.mint(&s.admin, &s.owner, &meta);
+    let buyer = Address::generate(&s.env);
+    let attacker = Address::generate(&s.env);
+    regulatory.set_target(&s.asset.address, &attacker);
+
+    s.asset.transfer(&s.owner, &buyer, &1);
+
+    assert_eq!(regulatory.reentry_blocked(), Some(true));
+    assert_eq!(s.asset.owner_of(&token_id), buyer);
+    assert_eq!(s.asset.balance(&buyer), 1);
+    assert_eq!(s.asset.balance(&s.owner), 0);
+    assert_eq!(s.asset.balance(&attacker), 0);
+}
+
+#[test]
+fn test_balance_observer_cannot_reenter_transfer() {
+    let s = setup();
+    let observer_id = s.env.register(ReentrantObserver, ());
+    let observer = ReentrantObserverClient::new(&s.env, &observer_id);
+    let attacker = Address::generate(&s.env);
+    observer.set_target(&s.asset.address, &attacker);
+    s.asset.set_balance_observer(&s.admin, &Some(observer_id));
+
+    let meta = metadata(&s.env, "PROJ-1", 2024);
+    s.registry.set_verified(&meta.project_id, &2024, &2);
+    let token_id = s.asset.mint(&s.admin, &s.owner, &meta);
+    assert_eq!(observer.reentry_blocked(), Some(true));
+
+    let buyer = Address::generate(&s.env);
+    s.asset.transfer(&s.owner, &buyer, &1);
+    assert_eq!(observer.reentry_blocked(), Some(true));
+    assert_eq!(s.asset.owner_of(&token_id), buyer);
+    assert_eq!(s.asset.balance(&buyer), 1);
+    assert_eq!(s.asset.balance(&attacker), 0);
+}
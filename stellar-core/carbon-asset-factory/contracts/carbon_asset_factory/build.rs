@@ -0,0 +1,39 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+// The deployment tests upload a CarbonAsset Wasm. It is built here from the
+// workspace's `carbon_asset`, so the tests always deploy the current code,
+// and its path is handed to them as `CARBON_ASSET_WASM`.
+fn main() {
+    println!("cargo:rerun-if-changed=../carbon_asset/src");
+    println!("cargo:rerun-if-changed=../carbon_asset/Cargo.toml");
+
+    // The factory's own Wasm build does not need it
+    if env::var("TARGET").unwrap().starts_with("wasm32") {
+        return;
+    }
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let target_dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("wasm");
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    // A separate target dir keeps clear of the outer build's lock, and the
+    // outer build's wrappers and flags are for the host, not Wasm
+    let status = Command::new(cargo)
+        .args(["build", "-p", "carbon_asset", "--target", "wasm32v1-none"])
+        .arg("--release")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("../../Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env_remove("RUSTC_WORKSPACE_WRAPPER")
+        .env_remove("RUSTC_WRAPPER")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("RUSTFLAGS")
+        .status()
+        .expect("failed to run cargo for carbon_asset");
+    assert!(status.success(), "building carbon_asset for Wasm failed");
+
+    let wasm = target_dir.join("wasm32v1-none/release/carbon_asset.wasm");
+    println!("cargo:rustc-env=CARBON_ASSET_WASM={}", wasm.display());
+}
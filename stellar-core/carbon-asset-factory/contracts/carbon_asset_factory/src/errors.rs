@@ -0,0 +1,12 @@
+use soroban_sdk::contracterror;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    NotDeployer = 4,
+    InvalidDecimals = 5,
+    ProjectAlreadyDeployed = 6,
+}
@@ -0,0 +1,16 @@
+use soroban_sdk::{contractevent, Address, String};
+
+#[contractevent]
+pub struct AssetDeployedEvent {
+    pub asset: Address,
+    pub project_id: String,
+    pub deployer: Address,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[contractevent]
+pub struct DeployerUpdatedEvent {
+    pub account: Address,
+    pub enabled: bool,
+}
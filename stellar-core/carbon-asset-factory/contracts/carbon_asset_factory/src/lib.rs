@@ -0,0 +1,254 @@
+#![no_std]
+
+pub mod errors;
+mod events;
+mod storage;
+#[cfg(test)]
+mod test;
+pub mod types;
+
+use soroban_sdk::{
+    contract, contractimpl, vec, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
+
+use crate::errors::ContractError;
+use crate::events::{AssetDeployedEvent, DeployerUpdatedEvent};
+use crate::storage::DataKey;
+use crate::types::DeployedAsset;
+
+// CarbonAsset credits are whole units, so instances always report 0 decimals.
+pub const ASSET_DECIMALS: u32 = 0;
+
+// ========================================================================
+// Contract
+// ========================================================================
+
+#[contract]
+pub struct CarbonAssetFactory;
+
+#[contractimpl]
+impl CarbonAssetFactory {
+    // ====================================================================
+    // Initialization
+    // ====================================================================
+
+    // `asset_wasm_hash` is the uploaded CarbonAsset Wasm. Every deployed
+    // instance is initialized with `admin` and `host_jurisdiction`.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset_wasm_hash: BytesN<32>,
+        host_jurisdiction: String,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::AssetWasmHash, &asset_wasm_hash);
+        env.storage()
+            .instance()
+            .set(&DataKey::HostJurisdiction, &host_jurisdiction);
+        env.storage().instance().set(&DataKey::AssetCount, &0u32);
+        Ok(())
+    }
+
+    // ====================================================================
+    // Deployment
+    // ====================================================================
+
+    // Deploy a CarbonAsset for `project_id` at the address derived from this
+    // factory and `salt`, and initialize it in the same call so no instance
+    // is left uninitialized. CarbonAsset `initialize` requires the admin's
+    // authorization, so the factory admin must also sign the transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deploy_asset(
+        env: Env,
+        deployer: Address,
+        salt: BytesN<32>,
+        project_id: String,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        retirement_tracker: Address,
+    ) -> Result<Address, ContractError> {
+        deployer.require_auth();
+
+        if !Self::is_deployer(env.clone(), deployer.clone()) {
+            return Err(ContractError::NotDeployer);
+        }
+        if decimals != ASSET_DECIMALS {
+            return Err(ContractError::InvalidDecimals);
+        }
+        let project_key = DataKey::ProjectAsset(project_id.clone());
+        if env.storage().persistent().has(&project_key) {
+            return Err(ContractError::ProjectAlreadyDeployed);
+        }
+
+        let admin = Self::get_admin(env.clone())?;
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AssetWasmHash)
+            .ok_or(ContractError::NotInitialized)?;
+        let host_jurisdiction: String = env
+            .storage()
+            .instance()
+            .get(&DataKey::HostJurisdiction)
+            .ok_or(ContractError::NotInitialized)?;
+
+        let asset = env
+            .deployer()
+            .with_current_contract(salt)
+            .deploy_v2(wasm_hash, ());
+        let init_args: Vec<Val> = vec![
+            &env,
+            admin.into_val(&env),
+            name.into_val(&env),
+            symbol.into_val(&env),
+            retirement_tracker.into_val(&env),
+            host_jurisdiction.into_val(&env),
+        ];
+        env.invoke_contract::<()>(&asset, &Symbol::new(&env, "initialize"), init_args);
+
+        let index: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AssetCount)
+            .unwrap_or(0);
+        let entry = DeployedAsset {
+            asset: asset.clone(),
+            project_id: project_id.clone(),
+            name: name.clone(),
+            symbol: symbol.clone(),
+            deployed_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Asset(index), &entry);
+        env.storage().persistent().set(&project_key, &asset);
+        env.storage()
+            .instance()
+            .set(&DataKey::AssetCount, &(index + 1));
+
+        AssetDeployedEvent {
+            asset: asset.clone(),
+            project_id,
+            deployer,
+            name,
+            symbol,
+        }
+        .publish(&env);
+
+        Ok(asset)
+    }
+
+    // ====================================================================
+    // Registry
+    // ====================================================================
+
+    // Deployed assets in deployment order.
+    pub fn list_deployed_assets(env: Env, start: u32, limit: u32) -> Vec<DeployedAsset> {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AssetCount)
+            .unwrap_or(0);
+        let end = start.saturating_add(limit).min(count);
+
+        let mut assets = Vec::new(&env);
+        for index in start..end {
+            let entry: DeployedAsset = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Asset(index))
+                .unwrap();
+            assets.push_back(entry);
+        }
+        assets
+    }
+
+    pub fn get_asset_by_project(env: Env, project_id: String) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProjectAsset(project_id))
+    }
+
+    pub fn asset_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AssetCount)
+            .unwrap_or(0)
+    }
+
+    // ====================================================================
+    // Administration
+    // ====================================================================
+
+    pub fn set_deployer(
+        env: Env,
+        caller: Address,
+        account: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        let admin = Self::get_admin(env.clone())?;
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        if enabled {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Deployer(account.clone()), &true);
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Deployer(account.clone()));
+        }
+
+        DeployerUpdatedEvent { account, enabled }.publish(&env);
+        Ok(())
+    }
+
+    pub fn is_deployer(env: Env, account: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Deployer(account))
+    }
+
+    // Point future deployments at a new CarbonAsset Wasm. Instances that are
+    // already deployed are not upgraded.
+    pub fn set_asset_wasm_hash(
+        env: Env,
+        caller: Address,
+        asset_wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        let admin = Self::get_admin(env.clone())?;
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AssetWasmHash, &asset_wasm_hash);
+        Ok(())
+    }
+
+    pub fn get_asset_wasm_hash(env: Env) -> Result<BytesN<32>, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AssetWasmHash)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)
+    }
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, Address, String};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    AssetWasmHash,
+    HostJurisdiction,
+    Deployer(Address),
+    AssetCount,
+    Asset(u32),
+    ProjectAsset(String),
+}
@@ -0,0 +1,84 @@
+#![cfg(test)]
+
+use super::{CarbonAssetFactory, CarbonAssetFactoryClient};
+use crate::errors::ContractError;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, BytesN, Env, String};
+
+fn setup<'a>() -> (Env, CarbonAssetFactoryClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let factory = CarbonAssetFactoryClient::new(&env, &env.register(CarbonAssetFactory, ()));
+    factory.initialize(
+        &admin,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &String::from_str(&env, "US"),
+    );
+
+    (env, factory, admin)
+}
+
+#[test]
+fn test_initialize_twice() {
+    let (env, factory, admin) = setup();
+    assert_eq!(
+        factory.try_initialize(
+            &admin,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &String::from_str(&env, "US"),
+        ),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+    assert_eq!(
+        factory.get_asset_wasm_hash(),
+        BytesN::from_array(&env, &[1u8; 32])
+    );
+}
+
+#[test]
+fn test_deploy_requires_deployer_role() {
+    let (env, factory, admin) = setup();
+    let deployer = Address::generate(&env);
+    let deploy = |decimals: u32| {
+        factory.try_deploy_asset(
+            &deployer,
+            &BytesN::from_array(&env, &[9u8; 32]),
+            &String::from_str(&env, "PROJ-1"),
+            &String::from_str(&env, "Project One Credits"),
+            &String::from_str(&env, "P1"),
+            &decimals,
+            &Address::generate(&env),
+        )
+    };
+
+    assert_eq!(deploy(0), Err(Ok(ContractError::NotDeployer)));
+
+    // Only the admin manages the role
+    assert_eq!(
+        factory.try_set_deployer(&deployer, &deployer, &true),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    factory.set_deployer(&admin, &deployer, &true);
+    assert!(factory.is_deployer(&deployer));
+    assert_eq!(deploy(7), Err(Ok(ContractError::InvalidDecimals)));
+
+    factory.set_deployer(&admin, &deployer, &false);
+    assert!(!factory.is_deployer(&deployer));
+    assert_eq!(deploy(0), Err(Ok(ContractError::NotDeployer)));
+    assert_eq!(factory.asset_count(), 0);
+}
+
+#[test]
+fn test_set_asset_wasm_hash_is_admin_only() {
+    let (env, factory, admin) = setup();
+    let new_hash = BytesN::from_array(&env, &[3u8; 32]);
+
+    assert_eq!(
+        factory.try_set_asset_wasm_hash(&Address::generate(&env), &new_hash),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    factory.set_asset_wasm_hash(&admin, &new_hash);
+    assert_eq!(factory.get_asset_wasm_hash(), new_hash);
+}
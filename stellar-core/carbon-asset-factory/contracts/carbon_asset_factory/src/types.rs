@@ -0,0 +1,12 @@
+use soroban_sdk::{contracttype, Address, String};
+
+// Registry entry for a CarbonAsset instance deployed by the factory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeployedAsset {
+    pub asset: Address,
+    pub project_id: String,
+    pub name: String,
+    pub symbol: String,
+    pub deployed_at: u64,
+}
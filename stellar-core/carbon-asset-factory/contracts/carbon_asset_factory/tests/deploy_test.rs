@@ -0,0 +1,132 @@
+#![cfg(test)]
+
+// Deploying needs the CarbonAsset Wasm, which the crate's build script
+// builds from the workspace's `carbon_asset`.
+
+use carbon_asset::CarbonAssetClient;
+use carbon_asset_factory::errors::ContractError;
+use carbon_asset_factory::{CarbonAssetFactory, CarbonAssetFactoryClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Bytes, BytesN, Env, String};
+
+const CARBON_ASSET_WASM: &[u8] = include_bytes!(env!("CARBON_ASSET_WASM"));
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    deployer: Address,
+    retirement_tracker: Address,
+    factory: CarbonAssetFactoryClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    // The admin signs the new asset's `initialize`, below `deploy_asset`
+    env.mock_all_auths_allowing_non_root_auth();
+    // The CarbonAsset Wasm is over the network's 128 KiB contract size
+    // limit, and instantiating it is over the test budget
+    env.cost_estimate().budget().reset_unlimited();
+    env.cost_estimate().disable_resource_limits();
+
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(Bytes::from_slice(&env, CARBON_ASSET_WASM));
+
+    let admin = Address::generate(&env);
+    let deployer = Address::generate(&env);
+    let factory = CarbonAssetFactoryClient::new(&env, &env.register(CarbonAssetFactory, ()));
+    factory.initialize(&admin, &wasm_hash, &String::from_str(&env, "KE"));
+    factory.set_deployer(&admin, &deployer, &true);
+
+    Setup {
+        retirement_tracker: Address::generate(&env),
+        env,
+        admin,
+        deployer,
+        factory,
+    }
+}
+
+fn deploy(s: &Setup, salt: u8, project_id: &str, name: &str, symbol: &str) -> Address {
+    s.factory.deploy_asset(
+        &s.deployer,
+        &BytesN::from_array(&s.env, &[salt; 32]),
+        &String::from_str(&s.env, project_id),
+        &String::from_str(&s.env, name),
+        &String::from_str(&s.env, symbol),
+        &0,
+        &s.retirement_tracker,
+    )
+}
+
+#[test]
+fn test_deploy_two_assets() {
+    let s = setup();
+    let first = deploy(&s, 1, "PROJ-1", "Project One Credits", "P1");
+    let second = deploy(&s, 2, "PROJ-2", "Project Two Credits", "P2");
+    assert_ne!(first, second);
+
+    // Both instances come out of deploy_asset already initialized
+    for (asset, name, symbol) in [
+        (&first, "Project One Credits", "P1"),
+        (&second, "Project Two Credits", "P2"),
+    ] {
+        let carbon = CarbonAssetClient::new(&s.env, asset);
+        assert_eq!(carbon.name(), String::from_str(&s.env, name));
+        assert_eq!(carbon.symbol(), String::from_str(&s.env, symbol));
+        assert_eq!(carbon.decimals(), 0);
+        assert_eq!(carbon.get_admin(), s.admin);
+        assert_eq!(carbon.get_retirement_tracker(), s.retirement_tracker);
+        assert_eq!(
+            carbon.get_host_jurisdiction(),
+            Some(String::from_str(&s.env, "KE"))
+        );
+    }
+
+    assert_eq!(
+        s.factory
+            .get_asset_by_project(&String::from_str(&s.env, "PROJ-1")),
+        Some(first.clone())
+    );
+    assert_eq!(
+        s.factory
+            .get_asset_by_project(&String::from_str(&s.env, "PROJ-2")),
+        Some(second.clone())
+    );
+    assert_eq!(
+        s.factory
+            .get_asset_by_project(&String::from_str(&s.env, "PROJ-3")),
+        None
+    );
+
+    let assets = s.factory.list_deployed_assets(&0, &10);
+    assert_eq!(assets.len(), 2);
+    assert_eq!(assets.get(0).unwrap().asset, first);
+    assert_eq!(assets.get(1).unwrap().asset, second);
+    let page = s.factory.list_deployed_assets(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(
+        page.get(0).unwrap().project_id,
+        String::from_str(&s.env, "PROJ-2")
+    );
+}
+
+#[test]
+fn test_one_asset_per_project() {
+    let s = setup();
+    deploy(&s, 1, "PROJ-1", "Project One Credits", "P1");
+
+    assert_eq!(
+        s.factory.try_deploy_asset(
+            &s.deployer,
+            &BytesN::from_array(&s.env, &[2u8; 32]),
+            &String::from_str(&s.env, "PROJ-1"),
+            &String::from_str(&s.env, "Project One Credits"),
+            &String::from_str(&s.env, "P1"),
+            &0,
+            &s.retirement_tracker,
+        ),
+        Err(Ok(ContractError::ProjectAlreadyDeployed))
+    );
+    assert_eq!(s.factory.asset_count(), 1);
+}
@@ -0,0 +1,10 @@
+use soroban_sdk::contracterror;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    UnknownSubsystem = 4,
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{contractevent, Address, Symbol};
+
+#[contractevent]
+pub struct HaltUpdatedEvent {
+    pub subsystem: Symbol,
+    pub halted: bool,
+    pub guardian: Address,
+}
+
+#[contractevent]
+pub struct GuardianUpdatedEvent {
+    pub old_guardian: Address,
+    pub new_guardian: Address,
+}
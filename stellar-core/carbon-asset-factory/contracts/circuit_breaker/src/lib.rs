@@ -0,0 +1,128 @@
+#![no_std]
+
+pub mod errors;
+mod events;
+mod storage;
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+use crate::errors::ContractError;
+use crate::events::{GuardianUpdatedEvent, HaltUpdatedEvent};
+use crate::storage::DataKey;
+
+// Subsystems that core contracts check before proceeding.
+pub const MINTING: &str = "minting";
+pub const TRANSFERS: &str = "transfers";
+pub const RETIREMENTS: &str = "retirements";
+pub const LISTINGS: &str = "listings";
+
+const SUBSYSTEMS: [&str; 4] = [MINTING, TRANSFERS, RETIREMENTS, LISTINGS];
+
+// ========================================================================
+// Contract
+// ========================================================================
+
+#[contract]
+pub struct CircuitBreaker;
+
+#[contractimpl]
+impl CircuitBreaker {
+    // ====================================================================
+    // Initialization
+    // ====================================================================
+
+    // Contracts consult this one only once it is registered with their
+    // `set_circuit_breaker`.
+    pub fn initialize(env: Env, admin: Address, guardian: Address) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+        Ok(())
+    }
+
+    // ====================================================================
+    // Halts
+    // ====================================================================
+
+    // Halt or resume one subsystem across every contract that consults this
+    // breaker. Only the guardian may flip flags.
+    pub fn set_halted(
+        env: Env,
+        guardian: Address,
+        subsystem: Symbol,
+        halted: bool,
+    ) -> Result<(), ContractError> {
+        guardian.require_auth();
+        if guardian != Self::get_guardian(env.clone())? {
+            return Err(ContractError::NotAuthorized);
+        }
+        if !SUBSYSTEMS
+            .iter()
+            .any(|name| subsystem == Symbol::new(&env, name))
+        {
+            return Err(ContractError::UnknownSubsystem);
+        }
+
+        let key = DataKey::Halted(subsystem.clone());
+        if halted {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+
+        HaltUpdatedEvent {
+            subsystem,
+            halted,
+            guardian,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Unknown subsystems are never halted.
+    pub fn is_halted(env: Env, subsystem: Symbol) -> bool {
+        env.storage().instance().has(&DataKey::Halted(subsystem))
+    }
+
+    // ====================================================================
+    // Administration
+    // ====================================================================
+
+    pub fn set_guardian(env: Env, caller: Address, guardian: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        if caller != Self::get_admin(env.clone())? {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let old_guardian = Self::get_guardian(env.clone())?;
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+
+        GuardianUpdatedEvent {
+            old_guardian,
+            new_guardian: guardian,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_guardian(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Guardian)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)
+    }
+}
@@ -0,0 +1,9 @@
+use soroban_sdk::{contracttype, Symbol};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Guardian,
+    Halted(Symbol),
+}
@@ -0,0 +1,68 @@
+#![cfg(test)]
+
+use super::{CircuitBreaker, CircuitBreakerClient};
+use crate::errors::ContractError;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Env, Symbol};
+
+fn setup<'a>() -> (Env, CircuitBreakerClient<'a>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let breaker = CircuitBreakerClient::new(&env, &env.register(CircuitBreaker, ()));
+    breaker.initialize(&admin, &guardian);
+
+    (env, breaker, admin, guardian)
+}
+
+#[test]
+fn test_guardian_halts_and_resumes_subsystems() {
+    let (env, breaker, _, guardian) = setup();
+    let transfers = Symbol::new(&env, "transfers");
+    let retirements = Symbol::new(&env, "retirements");
+
+    assert!(!breaker.is_halted(&transfers));
+    breaker.set_halted(&guardian, &transfers, &true);
+    assert!(breaker.is_halted(&transfers));
+    assert!(!breaker.is_halted(&retirements));
+
+    breaker.set_halted(&guardian, &transfers, &false);
+    assert!(!breaker.is_halted(&transfers));
+
+    assert_eq!(
+        breaker.try_set_halted(&guardian, &Symbol::new(&env, "staking"), &true),
+        Err(Ok(ContractError::UnknownSubsystem))
+    );
+    assert!(!breaker.is_halted(&Symbol::new(&env, "staking")));
+}
+
+#[test]
+fn test_only_guardian_halts_and_only_admin_rotates() {
+    let (env, breaker, admin, guardian) = setup();
+    let other = Address::generate(&env);
+    let minting = Symbol::new(&env, "minting");
+
+    assert_eq!(
+        breaker.try_set_halted(&admin, &minting, &true),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        breaker.try_set_guardian(&guardian, &other),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        breaker.try_initialize(&admin, &other),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+
+    breaker.set_guardian(&admin, &other);
+    assert_eq!(breaker.get_guardian(), other);
+    assert_eq!(
+        breaker.try_set_halted(&guardian, &minting, &true),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    breaker.set_halted(&other, &minting, &true);
+    assert!(breaker.is_halted(&minting));
+}
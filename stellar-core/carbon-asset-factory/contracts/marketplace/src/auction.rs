@@ -0,0 +1,428 @@
+use soroban_sdk::{contractimpl, token, Address, Env, Vec};
+
+use crate::errors::ContractError;
+use crate::events::{
+    AuctionCancelledEvent, AuctionCreatedEvent, AuctionFinalizedEvent,
+    AuctionSettlementFailedEvent, BidPlacedEvent,
+};
+use crate::storage::DataKey;
+use crate::types::{Auction, AuctionStatus, Bid};
+use crate::{Marketplace, MarketplaceArgs, MarketplaceClient, LISTINGS, TRANSFERS};
+
+// Finalization refunds every bidder and delivers tokens to every winner in
+// one transaction, so both are bounded. Each winner settles separately and
+// costs more ledger entries than a buyer of the same tokens in one fill; three
+// tokens, each with a royalty, and six bidders fit the network's
+// per-transaction footprint limit.
+pub const MAX_AUCTION_BIDDERS: u32 = 6;
+pub const MAX_AUCTION_TOKENS: u32 = 3;
+
+// ========================================================================
+// Sealed-bid batch auctions
+// ========================================================================
+
+#[contractimpl]
+impl Marketplace {
+    // Auction exactly `token_ids`, at most `MAX_AUCTION_TOKENS`, between
+    // `start_ts` (inclusive) and `end_ts` (exclusive). As with listings, the
+    // tokens stay with the seller until finalization, listed on the carbon
+    // asset so they cannot be moved elsewhere, and are delivered through the
+    // seller's allowance.
+    pub fn create_auction(
+        env: Env,
+        seller: Address,
+        token_ids: Vec<u32>,
+        reserve_price: i128,
+        bid_token: Address,
+        start_ts: u64,
+        end_ts: u64,
+    ) -> Result<u64, ContractError> {
+        seller.require_auth();
+        Self::require_not_halted(&env, LISTINGS)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        if token_ids.len() > MAX_AUCTION_TOKENS {
+            return Err(ContractError::BatchTooLarge);
+        }
+        if reserve_price <= 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+        if start_ts >= end_ts || end_ts <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidTimeWindow);
+        }
+
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        Self::check_tokens(&env, &carbon_asset, &seller, &token_ids)?;
+        Self::call_carbon::<()>(
+            &env,
+            &carbon_asset,
+            "list_tokens",
+            (
+                env.current_contract_address(),
+                seller.clone(),
+                token_ids.clone(),
+            ),
+        );
+
+        let auction_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextAuctionId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextAuctionId, &(auction_id + 1));
+
+        let auction = Auction {
+            auction_id,
+            seller: seller.clone(),
+            token_ids: token_ids.clone(),
+            amount: token_ids.len() as i128,
+            reserve_price,
+            bid_token: bid_token.clone(),
+            start_ts,
+            end_ts,
+            status: AuctionStatus::Open,
+            bidders: Vec::new(&env),
+            clearing_price: 0,
+            sold: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        AuctionCreatedEvent {
+            auction_id,
+            seller,
+            token_ids,
+            reserve_price,
+            bid_token,
+            start_ts,
+            end_ts,
+        }
+        .publish(&env);
+
+        Ok(auction_id)
+    }
+
+    // Bid for `quantity` credits at up to `price` each, escrowing
+    // `quantity * price` of the bid token. A bidder has one bid per auction;
+    // bidding again replaces it, may not lower the price, and only the escrow
+    // difference moves.
+    pub fn place_bid(
+        env: Env,
+        bidder: Address,
+        auction_id: u64,
+        quantity: i128,
+        price: i128,
+    ) -> Result<(), ContractError> {
+        bidder.require_auth();
+
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        let now = env.ledger().timestamp();
+        if auction.status != AuctionStatus::Open || now < auction.start_ts || now >= auction.end_ts
+        {
+            return Err(ContractError::AuctionClosed);
+        }
+        if quantity <= 0 || quantity > auction.amount {
+            return Err(ContractError::InvalidAmount);
+        }
+        if price <= 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+        let escrowed = quantity
+            .checked_mul(price)
+            .ok_or(ContractError::InvalidAmount)?;
+
+        let marketplace = env.current_contract_address();
+        let bid_token = token::TokenClient::new(&env, &auction.bid_token);
+        let bid_key = DataKey::Bid(auction_id, bidder.clone());
+        match env.storage().persistent().get::<DataKey, Bid>(&bid_key) {
+            Some(previous) => {
+                if price < previous.price {
+                    return Err(ContractError::BidTooLow);
+                }
+                if escrowed > previous.escrowed {
+                    bid_token.transfer(&bidder, &marketplace, &(escrowed - previous.escrowed));
+                } else if escrowed < previous.escrowed {
+                    bid_token.transfer(&marketplace, &bidder, &(previous.escrowed - escrowed));
+                }
+            }
+            None => {
+                if auction.bidders.len() >= MAX_AUCTION_BIDDERS {
+                    return Err(ContractError::TooManyBidders);
+                }
+                bid_token.transfer(&bidder, &marketplace, &escrowed);
+                auction.bidders.push_back(bidder.clone());
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Auction(auction_id), &auction);
+            }
+        }
+
+        let bid = Bid {
+            quantity,
+            price,
+            escrowed,
+            filled: 0,
+        };
+        env.storage().persistent().set(&bid_key, &bid);
+
+        BidPlacedEvent {
+            auction_id,
+            bidder,
+            quantity,
+            price,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // Withdraw an auction that has no bids yet and release its tokens.
+    // Seller-only.
+    pub fn cancel_auction(env: Env, auction_id: u64) -> Result<(), ContractError> {
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        auction.seller.require_auth();
+
+        if auction.status != AuctionStatus::Open {
+            return Err(ContractError::AuctionClosed);
+        }
+        if !auction.bidders.is_empty() {
+            return Err(ContractError::AuctionHasBids);
+        }
+
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        Self::call_carbon::<()>(
+            &env,
+            &carbon_asset,
+            "delist_tokens",
+            (
+                env.current_contract_address(),
+                auction.seller.clone(),
+                auction.token_ids.clone(),
+            ),
+        );
+
+        auction.status = AuctionStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        AuctionCancelledEvent {
+            auction_id,
+            seller: auction.seller,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // Clear the auction once `end_ts` has passed. Bids at or above the
+    // reserve are filled highest price first; bids tied at the price where
+    // supply runs out share the rest pro rata, with rounding units going to
+    // the earliest bidders. Winners receive the auctioned tokens in the order
+    // listed, in bidder order, each settling on its own: if the carbon asset
+    // refuses a winner's tokens (a frozen token, a missing royalty approval,
+    // a lapsed seller allowance) that winner is refunded and their tokens
+    // stay with the seller, without holding up the others. Every winner pays
+    // the lowest filled price, the excess escrow is refunded, and bids that
+    // win nothing are refunded in full. The seller is paid the proceeds less
+    // the platform fee, if any. Callable by anyone.
+    pub fn finalize(env: Env, auction_id: u64) -> Result<(), ContractError> {
+        Self::require_not_halted(&env, TRANSFERS)?;
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        if auction.status != AuctionStatus::Open {
+            return Err(ContractError::AuctionClosed);
+        }
+        if env.ledger().timestamp() < auction.end_ts {
+            return Err(ContractError::AuctionNotEnded);
+        }
+
+        let mut bids: Vec<Bid> = Vec::new(&env);
+        for bidder in auction.bidders.iter() {
+            let bid: Bid = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Bid(auction_id, bidder))
+                .unwrap();
+            bids.push_back(bid);
+        }
+
+        let (mut clearing_price, _) = Self::clear_bids(&mut bids, &auction);
+
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        let marketplace = env.current_contract_address();
+        let bid_token = token::TokenClient::new(&env, &auction.bid_token);
+        // Tokens nobody won, or whose winner could not take them, go back
+        // to the seller
+        let mut unsold = Vec::new(&env);
+        let mut next = 0;
+        let mut sold = 0;
+        for i in 0..auction.bidders.len() {
+            let bidder = auction.bidders.get(i).unwrap();
+            let mut bid = bids.get(i).unwrap();
+
+            if bid.filled > 0 {
+                let count = bid.filled as u32;
+                let token_ids = auction.token_ids.slice(next..next + count);
+                next += count;
+                let delivered = Self::try_call_carbon::<()>(
+                    &env,
+                    &carbon_asset,
+                    "transfer_with_sale_price",
+                    (
+                        marketplace.clone(),
+                        auction.seller.clone(),
+                        bidder.clone(),
+                        token_ids.clone(),
+                        clearing_price,
+                        auction.bid_token.clone(),
+                    ),
+                );
+                if delivered.is_some() {
+                    sold += bid.filled;
+                } else {
+                    AuctionSettlementFailedEvent {
+                        auction_id,
+                        bidder: bidder.clone(),
+                        quantity: bid.filled,
+                    }
+                    .publish(&env);
+                    bid.filled = 0;
+                    unsold.append(&token_ids);
+                }
+            }
+
+            let refund = bid.escrowed - bid.filled * clearing_price;
+            if refund > 0 {
+                bid_token.transfer(&marketplace, &bidder, &refund);
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Bid(auction_id, bidder), &bid);
+        }
+        // The platform fee stays behind in the marketplace
+        let mut fee = 0;
+        if sold > 0 {
+            let gross = sold * clearing_price;
+            fee = Self::accrue_platform_fee(&env, &auction.bid_token, gross)?;
+            bid_token.transfer(&marketplace, &auction.seller, &(gross - fee));
+        } else {
+            clearing_price = 0;
+        }
+
+        unsold.append(&auction.token_ids.slice(next..));
+        if !unsold.is_empty() {
+            Self::call_carbon::<()>(
+                &env,
+                &carbon_asset,
+                "delist_tokens",
+                (marketplace, auction.seller.clone(), unsold),
+            );
+        }
+
+        auction.status = AuctionStatus::Finalized;
+        auction.clearing_price = clearing_price;
+        auction.sold = sold;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        AuctionFinalizedEvent {
+            auction_id,
+            clearing_price,
+            sold,
+            fee,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_auction(env: Env, auction_id: u64) -> Result<Auction, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Auction(auction_id))
+            .ok_or(ContractError::AuctionNotFound)
+    }
+
+    pub fn get_bid(env: Env, auction_id: u64, bidder: Address) -> Option<Bid> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bid(auction_id, bidder))
+    }
+}
+
+impl Marketplace {
+    // Set `filled` on each bid (indexed like `auction.bidders`) and return the
+    // clearing price and total quantity sold.
+    fn clear_bids(bids: &mut Vec<Bid>, auction: &Auction) -> (i128, i128) {
+        let mut remaining = auction.amount;
+        let mut clearing_price = 0;
+        let mut ceiling: Option<i128> = None;
+
+        while remaining > 0 {
+            // Next lower price level at or above the reserve
+            let mut level: Option<i128> = None;
+            for bid in bids.iter() {
+                let below_ceiling = ceiling.is_none_or(|c| bid.price < c);
+                let above_level = level.is_none_or(|l| bid.price > l);
+                if bid.price >= auction.reserve_price && below_ceiling && above_level {
+                    level = Some(bid.price);
+                }
+            }
+            let Some(price) = level else {
+                break;
+            };
+
+            let mut demand = 0;
+            for bid in bids.iter() {
+                if bid.price == price {
+                    demand += bid.quantity;
+                }
+            }
+
+            if demand <= remaining {
+                for i in 0..bids.len() {
+                    let mut bid = bids.get(i).unwrap();
+                    if bid.price == price {
+                        bid.filled = bid.quantity;
+                        bids.set(i, bid);
+                    }
+                }
+                remaining -= demand;
+            } else {
+                let mut allocated = 0;
+                for i in 0..bids.len() {
+                    let mut bid = bids.get(i).unwrap();
+                    if bid.price == price {
+                        bid.filled = bid.quantity * remaining / demand;
+                        allocated += bid.filled;
+                        bids.set(i, bid);
+                    }
+                }
+                let mut leftover = remaining - allocated;
+                for i in 0..bids.len() {
+                    if leftover == 0 {
+                        break;
+                    }
+                    let mut bid = bids.get(i).unwrap();
+                    if bid.price == price && bid.filled < bid.quantity {
+                        bid.filled += 1;
+                        leftover -= 1;
+                        bids.set(i, bid);
+                    }
+                }
+                remaining = 0;
+            }
+
+            clearing_price = price;
+            ceiling = Some(price);
+        }
+
+        (clearing_price, auction.amount - remaining)
+    }
+}
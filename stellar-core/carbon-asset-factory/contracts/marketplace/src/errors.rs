@@ -0,0 +1,34 @@
+use soroban_sdk::contracterror;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidPrice = 4,
+    ListingNotFound = 5,
+    ListingNotActive = 6,
+    NotOwner = 7,
+    InsufficientAllowance = 8,
+    InsufficientPaymentAllowance = 9,
+    ExceedsRemaining = 10,
+    InvalidTimeWindow = 11,
+    AuctionNotFound = 12,
+    AuctionClosed = 13,
+    AuctionNotEnded = 14,
+    BidTooLow = 15,
+    TooManyBidders = 16,
+    OfferNotFound = 17,
+    OfferNotActive = 18,
+    OfferExpired = 19,
+    SystemHalted = 20,
+    NotAuthorized = 21,
+    ListingExpired = 22,
+    BatchTooLarge = 23,
+    ReaperBudgetInUse = 24,
+    PaymentTokenNotAllowed = 25,
+    FeeTooHigh = 26,
+    TokenListed = 27,
+    AuctionHasBids = 28,
+}
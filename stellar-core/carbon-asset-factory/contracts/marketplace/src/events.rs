@@ -0,0 +1,150 @@
+use soroban_sdk::{contractevent, Address, Vec};
+
+#[contractevent]
+pub struct ListingCreatedEvent {
+    pub listing_id: u64,
+    pub seller: Address,
+    pub token_ids: Vec<u32>,
+    pub amount: i128,
+    pub price_per_unit: i128,
+    pub payment_token: Address,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+pub struct ListingCancelledEvent {
+    pub listing_id: u64,
+    pub seller: Address,
+    pub remaining: i128,
+}
+
+#[contractevent]
+pub struct ListingExpiredEvent {
+    pub listing_id: u64,
+    pub seller: Address,
+    pub remaining: i128,
+}
+
+#[contractevent]
+pub struct ReaperConfigUpdatedEvent {
+    pub token: Address,
+    pub incentive: i128,
+    pub budget: i128,
+}
+
+#[contractevent]
+pub struct ReaperPaidEvent {
+    pub reaper: Address,
+    pub reaped: u32,
+    pub paid: i128,
+}
+
+#[contractevent]
+pub struct PurchaseEvent {
+    pub listing_id: u64,
+    pub buyer: Address,
+    pub seller: Address,
+    pub token_ids: Vec<u32>,
+    pub amount: i128,
+    // What the buyer paid, the platform fee kept from it and the seller's
+    // share.
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+    pub payment_token: Address,
+    pub remaining: i128,
+}
+
+#[contractevent]
+pub struct PlatformFeeUpdatedEvent {
+    pub bps: u32,
+    pub recipient: Address,
+    pub updated_by: Address,
+}
+
+#[contractevent]
+pub struct FeesWithdrawnEvent {
+    pub token: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct PaymentTokenUpdatedEvent {
+    pub payment_token: Address,
+    pub allowed: bool,
+    pub updated_by: Address,
+}
+
+#[contractevent]
+pub struct AuctionCreatedEvent {
+    pub auction_id: u64,
+    pub seller: Address,
+    pub token_ids: Vec<u32>,
+    pub reserve_price: i128,
+    pub bid_token: Address,
+    pub start_ts: u64,
+    pub end_ts: u64,
+}
+
+#[contractevent]
+pub struct BidPlacedEvent {
+    pub auction_id: u64,
+    pub bidder: Address,
+    pub quantity: i128,
+    pub price: i128,
+}
+
+#[contractevent]
+pub struct AuctionCancelledEvent {
+    pub auction_id: u64,
+    pub seller: Address,
+}
+
+// The carbon asset refused to deliver a winner's credits; the winner was
+// refunded in full.
+#[contractevent]
+pub struct AuctionSettlementFailedEvent {
+    pub auction_id: u64,
+    pub bidder: Address,
+    pub quantity: i128,
+}
+
+#[contractevent]
+pub struct AuctionFinalizedEvent {
+    pub auction_id: u64,
+    pub clearing_price: i128,
+    pub sold: i128,
+    // Platform fee kept from the seller's proceeds.
+    pub fee: i128,
+}
+
+#[contractevent]
+pub struct OfferCreatedEvent {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub token_ids: Vec<u32>,
+    pub price_per_unit: i128,
+    pub payment_token: Address,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+pub struct OfferAcceptedEvent {
+    pub offer_id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub token_ids: Vec<u32>,
+    pub amount: i128,
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+    pub remaining: i128,
+}
+
+#[contractevent]
+pub struct OfferCancelledEvent {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub refunded: i128,
+}
@@ -0,0 +1,121 @@
+use soroban_sdk::{contractimpl, token, Address, Env};
+
+use crate::errors::ContractError;
+use crate::events::{FeesWithdrawnEvent, PlatformFeeUpdatedEvent};
+use crate::storage::DataKey;
+use crate::types::PlatformFee;
+use crate::{Marketplace, MarketplaceArgs, MarketplaceClient};
+
+// Highest platform fee the admin can set, in basis points of a fill.
+pub const MAX_PLATFORM_FEE_BPS: u32 = 250;
+
+// ========================================================================
+// Platform fee
+// ========================================================================
+
+#[contractimpl]
+impl Marketplace {
+    // Take `bps` of every listing purchase, offer acceptance and auction
+    // settlement out of the seller's proceeds, for `recipient` to withdraw. Admin-only; 0 turns
+    // the fee off.
+    pub fn set_platform_fee(
+        env: Env,
+        caller: Address,
+        bps: u32,
+        recipient: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+        if bps > MAX_PLATFORM_FEE_BPS {
+            return Err(ContractError::FeeTooHigh);
+        }
+
+        env.storage().instance().set(
+            &DataKey::PlatformFee,
+            &PlatformFee {
+                bps,
+                recipient: recipient.clone(),
+            },
+        );
+
+        PlatformFeeUpdatedEvent {
+            bps,
+            recipient,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_platform_fee(env: Env) -> Option<PlatformFee> {
+        env.storage().instance().get(&DataKey::PlatformFee)
+    }
+
+    // Fees collected in `token` and not yet withdrawn.
+    pub fn get_accrued_fees(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccruedFees(token))
+            .unwrap_or(0)
+    }
+
+    // Pay everything accrued in `token` to `to`. Only the current fee
+    // recipient can withdraw, including fees accrued under an earlier one.
+    // Returns the amount paid.
+    pub fn withdraw_fees(env: Env, token: Address, to: Address) -> Result<i128, ContractError> {
+        let fee = Self::get_platform_fee(env.clone()).ok_or(ContractError::NotAuthorized)?;
+        fee.recipient.require_auth();
+
+        let accrued = Self::get_accrued_fees(env.clone(), token.clone());
+        if accrued > 0 {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::AccruedFees(token.clone()));
+            token::TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &to,
+                &accrued,
+            );
+        }
+
+        FeesWithdrawnEvent {
+            token,
+            to,
+            amount: accrued,
+        }
+        .publish(&env);
+        Ok(accrued)
+    }
+}
+
+impl Marketplace {
+    // The platform's share of a `gross` fill, rounded down.
+    pub(crate) fn platform_fee_on(env: &Env, gross: i128) -> Result<i128, ContractError> {
+        let bps = match Self::get_platform_fee(env.clone()) {
+            Some(fee) if fee.bps > 0 => fee.bps,
+            _ => return Ok(0),
+        };
+        Ok(gross
+            .checked_mul(bps as i128)
+            .ok_or(ContractError::InvalidAmount)?
+            / 10_000)
+    }
+
+    // The platform's share of a `gross` fill paid in `payment_token`, added
+    // to the fees accrued in that token. The caller moves the fee into the
+    // marketplace.
+    pub(crate) fn accrue_platform_fee(
+        env: &Env,
+        payment_token: &Address,
+        gross: i128,
+    ) -> Result<i128, ContractError> {
+        let fee = Self::platform_fee_on(env, gross)?;
+        if fee > 0 {
+            let accrued = Self::get_accrued_fees(env.clone(), payment_token.clone());
+            env.storage().persistent().set(
+                &DataKey::AccruedFees(payment_token.clone()),
+                &(accrued + fee),
+            );
+        }
+        Ok(fee)
+    }
+}
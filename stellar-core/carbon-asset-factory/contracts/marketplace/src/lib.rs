@@ -0,0 +1,480 @@
+#![no_std]
+
+mod auction;
+pub mod errors;
+mod events;
+mod fees;
+mod offer;
+mod payment_tokens;
+mod reaper;
+mod storage;
+#[cfg(test)]
+mod test;
+pub mod types;
+
+use soroban_sdk::{
+    contract, contractimpl, token, Address, Env, IntoVal, InvokeError, Symbol, TryFromVal, Val, Vec,
+};
+
+use crate::errors::ContractError;
+use crate::events::{ListingCancelledEvent, ListingCreatedEvent, PurchaseEvent};
+use crate::storage::DataKey;
+use crate::types::{AssetStatus, Listing, ListingStatus};
+
+pub use crate::auction::{MAX_AUCTION_BIDDERS, MAX_AUCTION_TOKENS};
+
+// Circuit breaker subsystems checked by the guarded entry points.
+const TRANSFERS: &str = "transfers";
+const LISTINGS: &str = "listings";
+
+// Buying out a listing reads about fifteen ledger entries per token on the
+// carbon asset. Four tokens, each with a royalty, fit the network's
+// per-transaction footprint limit.
+pub const MAX_LISTING_TOKENS: u32 = 4;
+
+// Shortest time a listing must stay open, so expiring listings cannot be
+// created just to be reaped for the incentive.
+pub const MIN_LISTING_DURATION: u64 = 3_600;
+
+// ========================================================================
+// Contract
+// ========================================================================
+
+#[contract]
+pub struct Marketplace;
+
+#[contractimpl]
+impl Marketplace {
+    // ====================================================================
+    // Initialization
+    // ====================================================================
+
+    // `admin` must sign, so the deployment cannot be claimed by whoever
+    // calls first.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        carbon_asset: Address,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::CarbonAsset) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::CarbonAsset, &carbon_asset);
+        env.storage().instance().set(&DataKey::NextListingId, &1u64);
+        Ok(())
+    }
+
+    // Consult `circuit_breaker` before creating listings, auctions and offers
+    // and before moving credits. Cancellations are never halted. Admin-only.
+    pub fn set_circuit_breaker(
+        env: Env,
+        caller: Address,
+        circuit_breaker: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CircuitBreaker, &circuit_breaker);
+        Ok(())
+    }
+
+    // ====================================================================
+    // Listings
+    // ====================================================================
+
+    // Offer exactly `token_ids`, at most `MAX_LISTING_TOKENS`, at a fixed
+    // price per credit, paid in one of the allowed payment tokens. Credits
+    // stay with the seller until bought, so the seller must hold every token
+    // and have approved this contract on the carbon asset for all of them.
+    // The tokens are listed on the carbon asset and the seller cannot move
+    // them elsewhere until the listing ends. When the listing covers the
+    // seller's whole balance, the tokens are flipped to Listed. Buyers
+    // receive the tokens in the order given. The listing can be bought until
+    // `expires_at` (exclusive), at least `MIN_LISTING_DURATION` away, and
+    // reaped after that.
+    pub fn create_listing(
+        env: Env,
+        seller: Address,
+        token_ids: Vec<u32>,
+        price_per_unit: i128,
+        payment_token: Address,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        seller.require_auth();
+        Self::require_not_halted(&env, LISTINGS)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        if token_ids.len() > MAX_LISTING_TOKENS {
+            return Err(ContractError::BatchTooLarge);
+        }
+        if price_per_unit <= 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+        if expires_at
+            < env
+                .ledger()
+                .timestamp()
+                .saturating_add(MIN_LISTING_DURATION)
+        {
+            return Err(ContractError::InvalidTimeWindow);
+        }
+        if !Self::is_payment_token_allowed(env.clone(), payment_token.clone()) {
+            return Err(ContractError::PaymentTokenNotAllowed);
+        }
+
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        let balance = Self::check_tokens(&env, &carbon_asset, &seller, &token_ids)?;
+
+        let marketplace = env.current_contract_address();
+        Self::call_carbon::<()>(
+            &env,
+            &carbon_asset,
+            "list_tokens",
+            (marketplace.clone(), seller.clone(), token_ids.clone()),
+        );
+        let amount = token_ids.len() as i128;
+        let token_listed = amount == balance;
+        if token_listed {
+            for token_id in token_ids.iter() {
+                Self::call_carbon::<()>(
+                    &env,
+                    &carbon_asset,
+                    "list",
+                    (marketplace.clone(), token_id),
+                );
+            }
+        }
+
+        let listing_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextListingId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextListingId, &(listing_id + 1));
+
+        let listing = Listing {
+            listing_id,
+            seller: seller.clone(),
+            token_ids: token_ids.clone(),
+            amount,
+            remaining: amount,
+            price_per_unit,
+            payment_token: payment_token.clone(),
+            status: ListingStatus::Active,
+            token_listed,
+            created_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Listing(listing_id), &listing);
+
+        ListingCreatedEvent {
+            listing_id,
+            seller,
+            token_ids,
+            amount,
+            price_per_unit,
+            payment_token,
+            expires_at,
+        }
+        .publish(&env);
+
+        Ok(listing_id)
+    }
+
+    // Withdraw the unsold remainder of a listing. Fills already made stand.
+    pub fn cancel_listing(env: Env, listing_id: u64) -> Result<(), ContractError> {
+        let mut listing = Self::get_listing(env.clone(), listing_id)?;
+        listing.seller.require_auth();
+
+        if listing.status != ListingStatus::Active {
+            return Err(ContractError::ListingNotActive);
+        }
+
+        listing.status = ListingStatus::Cancelled;
+        Self::release_unsold(&env, &mut listing)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Listing(listing_id), &listing);
+
+        ListingCancelledEvent {
+            listing_id,
+            seller: listing.seller,
+            remaining: listing.remaining,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // Buy the next `amount` credits of a listing, in the order listed.
+    // Payment of `amount * price_per_unit` is pulled from the buyer through
+    // the payment token's allowance and the credits through the seller's
+    // carbon asset allowance. The platform fee, if any, is kept from the
+    // seller's share. If a token sold carries a royalty, the carbon asset
+    // also pulls it from the buyer, on top of the price, through the buyer's
+    // payment token allowance to the carbon asset.
+    pub fn buy(
+        env: Env,
+        buyer: Address,
+        listing_id: u64,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        buyer.require_auth();
+        Self::require_not_halted(&env, TRANSFERS)?;
+
+        let mut listing = Self::get_listing(env.clone(), listing_id)?;
+        if listing.status != ListingStatus::Active {
+            return Err(ContractError::ListingNotActive);
+        }
+        if env.ledger().timestamp() >= listing.expires_at {
+            return Err(ContractError::ListingExpired);
+        }
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if amount > listing.remaining {
+            return Err(ContractError::ExceedsRemaining);
+        }
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+
+        let total_price = amount
+            .checked_mul(listing.price_per_unit)
+            .ok_or(ContractError::InvalidAmount)?;
+        let marketplace = env.current_contract_address();
+        let payment = token::TokenClient::new(&env, &listing.payment_token);
+        if payment.allowance(&buyer, &marketplace) < total_price {
+            return Err(ContractError::InsufficientPaymentAllowance);
+        }
+        let fee = Self::accrue_platform_fee(&env, &listing.payment_token, total_price)?;
+        let net = total_price - fee;
+        payment.transfer_from(&marketplace, &buyer, &listing.seller, &net);
+        if fee > 0 {
+            payment.transfer_from(&marketplace, &buyer, &marketplace, &fee);
+        }
+
+        let token_ids = Self::next_tokens(&listing, amount);
+        Self::call_carbon::<()>(
+            &env,
+            &carbon_asset,
+            "transfer_with_sale_price",
+            (
+                marketplace,
+                listing.seller.clone(),
+                buyer.clone(),
+                token_ids.clone(),
+                listing.price_per_unit,
+                listing.payment_token.clone(),
+            ),
+        );
+
+        listing.remaining -= amount;
+        if listing.remaining == 0 {
+            listing.status = ListingStatus::Filled;
+            // The carbon asset returns sold tokens to Issued
+            listing.token_listed = false;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Listing(listing_id), &listing);
+
+        PurchaseEvent {
+            listing_id,
+            buyer,
+            seller: listing.seller,
+            token_ids,
+            amount,
+            gross: total_price,
+            fee,
+            net,
+            payment_token: listing.payment_token,
+            remaining: listing.remaining,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // ====================================================================
+    // Read-only Accessors
+    // ====================================================================
+
+    pub fn get_listing(env: Env, listing_id: u64) -> Result<Listing, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(ContractError::ListingNotFound)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_carbon_asset(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::CarbonAsset)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_circuit_breaker(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::CircuitBreaker)
+    }
+
+    // ====================================================================
+    // Internal Helpers
+    // ====================================================================
+
+    // The seller must hold each of `token_ids`, none of them already listed
+    // or named twice, and have approved this contract to move them all.
+    // Returns the seller's balance.
+    fn check_tokens(
+        env: &Env,
+        carbon_asset: &Address,
+        seller: &Address,
+        token_ids: &Vec<u32>,
+    ) -> Result<i128, ContractError> {
+        for (i, token_id) in token_ids.iter().enumerate() {
+            if token_ids.first_index_of(token_id) != Some(i as u32) {
+                return Err(ContractError::InvalidAmount);
+            }
+            let owner: Address = Self::call_carbon(env, carbon_asset, "owner_of", (token_id,));
+            if owner != *seller {
+                return Err(ContractError::NotOwner);
+            }
+            let listed: i128 = Self::call_carbon(
+                env,
+                carbon_asset,
+                "listed_amount",
+                (seller.clone(), token_id),
+            );
+            if listed > 0 {
+                return Err(ContractError::TokenListed);
+            }
+        }
+
+        let marketplace = env.current_contract_address();
+        let allowance: i128 = Self::call_carbon(
+            env,
+            carbon_asset,
+            "allowance",
+            (seller.clone(), marketplace),
+        );
+        if allowance < token_ids.len() as i128 {
+            return Err(ContractError::InsufficientAllowance);
+        }
+        let balance: i128 = Self::call_carbon(env, carbon_asset, "balance", (seller.clone(),));
+        Ok(balance)
+    }
+
+    // The next `amount` unsold tokens of `listing`, in the order listed.
+    fn next_tokens(listing: &Listing, amount: i128) -> Vec<u32> {
+        let sold = (listing.amount - listing.remaining) as u32;
+        listing.token_ids.slice(sold..sold + amount as u32)
+    }
+
+    // Hand the unsold tokens back to the seller, returning any flipped to
+    // Listed to Issued unless something else (an admin lock, retirement)
+    // has already moved them out of Listed.
+    fn release_unsold(env: &Env, listing: &mut Listing) -> Result<(), ContractError> {
+        if listing.remaining == 0 {
+            return Ok(());
+        }
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        let marketplace = env.current_contract_address();
+        let unsold = Self::next_tokens(listing, listing.remaining);
+        Self::call_carbon::<()>(
+            env,
+            &carbon_asset,
+            "delist_tokens",
+            (marketplace.clone(), listing.seller.clone(), unsold.clone()),
+        );
+
+        if !listing.token_listed {
+            return Ok(());
+        }
+        listing.token_listed = false;
+        for token_id in unsold.iter() {
+            let status: AssetStatus =
+                Self::call_carbon(env, &carbon_asset, "get_status", (token_id,));
+            if status == AssetStatus::Listed {
+                Self::call_carbon::<()>(
+                    env,
+                    &carbon_asset,
+                    "delist",
+                    (marketplace.clone(), token_id),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        if *caller != Self::get_admin(env.clone())? {
+            return Err(ContractError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    // Queried once per entry point, before any credits move.
+    fn require_not_halted(env: &Env, subsystem: &str) -> Result<(), ContractError> {
+        let Some(breaker) = Self::get_circuit_breaker(env.clone()) else {
+            return Ok(());
+        };
+
+        let halted: bool = env.invoke_contract(
+            &breaker,
+            &Symbol::new(env, "is_halted"),
+            (Symbol::new(env, subsystem),).into_val(env),
+        );
+        if halted {
+            return Err(ContractError::SystemHalted);
+        }
+        Ok(())
+    }
+
+    fn call_carbon<T: TryFromVal<Env, Val>>(
+        env: &Env,
+        carbon_asset: &Address,
+        function: &str,
+        args: impl IntoVal<Env, Vec<Val>>,
+    ) -> T {
+        env.invoke_contract(
+            carbon_asset,
+            &Symbol::new(env, function),
+            args.into_val(env),
+        )
+    }
+
+    // None if the carbon asset call fails; its changes are rolled back.
+    fn try_call_carbon<T: TryFromVal<Env, Val>>(
+        env: &Env,
+        carbon_asset: &Address,
+        function: &str,
+        args: impl IntoVal<Env, Vec<Val>>,
+    ) -> Option<T> {
+        match env.try_invoke_contract::<T, InvokeError>(
+            carbon_asset,
+            &Symbol::new(env, function),
+            args.into_val(env),
+        ) {
+            Ok(Ok(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use soroban_sdk::{contractimpl, token, Address, Env, Vec};
+
+use crate::errors::ContractError;
+use crate::events::{OfferAcceptedEvent, OfferCancelledEvent, OfferCreatedEvent};
+use crate::storage::DataKey;
+use crate::types::{Offer, OfferStatus};
+use crate::{
+    Marketplace, MarketplaceArgs, MarketplaceClient, LISTINGS, MAX_LISTING_TOKENS, TRANSFERS,
+};
+
+// ========================================================================
+// Buyer offers
+// ========================================================================
+
+#[contractimpl]
+impl Marketplace {
+    // Offer to buy exactly `token_ids`, at most `MAX_LISTING_TOKENS`, at
+    // `price_per_unit`, escrowing the full price of the set until the offer
+    // is filled or cancelled. The offer can be accepted until `expires_at`
+    // (exclusive).
+    pub fn create_offer(
+        env: Env,
+        buyer: Address,
+        token_ids: Vec<u32>,
+        price_per_unit: i128,
+        payment_token: Address,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        buyer.require_auth();
+        Self::require_not_halted(&env, LISTINGS)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        if token_ids.len() > MAX_LISTING_TOKENS {
+            return Err(ContractError::BatchTooLarge);
+        }
+        if price_per_unit <= 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidTimeWindow);
+        }
+        let amount = token_ids.len() as i128;
+        let escrowed = amount
+            .checked_mul(price_per_unit)
+            .ok_or(ContractError::InvalidAmount)?;
+
+        // Panics if a token does not exist
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        for (i, token_id) in token_ids.iter().enumerate() {
+            if token_ids.first_index_of(token_id) != Some(i as u32) {
+                return Err(ContractError::InvalidAmount);
+            }
+            let _: Address = Self::call_carbon(&env, &carbon_asset, "owner_of", (token_id,));
+        }
+
+        let marketplace = env.current_contract_address();
+        token::TokenClient::new(&env, &payment_token).transfer(&buyer, &marketplace, &escrowed);
+
+        let offer_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextOfferId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextOfferId, &(offer_id + 1));
+
+        let offer = Offer {
+            offer_id,
+            buyer: buyer.clone(),
+            token_ids: token_ids.clone(),
+            amount,
+            remaining: amount,
+            price_per_unit,
+            payment_token: payment_token.clone(),
+            expires_at,
+            status: OfferStatus::Open,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Offer(offer_id), &offer);
+        for token_id in token_ids.iter() {
+            Self::index_offer(&env, DataKey::OffersByToken(token_id), offer_id);
+        }
+        Self::index_offer(&env, DataKey::OffersByBuyer(buyer.clone()), offer_id);
+
+        OfferCreatedEvent {
+            offer_id,
+            buyer,
+            token_ids,
+            price_per_unit,
+            payment_token,
+            expires_at,
+        }
+        .publish(&env);
+
+        Ok(offer_id)
+    }
+
+    // Sell `token_ids`, all still wanted by the offer, into an open offer.
+    // As with listings, the seller must hold each token, not have it listed,
+    // and have approved the marketplace on the carbon asset. Payment comes
+    // out of the offer's escrow and the tokens settle as a sale at the
+    // offer's price, so if any carries a royalty the carbon asset pulls it
+    // from the buyer through the buyer's payment token allowance to the
+    // carbon asset.
+    pub fn accept_offer(
+        env: Env,
+        seller: Address,
+        offer_id: u64,
+        token_ids: Vec<u32>,
+    ) -> Result<(), ContractError> {
+        seller.require_auth();
+        Self::require_not_halted(&env, TRANSFERS)?;
+
+        let mut offer = Self::get_offer(env.clone(), offer_id)?;
+        if offer.status != OfferStatus::Open {
+            return Err(ContractError::OfferNotActive);
+        }
+        if env.ledger().timestamp() >= offer.expires_at {
+            return Err(ContractError::OfferExpired);
+        }
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        for token_id in token_ids.iter() {
+            // Also rejects a token named twice
+            let Some(i) = offer.token_ids.first_index_of(token_id) else {
+                return Err(ContractError::ExceedsRemaining);
+            };
+            offer.token_ids.remove(i);
+        }
+
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        Self::check_tokens(&env, &carbon_asset, &seller, &token_ids)?;
+
+        let amount = token_ids.len() as i128;
+        let total_price = amount
+            .checked_mul(offer.price_per_unit)
+            .ok_or(ContractError::InvalidAmount)?;
+        offer.remaining -= amount;
+        if offer.remaining == 0 {
+            offer.status = OfferStatus::Filled;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Offer(offer_id), &offer);
+
+        // The platform fee stays behind in the marketplace with the escrow
+        let fee = Self::accrue_platform_fee(&env, &offer.payment_token, total_price)?;
+        let net = total_price - fee;
+        let marketplace = env.current_contract_address();
+        token::TokenClient::new(&env, &offer.payment_token).transfer(&marketplace, &seller, &net);
+        Self::call_carbon::<()>(
+            &env,
+            &carbon_asset,
+            "transfer_with_sale_price",
+            (
+                marketplace,
+                seller.clone(),
+                offer.buyer.clone(),
+                token_ids.clone(),
+                offer.price_per_unit,
+                offer.payment_token.clone(),
+            ),
+        );
+
+        OfferAcceptedEvent {
+            offer_id,
+            seller,
+            buyer: offer.buyer,
+            token_ids,
+            amount,
+            gross: total_price,
+            fee,
+            net,
+            remaining: offer.remaining,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // Withdraw an open offer and refund the escrow for its unfilled
+    // remainder. Buyer-only; this is also how the escrow of an expired offer
+    // is reclaimed.
+    pub fn cancel_offer(env: Env, offer_id: u64) -> Result<(), ContractError> {
+        let mut offer = Self::get_offer(env.clone(), offer_id)?;
+        offer.buyer.require_auth();
+
+        if offer.status != OfferStatus::Open {
+            return Err(ContractError::OfferNotActive);
+        }
+
+        let refunded = offer.remaining * offer.price_per_unit;
+        offer.status = OfferStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Offer(offer_id), &offer);
+
+        let marketplace = env.current_contract_address();
+        token::TokenClient::new(&env, &offer.payment_token).transfer(
+            &marketplace,
+            &offer.buyer,
+            &refunded,
+        );
+
+        OfferCancelledEvent {
+            offer_id,
+            buyer: offer.buyer,
+            refunded,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_offer(env: Env, offer_id: u64) -> Result<Offer, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Offer(offer_id))
+            .ok_or(ContractError::OfferNotFound)
+    }
+
+    // Offers naming `token_id`, oldest first, in any status.
+    pub fn get_offers_by_token(env: Env, token_id: u32, start: u32, limit: u32) -> Vec<Offer> {
+        Self::page_offers(&env, DataKey::OffersByToken(token_id), start, limit)
+    }
+
+    // Offers made by `buyer`, oldest first, in any status.
+    pub fn get_offers_by_buyer(env: Env, buyer: Address, start: u32, limit: u32) -> Vec<Offer> {
+        Self::page_offers(&env, DataKey::OffersByBuyer(buyer), start, limit)
+    }
+}
+
+impl Marketplace {
+    fn index_offer(env: &Env, key: DataKey, offer_id: u64) {
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        ids.push_back(offer_id);
+        env.storage().persistent().set(&key, &ids);
+    }
+
+    fn page_offers(env: &Env, key: DataKey, start: u32, limit: u32) -> Vec<Offer> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        let end = start.saturating_add(limit).min(ids.len());
+
+        let mut offers = Vec::new(env);
+        for i in start..end {
+            let offer: Offer = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Offer(ids.get(i).unwrap()))
+                .unwrap();
+            offers.push_back(offer);
+        }
+        offers
+    }
+}
@@ -0,0 +1,123 @@
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+
+use crate::errors::ContractError;
+use crate::events::PaymentTokenUpdatedEvent;
+use crate::storage::DataKey;
+use crate::types::{ListingStatus, Quote};
+use crate::{Marketplace, MarketplaceArgs, MarketplaceClient};
+
+// ========================================================================
+// Payment tokens
+// ========================================================================
+
+#[contractimpl]
+impl Marketplace {
+    // Accept `payment_token` as the currency of new listings, e.g. USDC or
+    // EURC. Admin-only, like the circuit breaker.
+    pub fn allow_payment_token(
+        env: Env,
+        caller: Address,
+        payment_token: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        let mut tokens = Self::list_payment_tokens(env.clone());
+        if !tokens.contains(&payment_token) {
+            tokens.push_back(payment_token.clone());
+            env.storage()
+                .instance()
+                .set(&DataKey::PaymentTokens, &tokens);
+        }
+
+        PaymentTokenUpdatedEvent {
+            payment_token,
+            allowed: true,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Stop accepting `payment_token` for new listings. Listings already
+    // priced in it can still be bought.
+    pub fn revoke_payment_token(
+        env: Env,
+        caller: Address,
+        payment_token: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        let mut tokens = Self::list_payment_tokens(env.clone());
+        if let Some(index) = tokens.first_index_of(&payment_token) {
+            tokens.remove(index);
+            env.storage()
+                .instance()
+                .set(&DataKey::PaymentTokens, &tokens);
+        }
+
+        PaymentTokenUpdatedEvent {
+            payment_token,
+            allowed: false,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // Payment tokens new listings may be priced in, in the order allowed.
+    pub fn list_payment_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PaymentTokens)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn is_payment_token_allowed(env: Env, payment_token: Address) -> bool {
+        Self::list_payment_tokens(env).contains(&payment_token)
+    }
+
+    // What buying `amount` credits from a listing would cost right now: the
+    // listing's payment token, the total the buyer pays, the royalty within
+    // that total that the carbon asset charges for the tokens `buy` would
+    // deliver, and the platform fee kept from the seller's share.
+    pub fn quote(env: Env, listing_id: u64, amount: i128) -> Result<Quote, ContractError> {
+        let listing = Self::get_listing(env.clone(), listing_id)?;
+        if listing.status != ListingStatus::Active {
+            return Err(ContractError::ListingNotActive);
+        }
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if amount > listing.remaining {
+            return Err(ContractError::ExceedsRemaining);
+        }
+
+        let price = amount
+            .checked_mul(listing.price_per_unit)
+            .ok_or(ContractError::InvalidAmount)?;
+        let carbon_asset = Self::get_carbon_asset(env.clone())?;
+        let mut royalty_total: i128 = 0;
+        for token_id in Self::next_tokens(&listing, amount).iter() {
+            let royalty: Option<(Address, i128)> = Self::call_carbon(
+                &env,
+                &carbon_asset,
+                "get_royalty_info",
+                (token_id, listing.price_per_unit),
+            );
+            royalty_total = royalty_total
+                .checked_add(royalty.map_or(0, |(_, royalty)| royalty))
+                .ok_or(ContractError::InvalidAmount)?;
+        }
+        let total_cost = price
+            .checked_add(royalty_total)
+            .ok_or(ContractError::InvalidAmount)?;
+        let platform_fee = Self::platform_fee_on(&env, price)?;
+        Ok(Quote {
+            payment_token: listing.payment_token,
+            total_cost,
+            royalty: royalty_total,
+            platform_fee,
+            seller_net: price - platform_fee,
+        })
+    }
+}
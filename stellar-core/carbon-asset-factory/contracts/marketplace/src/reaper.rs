@@ -0,0 +1,166 @@
+use soroban_sdk::{contractimpl, token, Address, Env, Vec};
+
+use crate::errors::ContractError;
+use crate::events::{ListingExpiredEvent, ReaperConfigUpdatedEvent, ReaperPaidEvent};
+use crate::storage::DataKey;
+use crate::types::{ListingStatus, ReaperConfig};
+use crate::{Marketplace, MarketplaceArgs, MarketplaceClient};
+
+// Each reaped listing calls back into the carbon asset to release its
+// credits and delist its token, so the number of listings per call is bounded.
+pub const MAX_REAP_BATCH: u32 = 25;
+
+// ========================================================================
+// Expired listing cleanup
+// ========================================================================
+
+#[contractimpl]
+impl Marketplace {
+    // Pay `incentive` of `token` per reaped listing out of the reaper budget.
+    // Admin-only, like the circuit breaker.
+    // The token can only be changed while the budget is empty.
+    pub fn set_reaper_incentive(
+        env: Env,
+        caller: Address,
+        token: Address,
+        incentive: i128,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+        if incentive < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let budget = match Self::get_reaper_config(env.clone()) {
+            Some(config) if config.token != token && config.budget > 0 => {
+                return Err(ContractError::ReaperBudgetInUse);
+            }
+            Some(config) => config.budget,
+            None => 0,
+        };
+        Self::store_reaper_config(
+            &env,
+            &ReaperConfig {
+                token,
+                incentive,
+                budget,
+            },
+        );
+        Ok(())
+    }
+
+    // Anyone may top up the reaper budget in the configured token.
+    pub fn fund_reaper_budget(
+        env: Env,
+        funder: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        funder.require_auth();
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let mut config =
+            Self::get_reaper_config(env.clone()).ok_or(ContractError::NotInitialized)?;
+
+        let marketplace = env.current_contract_address();
+        token::TokenClient::new(&env, &config.token).transfer(&funder, &marketplace, &amount);
+
+        config.budget = config
+            .budget
+            .checked_add(amount)
+            .ok_or(ContractError::InvalidAmount)?;
+        Self::store_reaper_config(&env, &config);
+        Ok(())
+    }
+
+    // Close every listing in `listing_ids` that is still active past its
+    // `expires_at`, returning flipped tokens to Issued. Ids that are unknown,
+    // no longer active or not yet expired are skipped, so racing reapers do
+    // not fail each other. The reaper is paid the incentive per listing
+    // reaped, except for its own listings, capped at what is left of the
+    // budget. Returns the number of listings reaped. Like cancellations, this
+    // is never halted.
+    pub fn reap_expired(
+        env: Env,
+        reaper: Address,
+        listing_ids: Vec<u64>,
+    ) -> Result<u32, ContractError> {
+        reaper.require_auth();
+        if listing_ids.len() > MAX_REAP_BATCH {
+            return Err(ContractError::BatchTooLarge);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut reaped = 0u32;
+        let mut rewarded = 0u32;
+        for listing_id in listing_ids.iter() {
+            let Ok(mut listing) = Self::get_listing(env.clone(), listing_id) else {
+                continue;
+            };
+            if listing.status != ListingStatus::Active || now < listing.expires_at {
+                continue;
+            }
+
+            if listing.seller != reaper {
+                rewarded += 1;
+            }
+            listing.status = ListingStatus::Expired;
+            Self::release_unsold(&env, &mut listing)?;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Listing(listing_id), &listing);
+
+            ListingExpiredEvent {
+                listing_id,
+                seller: listing.seller,
+                remaining: listing.remaining,
+            }
+            .publish(&env);
+            reaped += 1;
+        }
+
+        if reaped == 0 {
+            return Ok(0);
+        }
+        let mut paid = 0;
+        if let Some(mut config) = Self::get_reaper_config(env.clone()) {
+            paid = config
+                .incentive
+                .saturating_mul(rewarded as i128)
+                .min(config.budget);
+            if paid > 0 {
+                config.budget -= paid;
+                env.storage()
+                    .instance()
+                    .set(&DataKey::ReaperConfig, &config);
+                token::TokenClient::new(&env, &config.token).transfer(
+                    &env.current_contract_address(),
+                    &reaper,
+                    &paid,
+                );
+            }
+        }
+
+        ReaperPaidEvent {
+            reaper,
+            reaped,
+            paid,
+        }
+        .publish(&env);
+        Ok(reaped)
+    }
+
+    pub fn get_reaper_config(env: Env) -> Option<ReaperConfig> {
+        env.storage().instance().get(&DataKey::ReaperConfig)
+    }
+
+    fn store_reaper_config(env: &Env, config: &ReaperConfig) {
+        env.storage().instance().set(&DataKey::ReaperConfig, config);
+
+        ReaperConfigUpdatedEvent {
+            token: config.token.clone(),
+            incentive: config.incentive,
+            budget: config.budget,
+        }
+        .publish(env);
+    }
+}
@@ -0,0 +1,22 @@
+use soroban_sdk::{contracttype, Address};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    CarbonAsset,
+    CircuitBreaker,
+    NextListingId,
+    Listing(u64),
+    ReaperConfig,
+    NextAuctionId,
+    Auction(u64),
+    Bid(u64, Address),
+    NextOfferId,
+    Offer(u64),
+    OffersByToken(u32),
+    OffersByBuyer(Address),
+    PaymentTokens,
+    PlatformFee,
+    AccruedFees(Address),
+}
@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+use super::{Marketplace, MarketplaceClient, MIN_LISTING_DURATION};
+use crate::errors::ContractError;
+use carbon_asset::types::CarbonAssetMetadata;
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, BytesN, Env, String};
+
+fn setup<'a>() -> (Env, MarketplaceClient<'a>, CarbonAssetClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.add_marketplace(&admin, &marketplace.address);
+    marketplace.initialize(&admin, &carbon.address);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    carbon.mint(&admin, &seller, &meta);
+    carbon.mint(&admin, &seller, &meta);
+
+    (env, marketplace, carbon, seller)
+}
+
+#[test]
+fn test_initialize_only_once() {
+    let (env, marketplace, carbon, _) = setup();
+
+    assert_eq!(marketplace.get_carbon_asset(), carbon.address);
+    assert_eq!(marketplace.get_admin(), carbon.get_admin());
+    assert_eq!(
+        marketplace.try_initialize(&Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let env = Env::default();
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+    let admin = Address::generate(&env);
+    let carbon_asset = Address::generate(&env);
+
+    // Without the admin's signature the deployment stays unclaimed
+    assert!(marketplace.try_initialize(&admin, &carbon_asset).is_err());
+    assert_eq!(
+        marketplace.try_get_admin(),
+        Err(Ok(ContractError::NotInitialized))
+    );
+
+    env.mock_all_auths();
+    marketplace.initialize(&admin, &carbon_asset);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(marketplace.get_admin(), admin);
+
+    // Only the stored admin configures the marketplace
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        marketplace.try_set_circuit_breaker(&outsider, &Address::generate(&env)),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    marketplace.set_circuit_breaker(&admin, &outsider);
+    assert_eq!(marketplace.get_circuit_breaker(), Some(outsider));
+}
+
+#[test]
+fn test_create_listing_validation() {
+    let (env, marketplace, carbon, seller) = setup();
+    let payment_token = Address::generate(&env);
+    let live_until = env.ledger().sequence() + 100;
+
+    let token_ids = vec![&env, 1];
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &token_ids, &10, &payment_token, &10_000),
+        Err(Ok(ContractError::PaymentTokenNotAllowed))
+    );
+    marketplace.allow_payment_token(&carbon.get_admin(), &payment_token);
+
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &vec![&env], &10, &payment_token, &10_000),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &token_ids, &0, &payment_token, &10_000),
+        Err(Ok(ContractError::InvalidPrice))
+    );
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &token_ids, &10, &payment_token, &0),
+        Err(Ok(ContractError::InvalidTimeWindow))
+    );
+    let too_soon = env.ledger().timestamp() + MIN_LISTING_DURATION - 1;
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &token_ids, &10, &payment_token, &too_soon),
+        Err(Ok(ContractError::InvalidTimeWindow))
+    );
+    assert_eq!(
+        marketplace.try_create_listing(
+            &Address::generate(&env),
+            &token_ids,
+            &10,
+            &payment_token,
+            &10_000
+        ),
+        Err(Ok(ContractError::NotOwner))
+    );
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &token_ids, &10, &payment_token, &10_000),
+        Err(Ok(ContractError::InsufficientAllowance))
+    );
+
+    carbon.approve(&seller, &marketplace.address, &5, &live_until);
+    assert_eq!(
+        marketplace.try_create_listing(&seller, &vec![&env, 1, 1], &10, &payment_token, &10_000),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        marketplace.try_get_listing(&1),
+        Err(Ok(ContractError::ListingNotFound))
+    );
+    assert_eq!(
+        marketplace.try_cancel_listing(&1),
+        Err(Ok(ContractError::ListingNotFound))
+    );
+}
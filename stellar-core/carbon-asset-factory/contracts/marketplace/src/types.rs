@@ -0,0 +1,138 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Mirror of the CarbonAsset status returned by `get_status`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AssetStatus {
+    Issued,
+    Listed,
+    Locked,
+    Retired,
+    Invalidated,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListingStatus {
+    Active,
+    Filled,
+    Cancelled,
+    // Reaped after `expires_at` with credits still unsold.
+    Expired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub listing_id: u64,
+    pub seller: Address,
+    // The tokens for sale, sold in this order.
+    pub token_ids: Vec<u32>,
+    pub amount: i128,
+    pub remaining: i128,
+    pub price_per_unit: i128,
+    pub payment_token: Address,
+    pub status: ListingStatus,
+    // Whether token_ids were flipped to Listed on the carbon asset and the
+    // unsold ones still need to be delisted.
+    pub token_listed: bool,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuctionStatus {
+    Open,
+    Finalized,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub auction_id: u64,
+    pub seller: Address,
+    // The tokens on auction, delivered to winners in this order.
+    pub token_ids: Vec<u32>,
+    pub amount: i128,
+    pub reserve_price: i128,
+    pub bid_token: Address,
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub status: AuctionStatus,
+    // Bidders in order of their first bid; used to break ties.
+    pub bidders: Vec<Address>,
+    // Uniform price paid by every winner, zero until finalized with a sale.
+    pub clearing_price: i128,
+    pub sold: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bid {
+    pub quantity: i128,
+    pub price: i128,
+    pub escrowed: i128,
+    // Credits awarded at finalization.
+    pub filled: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OfferStatus {
+    Open,
+    Filled,
+    Cancelled,
+}
+
+// What buying from a listing would cost the buyer and pay the seller right
+// now, as `buy` would settle it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Quote {
+    pub payment_token: Address,
+    // Paid by the buyer: the price plus `royalty`.
+    pub total_cost: i128,
+    // Charged by the carbon asset on top of the price.
+    pub royalty: i128,
+    // Kept by the marketplace from the seller's share of the price.
+    pub platform_fee: i128,
+    pub seller_net: i128,
+}
+
+// A standing buy order for named tokens, with the full price of the unfilled
+// remainder held in escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Offer {
+    pub offer_id: u64,
+    pub buyer: Address,
+    // The tokens still wanted; each leaves the set once sold into the offer.
+    pub token_ids: Vec<u32>,
+    pub amount: i128,
+    pub remaining: i128,
+    pub price_per_unit: i128,
+    pub payment_token: Address,
+    pub expires_at: u64,
+    pub status: OfferStatus,
+}
+
+// Share of each fill, in basis points of the price, kept from the seller's
+// proceeds for `recipient`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFee {
+    pub bps: u32,
+    pub recipient: Address,
+}
+
+// Incentive paid to whoever reaps expired listings, drawn from a budget held
+// by the marketplace in `token`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReaperConfig {
+    pub token: Address,
+    pub incentive: i128,
+    pub budget: i128,
+}
@@ -0,0 +1,462 @@
+#![cfg(test)]
+
+use carbon_asset::types::CarbonAssetMetadata;
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use marketplace::errors::ContractError;
+use marketplace::types::AuctionStatus;
+use marketplace::{Marketplace, MarketplaceClient, MAX_AUCTION_BIDDERS, MAX_AUCTION_TOKENS};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+const START: u64 = 1_000;
+const END: u64 = 2_000;
+const FUNDS: i128 = 10_000;
+
+fn token_range(env: &Env, first: u32, count: u32) -> Vec<u32> {
+    let mut ids = Vec::new(env);
+    for token_id in first..first + count {
+        ids.push_back(token_id);
+    }
+    ids
+}
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    seller: Address,
+    bidders: [Address; 3],
+    carbon: CarbonAssetClient<'a>,
+    bid_token: TokenClient<'a>,
+    marketplace: MarketplaceClient<'a>,
+}
+
+// Three funded bidders and a seller holding `supply` credits approved for the
+// marketplace. The ledger is left inside the bidding window.
+fn setup<'a>(supply: u32) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.add_marketplace(&admin, &marketplace.address);
+    marketplace.initialize(&admin, &carbon.address);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..supply {
+        carbon.mint(&admin, &seller, &meta);
+    }
+    let live_until = env.ledger().sequence() + 1_000;
+    carbon.approve(
+        &seller,
+        &marketplace.address,
+        &(supply as i128),
+        &live_until,
+    );
+
+    let bid_token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let bidders = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for bidder in bidders.iter() {
+        StellarAssetClient::new(&env, &bid_token_id).mint(bidder, &FUNDS);
+    }
+    let bid_token = TokenClient::new(&env, &bid_token_id);
+    env.ledger().set_timestamp(START);
+
+    Setup {
+        env,
+        admin,
+        seller,
+        bidders,
+        carbon,
+        bid_token,
+        marketplace,
+    }
+}
+
+// Auction the seller's first `amount` tokens.
+fn create_auction(s: &Setup, amount: u32, reserve_price: i128) -> u64 {
+    s.marketplace.create_auction(
+        &s.seller,
+        &token_range(&s.env, 1, amount),
+        &reserve_price,
+        &s.bid_token.address,
+        &START,
+        &END,
+    )
+}
+
+#[test]
+fn test_highest_bids_win_at_clearing_price() {
+    let s = setup(3);
+    let auction_id = create_auction(&s, 3, 10);
+    let [a, b, c] = &s.bidders;
+
+    s.marketplace.place_bid(a, &auction_id, &2, &30);
+    s.marketplace.place_bid(b, &auction_id, &2, &20);
+    s.marketplace.place_bid(c, &auction_id, &1, &5);
+    assert_eq!(s.bid_token.balance(&s.marketplace.address), 105);
+
+    assert_eq!(
+        s.marketplace.try_finalize(&auction_id),
+        Err(Ok(ContractError::AuctionNotEnded))
+    );
+    s.env.ledger().set_timestamp(END);
+    assert_eq!(
+        s.marketplace.try_place_bid(c, &auction_id, &1, &50),
+        Err(Ok(ContractError::AuctionClosed))
+    );
+    s.marketplace.finalize(&auction_id);
+
+    // a fills 2, b takes the last credit; both pay b's price of 20
+    let auction = s.marketplace.get_auction(&auction_id);
+    assert_eq!(auction.status, AuctionStatus::Finalized);
+    assert_eq!(auction.clearing_price, 20);
+    assert_eq!(auction.sold, 3);
+    assert_eq!(s.carbon.balance(a), 2);
+    assert_eq!(s.carbon.balance(b), 1);
+    assert_eq!(s.carbon.balance(c), 0);
+    assert_eq!(s.carbon.balance(&s.seller), 0);
+
+    assert_eq!(s.bid_token.balance(a), FUNDS - 40);
+    assert_eq!(s.bid_token.balance(b), FUNDS - 20);
+    assert_eq!(s.bid_token.balance(c), FUNDS);
+    assert_eq!(s.bid_token.balance(&s.seller), 60);
+    assert_eq!(s.bid_token.balance(&s.marketplace.address), 0);
+    assert_eq!(s.marketplace.get_bid(&auction_id, b).unwrap().filled, 1);
+
+    assert_eq!(
+        s.marketplace.try_finalize(&auction_id),
+        Err(Ok(ContractError::AuctionClosed))
+    );
+}
+
+#[test]
+fn test_ties_at_clearing_price_split_pro_rata() {
+    let s = setup(3);
+    let auction_id = create_auction(&s, 3, 10);
+    let [a, b, c] = &s.bidders;
+
+    s.marketplace.place_bid(a, &auction_id, &2, &15);
+    s.marketplace.place_bid(b, &auction_id, &2, &15);
+    s.marketplace.place_bid(c, &auction_id, &1, &15);
+
+    s.env.ledger().set_timestamp(END);
+    s.marketplace.finalize(&auction_id);
+
+    // 3 credits across 5 demanded: 1, 1 and 0 pro rata, the rounding unit to
+    // the first bidder
+    assert_eq!(s.carbon.balance(a), 2);
+    assert_eq!(s.carbon.balance(b), 1);
+    assert_eq!(s.carbon.balance(c), 0);
+    assert_eq!(s.bid_token.balance(a), FUNDS - 30);
+    assert_eq!(s.bid_token.balance(b), FUNDS - 15);
+    assert_eq!(s.bid_token.balance(c), FUNDS);
+    assert_eq!(s.bid_token.balance(&s.seller), 45);
+    assert_eq!(s.bid_token.balance(&s.marketplace.address), 0);
+}
+
+#[test]
+fn test_no_bids_above_reserve_refunds_everyone() {
+    let s = setup(2);
+    let auction_id = create_auction(&s, 2, 50);
+    let [a, b, c] = &s.bidders;
+
+    s.marketplace.place_bid(a, &auction_id, &1, &49);
+    s.marketplace.place_bid(b, &auction_id, &2, &10);
+    s.marketplace.place_bid(c, &auction_id, &2, &1);
+
+    s.env.ledger().set_timestamp(END);
+    s.marketplace.finalize(&auction_id);
+
+    let auction = s.marketplace.get_auction(&auction_id);
+    assert_eq!(auction.sold, 0);
+    assert_eq!(auction.clearing_price, 0);
+    assert_eq!(s.carbon.balance(&s.seller), 2);
+    for bidder in s.bidders.iter() {
+        assert_eq!(s.bid_token.balance(bidder), FUNDS);
+        assert_eq!(s.carbon.balance(bidder), 0);
+    }
+    assert_eq!(s.bid_token.balance(&s.marketplace.address), 0);
+}
+
+#[test]
+fn test_bidder_raises_own_bid() {
+    let s = setup(2);
+    let auction_id = create_auction(&s, 2, 10);
+    let [a, b, _] = &s.bidders;
+
+    s.marketplace.place_bid(a, &auction_id, &1, &10);
+    s.marketplace.place_bid(b, &auction_id, &2, &12);
+    assert_eq!(s.bid_token.balance(a), FUNDS - 10);
+
+    // Raising only escrows the difference and keeps a's place in line
+    s.marketplace.place_bid(a, &auction_id, &2, &20);
+    assert_eq!(s.bid_token.balance(a), FUNDS - 40);
+    assert_eq!(
+        s.marketplace.try_place_bid(a, &auction_id, &2, &19),
+        Err(Ok(ContractError::BidTooLow))
+    );
+    let auction = s.marketplace.get_auction(&auction_id);
+    assert_eq!(auction.bidders.len(), 2);
+    assert_eq!(auction.bidders.get(0).unwrap(), a.clone());
+
+    s.env.ledger().set_timestamp(END);
+    s.marketplace.finalize(&auction_id);
+
+    assert_eq!(s.carbon.balance(a), 2);
+    assert_eq!(s.carbon.balance(b), 0);
+    assert_eq!(s.bid_token.balance(a), FUNDS - 40);
+    assert_eq!(s.bid_token.balance(b), FUNDS);
+    assert_eq!(s.bid_token.balance(&s.seller), 40);
+}
+
+#[test]
+fn test_auction_validation() {
+    let s = setup(2);
+    let [a, _, _] = &s.bidders;
+    let bid_token = &s.bid_token.address;
+
+    let tokens = vec![&s.env, 1, 2];
+    assert_eq!(
+        s.marketplace
+            .try_create_auction(&s.seller, &tokens, &10, bid_token, &END, &START),
+        Err(Ok(ContractError::InvalidTimeWindow))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_create_auction(&s.seller, &tokens, &0, bid_token, &START, &END),
+        Err(Ok(ContractError::InvalidPrice))
+    );
+    assert_eq!(
+        s.marketplace.try_create_auction(
+            &s.seller,
+            &Vec::new(&s.env),
+            &10,
+            bid_token,
+            &START,
+            &END
+        ),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        s.marketplace.try_create_auction(
+            &s.seller,
+            &vec![&s.env, 1, 1],
+            &10,
+            bid_token,
+            &START,
+            &END
+        ),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        s.marketplace.try_create_auction(
+            &s.seller,
+            &token_range(&s.env, 1, MAX_AUCTION_TOKENS + 1),
+            &10,
+            bid_token,
+            &START,
+            &END
+        ),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+
+    let auction_id =
+        s.marketplace
+            .create_auction(&s.seller, &tokens, &10, bid_token, &(START + 10), &END);
+    assert_eq!(s.carbon.listed_amount(&s.seller, &1), 1);
+    assert_eq!(
+        s.marketplace
+            .try_create_auction(&s.seller, &tokens, &10, bid_token, &START, &END),
+        Err(Ok(ContractError::TokenListed))
+    );
+    assert_eq!(
+        s.marketplace.try_place_bid(a, &auction_id, &1, &10),
+        Err(Ok(ContractError::AuctionClosed))
+    );
+    s.env.ledger().set_timestamp(START + 10);
+    assert_eq!(
+        s.marketplace.try_place_bid(a, &auction_id, &3, &10),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        s.marketplace.try_get_auction(&99),
+        Err(Ok(ContractError::AuctionNotFound))
+    );
+}
+
+#[test]
+fn test_winners_receive_the_auctioned_tokens() {
+    let s = setup(4);
+    let [a, b, _] = &s.bidders;
+    let auction_id = s.marketplace.create_auction(
+        &s.seller,
+        &vec![&s.env, 3, 1],
+        &10,
+        &s.bid_token.address,
+        &START,
+        &END,
+    );
+    assert_eq!(s.marketplace.get_auction(&auction_id).amount, 2);
+
+    // The auctioned tokens are reserved; the seller's others stay free
+    assert_eq!(s.carbon.listed_amount(&s.seller, &3), 1);
+    assert_eq!(s.carbon.free_balance(&s.seller), 2);
+    assert_eq!(
+        s.carbon.try_transfer_multi(&s.seller, a, &vec![&s.env, 3]),
+        Err(Ok(carbon_asset::errors::ContractError::TokenListed))
+    );
+
+    s.marketplace.place_bid(a, &auction_id, &1, &20);
+    s.marketplace.place_bid(b, &auction_id, &1, &30);
+    s.env.ledger().set_timestamp(END);
+    s.marketplace.finalize(&auction_id);
+
+    // Tokens go out in the order auctioned, to bidders in the order they bid
+    assert_eq!(s.carbon.owner_of(&3), a.clone());
+    assert_eq!(s.carbon.owner_of(&1), b.clone());
+    assert_eq!(s.carbon.owner_of(&2), s.seller);
+    assert_eq!(s.carbon.owner_of(&4), s.seller);
+    assert_eq!(s.carbon.listed_amount(&s.seller, &3), 0);
+    assert_eq!(s.bid_token.balance(&s.seller), 40);
+}
+
+#[test]
+fn test_failed_settlement_refunds_only_that_winner() {
+    let s = setup(3);
+    let auction_id = create_auction(&s, 3, 10);
+    let [a, b, c] = &s.bidders;
+
+    // b wins token 2, which carries a royalty b never approved
+    let developer = Address::generate(&s.env);
+    s.carbon.set_royalty(&s.admin, &2, &developer, &500);
+    s.marketplace.place_bid(a, &auction_id, &1, &300);
+    s.marketplace.place_bid(b, &auction_id, &1, &200);
+    s.marketplace.place_bid(c, &auction_id, &1, &150);
+
+    s.env.ledger().set_timestamp(END);
+    s.marketplace.finalize(&auction_id);
+
+    let auction = s.marketplace.get_auction(&auction_id);
+    assert_eq!(auction.status, AuctionStatus::Finalized);
+    assert_eq!(auction.clearing_price, 150);
+    assert_eq!(auction.sold, 2);
+    assert_eq!(s.carbon.owner_of(&1), a.clone());
+    assert_eq!(s.carbon.owner_of(&3), c.clone());
+    assert_eq!(s.marketplace.get_bid(&auction_id, b).unwrap().filled, 0);
+
+    // b's token goes back to the seller, free to sell again
+    assert_eq!(s.carbon.owner_of(&2), s.seller);
+    assert_eq!(s.carbon.listed_amount(&s.seller, &2), 0);
+    assert_eq!(s.carbon.free_balance(&s.seller), 1);
+
+    assert_eq!(s.bid_token.balance(a), FUNDS - 150);
+    assert_eq!(s.bid_token.balance(b), FUNDS);
+    assert_eq!(s.bid_token.balance(c), FUNDS - 150);
+    assert_eq!(s.bid_token.balance(&s.seller), 300);
+    assert_eq!(s.bid_token.balance(&developer), 0);
+    assert_eq!(s.bid_token.balance(&s.marketplace.address), 0);
+}
+
+#[test]
+fn test_cancel_auction_releases_tokens() {
+    let s = setup(2);
+    let [a, _, _] = &s.bidders;
+    let cancelled = create_auction(&s, 2, 10);
+
+    s.marketplace.cancel_auction(&cancelled);
+    assert_eq!(
+        s.marketplace.get_auction(&cancelled).status,
+        AuctionStatus::Cancelled
+    );
+    assert_eq!(s.carbon.free_balance(&s.seller), 2);
+    assert_eq!(
+        s.marketplace.try_place_bid(a, &cancelled, &1, &10),
+        Err(Ok(ContractError::AuctionClosed))
+    );
+    assert_eq!(
+        s.marketplace.try_cancel_auction(&cancelled),
+        Err(Ok(ContractError::AuctionClosed))
+    );
+
+    // The tokens can be auctioned again, but not withdrawn once bid on
+    let auction_id = create_auction(&s, 2, 10);
+    s.marketplace.place_bid(a, &auction_id, &1, &10);
+    assert_eq!(
+        s.marketplace.try_cancel_auction(&auction_id),
+        Err(Ok(ContractError::AuctionHasBids))
+    );
+}
+
+// An auction of the most tokens allowed, each with a royalty, finalizes with
+// the most bidders allowed and a platform fee within the network's default
+// per-transaction limits.
+#[test]
+fn test_full_auction_fits_network_limits() {
+    let s = setup(MAX_AUCTION_TOKENS);
+    s.marketplace
+        .set_platform_fee(&s.admin, &250, &Address::generate(&s.env));
+    let auction_id = create_auction(&s, MAX_AUCTION_TOKENS, 10);
+    let developer = Address::generate(&s.env);
+    for token_id in token_range(&s.env, 1, MAX_AUCTION_TOKENS).iter() {
+        s.carbon.set_royalty(&s.admin, &token_id, &developer, &500);
+    }
+
+    // As many bidders as an auction takes; the first three win one each
+    let live_until = s.env.ledger().sequence() + 1_000;
+    let mut bidders = Vec::new(&s.env);
+    for i in 0..MAX_AUCTION_BIDDERS {
+        let bidder = Address::generate(&s.env);
+        StellarAssetClient::new(&s.env, &s.bid_token.address).mint(&bidder, &FUNDS);
+        s.bid_token
+            .approve(&bidder, &s.carbon.address, &FUNDS, &live_until);
+        let price = if i < MAX_AUCTION_TOKENS { 100 } else { 50 };
+        s.marketplace.place_bid(&bidder, &auction_id, &1, &price);
+        bidders.push_back(bidder);
+    }
+    let late = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.bid_token.address).mint(&late, &FUNDS);
+    assert_eq!(
+        s.marketplace.try_place_bid(&late, &auction_id, &1, &100),
+        Err(Ok(ContractError::TooManyBidders))
+    );
+
+    s.env.ledger().set_timestamp(END);
+    s.marketplace.finalize(&auction_id);
+
+    assert_eq!(
+        s.marketplace.get_auction(&auction_id).sold,
+        MAX_AUCTION_TOKENS as i128
+    );
+    for token_id in token_range(&s.env, 1, MAX_AUCTION_TOKENS).iter() {
+        assert_eq!(
+            s.carbon.owner_of(&token_id),
+            bidders.get(token_id - 1).unwrap()
+        );
+    }
+    assert_eq!(
+        s.bid_token.balance(&developer),
+        MAX_AUCTION_TOKENS as i128 * 5
+    );
+}
@@ -0,0 +1,243 @@
+#![cfg(test)]
+
+use carbon_asset::types::CarbonAssetMetadata;
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use marketplace::errors::ContractError;
+use marketplace::types::PlatformFee;
+use marketplace::{Marketplace, MarketplaceClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+const EXPIRES_AT: u64 = 10_000;
+const FUNDS: i128 = 10_000;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    seller: Address,
+    buyer: Address,
+    treasury: Address,
+    carbon: CarbonAssetClient<'a>,
+    usdc: TokenClient<'a>,
+    eurc: TokenClient<'a>,
+    marketplace: MarketplaceClient<'a>,
+}
+
+// A seller holding `supply` credits approved for the marketplace and a buyer
+// funded in two allowed payment tokens.
+fn setup<'a>(supply: u32) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.add_marketplace(&admin, &marketplace.address);
+    marketplace.initialize(&admin, &carbon.address);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..supply {
+        carbon.mint(&admin, &seller, &meta);
+    }
+    let live_until = env.ledger().sequence() + 1_000;
+    carbon.approve(
+        &seller,
+        &marketplace.address,
+        &(supply as i128),
+        &live_until,
+    );
+
+    let usdc = payment_token(&env, &admin, &buyer, &marketplace);
+    let eurc = payment_token(&env, &admin, &buyer, &marketplace);
+
+    Setup {
+        env,
+        admin,
+        seller,
+        buyer,
+        treasury,
+        carbon,
+        usdc,
+        eurc,
+        marketplace,
+    }
+}
+
+// A payment token allowed on the marketplace, with the buyer funded and the
+// marketplace approved to pull all of it.
+fn payment_token<'a>(
+    env: &Env,
+    admin: &Address,
+    buyer: &Address,
+    marketplace: &MarketplaceClient,
+) -> TokenClient<'a> {
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &token_id).mint(buyer, &FUNDS);
+    marketplace.allow_payment_token(admin, &token_id);
+    let token = TokenClient::new(env, &token_id);
+    let live_until = env.ledger().sequence() + 1_000;
+    token.approve(buyer, &marketplace.address, &FUNDS, &live_until);
+    token
+}
+
+// List `amount` credits from `token_id` up.
+fn create_listing(s: &Setup, token_id: u32, amount: u32, price: i128, token: &Address) -> u64 {
+    let mut token_ids = Vec::new(&s.env);
+    for listed in token_id..token_id + amount {
+        token_ids.push_back(listed);
+    }
+    s.marketplace
+        .create_listing(&s.seller, &token_ids, &price, token, &EXPIRES_AT)
+}
+
+#[test]
+fn test_fee_math_on_partial_fills() {
+    let s = setup(5);
+    s.marketplace.set_platform_fee(&s.admin, &250, &s.treasury);
+    let listing_id = create_listing(&s, 1, 3, 101, &s.usdc.address);
+
+    // 2.5% of 101 rounds down to 2, of 202 to 5
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    assert_eq!(s.carbon.owner_of(&1), s.buyer);
+    assert_eq!(s.usdc.balance(&s.seller), 99);
+    let quote = s.marketplace.quote(&listing_id, &2);
+    assert_eq!((quote.total_cost, quote.royalty), (202, 0));
+    assert_eq!((quote.platform_fee, quote.seller_net), (5, 197));
+    s.marketplace.buy(&s.buyer, &listing_id, &2);
+    assert_eq!(s.usdc.balance(&s.seller), 99 + 197);
+    assert_eq!(s.usdc.balance(&s.buyer), FUNDS - 303);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.usdc.address), 7);
+    assert_eq!(s.usdc.balance(&s.marketplace.address), 7);
+
+    // Accepted offers pay the fee out of the escrow, which keeps it
+    let unsold = s.carbon.tokens_of_owner(&s.seller);
+    let offer_id =
+        s.marketplace
+            .create_offer(&s.buyer, &unsold, &101, &s.usdc.address, &EXPIRES_AT);
+    s.marketplace
+        .accept_offer(&s.seller, &offer_id, &unsold.slice(0..1));
+    assert_eq!(s.usdc.balance(&s.seller), 296 + 99);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.usdc.address), 9);
+    assert_eq!(s.usdc.balance(&s.marketplace.address), 9 + 101);
+    assert_eq!(s.carbon.balance(&s.buyer), 4);
+}
+
+#[test]
+fn test_auction_settlements_pay_the_platform_fee() {
+    let s = setup(2);
+    s.marketplace.set_platform_fee(&s.admin, &250, &s.treasury);
+    let mut token_ids = Vec::new(&s.env);
+    token_ids.push_back(1);
+    token_ids.push_back(2);
+    let auction_id =
+        s.marketplace
+            .create_auction(&s.seller, &token_ids, &10, &s.usdc.address, &0, &100);
+    s.marketplace.place_bid(&s.buyer, &auction_id, &2, &400);
+
+    // 2.5% of the 800 proceeds stays in the marketplace
+    s.env.ledger().set_timestamp(100);
+    s.marketplace.finalize(&auction_id);
+    assert_eq!(s.carbon.balance(&s.buyer), 2);
+    assert_eq!(s.usdc.balance(&s.seller), 780);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.usdc.address), 20);
+    assert_eq!(s.usdc.balance(&s.marketplace.address), 20);
+}
+
+#[test]
+fn test_platform_fee_is_admin_only_and_capped() {
+    let s = setup(1);
+    assert_eq!(s.marketplace.get_platform_fee(), None);
+    assert_eq!(
+        s.marketplace
+            .try_set_platform_fee(&s.seller, &100, &s.treasury),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_set_platform_fee(&s.admin, &251, &s.treasury),
+        Err(Ok(ContractError::FeeTooHigh))
+    );
+
+    s.marketplace.set_platform_fee(&s.admin, &250, &s.treasury);
+    assert_eq!(
+        s.marketplace.get_platform_fee(),
+        Some(PlatformFee {
+            bps: 250,
+            recipient: s.treasury.clone(),
+        })
+    );
+}
+
+#[test]
+fn test_zero_fee_mode_pays_seller_in_full() {
+    let s = setup(2);
+
+    // No fee configured
+    let listing_id = create_listing(&s, 1, 1, 101, &s.usdc.address);
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    assert_eq!(s.usdc.balance(&s.seller), 101);
+
+    // A fee of 0 bps takes nothing either
+    s.marketplace.set_platform_fee(&s.admin, &0, &s.treasury);
+    let listing_id = create_listing(&s, 2, 1, 101, &s.usdc.address);
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    assert_eq!(s.usdc.balance(&s.seller), 202);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.usdc.address), 0);
+    assert_eq!(s.usdc.balance(&s.marketplace.address), 0);
+    assert_eq!(s.marketplace.withdraw_fees(&s.usdc.address, &s.treasury), 0);
+}
+
+#[test]
+fn test_withdraw_fees_across_payment_tokens() {
+    let s = setup(2);
+    s.marketplace.set_platform_fee(&s.admin, &200, &s.treasury);
+    let usd_listing = create_listing(&s, 1, 1, 1_000, &s.usdc.address);
+    let eur_listing = create_listing(&s, 2, 1, 500, &s.eurc.address);
+    s.marketplace.buy(&s.buyer, &usd_listing, &1);
+    s.marketplace.buy(&s.buyer, &eur_listing, &1);
+
+    // Fees accrue separately per token
+    assert_eq!(s.marketplace.get_accrued_fees(&s.usdc.address), 20);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.eurc.address), 10);
+    assert_eq!(s.usdc.balance(&s.seller), 980);
+    assert_eq!(s.eurc.balance(&s.seller), 490);
+
+    // Fees accrued under an earlier recipient go to the current one
+    let new_treasury = Address::generate(&s.env);
+    s.marketplace
+        .set_platform_fee(&s.admin, &200, &new_treasury);
+    let vault = Address::generate(&s.env);
+    assert_eq!(s.marketplace.withdraw_fees(&s.usdc.address, &vault), 20);
+    assert_eq!(s.env.auths()[0].0, new_treasury);
+    assert_eq!(s.usdc.balance(&vault), 20);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.usdc.address), 0);
+    assert_eq!(s.marketplace.get_accrued_fees(&s.eurc.address), 10);
+
+    assert_eq!(
+        s.marketplace.withdraw_fees(&s.eurc.address, &new_treasury),
+        10
+    );
+    assert_eq!(s.eurc.balance(&new_treasury), 10);
+    assert_eq!(s.eurc.balance(&s.marketplace.address), 0);
+    assert_eq!(s.marketplace.withdraw_fees(&s.usdc.address, &vault), 0);
+    assert_eq!(s.usdc.balance(&vault), 20);
+}
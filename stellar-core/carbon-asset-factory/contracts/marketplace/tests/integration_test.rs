@@ -0,0 +1,454 @@
+#![cfg(test)]
+
+use carbon_asset::types::{AssetStatus, CarbonAssetMetadata};
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use marketplace::errors::ContractError;
+use marketplace::types::{ListingStatus, Quote};
+use marketplace::{Marketplace, MarketplaceClient, MAX_LISTING_TOKENS};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+const EXPIRES_AT: u64 = 10_000;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    seller: Address,
+    buyer: Address,
+    carbon: CarbonAssetClient<'a>,
+    payment: TokenClient<'a>,
+    marketplace: MarketplaceClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.add_marketplace(&admin, &marketplace.address);
+    marketplace.initialize(&admin, &carbon.address);
+
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &10_000);
+    let payment = TokenClient::new(&env, &payment_id);
+    marketplace.allow_payment_token(&admin, &payment_id);
+
+    Setup {
+        env,
+        admin,
+        seller,
+        buyer,
+        carbon,
+        payment,
+        marketplace,
+    }
+}
+
+// Mint `count` credits to the seller and approve the marketplace for them.
+fn mint_to_seller(s: &Setup, count: u32) -> u32 {
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&s.env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&s.env, &[7u8; 32]),
+    };
+    let first_token_id = s.carbon.mint(&s.admin, &s.seller, &meta);
+    for _ in 1..count {
+        s.carbon.mint(&s.admin, &s.seller, &meta);
+    }
+    let live_until = s.env.ledger().sequence() + 1_000;
+    s.carbon.approve(
+        &s.seller,
+        &s.marketplace.address,
+        &(count as i128),
+        &live_until,
+    );
+    first_token_id
+}
+
+// `count` consecutive token ids starting at `first`.
+fn token_range(s: &Setup, first: u32, count: u32) -> Vec<u32> {
+    let mut token_ids = Vec::new(&s.env);
+    for token_id in first..first + count {
+        token_ids.push_back(token_id);
+    }
+    token_ids
+}
+
+fn approve_payment(s: &Setup, amount: i128) {
+    let live_until = s.env.ledger().sequence() + 1_000;
+    s.payment
+        .approve(&s.buyer, &s.marketplace.address, &amount, &live_until);
+}
+
+#[test]
+fn test_partial_fills_until_filled() {
+    let s = setup();
+    let token_id = mint_to_seller(&s, 3);
+    let listing_id = s.marketplace.create_listing(
+        &s.seller,
+        &token_range(&s, token_id, 3),
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    // The whole balance is listed, so the tokens are flipped to Listed
+    for listed in token_range(&s, token_id, 3).iter() {
+        assert_eq!(s.carbon.get_status(&listed), AssetStatus::Listed);
+    }
+
+    approve_payment(&s, 300);
+    s.marketplace.buy(&s.buyer, &listing_id, &2);
+    let listing = s.marketplace.get_listing(&listing_id);
+    assert_eq!(listing.remaining, 1);
+    assert_eq!(listing.status, ListingStatus::Active);
+    assert_eq!(s.carbon.balance(&s.buyer), 2);
+    assert_eq!(s.carbon.balance(&s.seller), 1);
+    assert_eq!(s.payment.balance(&s.seller), 200);
+    assert_eq!(s.payment.balance(&s.buyer), 9_800);
+
+    // The first two listed tokens were sold and are returned to Issued
+    assert_eq!(s.carbon.owner_of(&token_id), s.buyer);
+    assert_eq!(s.carbon.owner_of(&(token_id + 1)), s.buyer);
+    assert_eq!(s.carbon.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(s.carbon.get_status(&(token_id + 2)), AssetStatus::Listed);
+
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &listing_id, &2),
+        Err(Ok(ContractError::ExceedsRemaining))
+    );
+
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    let listing = s.marketplace.get_listing(&listing_id);
+    assert_eq!(listing.remaining, 0);
+    assert_eq!(listing.status, ListingStatus::Filled);
+    assert_eq!(s.carbon.owner_of(&(token_id + 2)), s.buyer);
+    assert_eq!(s.carbon.get_status(&(token_id + 2)), AssetStatus::Issued);
+    assert_eq!(s.carbon.balance(&s.buyer), 3);
+    assert_eq!(s.payment.balance(&s.seller), 300);
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &listing_id, &1),
+        Err(Ok(ContractError::ListingNotActive))
+    );
+}
+
+#[test]
+fn test_listings_settle_in_allowed_payment_tokens() {
+    let s = setup();
+    let first = mint_to_seller(&s, 2);
+    let eurc_id = s
+        .env
+        .register_stellar_asset_contract_v2(s.admin.clone())
+        .address();
+    StellarAssetClient::new(&s.env, &eurc_id).mint(&s.buyer, &10_000);
+    let eurc = TokenClient::new(&s.env, &eurc_id);
+
+    assert_eq!(
+        s.marketplace.try_create_listing(
+            &s.seller,
+            &vec![&s.env, first],
+            &250,
+            &eurc_id,
+            &EXPIRES_AT
+        ),
+        Err(Ok(ContractError::PaymentTokenNotAllowed))
+    );
+    assert_eq!(
+        s.marketplace.try_allow_payment_token(&s.seller, &eurc_id),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    s.marketplace.allow_payment_token(&s.admin, &eurc_id);
+    assert_eq!(
+        s.marketplace.list_payment_tokens(),
+        vec![&s.env, s.payment.address.clone(), eurc_id.clone()]
+    );
+
+    let usd_listing = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, first],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+    let eur_listing = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, first + 1],
+        &250,
+        &eurc_id,
+        &EXPIRES_AT,
+    );
+    assert_eq!(
+        s.marketplace.get_listing(&eur_listing).payment_token,
+        eurc_id
+    );
+
+    // The royalty is charged on top of the price, in the listing's currency
+    let developer = Address::generate(&s.env);
+    s.carbon
+        .set_royalty(&s.admin, &(first + 1), &developer, &500);
+    assert_eq!(
+        s.marketplace.quote(&usd_listing, &1),
+        Quote {
+            payment_token: s.payment.address.clone(),
+            total_cost: 100,
+            royalty: 0,
+            platform_fee: 0,
+            seller_net: 100,
+        }
+    );
+    let quote = s.marketplace.quote(&eur_listing, &1);
+    assert_eq!(quote.payment_token, eurc_id);
+    assert_eq!((quote.total_cost, quote.royalty), (262, 12));
+    let (total_cost, fee) = (quote.total_cost, quote.royalty);
+    assert_eq!(
+        s.marketplace.try_quote(&eur_listing, &2),
+        Err(Ok(ContractError::ExceedsRemaining))
+    );
+
+    let live_until = s.env.ledger().sequence() + 1_000;
+    eurc.approve(&s.buyer, &s.marketplace.address, &250, &live_until);
+    eurc.approve(&s.buyer, &s.carbon.address, &fee, &live_until);
+    s.marketplace.buy(&s.buyer, &eur_listing, &1);
+    assert_eq!(s.carbon.owner_of(&(first + 1)), s.buyer);
+    assert_eq!(s.carbon.owner_of(&first), s.seller);
+    assert_eq!(eurc.balance(&s.buyer), 10_000 - total_cost);
+    assert_eq!(eurc.balance(&s.seller), 250);
+    assert_eq!(eurc.balance(&developer), fee);
+    assert_eq!(s.payment.balance(&s.buyer), 10_000);
+
+    // Revoking stops new listings only; the open one still sells
+    s.marketplace
+        .revoke_payment_token(&s.admin, &s.payment.address);
+    assert_eq!(s.marketplace.list_payment_tokens(), vec![&s.env, eurc_id]);
+    approve_payment(&s, 100);
+    s.marketplace.buy(&s.buyer, &usd_listing, &1);
+    assert_eq!(s.carbon.owner_of(&first), s.buyer);
+    assert_eq!(s.payment.balance(&s.seller), 100);
+}
+
+#[test]
+fn test_cancel_with_open_partial_fill() {
+    let s = setup();
+    let token_id = mint_to_seller(&s, 3);
+    let listing_id = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, token_id + 2, token_id, token_id + 1],
+        &50,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+    assert_eq!(s.carbon.get_status(&(token_id + 2)), AssetStatus::Listed);
+
+    approve_payment(&s, 50);
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    assert_eq!(s.carbon.owner_of(&(token_id + 2)), s.buyer);
+    assert_eq!(s.carbon.get_status(&(token_id + 2)), AssetStatus::Issued);
+
+    // The unsold credits stay locked with the seller
+    assert_eq!(s.carbon.listed_amount(&s.seller, &token_id), 1);
+    assert_eq!(
+        s.carbon.try_transfer(&s.seller, &s.buyer, &1),
+        Err(Ok(carbon_asset::errors::ContractError::TransferNotAllowed))
+    );
+
+    s.marketplace.cancel_listing(&listing_id);
+    let listing = s.marketplace.get_listing(&listing_id);
+    assert_eq!(listing.status, ListingStatus::Cancelled);
+    assert_eq!(listing.remaining, 2);
+    assert!(!listing.token_listed);
+    assert_eq!(s.carbon.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(s.carbon.get_status(&(token_id + 1)), AssetStatus::Issued);
+
+    // The completed fill stands; the remainder is no longer for sale
+    assert_eq!(s.carbon.balance(&s.buyer), 1);
+    assert_eq!(s.carbon.balance(&s.seller), 2);
+    assert_eq!(s.payment.balance(&s.seller), 50);
+    assert_eq!(s.carbon.listed_amount(&s.seller, &token_id), 0);
+    assert_eq!(s.carbon.free_balance(&s.seller), 2);
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &listing_id, &1),
+        Err(Ok(ContractError::ListingNotActive))
+    );
+    assert_eq!(
+        s.marketplace.try_cancel_listing(&listing_id),
+        Err(Ok(ContractError::ListingNotActive))
+    );
+}
+
+#[test]
+fn test_buy_rejected_without_payment_allowance() {
+    let s = setup();
+    let token_id = mint_to_seller(&s, 2);
+    let listing_id = s.marketplace.create_listing(
+        &s.seller,
+        &token_range(&s, token_id, 2),
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    approve_payment(&s, 199);
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &listing_id, &2),
+        Err(Ok(ContractError::InsufficientPaymentAllowance))
+    );
+    assert_eq!(s.marketplace.get_listing(&listing_id).remaining, 2);
+    assert_eq!(s.carbon.balance(&s.seller), 2);
+    assert_eq!(s.payment.balance(&s.buyer), 10_000);
+
+    approve_payment(&s, 200);
+    s.marketplace.buy(&s.buyer, &listing_id, &2);
+    assert_eq!(s.carbon.owner_of(&token_id), s.buyer);
+    assert_eq!(s.carbon.balance(&s.buyer), 2);
+}
+
+#[test]
+fn test_buy_delivers_exactly_the_listed_tokens() {
+    let s = setup();
+    let first = mint_to_seller(&s, 4);
+    let listing_id = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, first + 3, first + 1],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    // The seller holds credits ahead of the listed ones; the buyer still
+    // gets the listed tokens, in the order listed
+    approve_payment(&s, 200);
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    assert_eq!(s.carbon.owner_of(&(first + 3)), s.buyer);
+    assert_eq!(s.carbon.owner_of(&first), s.seller);
+    assert_eq!(s.carbon.owner_of(&(first + 1)), s.seller);
+
+    // The seller can move the unlisted credits but not the listed one, so
+    // the next fill still delivers it
+    s.carbon.transfer(&s.seller, &s.admin, &1);
+    assert_eq!(s.carbon.owner_of(&first), s.admin);
+    assert_eq!(
+        s.carbon
+            .try_transfer_multi(&s.seller, &s.admin, &vec![&s.env, first + 1]),
+        Err(Ok(carbon_asset::errors::ContractError::TokenListed))
+    );
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    let listing = s.marketplace.get_listing(&listing_id);
+    assert_eq!(listing.status, ListingStatus::Filled);
+    assert_eq!(s.carbon.owner_of(&(first + 1)), s.buyer);
+    assert_eq!(s.carbon.owner_of(&(first + 2)), s.seller);
+    assert_eq!(s.carbon.balance(&s.buyer), 2);
+    assert_eq!(s.carbon.listed_amount(&s.seller, &(first + 1)), 0);
+    assert_eq!(s.carbon.free_balance(&s.seller), 1);
+}
+
+#[test]
+fn test_tokens_are_listed_once() {
+    let s = setup();
+    let first = mint_to_seller(&s, 3);
+    s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, first, first + 1],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    assert_eq!(
+        s.marketplace.try_create_listing(
+            &s.seller,
+            &vec![&s.env, first + 2, first + 1],
+            &100,
+            &s.payment.address,
+            &EXPIRES_AT,
+        ),
+        Err(Ok(ContractError::TokenListed))
+    );
+    assert_eq!(
+        s.marketplace.try_create_listing(
+            &s.seller,
+            &vec![&s.env, first + 2, first + 2],
+            &100,
+            &s.payment.address,
+            &EXPIRES_AT,
+        ),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        s.marketplace.try_create_listing(
+            &s.seller,
+            &token_range(&s, first, MAX_LISTING_TOKENS + 1),
+            &100,
+            &s.payment.address,
+            &EXPIRES_AT,
+        ),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+}
+
+#[test]
+fn test_partial_listing_keeps_token_issued() {
+    let s = setup();
+    let token_id = mint_to_seller(&s, 3);
+    let listing_id = s.marketplace.create_listing(
+        &s.seller,
+        &token_range(&s, token_id, 2),
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    assert!(!s.marketplace.get_listing(&listing_id).token_listed);
+    assert_eq!(s.carbon.get_status(&token_id), AssetStatus::Issued);
+}
+
+// A full listing is created, bought out in one fill and another cancelled
+// within the network's default per-transaction limits, with a royalty on
+// every token.
+#[test]
+fn test_full_listing_fits_network_limits() {
+    let s = setup();
+    let first = mint_to_seller(&s, 2 * MAX_LISTING_TOKENS);
+    let developer = Address::generate(&s.env);
+    for token_id in token_range(&s, first, 2 * MAX_LISTING_TOKENS).iter() {
+        s.carbon.set_royalty(&s.admin, &token_id, &developer, &500);
+    }
+
+    let sold = token_range(&s, first, MAX_LISTING_TOKENS);
+    let listing_id =
+        s.marketplace
+            .create_listing(&s.seller, &sold, &100, &s.payment.address, &EXPIRES_AT);
+    let unsold = token_range(&s, first + MAX_LISTING_TOKENS, MAX_LISTING_TOKENS);
+    let cancelled =
+        s.marketplace
+            .create_listing(&s.seller, &unsold, &100, &s.payment.address, &EXPIRES_AT);
+
+    let amount = MAX_LISTING_TOKENS as i128;
+    approve_payment(&s, amount * 100);
+    let live_until = s.env.ledger().sequence() + 1_000;
+    s.payment
+        .approve(&s.buyer, &s.carbon.address, &(amount * 5), &live_until);
+    s.marketplace.buy(&s.buyer, &listing_id, &amount);
+    s.marketplace.cancel_listing(&cancelled);
+
+    for token_id in sold.iter() {
+        assert_eq!(s.carbon.owner_of(&token_id), s.buyer);
+    }
+    assert_eq!(s.payment.balance(&developer), amount * 5);
+    assert_eq!(s.carbon.free_balance(&s.seller), amount);
+}
@@ -0,0 +1,317 @@
+#![cfg(test)]
+
+use carbon_asset::types::CarbonAssetMetadata;
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use marketplace::errors::ContractError;
+use marketplace::types::OfferStatus;
+use marketplace::{Marketplace, MarketplaceClient, MAX_LISTING_TOKENS};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+const NOW: u64 = 1_000;
+const EXPIRES_AT: u64 = 2_000;
+const FUNDS: i128 = 10_000;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    seller: Address,
+    buyer: Address,
+    carbon: CarbonAssetClient<'a>,
+    payment: TokenClient<'a>,
+    marketplace: MarketplaceClient<'a>,
+}
+
+// A seller holding `supply` credits approved for the marketplace and a funded
+// buyer.
+fn setup<'a>(supply: u32) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.add_marketplace(&admin, &marketplace.address);
+    marketplace.initialize(&admin, &carbon.address);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..supply {
+        carbon.mint(&admin, &seller, &meta);
+    }
+    let live_until = env.ledger().sequence() + 1_000;
+    carbon.approve(
+        &seller,
+        &marketplace.address,
+        &(supply as i128),
+        &live_until,
+    );
+
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &FUNDS);
+    let payment = TokenClient::new(&env, &payment_id);
+    env.ledger().set_timestamp(NOW);
+
+    Setup {
+        env,
+        admin,
+        seller,
+        buyer,
+        carbon,
+        payment,
+        marketplace,
+    }
+}
+
+fn create_offer(s: &Setup, token_ids: Vec<u32>, price_per_unit: i128) -> u64 {
+    s.marketplace.create_offer(
+        &s.buyer,
+        &token_ids,
+        &price_per_unit,
+        &s.payment.address,
+        &EXPIRES_AT,
+    )
+}
+
+#[test]
+fn test_partial_accepts_until_filled() {
+    let s = setup(3);
+    let offer_id = create_offer(&s, vec![&s.env, 1, 2, 3], 100);
+    assert_eq!(s.payment.balance(&s.buyer), FUNDS - 300);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 300);
+
+    s.marketplace
+        .accept_offer(&s.seller, &offer_id, &vec![&s.env, 1, 3]);
+    let offer = s.marketplace.get_offer(&offer_id);
+    assert_eq!(offer.remaining, 1);
+    assert_eq!(offer.token_ids, vec![&s.env, 2]);
+    assert_eq!(offer.status, OfferStatus::Open);
+    assert_eq!(s.carbon.owner_of(&1), s.buyer);
+    assert_eq!(s.carbon.owner_of(&2), s.seller);
+    assert_eq!(s.carbon.owner_of(&3), s.buyer);
+    assert_eq!(s.payment.balance(&s.seller), 200);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 100);
+
+    // Sold tokens are no longer wanted, and nothing else substitutes for them
+    assert_eq!(
+        s.marketplace
+            .try_accept_offer(&s.seller, &offer_id, &vec![&s.env, 1]),
+        Err(Ok(ContractError::ExceedsRemaining))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_accept_offer(&s.seller, &offer_id, &vec![&s.env, 2, 2]),
+        Err(Ok(ContractError::ExceedsRemaining))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_accept_offer(&s.seller, &offer_id, &Vec::new(&s.env)),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+
+    // Token 1 went to the buyer with the first fill; the seller still owns 2
+    let offer_id = create_offer(&s, vec![&s.env, 1], 100);
+    assert_eq!(
+        s.marketplace
+            .try_accept_offer(&s.seller, &offer_id, &vec![&s.env, 1]),
+        Err(Ok(ContractError::NotOwner))
+    );
+}
+
+#[test]
+fn test_accept_offer_pays_royalties() {
+    let s = setup(2);
+    let developer = Address::generate(&s.env);
+    s.carbon.set_royalty(&s.admin, &2, &developer, &500);
+    let offer_id = create_offer(&s, vec![&s.env, 1, 2], 100);
+
+    // The royalty is charged to the buyer on top of the escrowed price
+    let live_until = s.env.ledger().sequence() + 1_000;
+    s.payment
+        .approve(&s.buyer, &s.carbon.address, &5, &live_until);
+    s.marketplace
+        .accept_offer(&s.seller, &offer_id, &vec![&s.env, 1, 2]);
+
+    assert_eq!(s.carbon.owner_of(&1), s.buyer);
+    assert_eq!(s.carbon.owner_of(&2), s.buyer);
+    assert_eq!(s.payment.balance(&developer), 5);
+    assert_eq!(s.payment.balance(&s.seller), 200);
+    assert_eq!(s.payment.balance(&s.buyer), FUNDS - 205);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 0);
+}
+
+#[test]
+fn test_accepting_depleted_offer_fails_cleanly() {
+    let s = setup(2);
+    let offer_id = create_offer(&s, vec![&s.env, 1], 100);
+
+    s.marketplace
+        .accept_offer(&s.seller, &offer_id, &vec![&s.env, 1]);
+    let offer = s.marketplace.get_offer(&offer_id);
+    assert_eq!(offer.remaining, 0);
+    assert_eq!(offer.status, OfferStatus::Filled);
+
+    // A second acceptance is rejected without moving credits or payment
+    let other_seller = Address::generate(&s.env);
+    s.carbon.transfer(&s.seller, &other_seller, &1);
+    assert_eq!(
+        s.marketplace
+            .try_accept_offer(&other_seller, &offer_id, &vec![&s.env, 2]),
+        Err(Ok(ContractError::OfferNotActive))
+    );
+    assert_eq!(s.carbon.balance(&s.buyer), 1);
+    assert_eq!(s.carbon.balance(&other_seller), 1);
+    assert_eq!(s.payment.balance(&s.seller), 100);
+    assert_eq!(s.payment.balance(&other_seller), 0);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 0);
+    assert_eq!(
+        s.marketplace.try_cancel_offer(&offer_id),
+        Err(Ok(ContractError::OfferNotActive))
+    );
+}
+
+#[test]
+fn test_expired_offer_is_reclaimed_by_buyer() {
+    let s = setup(2);
+    let offer_id = create_offer(&s, vec![&s.env, 1, 2], 100);
+    s.marketplace
+        .accept_offer(&s.seller, &offer_id, &vec![&s.env, 1]);
+
+    s.env.ledger().set_timestamp(EXPIRES_AT);
+    assert_eq!(
+        s.marketplace
+            .try_accept_offer(&s.seller, &offer_id, &vec![&s.env, 2]),
+        Err(Ok(ContractError::OfferExpired))
+    );
+
+    // Only the unfilled remainder is refunded
+    s.marketplace.cancel_offer(&offer_id);
+    let offer = s.marketplace.get_offer(&offer_id);
+    assert_eq!(offer.status, OfferStatus::Cancelled);
+    assert_eq!(offer.remaining, 1);
+    assert_eq!(s.payment.balance(&s.buyer), FUNDS - 100);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 0);
+    assert_eq!(s.carbon.balance(&s.buyer), 1);
+    assert_eq!(s.carbon.balance(&s.seller), 1);
+}
+
+#[test]
+fn test_offer_queries() {
+    let s = setup(2);
+    let first = create_offer(&s, vec![&s.env, 1], 10);
+    let second = create_offer(&s, vec![&s.env, 1, 2], 20);
+    let other_buyer = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.payment.address).mint(&other_buyer, &FUNDS);
+    let third = s.marketplace.create_offer(
+        &other_buyer,
+        &vec![&s.env, 2],
+        &30,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    let by_token = s.marketplace.get_offers_by_token(&1, &0, &10);
+    assert_eq!(by_token.len(), 2);
+    assert_eq!(by_token.get(0).unwrap().offer_id, first);
+    assert_eq!(by_token.get(1).unwrap().offer_id, second);
+    let page = s.marketplace.get_offers_by_token(&1, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().offer_id, second);
+    assert_eq!(s.marketplace.get_offers_by_token(&1, &5, &10).len(), 0);
+    let by_token = s.marketplace.get_offers_by_token(&2, &0, &10);
+    assert_eq!(by_token.len(), 2);
+    assert_eq!(by_token.get(1).unwrap().offer_id, third);
+
+    let by_buyer = s.marketplace.get_offers_by_buyer(&other_buyer, &0, &10);
+    assert_eq!(by_buyer.len(), 1);
+    assert_eq!(by_buyer.get(0).unwrap().offer_id, third);
+    assert_eq!(s.marketplace.get_offers_by_buyer(&s.buyer, &0, &1).len(), 1);
+}
+
+#[test]
+fn test_offer_validation() {
+    let s = setup(MAX_LISTING_TOKENS + 1);
+    let payment = &s.payment.address;
+    let one = vec![&s.env, 1];
+
+    assert_eq!(
+        s.marketplace
+            .try_create_offer(&s.buyer, &Vec::new(&s.env), &10, payment, &EXPIRES_AT),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_create_offer(&s.buyer, &vec![&s.env, 1, 1], &10, payment, &EXPIRES_AT),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    let mut too_many = Vec::new(&s.env);
+    for token_id in 1..=MAX_LISTING_TOKENS + 1 {
+        too_many.push_back(token_id);
+    }
+    assert_eq!(
+        s.marketplace
+            .try_create_offer(&s.buyer, &too_many, &10, payment, &EXPIRES_AT),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_create_offer(&s.buyer, &one, &0, payment, &EXPIRES_AT),
+        Err(Ok(ContractError::InvalidPrice))
+    );
+    assert_eq!(
+        s.marketplace
+            .try_create_offer(&s.buyer, &one, &10, payment, &NOW),
+        Err(Ok(ContractError::InvalidTimeWindow))
+    );
+    assert_eq!(
+        s.marketplace.try_get_offer(&99),
+        Err(Ok(ContractError::OfferNotFound))
+    );
+    assert_eq!(s.payment.balance(&s.buyer), FUNDS);
+}
+
+// An offer for the most tokens allowed, each with a royalty, is filled in
+// one acceptance within the network's default per-transaction limits.
+#[test]
+fn test_full_offer_fits_network_limits() {
+    let s = setup(MAX_LISTING_TOKENS);
+    let developer = Address::generate(&s.env);
+    let mut token_ids = Vec::new(&s.env);
+    for token_id in 1..=MAX_LISTING_TOKENS {
+        s.carbon.set_royalty(&s.admin, &token_id, &developer, &500);
+        token_ids.push_back(token_id);
+    }
+    let offer_id = create_offer(&s, token_ids.clone(), 100);
+
+    let amount = MAX_LISTING_TOKENS as i128;
+    let live_until = s.env.ledger().sequence() + 1_000;
+    s.payment
+        .approve(&s.buyer, &s.carbon.address, &(amount * 5), &live_until);
+    s.marketplace.accept_offer(&s.seller, &offer_id, &token_ids);
+
+    for token_id in token_ids.iter() {
+        assert_eq!(s.carbon.owner_of(&token_id), s.buyer);
+    }
+    assert_eq!(s.payment.balance(&developer), amount * 5);
+    assert_eq!(
+        s.marketplace.get_offer(&offer_id).status,
+        OfferStatus::Filled
+    );
+}
@@ -0,0 +1,309 @@
+#![cfg(test)]
+
+use carbon_asset::types::{AssetStatus, CarbonAssetMetadata};
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use marketplace::errors::ContractError;
+use marketplace::types::ListingStatus;
+use marketplace::{Marketplace, MarketplaceClient, MIN_LISTING_DURATION};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+const NOW: u64 = 1_000;
+const EXPIRES_AT: u64 = 10_000;
+const FUNDS: i128 = 10_000;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    seller: Address,
+    buyer: Address,
+    carbon: CarbonAssetClient<'a>,
+    payment: TokenClient<'a>,
+    marketplace: MarketplaceClient<'a>,
+}
+
+// A seller holding `supply` credits approved for the marketplace and a funded
+// buyer with the marketplace approved on the payment token.
+fn setup<'a>(supply: u32) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.add_marketplace(&admin, &marketplace.address);
+    marketplace.initialize(&admin, &carbon.address);
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    for _ in 0..supply {
+        carbon.mint(&admin, &seller, &meta);
+    }
+    let live_until = env.ledger().sequence() + 1_000;
+    carbon.approve(
+        &seller,
+        &marketplace.address,
+        &(supply as i128),
+        &live_until,
+    );
+
+    let payment_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &payment_id).mint(&buyer, &FUNDS);
+    StellarAssetClient::new(&env, &payment_id).mint(&admin, &FUNDS);
+    let payment = TokenClient::new(&env, &payment_id);
+    marketplace.allow_payment_token(&admin, &payment_id);
+    payment.approve(&buyer, &marketplace.address, &FUNDS, &live_until);
+    env.ledger().set_timestamp(NOW);
+
+    Setup {
+        env,
+        admin,
+        seller,
+        buyer,
+        carbon,
+        payment,
+        marketplace,
+    }
+}
+
+// List `token_id` on its own until EXPIRES_AT.
+fn list(s: &Setup, token_id: u32) -> u64 {
+    s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, token_id],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    )
+}
+
+#[test]
+fn test_buy_until_one_second_before_expiry() {
+    let s = setup(2);
+    let listing_id = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, 1, 2],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    s.env.ledger().set_timestamp(EXPIRES_AT - 1);
+    s.marketplace.buy(&s.buyer, &listing_id, &1);
+    assert_eq!(s.carbon.balance(&s.buyer), 1);
+
+    s.env.ledger().set_timestamp(EXPIRES_AT);
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &listing_id, &1),
+        Err(Ok(ContractError::ListingExpired))
+    );
+    s.env.ledger().set_timestamp(EXPIRES_AT + 1);
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &listing_id, &1),
+        Err(Ok(ContractError::ListingExpired))
+    );
+    assert_eq!(s.marketplace.get_listing(&listing_id).remaining, 1);
+    assert_eq!(s.carbon.balance(&s.seller), 1);
+}
+
+#[test]
+fn test_reap_expired_delists_and_pays_incentive() {
+    let s = setup(3);
+    let first = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, 1, 2, 3],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+    assert_eq!(s.carbon.get_status(&1), AssetStatus::Listed);
+
+    s.marketplace
+        .set_reaper_incentive(&s.admin, &s.payment.address, &30);
+    s.marketplace.fund_reaper_budget(&s.admin, &50);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 50);
+
+    // Nothing has expired yet, and unknown ids are skipped
+    let reaper = Address::generate(&s.env);
+    assert_eq!(
+        s.marketplace
+            .reap_expired(&reaper, &vec![&s.env, first, 99]),
+        0
+    );
+    assert_eq!(
+        s.marketplace.get_listing(&first).status,
+        ListingStatus::Active
+    );
+
+    // The first listing holds every credit, so the later ones need more
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&s.env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&s.env, &[7u8; 32]),
+    };
+    s.carbon.mint(&s.admin, &s.seller, &meta);
+    s.carbon.mint(&s.admin, &s.seller, &meta);
+
+    let second = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, 4],
+        &100,
+        &s.payment.address,
+        &(EXPIRES_AT + 10),
+    );
+    let third = s.marketplace.create_listing(
+        &s.seller,
+        &vec![&s.env, 5],
+        &100,
+        &s.payment.address,
+        &(EXPIRES_AT - 5),
+    );
+
+    s.env.ledger().set_timestamp(EXPIRES_AT);
+    assert_eq!(
+        s.marketplace
+            .reap_expired(&reaper, &vec![&s.env, first, second, third]),
+        2
+    );
+    let listing = s.marketplace.get_listing(&first);
+    assert_eq!(listing.status, ListingStatus::Expired);
+    assert!(!listing.token_listed);
+    assert_eq!(s.carbon.get_status(&1), AssetStatus::Issued);
+    // Only the unexpired listing still holds credits
+    assert_eq!(s.carbon.listed_amount(&s.seller, &1), 0);
+    assert_eq!(s.carbon.listed_amount(&s.seller, &4), 1);
+    assert_eq!(s.carbon.free_balance(&s.seller), 4);
+    assert_eq!(
+        s.marketplace.get_listing(&second).status,
+        ListingStatus::Active
+    );
+    assert_eq!(
+        s.marketplace.get_listing(&third).status,
+        ListingStatus::Expired
+    );
+
+    // Two listings earn 60, but only 50 was budgeted
+    assert_eq!(s.payment.balance(&reaper), 50);
+    assert_eq!(s.payment.balance(&s.marketplace.address), 0);
+    assert_eq!(s.marketplace.get_reaper_config().unwrap().budget, 0);
+
+    // Reaping again pays for the one listing left; expired listings cannot
+    // be bought
+    s.env.ledger().set_timestamp(EXPIRES_AT + 10);
+    s.marketplace.fund_reaper_budget(&s.admin, &100);
+    assert_eq!(
+        s.marketplace
+            .reap_expired(&reaper, &vec![&s.env, first, second, third]),
+        1
+    );
+    assert_eq!(s.payment.balance(&reaper), 80);
+    assert_eq!(s.marketplace.get_reaper_config().unwrap().budget, 70);
+    assert_eq!(
+        s.marketplace.try_buy(&s.buyer, &first, &1),
+        Err(Ok(ContractError::ListingNotActive))
+    );
+}
+
+#[test]
+fn test_sellers_are_not_paid_to_reap_their_own_listings() {
+    let s = setup(2);
+    s.marketplace
+        .set_reaper_incentive(&s.admin, &s.payment.address, &30);
+    s.marketplace.fund_reaper_budget(&s.admin, &100);
+
+    // A listing must stay open for the minimum duration
+    assert_eq!(
+        s.marketplace.try_create_listing(
+            &s.seller,
+            &vec![&s.env, 1],
+            &100,
+            &s.payment.address,
+            &(NOW + MIN_LISTING_DURATION - 1),
+        ),
+        Err(Ok(ContractError::InvalidTimeWindow))
+    );
+    let own = list(&s, 1);
+    let other_seller = Address::generate(&s.env);
+    s.carbon.transfer(&s.seller, &other_seller, &1);
+    let live_until = s.env.ledger().sequence() + 1_000;
+    s.carbon
+        .approve(&other_seller, &s.marketplace.address, &1, &live_until);
+    let other = s.marketplace.create_listing(
+        &other_seller,
+        &vec![&s.env, 2],
+        &100,
+        &s.payment.address,
+        &EXPIRES_AT,
+    );
+
+    // The seller reaps both but is only paid for the other seller's listing
+    s.env.ledger().set_timestamp(EXPIRES_AT);
+    assert_eq!(
+        s.marketplace
+            .reap_expired(&s.seller, &vec![&s.env, own, other]),
+        2
+    );
+    assert_eq!(s.payment.balance(&s.seller), 30);
+    assert_eq!(s.marketplace.get_reaper_config().unwrap().budget, 70);
+}
+
+#[test]
+fn test_reaper_limits() {
+    let s = setup(1);
+    let reaper = Address::generate(&s.env);
+
+    let mut listing_ids = Vec::new(&s.env);
+    for listing_id in 0..26u64 {
+        listing_ids.push_back(listing_id);
+    }
+    assert_eq!(
+        s.marketplace.try_reap_expired(&reaper, &listing_ids),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+
+    // Without an incentive configured, expired listings are reaped for free
+    let listing_id = list(&s, 1);
+    s.env.ledger().set_timestamp(EXPIRES_AT);
+    assert_eq!(
+        s.marketplace.try_fund_reaper_budget(&s.admin, &10),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        s.marketplace
+            .reap_expired(&reaper, &vec![&s.env, listing_id]),
+        1
+    );
+    assert_eq!(s.payment.balance(&reaper), 0);
+
+    assert_eq!(
+        s.marketplace
+            .try_set_reaper_incentive(&s.seller, &s.payment.address, &10),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    s.marketplace
+        .set_reaper_incentive(&s.admin, &s.payment.address, &10);
+    s.marketplace.fund_reaper_budget(&s.admin, &10);
+    assert_eq!(
+        s.marketplace
+            .try_set_reaper_incentive(&s.admin, &Address::generate(&s.env), &10),
+        Err(Ok(ContractError::ReaperBudgetInUse))
+    );
+}
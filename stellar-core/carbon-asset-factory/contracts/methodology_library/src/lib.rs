@@ -1,7 +1,8 @@
 #![no_std]
 #![allow(deprecated)]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol,
+    Vec,
 };
 
 #[contracterror]
@@ -17,6 +18,9 @@ pub enum Error {
     ProposalNotFound = 8,
     DelayNotMet = 9,
     ProposalAlreadyExists = 10,
+    AlreadyDeprecated = 11,
+    NotReporter = 12,
+    InvalidAmount = 13,
 }
 
 #[contracttype]
@@ -30,6 +34,14 @@ pub struct MethodologyMeta {
     pub ipfs_cid: Option<String>,
 }
 
+/// Issuance reported against a methodology by its reporters.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MethodologyUsage {
+    pub project_count: u32,
+    pub total_issued: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProposalType {
@@ -60,6 +72,11 @@ pub enum DataKey {
     DelayPeriod,
     NextProposalId,
     AuthorityProposal(u32),
+    Deprecated(u32),
+    Reporters,
+    Usage(u32),
+    UsageProject(u32, u32),
+    ProjectReported(u32, String),
 }
 
 #[contract]
@@ -68,21 +85,20 @@ pub struct MethodologyLibrary;
 #[contractimpl]
 impl MethodologyLibrary {
    
-    pub fn initialize(env: Env, admin: Address, name: String, symbol: String, delay_period: u64) -> Result<(), Error> {
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String, delay_period: u64) {
 
         admin.require_auth();
 
-        if env.storage().persistent().has(&DataKey::Admin) {
-            return Err(Error::AlreadyInitialized);
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Contract already initialized");
         }
-        env.storage().persistent().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().persistent().set(&DataKey::Name, &name);
         env.storage().persistent().set(&DataKey::Symbol, &symbol);
         env.storage().persistent().set(&DataKey::NextTokenId, &1u32);
         env.storage().persistent().set(&DataKey::Authorities, &Vec::<Address>::new(&env));
         env.storage().persistent().set(&DataKey::DelayPeriod, &delay_period);
         env.storage().persistent().set(&DataKey::NextProposalId, &1u32);
-        Ok(())
     }
 
     pub fn mint_methodology(env: Env, caller: Address, owner: Address, meta: MethodologyMeta) -> Result<u32, Error> {
@@ -191,9 +207,144 @@ impl MethodologyLibrary {
         }
     }
 
+    /// Mark a methodology as deprecated. Credits already minted against it
+    /// are not invalidated; consumers can query `is_deprecated` to flag them.
+    pub fn deprecate(env: Env, admin_caller: Address, token_id: u32) -> Result<(), Error> {
+        admin_caller.require_auth();
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        if admin_caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        if !env.storage().persistent().has(&DataKey::Methodology(token_id)) {
+            return Err(Error::TokenNotFound);
+        }
+        if Self::is_deprecated(env.clone(), token_id) {
+            return Err(Error::AlreadyDeprecated);
+        }
+
+        env.storage().persistent().set(&DataKey::Deprecated(token_id), &true);
+        env.events().publish(
+            (Symbol::new(&env, "methodology_deprecated"), token_id),
+            admin_caller
+        );
+        Ok(())
+    }
+
+    pub fn is_deprecated(env: Env, token_id: u32) -> bool {
+        env.storage().persistent().get(&DataKey::Deprecated(token_id)).unwrap_or(false)
+    }
+
+    /// Allow `reporter` (typically a Carbon Asset contract) to call
+    /// `register_project_usage`.
+    pub fn add_reporter(env: Env, admin_caller: Address, reporter: Address) -> Result<(), Error> {
+        admin_caller.require_auth();
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        if admin_caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut reporters = Self::get_reporters(env.clone());
+        if !reporters.contains(&reporter) {
+            reporters.push_back(reporter.clone());
+            env.storage().persistent().set(&DataKey::Reporters, &reporters);
+            env.events().publish((symbol_short!("rep_add"),), reporter);
+        }
+        Ok(())
+    }
+
+    pub fn remove_reporter(env: Env, admin_caller: Address, reporter: Address) -> Result<(), Error> {
+        admin_caller.require_auth();
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        if admin_caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut reporters = Self::get_reporters(env.clone());
+        if let Some(idx) = reporters.first_index_of(&reporter) {
+            reporters.remove(idx);
+            env.storage().persistent().set(&DataKey::Reporters, &reporters);
+            env.events().publish((symbol_short!("rep_rem"),), reporter);
+        }
+        Ok(())
+    }
+
+    pub fn get_reporters(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reporters)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Record that `amount` credits of `project_id` were issued under
+    /// `methodology_id`. Each project is listed once per methodology, in
+    /// the order it was first reported.
+    pub fn register_project_usage(
+        env: Env,
+        caller: Address,
+        methodology_id: u32,
+        project_id: String,
+        amount: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if !Self::get_reporters(env.clone()).contains(&caller) {
+            return Err(Error::NotReporter);
+        }
+        if !env.storage().persistent().has(&DataKey::Methodology(methodology_id)) {
+            return Err(Error::TokenNotFound);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut usage = Self::get_methodology_usage(env.clone(), methodology_id);
+        let reported_key = DataKey::ProjectReported(methodology_id, project_id.clone());
+        if !env.storage().persistent().has(&reported_key) {
+            env.storage().persistent().set(&reported_key, &true);
+            env.storage()
+                .persistent()
+                .set(&DataKey::UsageProject(methodology_id, usage.project_count), &project_id);
+            usage.project_count += 1;
+        }
+        usage.total_issued += amount;
+        env.storage().persistent().set(&DataKey::Usage(methodology_id), &usage);
+
+        env.events().publish(
+            (Symbol::new(&env, "project_usage"), methodology_id),
+            (project_id, amount)
+        );
+        Ok(())
+    }
+
+    pub fn get_methodology_usage(env: Env, methodology_id: u32) -> MethodologyUsage {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Usage(methodology_id))
+            .unwrap_or(MethodologyUsage {
+                project_count: 0,
+                total_issued: 0,
+            })
+    }
+
+    /// Projects reported under `methodology_id`, in first-reported order.
+    pub fn get_projects_by_methodology(env: Env, methodology_id: u32, start: u32, limit: u32) -> Vec<String> {
+        let count = Self::get_methodology_usage(env.clone(), methodology_id).project_count;
+        let end = start.saturating_add(limit).min(count);
+
+        let mut projects = Vec::new(&env);
+        for index in start..end {
+            let project_id: String = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UsageProject(methodology_id, index))
+                .unwrap();
+            projects.push_back(project_id);
+        }
+        projects
+    }
+
     pub fn add_authority(env: Env, admin_caller: Address, authority: Address) -> Result<(), Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
@@ -209,7 +360,7 @@ impl MethodologyLibrary {
 
     pub fn propose_add_authority(env: Env, admin_caller: Address, authority: Address) -> Result<u32, Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
@@ -243,7 +394,7 @@ impl MethodologyLibrary {
 
     pub fn remove_authority(env: Env, admin_caller: Address, authority: Address) -> Result<(), Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
@@ -262,7 +413,7 @@ impl MethodologyLibrary {
 
     pub fn propose_remove_authority(env: Env, admin_caller: Address, authority: Address) -> Result<u32, Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
@@ -337,7 +488,7 @@ impl MethodologyLibrary {
 
     pub fn cancel_authority_change(env: Env, admin_caller: Address, proposal_id: u32) -> Result<(), Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
@@ -370,7 +521,7 @@ impl MethodologyLibrary {
 
     pub fn set_delay_period(env: Env, admin_caller: Address, new_delay: u64) -> Result<(), Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
@@ -381,24 +532,31 @@ impl MethodologyLibrary {
 
     pub fn transfer_admin(env: Env, admin_caller: Address, new_admin: Address) -> Result<(), Error> {
         admin_caller.require_auth();
-        let admin: Address = env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
         if admin_caller != admin {
             return Err(Error::Unauthorized);
         }
-        env.storage().persistent().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
         env.events().publish((symbol_short!("adm_trns"),), (admin_caller, new_admin));
         Ok(())
     }
 
     pub fn get_admin(env: Env) -> Result<Address, Error> {
-        env.storage().persistent().get(&DataKey::Admin).ok_or(Error::NotInitialized)
+        env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)
+    }
+
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized")
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger as _};
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
     use soroban_sdk::{Env, String};
 
     #[test]
@@ -626,4 +784,144 @@ mod test {
         let result = client.try_mint_methodology(&non_authority, &owner, &meta);
         assert_eq!(result, Err(Ok(Error::NotAuthorizedAuthority)));
     }
+
+    #[test]
+    fn test_deprecate() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let authority = Address::generate(&env);
+        let owner = Address::generate(&env);
+
+        let contract_id = env.register(MethodologyLibrary, ());
+        let client = MethodologyLibraryClient::new(&env, &contract_id);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Carbon methodology"),
+            &String::from_str(&env, "CSC-METH"),
+            &7u64,
+        );
+        client.add_authority(&admin, &authority);
+
+        let meta = MethodologyMeta {
+            name: String::from_str(&env, "Improved Forest Management"),
+            version: String::from_str(&env, "VM0042 v2.1"),
+            registry: String::from_str(&env, "VERRA"),
+            registry_link: String::from_str(&env, "https://verra.org"),
+            issuing_authority: authority.clone(),
+            ipfs_cid: None,
+        };
+        let token_id = client.mint_methodology(&authority, &owner, &meta);
+        assert!(!client.is_deprecated(&token_id));
+
+        assert_eq!(client.try_deprecate(&owner, &token_id), Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.try_deprecate(&admin, &99), Err(Ok(Error::TokenNotFound)));
+
+        client.deprecate(&admin, &token_id);
+        assert_eq!(env.events().all().events().len(), 1);
+        assert!(client.is_deprecated(&token_id));
+        assert_eq!(client.try_deprecate(&admin, &token_id), Err(Ok(Error::AlreadyDeprecated)));
+
+        // Deprecation is advisory and does not revoke validity
+        assert!(client.is_valid_methodology(&token_id));
+    }
+
+    #[test]
+    fn test_project_usage() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let authority = Address::generate(&env);
+        let reporter = Address::generate(&env);
+
+        let contract_id = env.register(MethodologyLibrary, ());
+        let client = MethodologyLibraryClient::new(&env, &contract_id);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Carbon methodology"),
+            &String::from_str(&env, "CSC-METH"),
+            &7u64,
+        );
+        client.add_authority(&admin, &authority);
+
+        let meta = MethodologyMeta {
+            name: String::from_str(&env, "Improved Forest Management"),
+            version: String::from_str(&env, "VM0042 v2.1"),
+            registry: String::from_str(&env, "VERRA"),
+            registry_link: String::from_str(&env, "https://verra.org"),
+            issuing_authority: authority.clone(),
+            ipfs_cid: None,
+        };
+        let token_id = client.mint_methodology(&authority, &authority, &meta);
+        let proj_1 = String::from_str(&env, "PROJ-1");
+        let proj_2 = String::from_str(&env, "PROJ-2");
+
+        assert_eq!(
+            client.try_register_project_usage(&reporter, &token_id, &proj_1, &5),
+            Err(Ok(Error::NotReporter))
+        );
+        assert_eq!(client.try_add_reporter(&reporter, &reporter), Err(Ok(Error::Unauthorized)));
+        client.add_reporter(&admin, &reporter);
+        assert_eq!(
+            client.try_register_project_usage(&reporter, &99, &proj_1, &5),
+            Err(Ok(Error::TokenNotFound))
+        );
+        assert_eq!(
+            client.try_register_project_usage(&reporter, &token_id, &proj_1, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+
+        client.register_project_usage(&reporter, &token_id, &proj_1, &5);
+        client.register_project_usage(&reporter, &token_id, &proj_2, &3);
+        client.register_project_usage(&reporter, &token_id, &proj_1, &2);
+
+        let usage = client.get_methodology_usage(&token_id);
+        assert_eq!(usage, MethodologyUsage { project_count: 2, total_issued: 10 });
+        assert_eq!(
+            client.get_projects_by_methodology(&token_id, &0, &10),
+            Vec::from_array(&env, [proj_1.clone(), proj_2.clone()])
+        );
+        assert_eq!(
+            client.get_projects_by_methodology(&token_id, &1, &10),
+            Vec::from_array(&env, [proj_2])
+        );
+        assert!(client.get_projects_by_methodology(&token_id, &2, &10).is_empty());
+
+        client.remove_reporter(&admin, &reporter);
+        assert_eq!(
+            client.try_register_project_usage(&reporter, &token_id, &proj_1, &1),
+            Err(Ok(Error::NotReporter))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract already initialized")]
+    fn test_initialize_twice() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+
+        let contract_id = env.register(MethodologyLibrary, ());
+        let client = MethodologyLibraryClient::new(&env, &contract_id);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Carbon methodology"),
+            &String::from_str(&env, "CSC-METH"),
+            &7u64,
+        );
+        assert_eq!(client.admin(), admin);
+
+        client.initialize(
+            &Address::generate(&env),
+            &String::from_str(&env, "Carbon methodology"),
+            &String::from_str(&env, "CSC-METH"),
+            &7u64,
+        );
+    }
 }
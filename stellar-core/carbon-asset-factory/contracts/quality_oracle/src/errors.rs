@@ -0,0 +1,13 @@
+use soroban_sdk::contracterror;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    NotScorer = 4,
+    InvalidScore = 5,
+    InvalidQuorum = 6,
+    InvalidWindow = 7,
+}
@@ -0,0 +1,22 @@
+use soroban_sdk::{contractevent, Address, BytesN};
+
+#[contractevent]
+pub struct ScoreSubmittedEvent {
+    pub token_id: u32,
+    pub scorer: Address,
+    pub score: i128,
+    pub evidence_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct ScoreAggregatedEvent {
+    pub token_id: u32,
+    pub score: i128,
+    pub submissions: u32,
+}
+
+#[contractevent]
+pub struct ScorerUpdatedEvent {
+    pub scorer: Address,
+    pub enabled: bool,
+}
@@ -0,0 +1,279 @@
+#![no_std]
+
+pub mod errors;
+mod events;
+mod storage;
+#[cfg(test)]
+mod test;
+pub mod types;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::errors::ContractError;
+use crate::events::{ScoreAggregatedEvent, ScoreSubmittedEvent, ScorerUpdatedEvent};
+use crate::storage::DataKey;
+use crate::types::{Aggregate, ScoreSubmission};
+
+// Scores use the Carbon Asset quality scale, where 100 is full quality.
+pub const MAX_SCORE: i128 = 100;
+
+// ========================================================================
+// Contract
+// ========================================================================
+
+#[contract]
+pub struct QualityOracle;
+
+#[contractimpl]
+impl QualityOracle {
+    // ====================================================================
+    // Initialization
+    // ====================================================================
+
+    // This contract must be registered as the Carbon Asset's oracle
+    // (`set_oracle`) for aggregated scores to be accepted.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        carbon_asset: Address,
+        quorum: u32,
+        freshness_window: u64,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        if quorum == 0 {
+            return Err(ContractError::InvalidQuorum);
+        }
+        if freshness_window == 0 {
+            return Err(ContractError::InvalidWindow);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::CarbonAsset, &carbon_asset);
+        env.storage().instance().set(&DataKey::Quorum, &quorum);
+        env.storage()
+            .instance()
+            .set(&DataKey::FreshnessWindow, &freshness_window);
+        Ok(())
+    }
+
+    // ====================================================================
+    // Scoring
+    // ====================================================================
+
+    // Record `scorer`'s score for `token_id`, replacing any earlier score from
+    // the same scorer. Once at least `quorum` authorized scorers have scored
+    // within the freshness window, their median is pushed to the Carbon
+    // Asset. With an even count the median is the mean of the two middle
+    // scores, rounded down.
+    pub fn submit_score(
+        env: Env,
+        scorer: Address,
+        token_id: u32,
+        score: i128,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        scorer.require_auth();
+
+        if !Self::is_scorer(env.clone(), scorer.clone()) {
+            return Err(ContractError::NotScorer);
+        }
+        if !(0..=MAX_SCORE).contains(&score) {
+            return Err(ContractError::InvalidScore);
+        }
+
+        let now = env.ledger().timestamp();
+        let submission = ScoreSubmission {
+            scorer: scorer.clone(),
+            score,
+            evidence_hash: evidence_hash.clone(),
+            submitted_at: now,
+        };
+        let mut submissions = Self::get_submissions(env.clone(), token_id);
+        match submissions.iter().position(|s| s.scorer == scorer) {
+            Some(i) => submissions.set(i as u32, submission),
+            None => submissions.push_back(submission),
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Submissions(token_id), &submissions);
+
+        ScoreSubmittedEvent {
+            token_id,
+            scorer,
+            score,
+            evidence_hash,
+        }
+        .publish(&env);
+
+        let quorum = Self::get_quorum(env.clone())?;
+        let window = Self::get_freshness_window(env.clone())?;
+        let mut fresh: Vec<i128> = Vec::new(&env);
+        for s in submissions.iter() {
+            if now.saturating_sub(s.submitted_at) <= window
+                && Self::is_scorer(env.clone(), s.scorer.clone())
+            {
+                fresh.push_back(s.score);
+            }
+        }
+        if fresh.len() < quorum {
+            return Ok(());
+        }
+
+        let median = Self::median(&fresh);
+        let carbon_asset: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAsset)
+            .ok_or(ContractError::NotInitialized)?;
+        let args: Vec<Val> = (env.current_contract_address(), token_id, median).into_val(&env);
+        env.invoke_contract::<()>(
+            &carbon_asset,
+            &Symbol::new(&env, "update_quality_score"),
+            args,
+        );
+
+        let aggregate = Aggregate {
+            score: median,
+            submissions: fresh.len(),
+            updated_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Aggregate(token_id), &aggregate);
+
+        ScoreAggregatedEvent {
+            token_id,
+            score: median,
+            submissions: fresh.len(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_submissions(env: Env, token_id: u32) -> Vec<ScoreSubmission> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Submissions(token_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_aggregate(env: Env, token_id: u32) -> Option<Aggregate> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Aggregate(token_id))
+    }
+
+    // ====================================================================
+    // Administration
+    // ====================================================================
+
+    pub fn set_scorer(
+        env: Env,
+        caller: Address,
+        scorer: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        if enabled {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Scorer(scorer.clone()), &true);
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Scorer(scorer.clone()));
+        }
+
+        ScorerUpdatedEvent { scorer, enabled }.publish(&env);
+        Ok(())
+    }
+
+    pub fn is_scorer(env: Env, scorer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Scorer(scorer))
+    }
+
+    pub fn set_quorum(env: Env, caller: Address, quorum: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+        if quorum == 0 {
+            return Err(ContractError::InvalidQuorum);
+        }
+
+        env.storage().instance().set(&DataKey::Quorum, &quorum);
+        Ok(())
+    }
+
+    pub fn set_freshness_window(
+        env: Env,
+        caller: Address,
+        freshness_window: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+        if freshness_window == 0 {
+            return Err(ContractError::InvalidWindow);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::FreshnessWindow, &freshness_window);
+        Ok(())
+    }
+
+    pub fn get_quorum(env: Env) -> Result<u32, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Quorum)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_freshness_window(env: Env) -> Result<u64, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FreshnessWindow)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)
+    }
+}
+
+impl QualityOracle {
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        let admin = Self::get_admin(env.clone())?;
+        if *caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    fn median(scores: &Vec<i128>) -> i128 {
+        // Insertion sort; there is at most one score per scorer
+        let mut sorted: Vec<i128> = Vec::new(scores.env());
+        for score in scores.iter() {
+            let mut i = sorted.len();
+            while i > 0 && sorted.get(i - 1).unwrap() > score {
+                i -= 1;
+            }
+            sorted.insert(i, score);
+        }
+
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted.get(mid).unwrap()
+        } else {
+            (sorted.get(mid - 1).unwrap() + sorted.get(mid).unwrap()) / 2
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, Address};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    CarbonAsset,
+    Quorum,
+    FreshnessWindow,
+    Scorer(Address),
+    Submissions(u32),
+    Aggregate(u32),
+}
@@ -0,0 +1,94 @@
+#![cfg(test)]
+
+use super::{QualityOracle, QualityOracleClient};
+use crate::errors::ContractError;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, BytesN, Env};
+
+fn setup<'a>() -> (Env, QualityOracleClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = QualityOracleClient::new(&env, &env.register(QualityOracle, ()));
+    oracle.initialize(&admin, &Address::generate(&env), &2, &3_600);
+
+    (env, oracle, admin)
+}
+
+#[test]
+fn test_initialize_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let carbon_asset = Address::generate(&env);
+    let oracle = QualityOracleClient::new(&env, &env.register(QualityOracle, ()));
+
+    assert_eq!(
+        oracle.try_initialize(&admin, &carbon_asset, &0, &3_600),
+        Err(Ok(ContractError::InvalidQuorum))
+    );
+    assert_eq!(
+        oracle.try_initialize(&admin, &carbon_asset, &2, &0),
+        Err(Ok(ContractError::InvalidWindow))
+    );
+    oracle.initialize(&admin, &carbon_asset, &2, &3_600);
+    assert_eq!(
+        oracle.try_initialize(&admin, &carbon_asset, &2, &3_600),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_only_admin_configures() {
+    let (env, oracle, admin) = setup();
+    let other = Address::generate(&env);
+
+    assert_eq!(
+        oracle.try_set_scorer(&other, &other, &true),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        oracle.try_set_quorum(&other, &3),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        oracle.try_set_quorum(&admin, &0),
+        Err(Ok(ContractError::InvalidQuorum))
+    );
+
+    oracle.set_quorum(&admin, &3);
+    oracle.set_freshness_window(&admin, &60);
+    assert_eq!(oracle.get_quorum(), 3);
+    assert_eq!(oracle.get_freshness_window(), 60);
+}
+
+#[test]
+fn test_submission_validation() {
+    let (env, oracle, admin) = setup();
+    let scorer = Address::generate(&env);
+    let evidence = BytesN::from_array(&env, &[5u8; 32]);
+
+    assert_eq!(
+        oracle.try_submit_score(&scorer, &1, &50, &evidence),
+        Err(Ok(ContractError::NotScorer))
+    );
+
+    oracle.set_scorer(&admin, &scorer, &true);
+    assert_eq!(
+        oracle.try_submit_score(&scorer, &1, &101, &evidence),
+        Err(Ok(ContractError::InvalidScore))
+    );
+    assert_eq!(
+        oracle.try_submit_score(&scorer, &1, &-1, &evidence),
+        Err(Ok(ContractError::InvalidScore))
+    );
+
+    // Below quorum, so nothing is pushed to the (unset) carbon asset
+    oracle.submit_score(&scorer, &1, &50, &evidence);
+    assert_eq!(oracle.get_submissions(&1).len(), 1);
+    assert_eq!(oracle.get_aggregate(&1), None);
+
+    oracle.set_scorer(&admin, &scorer, &false);
+    assert!(!oracle.is_scorer(&scorer));
+}
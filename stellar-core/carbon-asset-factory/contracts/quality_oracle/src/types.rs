@@ -0,0 +1,20 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+// A scorer's latest score for a token. Resubmitting replaces it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreSubmission {
+    pub scorer: Address,
+    pub score: i128,
+    pub evidence_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
+// The last median pushed to the Carbon Asset for a token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Aggregate {
+    pub score: i128,
+    pub submissions: u32,
+    pub updated_at: u64,
+}
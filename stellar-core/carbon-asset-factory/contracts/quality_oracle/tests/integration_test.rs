@@ -0,0 +1,133 @@
+#![cfg(test)]
+
+use carbon_asset::types::CarbonAssetMetadata;
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use quality_oracle::{QualityOracle, QualityOracleClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, BytesN, Env, String};
+
+const WINDOW: u64 = 3_600;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    scorers: [Address; 3],
+    carbon: CarbonAssetClient<'a>,
+    oracle: QualityOracleClient<'a>,
+    token_id: u32,
+}
+
+// A Carbon Asset with one minted credit whose oracle is the quality oracle,
+// and three authorized scorers.
+fn setup<'a>(quorum: u32) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+    let oracle = QualityOracleClient::new(&env, &env.register(QualityOracle, ()));
+
+    carbon.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    carbon.set_oracle(&admin, &oracle.address);
+    oracle.initialize(&admin, &carbon.address, &quorum, &WINDOW);
+
+    let scorers = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for scorer in scorers.iter() {
+        oracle.set_scorer(&admin, scorer, &true);
+    }
+
+    let meta = CarbonAssetMetadata {
+        project_id: String::from_str(&env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&env, &[7u8; 32]),
+    };
+    let token_id = carbon.mint(&admin, &Address::generate(&env), &meta);
+    env.ledger().set_timestamp(10_000);
+
+    Setup {
+        env,
+        admin,
+        scorers,
+        carbon,
+        oracle,
+        token_id,
+    }
+}
+
+fn submit(s: &Setup, scorer: &Address, score: i128) {
+    let evidence = BytesN::from_array(&s.env, &[score as u8; 32]);
+    s.oracle
+        .submit_score(scorer, &s.token_id, &score, &evidence);
+}
+
+#[test]
+fn test_quorum_not_met_leaves_score_unchanged() {
+    let s = setup(3);
+    let [a, b, c] = &s.scorers;
+
+    submit(&s, a, 40);
+    submit(&s, b, 90);
+    assert_eq!(s.oracle.get_submissions(&s.token_id).len(), 2);
+    assert_eq!(s.oracle.get_aggregate(&s.token_id), None);
+    assert_eq!(s.carbon.get_quality_score(&s.token_id), 0);
+
+    // The third score reaches quorum; the median of 40, 70, 90 is pushed
+    submit(&s, c, 70);
+    let aggregate = s.oracle.get_aggregate(&s.token_id).unwrap();
+    assert_eq!(aggregate.score, 70);
+    assert_eq!(aggregate.submissions, 3);
+    assert_eq!(s.carbon.get_quality_score(&s.token_id), 70);
+}
+
+#[test]
+fn test_median_of_even_count_ignores_stale_scores() {
+    let s = setup(2);
+    let [a, b, c] = &s.scorers;
+
+    submit(&s, a, 20);
+    s.env.ledger().set_timestamp(10_000 + WINDOW + 1);
+
+    // a's score is stale, so b alone is short of quorum
+    submit(&s, b, 70);
+    assert_eq!(s.oracle.get_aggregate(&s.token_id), None);
+
+    // Two fresh scores: the mean of 70 and 81, rounded down
+    submit(&s, c, 81);
+    let aggregate = s.oracle.get_aggregate(&s.token_id).unwrap();
+    assert_eq!(aggregate.score, 75);
+    assert_eq!(aggregate.submissions, 2);
+    assert_eq!(s.carbon.get_quality_score(&s.token_id), 75);
+}
+
+#[test]
+fn test_resubmission_overwrites() {
+    let s = setup(2);
+    let [a, b, _] = &s.scorers;
+
+    submit(&s, a, 30);
+    submit(&s, b, 50);
+    assert_eq!(s.carbon.get_quality_score(&s.token_id), 40);
+
+    submit(&s, a, 90);
+    let submissions = s.oracle.get_submissions(&s.token_id);
+    assert_eq!(submissions.len(), 2);
+    assert_eq!(submissions.get(0).unwrap().score, 90);
+    assert_eq!(s.carbon.get_quality_score(&s.token_id), 70);
+
+    // A removed scorer's submission no longer counts toward the median
+    s.oracle.set_scorer(&s.admin, a, &false);
+    submit(&s, b, 60);
+    assert_eq!(s.carbon.get_quality_score(&s.token_id), 70);
+    assert_eq!(s.oracle.get_aggregate(&s.token_id).unwrap().submissions, 2);
+}
@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, Address, BytesN, Env,
-    IntoVal, String, Symbol, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, IntoVal, InvokeError, String, Symbol, Vec,
 };
 
 // ========================================================================
@@ -20,6 +20,109 @@ pub struct RetirementRecord {
     pub reason: Option<String>,      // Optional field for corporate reporting
 }
 
+/// One retired credit within a retirement certificate
+#[derive(Clone)]
+#[contracttype]
+pub struct CertificateComponent {
+    pub token_id: u32,      // ID of the retired CarbonAsset
+    pub project_id: String, // Project ID from the CarbonAsset metadata
+    pub vintage_year: u64,  // Vintage year from the CarbonAsset metadata
+    pub amount: i128,       // Whole credits retired from this token
+}
+
+/// Retirement certificate issued when credits are burned (immutable apart
+/// from a single reassignment of the claim owner)
+#[derive(Clone)]
+#[contracttype]
+pub struct RetirementCertificate {
+    pub cert_id: BytesN<32>,  // Deterministic certificate identifier
+    pub holder: Address,      // Stellar account whose credits were burned
+    pub beneficiary: String,  // Party on whose behalf the offset is claimed
+    pub token_id: u32,        // First (or only) retired CarbonAsset
+    pub amount: i128,         // Total whole credits covered
+    pub reason_code: Symbol,  // Registered retirement reason code
+    pub category: Symbol,     // Compliance category of the reason code
+    pub note: Option<String>, // Optional free-text note for corporate reporting
+    pub timestamp: u64,       // Ledger timestamp of issuance
+    pub components: Vec<CertificateComponent>, // Per-token breakdown
+    pub owner: Address,       // Current claim owner, initially the holder
+    pub assigned_at: Option<u64>, // Ledger timestamp of the single reassignment
+    pub portfolio_id: Option<BytesN<32>>, // Batch retirement this certificate belongs to
+}
+
+/// Retirement awaiting confirmation; its tokens are escrowed by the tracker
+#[derive(Clone)]
+#[contracttype]
+pub struct RetirementRequest {
+    pub request_id: u64,      // Sequential request identifier
+    pub holder: Address,      // Stellar account whose credits are escrowed
+    pub token_ids: Vec<u32>,  // Escrowed CarbonAsset tokens
+    pub beneficiary: String,  // Party on whose behalf the offset is claimed
+    pub reason_code: Symbol,  // Registered retirement reason code
+    pub note: Option<String>, // Optional free-text note for the certificate
+    pub requested_at: u64,    // Ledger timestamp of the request
+    pub expires_at: u64,      // End of the cancellation window
+}
+
+/// Admin-registered retirement reason code
+#[derive(Clone)]
+#[contracttype]
+pub struct ReasonCode {
+    pub code: Symbol,        // Short code referenced by retirements
+    pub description: String, // Human-readable description
+    pub category: Symbol,    // Compliance category, e.g. voluntary, compliance, corsia
+}
+
+/// Credits retired for one vintage year within an annual report
+#[derive(Clone)]
+#[contracttype]
+pub struct VintageTotal {
+    pub vintage_year: u64,
+    pub amount: i128,
+}
+
+/// Credits retired for one project within an annual report
+#[derive(Clone)]
+#[contracttype]
+pub struct ProjectTotal {
+    pub project_id: String,
+    pub amount: i128,
+}
+
+/// Credits retired under one reason category within an annual report
+#[derive(Clone)]
+#[contracttype]
+pub struct CategoryTotal {
+    pub category: Symbol,
+    pub amount: i128,
+}
+
+/// Calendar-year retirement statement for a certificate owner
+///
+/// Each breakdown holds at most `MAX_REPORT_LINES` entries; credits in
+/// further buckets count toward `total` only.
+#[derive(Clone)]
+#[contracttype]
+pub struct RetirementReport {
+    pub owner: Address,                  // Current claim owner of the certificates
+    pub year: u32,                       // Calendar year (UTC) of the retirements
+    pub total: i128,                     // Whole credits retired in the year
+    pub certificates: u32,               // Certificates counted in the report
+    pub by_vintage: Vec<VintageTotal>,   // Per-vintage breakdown
+    pub by_project: Vec<ProjectTotal>,   // Per-project breakdown
+    pub by_category: Vec<CategoryTotal>, // Per-reason-category breakdown
+}
+
+/// Mirror of the CarbonAsset metadata returned by `get_metadata`
+#[derive(Clone)]
+#[contracttype]
+pub struct CarbonAssetMetadata {
+    pub project_id: String,
+    pub vintage_year: u64,
+    pub methodology_id: u32,
+    pub geo_hash: BytesN<32>,
+}
+
 /// Storage keys for the contract
 #[derive(Clone)]
 #[contracttype]
@@ -27,15 +130,44 @@ pub enum DataKey {
     Admin,
     CarbonAssetContract,
     EventNonce,
-    RetirementLedger(u32), // token_id -> RetirementRecord
-    EntityIndex(Address),  // retiring_entity -> Vec<u32>
+    RetirementLedger(u32),           // token_id -> RetirementRecord
+    EntityIndex(Address),            // retiring_entity -> Vec<u32>
+    Certificate(BytesN<32>),         // cert_id -> RetirementCertificate
+    HolderCertificates(Address),     // holder -> Vec<BytesN<32>>
+    TokenCertificates(u32),          // token_id -> Vec<BytesN<32>>
+    TotalRetired,                    // total credits retired with a certificate
+    ProjectRetired(String),          // project_id -> credits retired
+    VintageRetired(String, u64),     // (project_id, vintage_year) -> credits retired
+    ConfirmationWindow,              // seconds a retirement request stays cancellable
+    NextRequestId,                   // last issued retirement request ID
+    RetirementRequest(u64),          // request_id -> RetirementRequest
+    ReasonCode(Symbol),              // code -> ReasonCode
+    CategoryCertificates(Symbol),    // category -> Vec<BytesN<32>>
+    OwnerCertificates(Address),      // owner -> Vec<BytesN<32>>
+    AnnualReport(Address, u32),      // (owner, year) -> RetirementReport
+    CircuitBreaker,                  // Circuit breaker consulted before retiring
+    AuditTrail,                      // Audit trail receiving issued certificates
+    Portfolio(BytesN<32>),           // portfolio_id -> Vec<BytesN<32>>
+    BeneficiaryCertificates(String), // beneficiary -> Vec<BytesN<32>>, oldest first
 }
 
+/// Default confirmation window for retirement requests (24 hours)
+const DEFAULT_CONFIRMATION_WINDOW: u64 = 86_400;
+
+/// Maximum entries in each breakdown of an annual retirement report
+pub const MAX_REPORT_LINES: u32 = 20;
+
+/// Maximum tokens in one `process_retirement_batch` portfolio
+pub const MAX_PORTFOLIO_ITEMS: u32 = 15;
+
+/// Audit trail event type recorded for each issued certificate
+pub const CERTIFICATE_EVENT_TYPE: &str = "RETIREMENT_CERTIFICATE";
+
 // ========================================================================
 // Contract Errors
 // ========================================================================
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[contracterror]
 pub enum ContractError {
     NotAuthorized = 1,
@@ -45,6 +177,16 @@ pub enum ContractError {
     BurnFailed = 5,
     ContractNotInitialized = 6,
     EventNonceOverflow = 7,
+    EmptyBundle = 8,
+    RequestNotFound = 9,
+    ConfirmationWindowOpen = 10,
+    ConfirmationWindowExpired = 11,
+    UnknownReasonCode = 12,
+    CertificateNotFound = 13,
+    CertificateAlreadyAssigned = 14,
+    SystemHalted = 15,
+    PortfolioTooLarge = 16,
+    DuplicateToken = 17,
 }
 
 // ========================================================================
@@ -60,6 +202,53 @@ pub struct RetirementEvent {
     pub event_nonce: u64,
 }
 
+#[contractevent]
+pub struct CertificateIssuedEvent {
+    pub cert_id: BytesN<32>,
+    pub token_id: u32,
+    pub holder: Address,
+    pub beneficiary: String,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct PortfolioRetiredEvent {
+    pub portfolio_id: BytesN<32>,
+    pub holder: Address,
+    pub certificates: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct RetirementRequestedEvent {
+    pub request_id: u64,
+    pub holder: Address,
+    pub amount: i128,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+pub struct RetirementCancelledEvent {
+    pub request_id: u64,
+    pub holder: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct CertificateAssignedEvent {
+    pub cert_id: BytesN<32>,
+    pub from: Address,
+    pub to: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct ReasonCodeRegisteredEvent {
+    pub code: Symbol,
+    pub category: Symbol,
+}
+
 #[contractevent]
 pub struct ContractUpdatedEvent {
     pub old_contract: Address,
@@ -117,6 +306,7 @@ impl RetirementTracker {
         reason: Option<String>,
     ) -> Result<RetirementRecord, ContractError> {
         retiring_entity.require_auth();
+        Self::require_not_halted(&env)?;
         Self::retire_internal(env, token_id, retiring_entity, reason, None)
     }
 
@@ -130,6 +320,7 @@ impl RetirementTracker {
         tx_hash: BytesN<32>,
     ) -> Result<RetirementRecord, ContractError> {
         retiring_entity.require_auth();
+        Self::require_not_halted(&env)?;
         Self::retire_internal(env, token_id, retiring_entity, reason, Some(tx_hash))
     }
 
@@ -139,6 +330,26 @@ impl RetirementTracker {
         retiring_entity: Address,
         reason: Option<String>,
         tx_hash: Option<BytesN<32>>,
+    ) -> Result<RetirementRecord, ContractError> {
+        Self::retire_from(
+            env,
+            token_id,
+            retiring_entity.clone(),
+            retiring_entity,
+            reason,
+            tx_hash,
+        )
+    }
+
+    /// Burn a token held by `owner` and record the retirement for `retiring_entity`.
+    /// The two differ only when the token is escrowed by a pending retirement request.
+    fn retire_from(
+        env: Env,
+        token_id: u32,
+        owner: Address,
+        retiring_entity: Address,
+        reason: Option<String>,
+        tx_hash: Option<BytesN<32>>,
     ) -> Result<RetirementRecord, ContractError> {
         // Check if token is already retired
         let ledger_key = DataKey::RetirementLedger(token_id);
@@ -153,9 +364,6 @@ impl RetirementTracker {
             .get(&DataKey::CarbonAssetContract)
             .ok_or(ContractError::ContractNotInitialized)?;
 
-        // Get current timestamp
-        let timestamp = env.ledger().timestamp();
-
         // Call burn_token on CarbonAsset contract
         // The contract must be pre-authorized as a burner on the CarbonAsset contract
         // We assume CarbonAsset has a burn_token function that accepts (token_id: u32, from: Address)
@@ -163,10 +371,24 @@ impl RetirementTracker {
         let burn_symbol = Symbol::new(&env, "burn_token");
         let mut burn_args = Vec::new(&env);
         burn_args.push_back(token_id.into_val(&env));
-        burn_args.push_back(retiring_entity.clone().into_val(&env));
-        env.invoke_contract::<()>(&carbon_asset_contract, &burn_symbol, burn_args);
+        burn_args.push_back(owner.into_val(&env));
+        env.invoke_contract::<BytesN<32>>(&carbon_asset_contract, &burn_symbol, burn_args);
+
+        Self::record_retirement(&env, token_id, retiring_entity, reason, tx_hash)
+    }
 
-        let event_nonce = Self::next_event_nonce(&env)?;
+    /// Write the retirement record and entity index for a token already
+    /// burned on CarbonAsset.
+    fn record_retirement(
+        env: &Env,
+        token_id: u32,
+        retiring_entity: Address,
+        reason: Option<String>,
+        tx_hash: Option<BytesN<32>>,
+    ) -> Result<RetirementRecord, ContractError> {
+        let ledger_key = DataKey::RetirementLedger(token_id);
+        let timestamp = env.ledger().timestamp();
+        let event_nonce = Self::next_event_nonce(env)?;
 
         // Create retirement record
         let record = RetirementRecord {
@@ -187,7 +409,7 @@ impl RetirementTracker {
             .storage()
             .persistent()
             .get(&entity_key)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
         entity_retirements.push_back(token_id);
         env.storage()
             .persistent()
@@ -201,179 +423,1170 @@ impl RetirementTracker {
             tx_hash,
             event_nonce,
         }
-        .publish(&env);
+        .publish(env);
         Ok(record)
     }
 
-    /// Retire multiple carbon credit tokens in a single transaction
+    /// Retire a carbon credit token and issue a retirement certificate
+    ///
+    /// Burns the token on the CarbonAsset contract, writes the retirement
+    /// record, and stores an immutable certificate indexed by holder and token.
     ///
     /// # Arguments
-    /// * `token_ids` - Vector of token IDs to retire
-    /// * `retiring_entity` - The Stellar account address retiring the credits
-    /// * `reason` - Optional reason for retirement (applied to all tokens)
+    /// * `holder` - The Stellar account that owns the credit
+    /// * `token_id` - The ID of the CarbonAsset token to retire
+    /// * `beneficiary` - The party on whose behalf the offset is claimed
+    /// * `reason_code` - Registered reason code recorded on the certificate
+    /// * `note` - Optional free-text note recorded on the certificate
     ///
     /// # Returns
-    /// Vector of RetirementRecords created
+    /// The ID of the issued certificate
     ///
     /// # Errors
-    /// Returns errors for individual tokens that fail, but continues processing others
-    pub fn batch_retire(
-        env: Env,
-        token_ids: Vec<u32>,
-        retiring_entity: Address,
-        reason: Option<String>,
-    ) -> Vec<RetirementRecord> {
-        retiring_entity.require_auth();
-
-        let mut results = Vec::new(&env);
-
-        for i in 0..token_ids.len() {
-            let token_id = token_ids.get(i).unwrap();
-
-            // Attempt to retire each token
-            // Continue even if one fails
-            if let Ok(record) = Self::retire_internal(
-                env.clone(),
-                token_id,
-                retiring_entity.clone(),
-                reason.clone(),
-                None,
-            ) {
-                results.push_back(record);
-            }
-        }
-
-        results
-    }
-
-    /// Retire multiple carbon credit tokens with caller-supplied transaction
-    /// hashes. Each successful retirement receives its own event nonce.
-    pub fn batch_retire_with_tx_hashes(
+    /// * `ContractError::UnknownReasonCode` - The reason code is not registered
+    /// * `ContractError::TokenAlreadyRetired` - Token has already been retired
+    /// * `ContractError::ContractNotInitialized` - Contract is not initialized
+    pub fn process_retirement(
         env: Env,
-        token_ids: Vec<u32>,
-        retiring_entity: Address,
-        reason: Option<String>,
-        tx_hashes: Vec<BytesN<32>>,
-    ) -> Vec<RetirementRecord> {
-        retiring_entity.require_auth();
-
-        let mut results = Vec::new(&env);
-
-        for i in 0..token_ids.len() {
-            if i >= tx_hashes.len() {
-                break;
-            }
-
-            let token_id = token_ids.get(i).unwrap();
-            let tx_hash = tx_hashes.get(i).unwrap();
-
-            if let Ok(record) = Self::retire_internal(
-                env.clone(),
-                token_id,
-                retiring_entity.clone(),
-                reason.clone(),
-                Some(tx_hash),
-            ) {
-                results.push_back(record);
-            }
-        }
-
-        results
+        holder: Address,
+        token_id: u32,
+        beneficiary: String,
+        reason_code: Symbol,
+        note: Option<String>,
+    ) -> Result<BytesN<32>, ContractError> {
+        holder.require_auth();
+        Self::require_not_halted(&env)?;
+        let reason = Self::registered_reason(&env, &reason_code)?;
+
+        let record =
+            Self::retire_internal(env.clone(), token_id, holder.clone(), note.clone(), None)?;
+
+        let mut records = Vec::new(&env);
+        records.push_back(record);
+        let certificate =
+            Self::issue_certificate(&env, &holder, &records, beneficiary, reason, note, None);
+        Ok(certificate.cert_id)
     }
 
-    /// Check if a token has been retired
+    /// Record a retirement burned directly on CarbonAsset by `retire_direct`
+    /// and issue its certificate
+    ///
+    /// Only the CarbonAsset contract may call this. The token is already
+    /// burned, so nothing is burned here, and the metadata is passed in
+    /// because CarbonAsset cannot be called back during its own call.
     ///
     /// # Arguments
-    /// * `token_id` - The token ID to check
+    /// * `holder` - The Stellar account whose credit was burned
+    /// * `token_id` - The ID of the retired CarbonAsset token
+    /// * `metadata` - The token's CarbonAsset metadata
+    /// * `beneficiary` - The party on whose behalf the offset is claimed
+    /// * `reason_code` - Registered reason code recorded on the certificate
     ///
     /// # Returns
-    /// `true` if the token is retired, `false` otherwise
-    pub fn is_retired(env: Env, token_id: u32) -> bool {
-        let ledger_key = DataKey::RetirementLedger(token_id);
-        env.storage().persistent().has(&ledger_key)
+    /// The ID of the issued certificate
+    ///
+    /// # Errors
+    /// * `ContractError::UnknownReasonCode` - The reason code is not registered
+    /// * `ContractError::TokenAlreadyRetired` - Token has already been retired
+    /// * `ContractError::ContractNotInitialized` - Contract is not initialized
+    pub fn record_direct_retirement(
+        env: Env,
+        holder: Address,
+        token_id: u32,
+        metadata: CarbonAssetMetadata,
+        beneficiary: String,
+        reason_code: Symbol,
+    ) -> Result<BytesN<32>, ContractError> {
+        let carbon_asset_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .ok_or(ContractError::ContractNotInitialized)?;
+        carbon_asset_contract.require_auth();
+        Self::require_not_halted(&env)?;
+        let reason = Self::registered_reason(&env, &reason_code)?;
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::RetirementLedger(token_id))
+        {
+            return Err(ContractError::TokenAlreadyRetired);
+        }
+
+        let record = Self::record_retirement(&env, token_id, holder.clone(), None, None)?;
+        let records = Vec::from_array(&env, [record]);
+        let metadata = Vec::from_array(&env, [metadata]);
+        let certificate = Self::issue_certificate_with_metadata(
+            &env,
+            &holder,
+            &records,
+            &metadata,
+            beneficiary,
+            reason,
+            None,
+            None,
+        );
+        Ok(certificate.cert_id)
     }
 
-    /// Get the full retirement record for a token
+    /// Retire several carbon credit tokens under a single bundled certificate
+    ///
+    /// All tokens are retired or none are: if any token fails to retire the
+    /// whole bundle is rejected.
     ///
     /// # Arguments
-    /// * `token_id` - The token ID to query
+    /// * `holder` - The Stellar account that owns the credits
+    /// * `token_ids` - The IDs of the CarbonAsset tokens to retire
+    /// * `beneficiary` - The party on whose behalf the offset is claimed
+    /// * `reason_code` - Registered reason code recorded on the certificate
+    /// * `note` - Optional free-text note recorded on the certificate
     ///
     /// # Returns
-    /// `Some(RetirementRecord)` if the token is retired, `None` otherwise
-    pub fn get_retirement_record(env: Env, token_id: u32) -> Option<RetirementRecord> {
-        let ledger_key = DataKey::RetirementLedger(token_id);
-        env.storage().persistent().get(&ledger_key)
+    /// The ID of the bundled certificate
+    ///
+    /// # Errors
+    /// * `ContractError::EmptyBundle` - No token IDs were supplied
+    /// * `ContractError::UnknownReasonCode` - The reason code is not registered
+    /// * `ContractError::TokenAlreadyRetired` - A token has already been retired
+    pub fn retire_bundle(
+        env: Env,
+        holder: Address,
+        token_ids: Vec<u32>,
+        beneficiary: String,
+        reason_code: Symbol,
+        note: Option<String>,
+    ) -> Result<BytesN<32>, ContractError> {
+        holder.require_auth();
+        Self::require_not_halted(&env)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::EmptyBundle);
+        }
+        let reason = Self::registered_reason(&env, &reason_code)?;
+
+        let mut records = Vec::new(&env);
+        for token_id in token_ids.iter() {
+            let record =
+                Self::retire_internal(env.clone(), token_id, holder.clone(), note.clone(), None)?;
+            records.push_back(record);
+        }
+
+        let certificate =
+            Self::issue_certificate(&env, &holder, &records, beneficiary, reason, note, None);
+        Ok(certificate.cert_id)
     }
 
-    /// Get all token IDs retired by a specific entity
+    /// Retire a blended portfolio of carbon credit tokens, one certificate per token
+    ///
+    /// Every token is checked before any is burned, and all are retired or
+    /// none are. The certificates share a `portfolio_id`, so the set can be
+    /// read back with `get_certificates_by_portfolio`.
     ///
     /// # Arguments
-    /// * `retiring_entity` - The address to query
+    /// * `holder` - The Stellar account that owns the credits
+    /// * `token_ids` - The IDs of the CarbonAsset tokens to retire, at most `MAX_PORTFOLIO_ITEMS`
+    /// * `beneficiary` - The party on whose behalf the offset is claimed
+    /// * `reason_code` - Registered reason code recorded on every certificate
+    /// * `note` - Optional free-text note recorded on every certificate
     ///
     /// # Returns
-    /// Vector of token IDs retired by the entity
-    pub fn get_retirements_by_entity(env: Env, retiring_entity: Address) -> Vec<u32> {
-        let entity_key = DataKey::EntityIndex(retiring_entity);
-        env.storage()
-            .persistent()
-            .get(&entity_key)
-            .unwrap_or(Vec::new(&env))
-    }
+    /// The IDs of the issued certificates, in the order of `token_ids`
+    ///
+    /// # Errors
+    /// * `ContractError::EmptyBundle` - No token IDs were supplied
+    /// * `ContractError::PortfolioTooLarge` - More than `MAX_PORTFOLIO_ITEMS` token IDs
+    /// * `ContractError::DuplicateToken` - A token ID appears more than once
+    /// * `ContractError::UnknownReasonCode` - The reason code is not registered
+    /// * `ContractError::TokenAlreadyRetired` - A token has already been retired
+    /// * `ContractError::TokenNotOwned` - The holder does not own a token
+    pub fn process_retirement_batch(
+        env: Env,
+        holder: Address,
+        token_ids: Vec<u32>,
+        beneficiary: String,
+        reason_code: Symbol,
+        note: Option<String>,
+    ) -> Result<Vec<BytesN<32>>, ContractError> {
+        holder.require_auth();
+        Self::require_not_halted(&env)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::EmptyBundle);
+        }
+        if token_ids.len() > MAX_PORTFOLIO_ITEMS {
+            return Err(ContractError::PortfolioTooLarge);
+        }
+        let reason = Self::registered_reason(&env, &reason_code)?;
+        for (i, token_id) in token_ids.iter().enumerate() {
+            if token_ids.first_index_of(token_id) != Some(i as u32) {
+                return Err(ContractError::DuplicateToken);
+            }
+            if Self::is_retired(env.clone(), token_id) {
+                return Err(ContractError::TokenAlreadyRetired);
+            }
+            if Self::token_owner(&env, token_id) != Some(holder.clone()) {
+                return Err(ContractError::TokenNotOwned);
+            }
+        }
 
-    /// Get the latest contract-scoped event nonce.
-    pub fn get_event_nonce(env: Env) -> u64 {
-        env.storage()
-            .instance()
-            .get(&DataKey::EventNonce)
-            .unwrap_or(0u64)
-    }
+        let mut records = Vec::new(&env);
+        for token_id in token_ids.iter() {
+            let record =
+                Self::retire_internal(env.clone(), token_id, holder.clone(), note.clone(), None)?;
+            records.push_back(record);
+        }
 
-    // ========================================================================
-    // Admin Functions
-    // ========================================================================
+        // Anchored on the first event nonce, like certificate ids, but over a
+        // shorter payload so the two cannot collide.
+        let first = records.get(0).unwrap();
+        let mut payload = Bytes::new(&env);
+        payload.append(&Bytes::from_array(&env, &first.event_nonce.to_be_bytes()));
+        payload.append(&Bytes::from_array(&env, &first.timestamp.to_be_bytes()));
+        let portfolio_id: BytesN<32> = env.crypto().sha256(&payload).into();
+
+        let mut cert_ids = Vec::new(&env);
+        for record in records.iter() {
+            let mut single = Vec::new(&env);
+            single.push_back(record);
+            let certificate = Self::issue_certificate(
+                &env,
+                &holder,
+                &single,
+                beneficiary.clone(),
+                reason.clone(),
+                note.clone(),
+                Some(portfolio_id.clone()),
+            );
+            cert_ids.push_back(certificate.cert_id);
+        }
 
-    /// Update the linked CarbonAsset contract address
+        PortfolioRetiredEvent {
+            portfolio_id,
+            holder,
+            certificates: cert_ids.len(),
+            timestamp: first.timestamp,
+        }
+        .publish(&env);
+        Ok(cert_ids)
+    }
+
+    /// Request the retirement of carbon credit tokens, escrowing them in the tracker
+    ///
+    /// The tokens stay cancellable until the confirmation window elapses. The
+    /// holder may confirm at any time; once the window has elapsed anyone may
+    /// confirm to finalize an abandoned request.
     ///
     /// # Arguments
-    /// * `new_contract` - The new CarbonAsset contract address
+    /// * `holder` - The Stellar account that owns the credits
+    /// * `token_ids` - The IDs of the CarbonAsset tokens to retire
+    /// * `beneficiary` - The party on whose behalf the offset is claimed
+    /// * `reason_code` - Registered reason code recorded on the certificate
+    /// * `note` - Optional free-text note recorded on the certificate
+    ///
+    /// # Returns
+    /// The ID of the retirement request
     ///
     /// # Errors
-    /// * `ContractError::NotAuthorized` - Caller is not the admin
-    pub fn update_carbon_asset_contract(
+    /// * `ContractError::EmptyBundle` - No token IDs were supplied
+    /// * `ContractError::UnknownReasonCode` - The reason code is not registered
+    /// * `ContractError::ContractNotInitialized` - Contract is not initialized
+    pub fn request_retirement(
         env: Env,
-        caller: Address,
-        new_contract: Address,
-    ) -> Result<(), ContractError> {
-        // Require auth for admin function
-        caller.require_auth();
+        holder: Address,
+        token_ids: Vec<u32>,
+        beneficiary: String,
+        reason_code: Symbol,
+        note: Option<String>,
+    ) -> Result<u64, ContractError> {
+        holder.require_auth();
+        Self::require_not_halted(&env)?;
+
+        if token_ids.is_empty() {
+            return Err(ContractError::EmptyBundle);
+        }
+        Self::registered_reason(&env, &reason_code)?;
 
-        let admin: Address = env
+        let carbon_asset_contract: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
+            .get(&DataKey::CarbonAssetContract)
             .ok_or(ContractError::ContractNotInitialized)?;
 
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
+        let escrow_symbol = Symbol::new(&env, "escrow_for_retirement");
+        for token_id in token_ids.iter() {
+            let mut escrow_args = Vec::new(&env);
+            escrow_args.push_back(token_id.into_val(&env));
+            escrow_args.push_back(holder.clone().into_val(&env));
+            env.invoke_contract::<()>(&carbon_asset_contract, &escrow_symbol, escrow_args);
         }
 
-        let old_contract: Address = env
+        let request_id = env
             .storage()
             .instance()
-            .get(&DataKey::CarbonAssetContract)
-            .ok_or(ContractError::ContractNotInitialized)?;
-
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(0u64)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRequestId, &request_id);
+
+        let requested_at = env.ledger().timestamp();
+        let expires_at = requested_at + Self::get_confirmation_window(env.clone());
+        let request = RetirementRequest {
+            request_id,
+            holder: holder.clone(),
+            token_ids,
+            beneficiary,
+            reason_code,
+            note,
+            requested_at,
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::RetirementRequest(request_id), &request);
+
+        RetirementRequestedEvent {
+            request_id,
+            holder,
+            amount: request.token_ids.len() as i128,
+            expires_at,
+        }
+        .publish(&env);
+        Ok(request_id)
+    }
+
+    /// Finalize a pending retirement request: burn the escrowed tokens and
+    /// issue a certificate to the holder
+    ///
+    /// # Arguments
+    /// * `caller` - The holder, or anyone once the confirmation window has elapsed
+    /// * `request_id` - The retirement request to confirm
+    ///
+    /// # Returns
+    /// The ID of the issued certificate
+    ///
+    /// # Errors
+    /// * `ContractError::RequestNotFound` - No pending request with this ID
+    /// * `ContractError::ConfirmationWindowOpen` - Caller is not the holder and the window is still open
+    pub fn confirm_retirement(
+        env: Env,
+        caller: Address,
+        request_id: u64,
+    ) -> Result<BytesN<32>, ContractError> {
+        caller.require_auth();
+        Self::require_not_halted(&env)?;
+
+        let request_key = DataKey::RetirementRequest(request_id);
+        let request: RetirementRequest = env
+            .storage()
+            .persistent()
+            .get(&request_key)
+            .ok_or(ContractError::RequestNotFound)?;
+
+        if caller != request.holder && env.ledger().timestamp() < request.expires_at {
+            return Err(ContractError::ConfirmationWindowOpen);
+        }
+        let reason = Self::registered_reason(&env, &request.reason_code)?;
+
+        let mut records = Vec::new(&env);
+        for token_id in request.token_ids.iter() {
+            let record = Self::retire_from(
+                env.clone(),
+                token_id,
+                env.current_contract_address(),
+                request.holder.clone(),
+                request.note.clone(),
+                None,
+            )?;
+            records.push_back(record);
+        }
+        env.storage().persistent().remove(&request_key);
+
+        let certificate = Self::issue_certificate(
+            &env,
+            &request.holder,
+            &records,
+            request.beneficiary,
+            reason,
+            request.note,
+            None,
+        );
+        Ok(certificate.cert_id)
+    }
+
+    /// Cancel a pending retirement request and return the escrowed tokens
+    ///
+    /// # Arguments
+    /// * `request_id` - The retirement request to cancel
+    ///
+    /// # Errors
+    /// * `ContractError::RequestNotFound` - No pending request with this ID
+    /// * `ContractError::ConfirmationWindowExpired` - The confirmation window has elapsed
+    pub fn cancel_retirement(env: Env, request_id: u64) -> Result<(), ContractError> {
+        let request_key = DataKey::RetirementRequest(request_id);
+        let request: RetirementRequest = env
+            .storage()
+            .persistent()
+            .get(&request_key)
+            .ok_or(ContractError::RequestNotFound)?;
+        request.holder.require_auth();
+
+        if env.ledger().timestamp() >= request.expires_at {
+            return Err(ContractError::ConfirmationWindowExpired);
+        }
+
+        let carbon_asset_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        let release_symbol = Symbol::new(&env, "release_from_escrow");
+        for token_id in request.token_ids.iter() {
+            let mut release_args = Vec::new(&env);
+            release_args.push_back(token_id.into_val(&env));
+            release_args.push_back(request.holder.clone().into_val(&env));
+            env.invoke_contract::<()>(&carbon_asset_contract, &release_symbol, release_args);
+        }
+        env.storage().persistent().remove(&request_key);
+
+        RetirementCancelledEvent {
+            request_id,
+            holder: request.holder,
+            amount: request.token_ids.len() as i128,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    fn issue_certificate(
+        env: &Env,
+        holder: &Address,
+        records: &Vec<RetirementRecord>,
+        beneficiary: String,
+        reason: ReasonCode,
+        note: Option<String>,
+        portfolio_id: Option<BytesN<32>>,
+    ) -> RetirementCertificate {
+        let mut metadata = Vec::new(env);
+        for record in records.iter() {
+            metadata.push_back(Self::fetch_metadata(env, record.token_id));
+        }
+        Self::issue_certificate_with_metadata(
+            env,
+            holder,
+            records,
+            &metadata,
+            beneficiary,
+            reason,
+            note,
+            portfolio_id,
+        )
+    }
+
+    /// Same as `issue_certificate`, with the metadata of each record already
+    /// known, for callbacks from CarbonAsset that cannot be re-entered.
+    #[allow(clippy::too_many_arguments)]
+    fn issue_certificate_with_metadata(
+        env: &Env,
+        holder: &Address,
+        records: &Vec<RetirementRecord>,
+        metadata: &Vec<CarbonAssetMetadata>,
+        beneficiary: String,
+        reason: ReasonCode,
+        note: Option<String>,
+        portfolio_id: Option<BytesN<32>>,
+    ) -> RetirementCertificate {
+        let first = records.get(0).unwrap();
+
+        // Event nonces are unique per successful retirement, so they anchor the id.
+        let mut payload = Bytes::new(env);
+        let mut components = Vec::new(env);
+        for (record, metadata) in records.iter().zip(metadata.iter()) {
+            payload.append(&Bytes::from_array(env, &record.token_id.to_be_bytes()));
+            payload.append(&Bytes::from_array(env, &record.event_nonce.to_be_bytes()));
+
+            Self::record_statistics(env, &metadata, 1);
+            components.push_back(CertificateComponent {
+                token_id: record.token_id,
+                project_id: metadata.project_id,
+                vintage_year: metadata.vintage_year,
+                amount: 1,
+            });
+        }
+        payload.append(&Bytes::from_array(env, &first.timestamp.to_be_bytes()));
+        let cert_id: BytesN<32> = env.crypto().sha256(&payload).into();
+
+        let certificate = RetirementCertificate {
+            cert_id: cert_id.clone(),
+            holder: holder.clone(),
+            beneficiary: beneficiary.clone(),
+            token_id: first.token_id,
+            amount: components.len() as i128,
+            reason_code: reason.code,
+            category: reason.category.clone(),
+            note,
+            timestamp: first.timestamp,
+            components,
+            owner: holder.clone(),
+            assigned_at: None,
+            portfolio_id: portfolio_id.clone(),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Certificate(cert_id.clone()), &certificate);
+        Self::append_certificate_id(env, &DataKey::HolderCertificates(holder.clone()), &cert_id);
+        Self::append_certificate_id(env, &DataKey::OwnerCertificates(holder.clone()), &cert_id);
+        Self::append_certificate_id(
+            env,
+            &DataKey::BeneficiaryCertificates(beneficiary.clone()),
+            &cert_id,
+        );
+        Self::append_certificate_id(
+            env,
+            &DataKey::CategoryCertificates(reason.category),
+            &cert_id,
+        );
+        for record in records.iter() {
+            Self::append_certificate_id(
+                env,
+                &DataKey::TokenCertificates(record.token_id),
+                &cert_id,
+            );
+        }
+        if let Some(portfolio_id) = portfolio_id {
+            Self::append_certificate_id(env, &DataKey::Portfolio(portfolio_id), &cert_id);
+        }
+        Self::adjust_annual_report(env, holder, &certificate, 1);
+        Self::record_certificate_audit(env, &certificate);
+
+        CertificateIssuedEvent {
+            cert_id,
+            token_id: first.token_id,
+            holder: holder.clone(),
+            beneficiary,
+            amount: certificate.amount,
+            timestamp: first.timestamp,
+        }
+        .publish(env);
+
+        certificate
+    }
+
+    /// Check a certificate against its canonical hash
+    ///
+    /// The hash is the SHA-256 of the certificate as issued, so it still
+    /// matches after `assign_certificate`. It is the value recorded in the
+    /// audit trail at issuance.
+    ///
+    /// # Returns
+    /// False when the certificate does not exist or the hash differs
+    pub fn verify_certificate(env: Env, cert_id: BytesN<32>, expected_hash: BytesN<32>) -> bool {
+        match Self::get_certificate(env.clone(), cert_id) {
+            Some(certificate) => Self::certificate_hash(&env, &certificate) == expected_hash,
+            None => false,
+        }
+    }
+
+    /// Assign the claim on a certificate to a new owner
+    ///
+    /// Lets a broker that retired in bulk hand the claim to an end client. A
+    /// certificate can be reassigned only once so claims cannot be traded on.
+    /// The original holder index is unchanged; the owner index moves.
+    ///
+    /// # Arguments
+    /// * `cert_id` - The certificate to assign
+    /// * `new_owner` - The account receiving the claim
+    ///
+    /// # Errors
+    /// * `ContractError::CertificateNotFound` - No certificate with this ID
+    /// * `ContractError::CertificateAlreadyAssigned` - The certificate was already reassigned
+    pub fn assign_certificate(
+        env: Env,
+        cert_id: BytesN<32>,
+        new_owner: Address,
+    ) -> Result<(), ContractError> {
+        let cert_key = DataKey::Certificate(cert_id.clone());
+        let mut certificate: RetirementCertificate = env
+            .storage()
+            .persistent()
+            .get(&cert_key)
+            .ok_or(ContractError::CertificateNotFound)?;
+        certificate.owner.require_auth();
+
+        if certificate.assigned_at.is_some() {
+            return Err(ContractError::CertificateAlreadyAssigned);
+        }
+
+        let previous_owner = certificate.owner.clone();
+        let timestamp = env.ledger().timestamp();
+        certificate.owner = new_owner.clone();
+        certificate.assigned_at = Some(timestamp);
+        env.storage().persistent().set(&cert_key, &certificate);
+
+        let previous_key = DataKey::OwnerCertificates(previous_owner.clone());
+        let mut previous_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&previous_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(index) = previous_ids.first_index_of(&cert_id) {
+            previous_ids.remove(index);
+        }
+        env.storage().persistent().set(&previous_key, &previous_ids);
+        Self::append_certificate_id(
+            &env,
+            &DataKey::OwnerCertificates(new_owner.clone()),
+            &cert_id,
+        );
+        Self::adjust_annual_report(&env, &previous_owner, &certificate, -1);
+        Self::adjust_annual_report(&env, &new_owner, &certificate, 1);
+
+        CertificateAssignedEvent {
+            cert_id,
+            from: previous_owner,
+            to: new_owner,
+            timestamp,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Retire multiple carbon credit tokens in a single transaction
+    ///
+    /// # Arguments
+    /// * `token_ids` - Vector of token IDs to retire
+    /// * `retiring_entity` - The Stellar account address retiring the credits
+    /// * `reason` - Optional reason for retirement (applied to all tokens)
+    ///
+    /// # Returns
+    /// Vector of RetirementRecords created
+    ///
+    /// # Errors
+    /// Returns errors for individual tokens that fail, but continues processing others
+    pub fn batch_retire(
+        env: Env,
+        token_ids: Vec<u32>,
+        retiring_entity: Address,
+        reason: Option<String>,
+    ) -> Vec<RetirementRecord> {
+        retiring_entity.require_auth();
+
+        let mut results = Vec::new(&env);
+        if Self::require_not_halted(&env).is_err() {
+            return results;
+        }
+
+        for i in 0..token_ids.len() {
+            let token_id = token_ids.get(i).unwrap();
+
+            // Attempt to retire each token
+            // Continue even if one fails
+            if let Ok(record) = Self::retire_internal(
+                env.clone(),
+                token_id,
+                retiring_entity.clone(),
+                reason.clone(),
+                None,
+            ) {
+                results.push_back(record);
+            }
+        }
+
+        results
+    }
+
+    /// Retire multiple carbon credit tokens with caller-supplied transaction
+    /// hashes. Each successful retirement receives its own event nonce.
+    pub fn batch_retire_with_tx_hashes(
+        env: Env,
+        token_ids: Vec<u32>,
+        retiring_entity: Address,
+        reason: Option<String>,
+        tx_hashes: Vec<BytesN<32>>,
+    ) -> Vec<RetirementRecord> {
+        retiring_entity.require_auth();
+
+        let mut results = Vec::new(&env);
+        if Self::require_not_halted(&env).is_err() {
+            return results;
+        }
+
+        for i in 0..token_ids.len() {
+            if i >= tx_hashes.len() {
+                break;
+            }
+
+            let token_id = token_ids.get(i).unwrap();
+            let tx_hash = tx_hashes.get(i).unwrap();
+
+            if let Ok(record) = Self::retire_internal(
+                env.clone(),
+                token_id,
+                retiring_entity.clone(),
+                reason.clone(),
+                Some(tx_hash),
+            ) {
+                results.push_back(record);
+            }
+        }
+
+        results
+    }
+
+    /// Check if a token has been retired
+    ///
+    /// # Arguments
+    /// * `token_id` - The token ID to check
+    ///
+    /// # Returns
+    /// `true` if the token is retired, `false` otherwise
+    pub fn is_retired(env: Env, token_id: u32) -> bool {
+        let ledger_key = DataKey::RetirementLedger(token_id);
+        env.storage().persistent().has(&ledger_key)
+    }
+
+    /// Get the full retirement record for a token
+    ///
+    /// # Arguments
+    /// * `token_id` - The token ID to query
+    ///
+    /// # Returns
+    /// `Some(RetirementRecord)` if the token is retired, `None` otherwise
+    pub fn get_retirement_record(env: Env, token_id: u32) -> Option<RetirementRecord> {
+        let ledger_key = DataKey::RetirementLedger(token_id);
+        env.storage().persistent().get(&ledger_key)
+    }
+
+    /// Get all token IDs retired by a specific entity
+    ///
+    /// # Arguments
+    /// * `retiring_entity` - The address to query
+    ///
+    /// # Returns
+    /// Vector of token IDs retired by the entity
+    pub fn get_retirements_by_entity(env: Env, retiring_entity: Address) -> Vec<u32> {
+        let entity_key = DataKey::EntityIndex(retiring_entity);
+        env.storage()
+            .persistent()
+            .get(&entity_key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a retirement certificate by its ID
+    ///
+    /// # Returns
+    /// `Some(RetirementCertificate)` if the certificate exists, `None` otherwise
+    pub fn get_certificate(env: Env, cert_id: BytesN<32>) -> Option<RetirementCertificate> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Certificate(cert_id))
+    }
+
+    /// Get a page of certificates issued to a holder, in issuance order
+    ///
+    /// # Arguments
+    /// * `holder` - The address to query
+    /// * `start` - Index of the first certificate to return
+    /// * `limit` - Maximum number of certificates to return
+    pub fn get_certificates_by_holder(
+        env: Env,
+        holder: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RetirementCertificate> {
+        Self::certificates_page(&env, &DataKey::HolderCertificates(holder), start, limit)
+    }
+
+    /// Get a page of certificates currently owned by an account
+    ///
+    /// Unlike `get_certificates_by_holder`, this follows reassignments made
+    /// with `assign_certificate`.
+    ///
+    /// # Arguments
+    /// * `owner` - The address to query
+    /// * `start` - Index of the first certificate to return
+    /// * `limit` - Maximum number of certificates to return
+    pub fn get_certificates_by_owner(
+        env: Env,
+        owner: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RetirementCertificate> {
+        Self::certificates_page(&env, &DataKey::OwnerCertificates(owner), start, limit)
+    }
+
+    /// Get a page of certificates issued for a token, in issuance order
+    ///
+    /// # Arguments
+    /// * `token_id` - The token ID to query
+    /// * `start` - Index of the first certificate to return
+    /// * `limit` - Maximum number of certificates to return
+    pub fn get_certificates_by_token(
+        env: Env,
+        token_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RetirementCertificate> {
+        Self::certificates_page(&env, &DataKey::TokenCertificates(token_id), start, limit)
+    }
+
+    /// Get a page of certificates whose reason code belongs to a category,
+    /// in issuance order
+    ///
+    /// # Arguments
+    /// * `category` - The reason category to query
+    /// * `start` - Index of the first certificate to return
+    /// * `limit` - Maximum number of certificates to return
+    pub fn get_certificates_by_category(
+        env: Env,
+        category: Symbol,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RetirementCertificate> {
+        Self::certificates_page(&env, &DataKey::CategoryCertificates(category), start, limit)
+    }
+
+    /// Get the certificates issued by one `process_retirement_batch` call,
+    /// in the order their tokens were given
+    pub fn get_certificates_by_portfolio(
+        env: Env,
+        portfolio_id: BytesN<32>,
+    ) -> Vec<RetirementCertificate> {
+        Self::certificates_page(
+            &env,
+            &DataKey::Portfolio(portfolio_id),
+            0,
+            MAX_PORTFOLIO_ITEMS,
+        )
+    }
+
+    /// Get a page of a beneficiary's certificates issued within a time window
+    ///
+    /// Certificates are indexed per beneficiary as they are issued, so the
+    /// index is in timestamp order and the window start is found by binary
+    /// search rather than a scan.
+    ///
+    /// # Arguments
+    /// * `beneficiary` - The party on whose behalf the offsets were claimed
+    /// * `start_ts` - Earliest issuance timestamp, inclusive
+    /// * `end_ts` - Latest issuance timestamp, inclusive
+    /// * `start` - Index of the first certificate to return within the window
+    /// * `limit` - Maximum number of certificates to return
+    pub fn certificates_in_range(
+        env: Env,
+        beneficiary: String,
+        start_ts: u64,
+        end_ts: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RetirementCertificate> {
+        let cert_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BeneficiaryCertificates(beneficiary))
+            .unwrap_or(Vec::new(&env));
+        let mut certificates = Vec::new(&env);
+
+        // First certificate issued at or after `start_ts`
+        let (mut low, mut high) = (0u32, cert_ids.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::certificate_at(&env, &cert_ids, mid).timestamp < start_ts {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let mut i = low.saturating_add(start);
+        while i < cert_ids.len() && certificates.len() < limit {
+            let certificate = Self::certificate_at(&env, &cert_ids, i);
+            if certificate.timestamp > end_ts {
+                break;
+            }
+            certificates.push_back(certificate);
+            i += 1;
+        }
+        certificates
+    }
+
+    /// Get a registered retirement reason code
+    pub fn get_reason_code(env: Env, code: Symbol) -> Option<ReasonCode> {
+        env.storage().persistent().get(&DataKey::ReasonCode(code))
+    }
+
+    /// Get the total number of credits retired with a certificate
+    pub fn total_retired(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalRetired)
+            .unwrap_or(0i128)
+    }
+
+    /// Get the number of credits retired for a project
+    ///
+    /// # Arguments
+    /// * `project_id` - The project ID from the CarbonAsset metadata
+    pub fn total_retired_by_project(env: Env, project_id: String) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProjectRetired(project_id))
+            .unwrap_or(0i128)
+    }
+
+    /// Get the number of credits retired for a project vintage
+    ///
+    /// # Arguments
+    /// * `project_id` - The project ID from the CarbonAsset metadata
+    /// * `vintage_year` - The vintage year from the CarbonAsset metadata
+    pub fn total_retired_by_vintage(env: Env, project_id: String, vintage_year: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VintageRetired(project_id, vintage_year))
+            .unwrap_or(0i128)
+    }
+
+    /// Get an owner's retirement statement for a calendar year
+    ///
+    /// Certificates count toward the UTC year of their retirement timestamp
+    /// and move with the claim when a certificate is reassigned. The report
+    /// is maintained at issuance, so reading it does not scan certificates.
+    ///
+    /// # Arguments
+    /// * `owner` - The certificate owner
+    /// * `year` - The calendar year, e.g. 2025
+    pub fn get_annual_report(env: Env, owner: Address, year: u32) -> RetirementReport {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AnnualReport(owner.clone(), year))
+            .unwrap_or(RetirementReport {
+                owner,
+                year,
+                total: 0,
+                certificates: 0,
+                by_vintage: Vec::new(&env),
+                by_project: Vec::new(&env),
+                by_category: Vec::new(&env),
+            })
+    }
+
+    /// Get a pending retirement request
+    ///
+    /// # Returns
+    /// `Some(RetirementRequest)` while the request is pending, `None` once it
+    /// has been confirmed or cancelled
+    pub fn get_retirement_request(env: Env, request_id: u64) -> Option<RetirementRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RetirementRequest(request_id))
+    }
+
+    /// Get the confirmation window applied to new retirement requests, in seconds
+    pub fn get_confirmation_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConfirmationWindow)
+            .unwrap_or(DEFAULT_CONFIRMATION_WINDOW)
+    }
+
+    /// Get the latest contract-scoped event nonce.
+    pub fn get_event_nonce(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventNonce)
+            .unwrap_or(0u64)
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Update the linked CarbonAsset contract address
+    ///
+    /// # Arguments
+    /// * `new_contract` - The new CarbonAsset contract address
+    ///
+    /// # Errors
+    /// * `ContractError::NotAuthorized` - Caller is not the admin
+    pub fn update_carbon_asset_contract(
+        env: Env,
+        caller: Address,
+        new_contract: Address,
+    ) -> Result<(), ContractError> {
+        // Require auth for admin function
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let old_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CarbonAssetContract, &new_contract);
+
+        // Emit event
+        ContractUpdatedEvent {
+            old_contract,
+            new_contract,
+            updated_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set the circuit breaker consulted before retiring
+    ///
+    /// While the breaker halts `retirements`, retirement entry points fail
+    /// with `SystemHalted` and the batch variants retire nothing. Cancelling
+    /// a pending request is never halted.
+    ///
+    /// # Arguments
+    /// * `circuit_breaker` - Address of the CircuitBreaker contract
+    ///
+    /// # Errors
+    /// * `ContractError::NotAuthorized` - Caller is not the admin
+    pub fn set_circuit_breaker(
+        env: Env,
+        caller: Address,
+        circuit_breaker: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CircuitBreaker, &circuit_breaker);
+        Ok(())
+    }
+
+    /// Set the audit trail that records issued certificates
+    ///
+    /// Each certificate is then recorded as a `RETIREMENT_CERTIFICATE` event
+    /// with the hex certificate id as primary entity, the beneficiary as
+    /// secondary entity, and the hex certificate hash as event data. The
+    /// tracker must be an authorized emitter of the audit trail, or
+    /// certificate issuance fails.
+    ///
+    /// # Arguments
+    /// * `audit_trail` - Address of the AuditTrail contract
+    ///
+    /// # Errors
+    /// * `ContractError::NotAuthorized` - Caller is not the admin
+    pub fn set_audit_trail(
+        env: Env,
+        caller: Address,
+        audit_trail: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
         env.storage()
             .instance()
-            .set(&DataKey::CarbonAssetContract, &new_contract);
+            .set(&DataKey::AuditTrail, &audit_trail);
+        Ok(())
+    }
 
-        // Emit event
-        ContractUpdatedEvent {
-            old_contract,
-            new_contract,
-            updated_by: caller,
+    /// Register or update a retirement reason code
+    ///
+    /// # Arguments
+    /// * `code` - Short code referenced by retirements
+    /// * `description` - Human-readable description
+    /// * `category` - Compliance category, e.g. `voluntary`, `compliance`, `corsia`
+    ///
+    /// # Errors
+    /// * `ContractError::NotAuthorized` - Caller is not the admin
+    pub fn register_reason_code(
+        env: Env,
+        caller: Address,
+        code: Symbol,
+        description: String,
+        category: Symbol,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
         }
-        .publish(&env);
+
+        let reason = ReasonCode {
+            code: code.clone(),
+            description,
+            category: category.clone(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReasonCode(code.clone()), &reason);
+
+        ReasonCodeRegisteredEvent { code, category }.publish(&env);
+        Ok(())
+    }
+
+    /// Set the confirmation window applied to new retirement requests
+    ///
+    /// # Arguments
+    /// * `window` - Seconds during which a request may be cancelled
+    ///
+    /// # Errors
+    /// * `ContractError::NotAuthorized` - Caller is not the admin
+    pub fn set_confirmation_window(
+        env: Env,
+        caller: Address,
+        window: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::ContractNotInitialized)?;
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ConfirmationWindow, &window);
         Ok(())
     }
 
@@ -387,6 +1600,306 @@ impl RetirementTracker {
         env.storage().instance().get(&DataKey::CarbonAssetContract)
     }
 
+    /// Get the circuit breaker consulted before retiring, if any
+    pub fn get_circuit_breaker(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::CircuitBreaker)
+    }
+
+    /// Get the audit trail that records issued certificates, if any
+    pub fn get_audit_trail(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AuditTrail)
+    }
+
+    /// Queried once per entry point, before any token is retired
+    fn require_not_halted(env: &Env) -> Result<(), ContractError> {
+        let Some(breaker) = Self::get_circuit_breaker(env.clone()) else {
+            return Ok(());
+        };
+
+        let mut args = Vec::new(env);
+        args.push_back(Symbol::new(env, "retirements").into_val(env));
+        let halted: bool = env.invoke_contract(&breaker, &Symbol::new(env, "is_halted"), args);
+        if halted {
+            return Err(ContractError::SystemHalted);
+        }
+        Ok(())
+    }
+
+    fn registered_reason(env: &Env, code: &Symbol) -> Result<ReasonCode, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReasonCode(code.clone()))
+            .ok_or(ContractError::UnknownReasonCode)
+    }
+
+    /// None when the token does not exist or has been burned
+    fn token_owner(env: &Env, token_id: u32) -> Option<Address> {
+        let carbon_asset_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)?;
+
+        let mut owner_args = Vec::new(env);
+        owner_args.push_back(token_id.into_val(env));
+        match env.try_invoke_contract::<Address, InvokeError>(
+            &carbon_asset_contract,
+            &Symbol::new(env, "owner_of"),
+            owner_args,
+        ) {
+            Ok(Ok(owner)) => Some(owner),
+            _ => None,
+        }
+    }
+
+    fn fetch_metadata(env: &Env, token_id: u32) -> CarbonAssetMetadata {
+        let carbon_asset_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .unwrap();
+
+        // Metadata stays readable on CarbonAsset after the token is burned.
+        let mut metadata_args = Vec::new(env);
+        metadata_args.push_back(token_id.into_val(env));
+        env.invoke_contract(
+            &carbon_asset_contract,
+            &Symbol::new(env, "get_metadata"),
+            metadata_args,
+        )
+    }
+
+    fn record_statistics(env: &Env, metadata: &CarbonAssetMetadata, amount: i128) {
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRetired)
+            .unwrap_or(0i128);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRetired, &(total + amount));
+
+        let project_key = DataKey::ProjectRetired(metadata.project_id.clone());
+        let project_total: i128 = env
+            .storage()
+            .persistent()
+            .get(&project_key)
+            .unwrap_or(0i128);
+        env.storage()
+            .persistent()
+            .set(&project_key, &(project_total + amount));
+
+        let vintage_key =
+            DataKey::VintageRetired(metadata.project_id.clone(), metadata.vintage_year);
+        let vintage_total: i128 = env
+            .storage()
+            .persistent()
+            .get(&vintage_key)
+            .unwrap_or(0i128);
+        env.storage()
+            .persistent()
+            .set(&vintage_key, &(vintage_total + amount));
+    }
+
+    fn adjust_annual_report(
+        env: &Env,
+        owner: &Address,
+        certificate: &RetirementCertificate,
+        sign: i128,
+    ) {
+        let year = Self::calendar_year(certificate.timestamp);
+        let mut report = Self::get_annual_report(env.clone(), owner.clone(), year);
+        report.total += sign * certificate.amount;
+        report.certificates = if sign > 0 {
+            report.certificates + 1
+        } else {
+            report.certificates.saturating_sub(1)
+        };
+
+        // Entries that drop to zero are removed to free room under the cap.
+        for component in certificate.components.iter() {
+            let amount = sign * component.amount;
+
+            let position = report
+                .by_vintage
+                .iter()
+                .position(|line| line.vintage_year == component.vintage_year);
+            match position {
+                Some(i) => {
+                    let mut line = report.by_vintage.get(i as u32).unwrap();
+                    line.amount += amount;
+                    if line.amount == 0 {
+                        report.by_vintage.remove(i as u32);
+                    } else {
+                        report.by_vintage.set(i as u32, line);
+                    }
+                }
+                None if sign > 0 && report.by_vintage.len() < MAX_REPORT_LINES => {
+                    report.by_vintage.push_back(VintageTotal {
+                        vintage_year: component.vintage_year,
+                        amount,
+                    });
+                }
+                None => {}
+            }
+
+            let position = report
+                .by_project
+                .iter()
+                .position(|line| line.project_id == component.project_id);
+            match position {
+                Some(i) => {
+                    let mut line = report.by_project.get(i as u32).unwrap();
+                    line.amount += amount;
+                    if line.amount == 0 {
+                        report.by_project.remove(i as u32);
+                    } else {
+                        report.by_project.set(i as u32, line);
+                    }
+                }
+                None if sign > 0 && report.by_project.len() < MAX_REPORT_LINES => {
+                    report.by_project.push_back(ProjectTotal {
+                        project_id: component.project_id.clone(),
+                        amount,
+                    });
+                }
+                None => {}
+            }
+        }
+
+        let amount = sign * certificate.amount;
+        let position = report
+            .by_category
+            .iter()
+            .position(|line| line.category == certificate.category);
+        match position {
+            Some(i) => {
+                let mut line = report.by_category.get(i as u32).unwrap();
+                line.amount += amount;
+                if line.amount == 0 {
+                    report.by_category.remove(i as u32);
+                } else {
+                    report.by_category.set(i as u32, line);
+                }
+            }
+            None if sign > 0 && report.by_category.len() < MAX_REPORT_LINES => {
+                report.by_category.push_back(CategoryTotal {
+                    category: certificate.category.clone(),
+                    amount,
+                });
+            }
+            None => {}
+        }
+
+        let key = DataKey::AnnualReport(owner.clone(), year);
+        if report.certificates == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &report);
+        }
+    }
+
+    /// Convert a ledger timestamp to its UTC calendar year
+    fn calendar_year(timestamp: u64) -> u32 {
+        // Days-to-civil conversion on a March-based year (H. Hinnant).
+        let z = timestamp / 86_400 + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let year = yoe + era * 400 + if mp >= 10 { 1 } else { 0 };
+        year as u32
+    }
+
+    /// The claim owner and its reassignment are reset to their issued values
+    fn certificate_hash(env: &Env, certificate: &RetirementCertificate) -> BytesN<32> {
+        let mut issued = certificate.clone();
+        issued.owner = issued.holder.clone();
+        issued.assigned_at = None;
+        env.crypto().sha256(&issued.to_xdr(env)).into()
+    }
+
+    /// The certificate id doubles as the audit transaction hash, so each
+    /// certificate yields a distinct audit event
+    fn record_certificate_audit(env: &Env, certificate: &RetirementCertificate) {
+        let Some(audit_trail) = Self::get_audit_trail(env.clone()) else {
+            return;
+        };
+
+        let hash = Self::certificate_hash(env, certificate);
+        let mut args = Vec::new(env);
+        args.push_back(env.current_contract_address().into_val(env));
+        args.push_back(String::from_str(env, CERTIFICATE_EVENT_TYPE).into_val(env));
+        args.push_back(Self::hex_string(env, &certificate.cert_id).into_val(env));
+        args.push_back(Some(certificate.beneficiary.clone()).into_val(env));
+        args.push_back(Self::hex_string(env, &hash).into_val(env));
+        args.push_back(certificate.cert_id.clone().into_val(env));
+        env.invoke_contract::<BytesN<32>>(
+            &audit_trail,
+            &Symbol::new(env, "record_event_auth"),
+            args,
+        );
+    }
+
+    fn hex_string(env: &Env, bytes: &BytesN<32>) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut hex = [0u8; 64];
+        for (i, byte) in bytes.to_array().iter().enumerate() {
+            hex[2 * i] = DIGITS[(byte >> 4) as usize];
+            hex[2 * i + 1] = DIGITS[(byte & 0x0f) as usize];
+        }
+        String::from_bytes(env, &hex)
+    }
+
+    fn append_certificate_id(env: &Env, index_key: &DataKey, cert_id: &BytesN<32>) {
+        let mut cert_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(index_key)
+            .unwrap_or(Vec::new(env));
+        cert_ids.push_back(cert_id.clone());
+        env.storage().persistent().set(index_key, &cert_ids);
+    }
+
+    fn certificate_at(env: &Env, cert_ids: &Vec<BytesN<32>>, index: u32) -> RetirementCertificate {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Certificate(cert_ids.get(index).unwrap()))
+            .unwrap()
+    }
+
+    fn certificates_page(
+        env: &Env,
+        index_key: &DataKey,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RetirementCertificate> {
+        let cert_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(index_key)
+            .unwrap_or(Vec::new(env));
+        let mut certificates = Vec::new(env);
+
+        let total = cert_ids.len();
+        if start >= total {
+            return certificates;
+        }
+
+        let end = core::cmp::min(start.saturating_add(limit), total);
+        for i in start..end {
+            let cert_id = cert_ids.get(i).unwrap();
+            if let Some(certificate) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, RetirementCertificate>(&DataKey::Certificate(cert_id))
+            {
+                certificates.push_back(certificate);
+            }
+        }
+        certificates
+    }
+
     fn next_event_nonce(env: &Env) -> Result<u64, ContractError> {
         let current = env
             .storage()
@@ -403,16 +1916,33 @@ impl RetirementTracker {
 
 #[cfg(test)]
 mod test {
-    use super::{RetirementTracker, RetirementTrackerClient};
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+    use super::{CarbonAssetMetadata, RetirementTracker, RetirementTrackerClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short, Address, BytesN, Env, String, Symbol, Vec,
+    };
 
     #[contract]
     pub struct MockCarbonAsset;
 
     #[contractimpl]
     impl MockCarbonAsset {
-        pub fn burn_token(_env: Env, _token_id: u32, _from: Address) {}
+        pub fn burn_token(env: Env, _token_id: u32, _from: Address) -> BytesN<32> {
+            BytesN::from_array(&env, &[0u8; 32])
+        }
+
+        pub fn escrow_for_retirement(_env: Env, _token_id: u32, _from: Address) {}
+
+        pub fn release_from_escrow(_env: Env, _token_id: u32, _to: Address) {}
+
+        pub fn get_metadata(env: Env, _token_id: u32) -> CarbonAssetMetadata {
+            CarbonAssetMetadata {
+                project_id: String::from_str(&env, "PROJ-1"),
+                vintage_year: 2024,
+                methodology_id: 1,
+                geo_hash: BytesN::from_array(&env, &[0u8; 32]),
+            }
+        }
     }
 
     fn setup() -> (Env, RetirementTrackerClient<'static>, Address) {
@@ -426,6 +1956,18 @@ mod test {
         let client = RetirementTrackerClient::new(&env, &tracker_contract);
 
         client.initialize(&admin, &asset_contract);
+        client.register_reason_code(
+            &admin,
+            &symbol_short!("OFFSET"),
+            &String::from_str(&env, "Voluntary corporate offset"),
+            &symbol_short!("voluntary"),
+        );
+        client.register_reason_code(
+            &admin,
+            &symbol_short!("CORSIA"),
+            &String::from_str(&env, "CORSIA offsetting obligation"),
+            &symbol_short!("corsia"),
+        );
 
         (env, client, retiring_entity)
     }
@@ -501,4 +2043,374 @@ mod test {
         assert_eq!(records.get(1).unwrap().event_nonce, 2);
         assert_eq!(client.get_event_nonce(), 2);
     }
+
+    #[test]
+    fn record_direct_retirement_certifies_an_already_burned_token() {
+        let (env, client, holder) = setup();
+        let metadata = CarbonAssetMetadata {
+            project_id: String::from_str(&env, "PROJ-9"),
+            vintage_year: 2021,
+            methodology_id: 3,
+            geo_hash: BytesN::from_array(&env, &[0u8; 32]),
+        };
+        let beneficiary = String::from_str(&env, "Acme Corp FY2025");
+
+        let cert_id = client.record_direct_retirement(
+            &holder,
+            &4,
+            &metadata,
+            &beneficiary,
+            &symbol_short!("OFFSET"),
+        );
+        assert!(client.is_retired(&4));
+        let certificate = client.get_certificate(&cert_id).unwrap();
+        assert_eq!(certificate.holder, holder);
+        assert_eq!(certificate.amount, 1);
+        // The passed metadata is used, not the asset's
+        let component = certificate.components.get(0).unwrap();
+        assert_eq!(component.project_id, metadata.project_id);
+        assert_eq!(component.vintage_year, 2021);
+        assert_eq!(client.total_retired_by_project(&metadata.project_id), 1);
+
+        let again = client.try_record_direct_retirement(
+            &holder,
+            &4,
+            &metadata,
+            &beneficiary,
+            &symbol_short!("OFFSET"),
+        );
+        assert!(again.is_err());
+    }
+
+    #[test]
+    fn process_retirement_issues_indexed_certificates() {
+        let (env, client, retiring_entity) = setup();
+        let beneficiary = String::from_str(&env, "Acme Corp FY2025");
+        let reason = symbol_short!("OFFSET");
+
+        let first = client.process_retirement(&retiring_entity, &1, &beneficiary, &reason, &None);
+        let second = client.process_retirement(&retiring_entity, &2, &beneficiary, &reason, &None);
+        assert_ne!(first, second);
+        assert!(client.is_retired(&1));
+
+        let certificate = client.get_certificate(&first).unwrap();
+        assert_eq!(certificate.cert_id, first);
+        assert_eq!(certificate.holder, retiring_entity);
+        assert_eq!(certificate.beneficiary, beneficiary);
+        assert_eq!(certificate.token_id, 1);
+        assert_eq!(certificate.amount, 1);
+        assert_eq!(certificate.reason_code, reason);
+        assert_eq!(certificate.category, symbol_short!("voluntary"));
+        assert_eq!(certificate.components.len(), 1);
+        assert_eq!(certificate.components.get(0).unwrap().token_id, 1);
+
+        let by_holder = client.get_certificates_by_holder(&retiring_entity, &0, &10);
+        assert_eq!(by_holder.len(), 2);
+        assert_eq!(by_holder.get(1).unwrap().cert_id, second);
+
+        let paged = client.get_certificates_by_holder(&retiring_entity, &1, &10);
+        assert_eq!(paged.len(), 1);
+        assert_eq!(paged.get(0).unwrap().cert_id, second);
+        assert_eq!(
+            client
+                .get_certificates_by_holder(&retiring_entity, &2, &10)
+                .len(),
+            0
+        );
+
+        let by_token = client.get_certificates_by_token(&2, &0, &10);
+        assert_eq!(by_token.len(), 1);
+        assert_eq!(by_token.get(0).unwrap().cert_id, second);
+    }
+
+    #[test]
+    fn retire_bundle_rejects_empty_and_partially_retired_bundles() {
+        let (env, client, retiring_entity) = setup();
+        let beneficiary = String::from_str(&env, "Acme Corp");
+        let reason = symbol_short!("OFFSET");
+
+        let empty = client.try_retire_bundle(
+            &retiring_entity,
+            &Vec::new(&env),
+            &beneficiary,
+            &reason,
+            &None,
+        );
+        assert!(empty.is_err());
+
+        client.retire(&2, &retiring_entity, &None);
+        let mut token_ids = Vec::new(&env);
+        token_ids.push_back(1);
+        token_ids.push_back(2);
+        let partial =
+            client.try_retire_bundle(&retiring_entity, &token_ids, &beneficiary, &reason, &None);
+        assert!(partial.is_err());
+        assert!(!client.is_retired(&1));
+        assert_eq!(client.get_event_nonce(), 1);
+    }
+
+    #[test]
+    fn process_retirement_rejects_already_retired_token() {
+        let (env, client, retiring_entity) = setup();
+        let beneficiary = String::from_str(&env, "Acme Corp");
+        let reason = symbol_short!("OFFSET");
+
+        client.process_retirement(&retiring_entity, &1, &beneficiary, &reason, &None);
+        let duplicate =
+            client.try_process_retirement(&retiring_entity, &1, &beneficiary, &reason, &None);
+        assert!(duplicate.is_err());
+        assert_eq!(client.get_certificates_by_token(&1, &0, &10).len(), 1);
+    }
+
+    #[test]
+    fn confirm_retirement_is_holder_only_until_window_elapses() {
+        let (env, client, retiring_entity) = setup();
+        let other = Address::generate(&env);
+        let mut token_ids = Vec::new(&env);
+        token_ids.push_back(1);
+
+        let request_id = client.request_retirement(
+            &retiring_entity,
+            &token_ids,
+            &String::from_str(&env, "Acme Corp"),
+            &symbol_short!("OFFSET"),
+            &None,
+        );
+        let request = client.get_retirement_request(&request_id).unwrap();
+        assert_eq!(request.expires_at, request.requested_at + 86_400);
+
+        assert!(client.try_confirm_retirement(&other, &request_id).is_err());
+
+        env.ledger()
+            .with_mut(|li| li.timestamp = request.expires_at);
+        let cert_id = client.confirm_retirement(&other, &request_id);
+
+        assert!(client.is_retired(&1));
+        assert!(client.get_retirement_request(&request_id).is_none());
+        assert_eq!(
+            client.get_certificate(&cert_id).unwrap().holder,
+            retiring_entity
+        );
+        assert!(client.try_confirm_retirement(&other, &request_id).is_err());
+    }
+
+    #[test]
+    fn reason_codes_are_registered_and_validated() {
+        let (env, client, retiring_entity) = setup();
+        let beneficiary = String::from_str(&env, "Acme Corp");
+
+        let registered = client.get_reason_code(&symbol_short!("CORSIA")).unwrap();
+        assert_eq!(registered.category, symbol_short!("corsia"));
+        assert!(client.get_reason_code(&symbol_short!("UNKNOWN")).is_none());
+
+        let unknown = client.try_process_retirement(
+            &retiring_entity,
+            &1,
+            &beneficiary,
+            &symbol_short!("UNKNOWN"),
+            &None,
+        );
+        assert!(unknown.is_err());
+        assert!(!client.is_retired(&1));
+        assert_eq!(client.get_event_nonce(), 0);
+
+        let note = Some(String::from_str(&env, "FY2025 flight emissions"));
+        let cert_id = client.process_retirement(
+            &retiring_entity,
+            &1,
+            &beneficiary,
+            &symbol_short!("CORSIA"),
+            &note,
+        );
+        let certificate = client.get_certificate(&cert_id).unwrap();
+        assert_eq!(certificate.reason_code, symbol_short!("CORSIA"));
+        assert_eq!(certificate.note, note);
+        assert_eq!(client.get_retirement_record(&1).unwrap().reason, note);
+    }
+
+    #[test]
+    fn certificates_are_indexed_by_reason_category() {
+        let (env, client, retiring_entity) = setup();
+        let beneficiary = String::from_str(&env, "Acme Corp");
+
+        let voluntary = client.process_retirement(
+            &retiring_entity,
+            &1,
+            &beneficiary,
+            &symbol_short!("OFFSET"),
+            &None,
+        );
+        let corsia = client.process_retirement(
+            &retiring_entity,
+            &2,
+            &beneficiary,
+            &symbol_short!("CORSIA"),
+            &None,
+        );
+        let second_corsia = client.process_retirement(
+            &retiring_entity,
+            &3,
+            &beneficiary,
+            &symbol_short!("CORSIA"),
+            &None,
+        );
+
+        let by_voluntary =
+            client.get_certificates_by_category(&symbol_short!("voluntary"), &0, &10);
+        assert_eq!(by_voluntary.len(), 1);
+        assert_eq!(by_voluntary.get(0).unwrap().cert_id, voluntary);
+
+        let by_corsia = client.get_certificates_by_category(&symbol_short!("corsia"), &0, &10);
+        assert_eq!(by_corsia.len(), 2);
+        assert_eq!(by_corsia.get(0).unwrap().cert_id, corsia);
+        let paged = client.get_certificates_by_category(&symbol_short!("corsia"), &1, &1);
+        assert_eq!(paged.len(), 1);
+        assert_eq!(paged.get(0).unwrap().cert_id, second_corsia);
+
+        let compliance = Symbol::new(&env, "compliance");
+        assert_eq!(
+            client
+                .get_certificates_by_category(&compliance, &0, &10)
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn assign_certificate_moves_owner_index_once() {
+        let (env, client, broker) = setup();
+        let client_a = Address::generate(&env);
+        let client_b = Address::generate(&env);
+        let beneficiary = String::from_str(&env, "Acme Corp");
+        let reason = symbol_short!("OFFSET");
+
+        let first = client.process_retirement(&broker, &1, &beneficiary, &reason, &None);
+        let second = client.process_retirement(&broker, &2, &beneficiary, &reason, &None);
+        assert_eq!(client.get_certificate(&first).unwrap().owner, broker);
+        assert_eq!(client.get_certificates_by_owner(&broker, &0, &10).len(), 2);
+
+        client.assign_certificate(&first, &client_a);
+
+        let certificate = client.get_certificate(&first).unwrap();
+        assert_eq!(certificate.owner, client_a);
+        assert_eq!(certificate.holder, broker);
+        assert!(certificate.assigned_at.is_some());
+
+        let broker_owned = client.get_certificates_by_owner(&broker, &0, &10);
+        assert_eq!(broker_owned.len(), 1);
+        assert_eq!(broker_owned.get(0).unwrap().cert_id, second);
+        let client_owned = client.get_certificates_by_owner(&client_a, &0, &10);
+        assert_eq!(client_owned.len(), 1);
+        assert_eq!(client_owned.get(0).unwrap().cert_id, first);
+        assert_eq!(client.get_certificates_by_holder(&broker, &0, &10).len(), 2);
+
+        let reassigned = client.try_assign_certificate(&first, &client_b);
+        assert!(reassigned.is_err());
+        assert_eq!(client.get_certificate(&first).unwrap().owner, client_a);
+        assert_eq!(
+            client.get_certificates_by_owner(&client_b, &0, &10).len(),
+            0
+        );
+
+        let missing =
+            client.try_assign_certificate(&BytesN::from_array(&env, &[9u8; 32]), &client_b);
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn annual_report_splits_retirements_at_year_boundary() {
+        let (env, client, broker) = setup();
+        let end_client = Address::generate(&env);
+        let beneficiary = String::from_str(&env, "Acme Corp");
+
+        // 2024-12-31T23:59:59Z
+        env.ledger().set_timestamp(1_735_689_599);
+        client.process_retirement(&broker, &1, &beneficiary, &symbol_short!("OFFSET"), &None);
+
+        // 2025-01-01T00:00:00Z
+        env.ledger().set_timestamp(1_735_689_600);
+        let mut token_ids = Vec::new(&env);
+        token_ids.push_back(2u32);
+        token_ids.push_back(3u32);
+        let bundle = client.retire_bundle(
+            &broker,
+            &token_ids,
+            &beneficiary,
+            &symbol_short!("CORSIA"),
+            &None,
+        );
+        client.process_retirement(&broker, &4, &beneficiary, &symbol_short!("OFFSET"), &None);
+
+        let report_2024 = client.get_annual_report(&broker, &2024);
+        assert_eq!(report_2024.total, 1);
+        assert_eq!(report_2024.certificates, 1);
+        assert_eq!(report_2024.by_category.len(), 1);
+        assert_eq!(
+            report_2024.by_category.get(0).unwrap().category,
+            symbol_short!("voluntary")
+        );
+
+        let report_2025 = client.get_annual_report(&broker, &2025);
+        assert_eq!(report_2025.total, 3);
+        assert_eq!(report_2025.certificates, 2);
+        assert_eq!(report_2025.by_vintage.len(), 1);
+        assert_eq!(report_2025.by_vintage.get(0).unwrap().vintage_year, 2024);
+        assert_eq!(report_2025.by_vintage.get(0).unwrap().amount, 3);
+        assert_eq!(report_2025.by_project.len(), 1);
+        assert_eq!(report_2025.by_project.get(0).unwrap().amount, 3);
+        assert_eq!(report_2025.by_category.len(), 2);
+        assert_eq!(
+            report_2025.by_category.get(0).unwrap().category,
+            symbol_short!("corsia")
+        );
+        assert_eq!(report_2025.by_category.get(0).unwrap().amount, 2);
+        assert_eq!(report_2025.by_category.get(1).unwrap().amount, 1);
+
+        // The claim, and its place in the report, follows a reassignment.
+        client.assign_certificate(&bundle, &end_client);
+        let broker_2025 = client.get_annual_report(&broker, &2025);
+        assert_eq!(broker_2025.total, 1);
+        assert_eq!(broker_2025.certificates, 1);
+        assert_eq!(broker_2025.by_category.len(), 1);
+        let client_2025 = client.get_annual_report(&end_client, &2025);
+        assert_eq!(client_2025.total, 2);
+        assert_eq!(client_2025.by_category.get(0).unwrap().amount, 2);
+        assert_eq!(client.get_annual_report(&end_client, &2024).total, 0);
+        assert_eq!(client.get_annual_report(&broker, &2026).certificates, 0);
+    }
+
+    #[test]
+    fn certificates_in_range_filters_by_beneficiary_and_time() {
+        let (env, client, broker) = setup();
+        let acme = String::from_str(&env, "Acme Corp");
+        let globex = String::from_str(&env, "Globex");
+        let offset = symbol_short!("OFFSET");
+
+        for (token_id, timestamp) in [(1u32, 1_000u64), (2, 2_000), (3, 3_000), (4, 4_000)] {
+            env.ledger().set_timestamp(timestamp);
+            client.process_retirement(&broker, &token_id, &acme, &offset, &None);
+        }
+        env.ledger().set_timestamp(2_500);
+        client.process_retirement(&broker, &5, &globex, &offset, &None);
+
+        let window = client.certificates_in_range(&acme, &2_000, &3_000, &0, &10);
+        assert_eq!(window.len(), 2);
+        assert_eq!(window.get(0).unwrap().token_id, 2);
+        assert_eq!(window.get(1).unwrap().token_id, 3);
+
+        let page = client.certificates_in_range(&acme, &1_500, &5_000, &1, &2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().token_id, 3);
+        assert_eq!(page.get(1).unwrap().token_id, 4);
+
+        let other = client.certificates_in_range(&globex, &0, &u64::MAX, &0, &10);
+        assert_eq!(other.len(), 1);
+        assert_eq!(other.get(0).unwrap().token_id, 5);
+        assert_eq!(
+            client
+                .certificates_in_range(&acme, &4_001, &u64::MAX, &0, &10)
+                .len(),
+            0
+        );
+    }
 }
@@ -0,0 +1,464 @@
+#![cfg(test)]
+
+use carbon_asset::types::{AssetStatus, CarbonAssetMetadata};
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use retirement_tracker::{ContractError, RetirementTracker, RetirementTrackerClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    asset: CarbonAssetClient<'a>,
+    tracker: RetirementTrackerClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let asset_id = env.register(CarbonAsset, ());
+    let tracker_id = env.register(RetirementTracker, ());
+    let asset = CarbonAssetClient::new(&env, &asset_id);
+    let tracker = RetirementTrackerClient::new(&env, &tracker_id);
+
+    asset.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &tracker_id,
+        &String::from_str(&env, "US"),
+    );
+    tracker.initialize(&admin, &asset_id);
+    tracker.register_reason_code(
+        &admin,
+        &symbol_short!("OFFSET"),
+        &String::from_str(&env, "Voluntary corporate offset"),
+        &symbol_short!("voluntary"),
+    );
+
+    Setup {
+        env,
+        admin,
+        asset,
+        tracker,
+    }
+}
+
+fn metadata(env: &Env, project_id: &str, vintage_year: u64) -> CarbonAssetMetadata {
+    CarbonAssetMetadata {
+        project_id: String::from_str(env, project_id),
+        vintage_year,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(env, &[7u8; 32]),
+    }
+}
+
+#[test]
+fn test_process_retirement_burns_and_issues_certificate() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-1", 2024));
+    assert_eq!(s.asset.balance(&holder), 1);
+
+    let beneficiary = String::from_str(&s.env, "Acme Corp");
+    let reason = symbol_short!("OFFSET");
+    let cert_id = s
+        .tracker
+        .process_retirement(&holder, &token_id, &beneficiary, &reason, &None);
+
+    assert_eq!(s.asset.balance(&holder), 0);
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Retired);
+    assert!(s.asset.is_burned(&token_id));
+
+    let certificate = s.tracker.get_certificate(&cert_id).unwrap();
+    assert_eq!(certificate.holder, holder);
+    assert_eq!(certificate.beneficiary, beneficiary);
+    assert_eq!(certificate.token_id, token_id);
+    assert_eq!(certificate.amount, 1);
+    assert_eq!(certificate.reason_code, reason);
+
+    let by_holder = s.tracker.get_certificates_by_holder(&holder, &0, &10);
+    assert_eq!(by_holder.len(), 1);
+    let by_token = s.tracker.get_certificates_by_token(&token_id, &0, &10);
+    assert_eq!(by_token.get(0).unwrap().cert_id, cert_id);
+}
+
+#[test]
+fn test_retire_direct_burns_on_the_asset_and_certifies() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-1", 2024));
+
+    let beneficiary = String::from_str(&s.env, "Acme Corp");
+    let cert_id = s
+        .asset
+        .retire_direct(
+            &holder,
+            &1,
+            &token_id,
+            &Some(beneficiary.clone()),
+            &symbol_short!("OFFSET"),
+        )
+        .unwrap();
+
+    assert_eq!(s.asset.balance(&holder), 0);
+    assert_eq!(s.asset.retired_supply(), 1);
+    assert!(s.asset.is_burned(&token_id));
+    assert!(s.tracker.is_retired(&token_id));
+    let certificate = s.tracker.get_certificate(&cert_id).unwrap();
+    assert_eq!(certificate.holder, holder);
+    assert_eq!(certificate.beneficiary, beneficiary);
+    assert_eq!(
+        s.tracker
+            .total_retired_by_project(&String::from_str(&s.env, "PROJ-1")),
+        1
+    );
+}
+
+#[test]
+fn test_process_retirement_requires_token_ownership() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let other = Address::generate(&s.env);
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-1", 2024));
+
+    let result = s.tracker.try_process_retirement(
+        &other,
+        &token_id,
+        &String::from_str(&s.env, "Acme Corp"),
+        &symbol_short!("OFFSET"),
+        &None,
+    );
+    assert!(result.is_err());
+    assert_eq!(s.asset.owner_of(&token_id), holder);
+    assert!(s
+        .tracker
+        .get_certificates_by_token(&token_id, &0, &10)
+        .is_empty());
+}
+
+#[test]
+fn test_retirement_statistics_by_project_and_vintage() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let beneficiary = String::from_str(&s.env, "Acme Corp");
+    let reason = symbol_short!("OFFSET");
+
+    let minted = [
+        ("PROJ-A", 2023u64),
+        ("PROJ-A", 2023u64),
+        ("PROJ-A", 2024u64),
+        ("PROJ-B", 2024u64),
+    ];
+    for (project_id, vintage_year) in minted.iter() {
+        let token_id = s.asset.mint(
+            &s.admin,
+            &holder,
+            &metadata(&s.env, project_id, *vintage_year),
+        );
+        s.tracker
+            .process_retirement(&holder, &token_id, &beneficiary, &reason, &None);
+    }
+
+    let proj_a = String::from_str(&s.env, "PROJ-A");
+    let proj_b = String::from_str(&s.env, "PROJ-B");
+    assert_eq!(s.tracker.total_retired(), 4);
+    assert_eq!(s.tracker.total_retired_by_project(&proj_a), 3);
+    assert_eq!(s.tracker.total_retired_by_project(&proj_b), 1);
+    assert_eq!(s.tracker.total_retired_by_vintage(&proj_a, &2023), 2);
+    assert_eq!(s.tracker.total_retired_by_vintage(&proj_a, &2024), 1);
+    assert_eq!(s.tracker.total_retired_by_vintage(&proj_b, &2024), 1);
+    assert_eq!(s.tracker.total_retired_by_vintage(&proj_b, &2023), 0);
+}
+
+#[test]
+fn test_failed_retirement_leaves_statistics_unchanged() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let beneficiary = String::from_str(&s.env, "Acme Corp");
+    let reason = symbol_short!("OFFSET");
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-A", 2024));
+
+    s.tracker
+        .process_retirement(&holder, &token_id, &beneficiary, &reason, &None);
+    let duplicate =
+        s.tracker
+            .try_process_retirement(&holder, &token_id, &beneficiary, &reason, &None);
+    assert!(duplicate.is_err());
+
+    let proj_a = String::from_str(&s.env, "PROJ-A");
+    assert_eq!(s.tracker.total_retired(), 1);
+    assert_eq!(s.tracker.total_retired_by_project(&proj_a), 1);
+    assert_eq!(s.tracker.total_retired_by_vintage(&proj_a, &2024), 1);
+}
+
+#[test]
+fn test_retire_bundle_issues_single_certificate_with_components() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let first = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-A", 2023));
+    let second = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-B", 2024));
+
+    let mut token_ids = Vec::new(&s.env);
+    token_ids.push_back(first);
+    token_ids.push_back(second);
+    let cert_id = s.tracker.retire_bundle(
+        &holder,
+        &token_ids,
+        &String::from_str(&s.env, "Acme Corp"),
+        &symbol_short!("OFFSET"),
+        &Some(String::from_str(&s.env, "FY2025 target")),
+    );
+
+    assert_eq!(s.asset.balance(&holder), 0);
+    assert!(s.asset.is_burned(&first));
+    assert!(s.asset.is_burned(&second));
+
+    let certificate = s.tracker.get_certificate(&cert_id).unwrap();
+    assert_eq!(certificate.amount, 2);
+    assert_eq!(certificate.components.len(), 2);
+    let component = certificate.components.get(0).unwrap();
+    assert_eq!(component.token_id, first);
+    assert_eq!(component.project_id, String::from_str(&s.env, "PROJ-A"));
+    assert_eq!(component.vintage_year, 2023);
+    let component = certificate.components.get(1).unwrap();
+    assert_eq!(component.token_id, second);
+    assert_eq!(component.project_id, String::from_str(&s.env, "PROJ-B"));
+    assert_eq!(component.vintage_year, 2024);
+
+    assert_eq!(
+        s.tracker.get_certificates_by_holder(&holder, &0, &10).len(),
+        1
+    );
+    assert_eq!(
+        s.tracker
+            .get_certificates_by_token(&second, &0, &10)
+            .get(0)
+            .unwrap()
+            .cert_id,
+        cert_id
+    );
+    assert_eq!(s.tracker.total_retired(), 2);
+}
+
+#[test]
+fn test_process_retirement_batch_groups_portfolio_certificates() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let mut token_ids = Vec::new(&s.env);
+    for (project_id, vintage_year) in [("FOREST-1", 2023), ("WIND-1", 2024), ("SOLAR-1", 2024)] {
+        token_ids.push_back(s.asset.mint(
+            &s.admin,
+            &holder,
+            &metadata(&s.env, project_id, vintage_year),
+        ));
+    }
+
+    let cert_ids = s.tracker.process_retirement_batch(
+        &holder,
+        &token_ids,
+        &String::from_str(&s.env, "Acme Corp"),
+        &symbol_short!("OFFSET"),
+        &None,
+    );
+    assert_eq!(cert_ids.len(), 3);
+    assert_eq!(s.asset.balance(&holder), 0);
+    assert_eq!(s.tracker.total_retired(), 3);
+
+    let portfolio_id = s
+        .tracker
+        .get_certificate(&cert_ids.get(0).unwrap())
+        .unwrap()
+        .portfolio_id
+        .unwrap();
+    let portfolio = s.tracker.get_certificates_by_portfolio(&portfolio_id);
+    assert_eq!(portfolio.len(), 3);
+    for (i, certificate) in portfolio.iter().enumerate() {
+        let i = i as u32;
+        assert_eq!(certificate.cert_id, cert_ids.get(i).unwrap());
+        assert_eq!(certificate.token_id, token_ids.get(i).unwrap());
+        assert_eq!(certificate.amount, 1);
+        assert_eq!(certificate.portfolio_id, Some(portfolio_id.clone()));
+        assert!(s.asset.is_burned(&certificate.token_id));
+    }
+    assert_eq!(
+        s.tracker.get_certificates_by_holder(&holder, &0, &10).len(),
+        3
+    );
+
+    // Single retirements do not belong to a portfolio
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "FOREST-1", 2023));
+    let cert_id = s.tracker.process_retirement(
+        &holder,
+        &token_id,
+        &String::from_str(&s.env, "Acme Corp"),
+        &symbol_short!("OFFSET"),
+        &None,
+    );
+    assert_eq!(
+        s.tracker.get_certificate(&cert_id).unwrap().portfolio_id,
+        None
+    );
+}
+
+#[test]
+fn test_process_retirement_batch_with_bad_item_changes_nothing() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let other = Address::generate(&s.env);
+    let first = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "FOREST-1", 2023));
+    let second = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "WIND-1", 2024));
+    let foreign = s
+        .asset
+        .mint(&s.admin, &other, &metadata(&s.env, "SOLAR-1", 2024));
+    let beneficiary = String::from_str(&s.env, "Acme Corp");
+    let reason = symbol_short!("OFFSET");
+
+    let mut token_ids = Vec::new(&s.env);
+    token_ids.push_back(first);
+    token_ids.push_back(second);
+    token_ids.push_back(foreign);
+    assert_eq!(
+        s.tracker
+            .try_process_retirement_batch(&holder, &token_ids, &beneficiary, &reason, &None),
+        Err(Ok(ContractError::TokenNotOwned))
+    );
+
+    token_ids.set(2, first);
+    assert_eq!(
+        s.tracker
+            .try_process_retirement_batch(&holder, &token_ids, &beneficiary, &reason, &None),
+        Err(Ok(ContractError::DuplicateToken))
+    );
+
+    let mut oversized = Vec::new(&s.env);
+    for token_id in 0..16 {
+        oversized.push_back(token_id);
+    }
+    assert_eq!(
+        s.tracker
+            .try_process_retirement_batch(&holder, &oversized, &beneficiary, &reason, &None),
+        Err(Ok(ContractError::PortfolioTooLarge))
+    );
+
+    assert_eq!(s.asset.balance(&holder), 2);
+    assert_eq!(s.asset.get_status(&first), AssetStatus::Issued);
+    assert!(!s.tracker.is_retired(&first));
+    assert!(!s.tracker.is_retired(&second));
+    assert_eq!(s.tracker.total_retired(), 0);
+    assert_eq!(s.tracker.get_event_nonce(), 0);
+    assert!(s
+        .tracker
+        .get_certificates_by_holder(&holder, &0, &10)
+        .is_empty());
+}
+
+fn request_single(s: &Setup, holder: &Address, token_id: u32) -> u64 {
+    let mut token_ids = Vec::new(&s.env);
+    token_ids.push_back(token_id);
+    s.tracker.request_retirement(
+        holder,
+        &token_ids,
+        &String::from_str(&s.env, "Acme Corp"),
+        &symbol_short!("OFFSET"),
+        &None,
+    )
+}
+
+#[test]
+fn test_request_retirement_escrows_until_confirmed() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-1", 2024));
+
+    let request_id = request_single(&s, &holder, token_id);
+    assert_eq!(s.asset.balance(&holder), 0);
+    assert_eq!(s.asset.owner_of(&token_id), s.tracker.address);
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Locked);
+    assert!(!s.tracker.is_retired(&token_id));
+
+    let cert_id = s.tracker.confirm_retirement(&holder, &request_id);
+    assert!(s.asset.is_burned(&token_id));
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Retired);
+    assert_eq!(s.tracker.get_certificate(&cert_id).unwrap().holder, holder);
+    assert_eq!(s.tracker.get_retirements_by_entity(&holder).len(), 1);
+    assert_eq!(s.tracker.total_retired(), 1);
+}
+
+#[test]
+fn test_cancel_retirement_returns_tokens_within_window() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-1", 2024));
+    s.tracker.set_confirmation_window(&s.admin, &3_600);
+
+    let request_id = request_single(&s, &holder, token_id);
+    s.env.ledger().with_mut(|li| li.timestamp += 3_599);
+    s.tracker.cancel_retirement(&request_id);
+
+    assert_eq!(s.asset.owner_of(&token_id), holder);
+    assert_eq!(s.asset.balance(&holder), 1);
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Issued);
+    assert!(s.tracker.get_retirement_request(&request_id).is_none());
+    assert!(s
+        .tracker
+        .try_confirm_retirement(&holder, &request_id)
+        .is_err());
+    assert_eq!(s.tracker.total_retired(), 0);
+}
+
+#[test]
+fn test_cancel_after_window_rejected_and_anyone_may_confirm() {
+    let s = setup();
+    let holder = Address::generate(&s.env);
+    let keeper = Address::generate(&s.env);
+    let token_id = s
+        .asset
+        .mint(&s.admin, &holder, &metadata(&s.env, "PROJ-1", 2024));
+
+    let request_id = request_single(&s, &holder, token_id);
+    assert!(s
+        .tracker
+        .try_confirm_retirement(&keeper, &request_id)
+        .is_err());
+
+    s.env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert!(s.tracker.try_cancel_retirement(&request_id).is_err());
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Locked);
+
+    let cert_id = s.tracker.confirm_retirement(&keeper, &request_id);
+    assert!(s.asset.is_burned(&token_id));
+    let certificate = s.tracker.get_certificate(&cert_id).unwrap();
+    assert_eq!(certificate.holder, holder);
+    assert_eq!(
+        s.tracker.get_certificates_by_holder(&holder, &0, &10).len(),
+        1
+    );
+}
@@ -0,0 +1,219 @@
+// Cross-contract test harness. `TestWorld::new()` registers the contracts in
+// one `Env` and wires their addresses together, so scenario tests can drive a
+// credit through its whole lifecycle.
+//
+// To add a contract, register and initialize it in `TestWorld::new()` and
+// expose its client as a field.
+
+use std::cell::Cell;
+
+use audit_trail::{AuditTrailContract, AuditTrailContractClient, DEFAULT_RETENTION_POLICY};
+use carbon_asset::types::CarbonAssetMetadata;
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use circuit_breaker::{CircuitBreaker, CircuitBreakerClient};
+use marketplace::{Marketplace, MarketplaceClient};
+use methodology_library::{MethodologyLibrary, MethodologyLibraryClient, MethodologyMeta};
+use retirement_tracker::{RetirementTracker, RetirementTrackerClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol};
+
+pub const PROJECT_ID: &str = "PROJ-1";
+pub const VINTAGE_YEAR: u64 = 2024;
+
+// Allowances granted by the helpers stay live for this many ledgers.
+const ALLOWANCE_LEDGERS: u32 = 1_000;
+
+pub struct TestWorld<'a> {
+    pub env: Env,
+    pub admin: Address,
+    // Issuing authority registered on the Methodology Library.
+    pub authority: Address,
+    // Off-chain indexer authorized to record audit events.
+    pub indexer: Address,
+    // Circuit breaker guardian, able to halt subsystems.
+    pub guardian: Address,
+    pub carbon: CarbonAssetClient<'a>,
+    pub marketplace: MarketplaceClient<'a>,
+    pub methodology: MethodologyLibraryClient<'a>,
+    pub tracker: RetirementTrackerClient<'a>,
+    pub audit: AuditTrailContractClient<'a>,
+    pub breaker: CircuitBreakerClient<'a>,
+    pub payment: TokenClient<'a>,
+    audit_count: Cell<u32>,
+}
+
+impl<'a> TestWorld<'a> {
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let authority = Address::generate(&env);
+        let indexer = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+        let marketplace = MarketplaceClient::new(&env, &env.register(Marketplace, ()));
+        let methodology =
+            MethodologyLibraryClient::new(&env, &env.register(MethodologyLibrary, ()));
+        let tracker = RetirementTrackerClient::new(&env, &env.register(RetirementTracker, ()));
+        let audit = AuditTrailContractClient::new(&env, &env.register(AuditTrailContract, ()));
+        let breaker = CircuitBreakerClient::new(&env, &env.register(CircuitBreaker, ()));
+
+        carbon.initialize(
+            &admin,
+            &String::from_str(&env, "Carbon Asset"),
+            &String::from_str(&env, "C01"),
+            &tracker.address,
+            &String::from_str(&env, "US"),
+        );
+        carbon.add_marketplace(&admin, &marketplace.address);
+        carbon.set_methodology_library(&admin, &methodology.address);
+        carbon.set_circuit_breaker(&admin, &breaker.address);
+
+        marketplace.initialize(&admin, &carbon.address);
+        marketplace.set_circuit_breaker(&admin, &breaker.address);
+
+        methodology.initialize(
+            &admin,
+            &String::from_str(&env, "Methodology Library"),
+            &String::from_str(&env, "METH"),
+            &0,
+        );
+        methodology.add_authority(&admin, &authority);
+        methodology.add_reporter(&admin, &carbon.address);
+
+        tracker.initialize(&admin, &carbon.address);
+        tracker.set_circuit_breaker(&admin, &breaker.address);
+        tracker.register_reason_code(
+            &admin,
+            &symbol_short!("OFFSET"),
+            &String::from_str(&env, "Voluntary corporate offset"),
+            &symbol_short!("voluntary"),
+        );
+
+        audit.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+        audit.authorize_emitter(&indexer);
+        audit.authorize_emitter(&tracker.address);
+        tracker.set_audit_trail(&admin, &audit.address);
+
+        breaker.initialize(&admin, &guardian);
+
+        let payment_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let payment = TokenClient::new(&env, &payment_id);
+        marketplace.allow_payment_token(&admin, &payment_id);
+
+        TestWorld {
+            env,
+            admin,
+            authority,
+            indexer,
+            guardian,
+            carbon,
+            marketplace,
+            methodology,
+            tracker,
+            audit,
+            breaker,
+            payment,
+            audit_count: Cell::new(0),
+        }
+    }
+
+    pub fn account(&self) -> Address {
+        Address::generate(&self.env)
+    }
+
+    pub fn advance_time(&self, seconds: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + seconds);
+    }
+
+    // Ledger timestamp `seconds` from now, for listing and offer expiries.
+    pub fn deadline(&self, seconds: u64) -> u64 {
+        self.env.ledger().timestamp() + seconds
+    }
+
+    // Register a methodology issued by `authority` and return its token id.
+    pub fn register_methodology(&self, name: &str) -> u32 {
+        let meta = MethodologyMeta {
+            name: String::from_str(&self.env, name),
+            version: String::from_str(&self.env, "1.0"),
+            registry: String::from_str(&self.env, "VCS"),
+            registry_link: String::from_str(&self.env, "https://registry.verra.org"),
+            issuing_authority: self.authority.clone(),
+            ipfs_cid: None,
+        };
+        self.methodology
+            .mint_methodology(&self.authority, &self.authority, &meta)
+    }
+
+    pub fn metadata(&self, methodology_id: u32) -> CarbonAssetMetadata {
+        CarbonAssetMetadata {
+            project_id: String::from_str(&self.env, PROJECT_ID),
+            vintage_year: VINTAGE_YEAR,
+            methodology_id,
+            geo_hash: BytesN::from_array(&self.env, &[7u8; 32]),
+        }
+    }
+
+    // Mint one credit under `methodology_id` to `owner`.
+    pub fn mint(&self, owner: &Address, methodology_id: u32) -> u32 {
+        self.carbon
+            .mint(&self.admin, owner, &self.metadata(methodology_id))
+    }
+
+    pub fn fund(&self, account: &Address, amount: i128) {
+        StellarAssetClient::new(&self.env, &self.payment.address).mint(account, &amount);
+    }
+
+    // Let the marketplace move `amount` of `owner`'s credits.
+    pub fn approve_credits(&self, owner: &Address, amount: i128) {
+        let live_until = self.env.ledger().sequence() + ALLOWANCE_LEDGERS;
+        self.carbon
+            .approve(owner, &self.marketplace.address, &amount, &live_until);
+    }
+
+    // Let the marketplace spend `amount` of `buyer`'s payment token.
+    pub fn approve_payment(&self, buyer: &Address, amount: i128) {
+        let live_until = self.env.ledger().sequence() + ALLOWANCE_LEDGERS;
+        self.payment
+            .approve(buyer, &self.marketplace.address, &amount, &live_until);
+    }
+
+    pub fn set_halted(&self, subsystem: &str, halted: bool) {
+        self.breaker
+            .set_halted(&self.guardian, &Symbol::new(&self.env, subsystem), &halted);
+    }
+
+    pub fn offset_reason(&self) -> Symbol {
+        symbol_short!("OFFSET")
+    }
+
+    // Record an audit event for the project, as the indexer does for each
+    // lifecycle step. Every call gets its own transaction hash.
+    pub fn record_audit(&self, event_type: &str, event_data: &str) -> BytesN<32> {
+        let count = self.audit_count.get() + 1;
+        self.audit_count.set(count);
+        let mut tx_hash = [0u8; 32];
+        tx_hash[..4].copy_from_slice(&count.to_be_bytes());
+
+        self.audit.record_event_auth(
+            &self.indexer,
+            &String::from_str(&self.env, event_type),
+            &String::from_str(&self.env, PROJECT_ID),
+            &None,
+            &String::from_str(&self.env, event_data),
+            &BytesN::from_array(&self.env, &tx_hash),
+        )
+    }
+}
+
+impl Default for TestWorld<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
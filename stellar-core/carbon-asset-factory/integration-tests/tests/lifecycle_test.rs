@@ -0,0 +1,340 @@
+use carbon_asset::types::AssetStatus;
+use integration_tests::{TestWorld, PROJECT_ID, VINTAGE_YEAR};
+use regulatory_checks::{JurisdictionRule, OperationType, RegulatoryCheck, RegulatoryCheckClient};
+use retirement_tracker::CERTIFICATE_EVENT_TYPE;
+use soroban_sdk::testutils::Events as _;
+use soroban_sdk::{contractevent, vec, Address, BytesN, Event, String};
+
+#[test]
+fn test_credit_lifecycle() {
+    let w = TestWorld::new();
+    let seller = w.account();
+    let buyer = w.account();
+    let corporate = w.account();
+
+    // Register methodology
+    let methodology_id = w.register_methodology("VM0042");
+    assert!(w.methodology.is_valid_methodology(&methodology_id));
+    w.record_audit("methodology_registered", "VM0042");
+
+    // Mint
+    w.advance_time(60);
+    let token_id = w.mint(&seller, methodology_id);
+    assert_eq!(w.carbon.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(
+        w.carbon.get_metadata(&token_id).methodology_id,
+        methodology_id
+    );
+    assert!(!w.carbon.is_methodology_deprecated(&token_id));
+    w.record_audit("credit_minted", "token 1");
+
+    // List
+    w.advance_time(60);
+    w.approve_credits(&seller, 1);
+    let listing_id = w.marketplace.create_listing(
+        &seller,
+        &vec![&w.env, token_id],
+        &250,
+        &w.payment.address,
+        &w.deadline(3_600),
+    );
+    assert_eq!(w.carbon.get_status(&token_id), AssetStatus::Listed);
+    w.record_audit("credit_listed", "listing 1");
+
+    // Sell through the marketplace
+    w.advance_time(60);
+    w.fund(&buyer, 250);
+    w.approve_payment(&buyer, 250);
+    w.marketplace.buy(&buyer, &listing_id, &1);
+    assert_eq!(w.carbon.owner_of(&token_id), buyer);
+    assert_eq!(w.carbon.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(w.payment.balance(&seller), 250);
+    w.record_audit("credit_sold", "listing 1");
+
+    // Transfer
+    w.advance_time(60);
+    w.carbon.transfer(&buyer, &corporate, &1);
+    assert_eq!(w.carbon.owner_of(&token_id), corporate);
+    w.record_audit("credit_transferred", "token 1");
+
+    // Retire, which burns the credit
+    w.advance_time(60);
+    let beneficiary = String::from_str(&w.env, "Acme Corp");
+    let cert_id = w.tracker.process_retirement(
+        &corporate,
+        &token_id,
+        &beneficiary,
+        &w.offset_reason(),
+        &None,
+    );
+    assert_eq!(w.carbon.get_status(&token_id), AssetStatus::Retired);
+    assert!(w.carbon.is_burned(&token_id));
+    assert_eq!(w.carbon.balance(&corporate), 0);
+    assert!(w.tracker.is_retired(&token_id));
+    assert_eq!(
+        w.tracker.get_certificate(&cert_id).unwrap().beneficiary,
+        beneficiary
+    );
+    assert_eq!(
+        w.tracker
+            .total_retired_by_vintage(&String::from_str(&w.env, PROJECT_ID), &VINTAGE_YEAR),
+        1
+    );
+    w.record_audit("credit_retired", "token 1");
+
+    // Query the audit trail
+    let events = w
+        .audit
+        .get_events_by_entity(&String::from_str(&w.env, PROJECT_ID));
+    let expected = [
+        "methodology_registered",
+        "credit_minted",
+        "credit_listed",
+        "credit_sold",
+        "credit_transferred",
+        "credit_retired",
+    ];
+    assert_eq!(events.len(), expected.len() as u32);
+    for (event, event_type) in events.iter().zip(expected) {
+        assert_eq!(event.event_type, String::from_str(&w.env, event_type));
+        assert_eq!(event.emitting_contract, w.indexer);
+    }
+    // The tracker records the certificate on its own
+    assert_eq!(w.audit.get_event_count(), expected.len() as u32 + 1);
+}
+
+#[test]
+fn test_methodology_deprecation_reaches_minted_credits() {
+    let w = TestWorld::new();
+    let holder = w.account();
+
+    let methodology_id = w.register_methodology("VM0007");
+    let token_id = w.mint(&holder, methodology_id);
+    assert!(!w.carbon.is_methodology_deprecated(&token_id));
+
+    w.methodology.deprecate(&w.admin, &methodology_id);
+    assert!(w.carbon.is_methodology_deprecated(&token_id));
+
+    // Deprecation is advisory; the credit can still be retired
+    let beneficiary = String::from_str(&w.env, "Acme Corp");
+    w.tracker
+        .process_retirement(&holder, &token_id, &beneficiary, &w.offset_reason(), &None);
+    assert!(w.carbon.is_burned(&token_id));
+}
+
+#[test]
+fn test_methodology_usage_tracks_minted_projects() {
+    let w = TestWorld::new();
+    let holder = w.account();
+
+    let forestry = w.register_methodology("VM0042");
+    let cookstoves = w.register_methodology("VMR0006");
+
+    let mut other_project = w.metadata(forestry);
+    other_project.project_id = String::from_str(&w.env, "PROJ-2");
+
+    w.mint(&holder, forestry);
+    w.carbon
+        .mint_batch(&w.admin, &holder, &w.metadata(forestry), &3, &1_000, &3);
+    w.carbon.mint(&w.admin, &holder, &other_project);
+    w.mint(&holder, cookstoves);
+
+    let usage = w.methodology.get_methodology_usage(&forestry);
+    assert_eq!(usage.project_count, 2);
+    assert_eq!(usage.total_issued, 5);
+    let projects = w
+        .methodology
+        .get_projects_by_methodology(&forestry, &0, &10);
+    assert_eq!(projects.len(), 2);
+    assert_eq!(
+        projects.get(0).unwrap(),
+        String::from_str(&w.env, PROJECT_ID)
+    );
+    assert_eq!(projects.get(1).unwrap(), other_project.project_id);
+
+    let usage = w.methodology.get_methodology_usage(&cookstoves);
+    assert_eq!(usage.project_count, 1);
+    assert_eq!(usage.total_issued, 1);
+    let projects = w
+        .methodology
+        .get_projects_by_methodology(&cookstoves, &0, &10);
+    assert_eq!(
+        projects.get(0).unwrap(),
+        String::from_str(&w.env, PROJECT_ID)
+    );
+}
+
+#[test]
+fn test_halted_transfers_leave_retirement_running() {
+    let w = TestWorld::new();
+    let holder = w.account();
+    let buyer = w.account();
+    let methodology_id = w.register_methodology("VM0042");
+    let listed = w.mint(&holder, methodology_id);
+    let retired = w.mint(&holder, methodology_id);
+
+    w.approve_credits(&holder, 1);
+    let listing_id = w.marketplace.create_listing(
+        &holder,
+        &vec![&w.env, listed],
+        &250,
+        &w.payment.address,
+        &w.deadline(3_600),
+    );
+    w.fund(&buyer, 250);
+    w.approve_payment(&buyer, 250);
+
+    w.set_halted("transfers", true);
+    assert_eq!(
+        w.carbon.try_transfer(&holder, &buyer, &1),
+        Err(Ok(carbon_asset::errors::ContractError::SystemHalted))
+    );
+    assert_eq!(
+        w.marketplace.try_buy(&buyer, &listing_id, &1),
+        Err(Ok(marketplace::errors::ContractError::SystemHalted))
+    );
+    assert_eq!(w.carbon.balance_of(&holder), 2);
+
+    // Retirement is a separate subsystem and keeps working
+    let beneficiary = String::from_str(&w.env, "Acme Corp");
+    w.tracker
+        .process_retirement(&holder, &retired, &beneficiary, &w.offset_reason(), &None);
+    assert!(w.tracker.is_retired(&retired));
+    assert!(w.carbon.is_burned(&retired));
+
+    w.set_halted("transfers", false);
+    w.marketplace.buy(&buyer, &listing_id, &1);
+    assert_eq!(w.carbon.owner_of(&listed), buyer);
+
+    w.set_halted("retirements", true);
+    let halted =
+        w.tracker
+            .try_process_retirement(&buyer, &listed, &beneficiary, &w.offset_reason(), &None);
+    assert!(matches!(
+        halted,
+        Err(Ok(retirement_tracker::ContractError::SystemHalted))
+    ));
+    assert!(!w.carbon.is_burned(&listed));
+}
+
+// Same shape as the Carbon Asset's transfer event, to compare against what
+// it publishes.
+#[contractevent]
+struct TransferEvent {
+    sequence: u64,
+    token_id: u32,
+    from: Address,
+    to: Address,
+    rule_version: Option<u64>,
+}
+
+#[test]
+fn test_transfer_events_record_the_rule_version() {
+    let w = TestWorld::new();
+    let s = |v: &str| String::from_str(&w.env, v);
+    let regulator = RegulatoryCheckClient::new(&w.env, &w.env.register(RegulatoryCheck, ()));
+    // The admin doubles as governance here
+    regulator.initialize(&w.admin, &w.admin, &w.carbon.address);
+    regulator.add_rule(
+        &w.admin,
+        &JurisdictionRule {
+            rule_id: s("open-transfers"),
+            description: s("Transfers allowed everywhere"),
+            source_jur: s("ANY"),
+            dest_jur: s("ANY"),
+            host_jur: s("ANY"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+        },
+    );
+    w.carbon.set_regulatory_check(&w.admin, &regulator.address);
+
+    let seller = w.account();
+    let buyer = w.account();
+    regulator.set_address_jurisdiction(&w.admin, &seller, &s("US"));
+    regulator.set_address_jurisdiction(&w.admin, &buyer, &s("DE"));
+    let methodology_id = w.register_methodology("VM0042");
+
+    let transfer_event = |token_id: u32, rule_version: u64| {
+        TransferEvent {
+            // The SEP-41 transfer event follows with the next sequence
+            sequence: w.carbon.get_event_sequence() - 1,
+            token_id,
+            from: seller.clone(),
+            to: buyer.clone(),
+            rule_version: Some(rule_version),
+        }
+        .to_xdr(&w.env, &w.carbon.address)
+    };
+
+    let first = w.mint(&seller, methodology_id);
+    w.carbon.transfer(&seller, &buyer, &1);
+    let events = w.env.events().all().filter_by_contract(&w.carbon.address);
+    assert_eq!(events.events()[0], transfer_event(first, 1));
+
+    // Tightening the rules between the two transfers bumps the version
+    regulator.set_tier_limit(&w.admin, &0, &10);
+    regulator.allow_corridor(&w.admin, &s("US"), &s("DE"));
+    assert_eq!(regulator.get_rule_version(), 3);
+
+    let second = w.mint(&seller, methodology_id);
+    w.carbon.transfer(&seller, &buyer, &1);
+    let events = w.env.events().all().filter_by_contract(&w.carbon.address);
+    assert_eq!(events.events()[0], transfer_event(second, 3));
+
+    let history = regulator.get_rule_history(&1, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().changed_by, w.admin);
+}
+
+fn hex(bytes: &[u8]) -> std::string::String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn test_certificates_are_anchored_in_the_audit_trail() {
+    let w = TestWorld::new();
+    let holder = w.account();
+    let client = w.account();
+    let methodology_id = w.register_methodology("VM0042");
+    let token_id = w.mint(&holder, methodology_id);
+
+    let beneficiary = String::from_str(&w.env, "Acme Corp");
+    let cert_id =
+        w.tracker
+            .process_retirement(&holder, &token_id, &beneficiary, &w.offset_reason(), &None);
+
+    let cert_entity = String::from_str(&w.env, &hex(&cert_id.to_array()));
+    let events = w.audit.get_events_by_entity(&cert_entity);
+    assert_eq!(events.len(), 1);
+    let event = events.get(0).unwrap();
+    assert_eq!(
+        event.event_type,
+        String::from_str(&w.env, CERTIFICATE_EVENT_TYPE)
+    );
+    assert_eq!(event.emitting_contract, w.tracker.address);
+    assert_eq!(event.secondary_entity_id, Some(beneficiary));
+
+    // The event data is the hex certificate hash
+    let mut hash_hex = [0u8; 64];
+    assert_eq!(event.event_data.len(), 64);
+    event.event_data.copy_into_slice(&mut hash_hex);
+    let mut hash = [0u8; 32];
+    for (i, pair) in hash_hex.chunks(2).enumerate() {
+        let pair = core::str::from_utf8(pair).unwrap();
+        hash[i] = u8::from_str_radix(pair, 16).unwrap();
+    }
+    let hash = BytesN::from_array(&w.env, &hash);
+    assert!(w.tracker.verify_certificate(&cert_id, &hash));
+    assert!(!w
+        .tracker
+        .verify_certificate(&cert_id, &BytesN::from_array(&w.env, &[0u8; 32])));
+    assert!(!w
+        .tracker
+        .verify_certificate(&BytesN::from_array(&w.env, &[9u8; 32]), &hash));
+
+    // Reassigning the claim leaves the issued certificate's hash intact
+    w.tracker.assign_certificate(&cert_id, &client);
+    assert!(w.tracker.verify_certificate(&cert_id, &hash));
+}
@@ -2,12 +2,68 @@
 mod test;
 
 use soroban_sdk::{
-    contract, contractevent, contractimpl, contracttype, Address, Bytes, BytesN, Env, Map, String, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN, Env, Map, String, Vec,
 };
 
 /// Maximum allowed event payload size in bytes.
 pub const MAX_EVENT_PAYLOAD_SIZE: u32 = 1024;
 
+/// Most entity ids accepted by one `get_events_for_entities` call.
+pub const MAX_QUERY_ENTITIES: u32 = 20;
+
+/// Largest page `get_events_for_entities` returns; bigger limits are clamped.
+pub const MAX_QUERY_LIMIT: u32 = 100;
+
+/// Event ids per chunk returned by `get_day_event_ids`.
+pub const DAY_CHUNK_SIZE: u32 = 100;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// 90 days of ledgers at 5 seconds each, matching the default retention
+/// period.
+pub const DEFAULT_RETENTION_POLICY: RetentionPolicy = RetentionPolicy {
+    event_ttl: 1_555_200,
+    index_ttl: 1_555_200,
+    allow_pruning: true,
+};
+
+/// TTLs, in ledgers, applied when event records (`event_ttl`) and index
+/// entries (`index_ttl`) are written or read. Bumps never extend an entry
+/// past its retention period. Policy changes apply to later bumps only.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionPolicy {
+    pub event_ttl: u32,
+    pub index_ttl: u32,
+    pub allow_pruning: bool,
+}
+
+pub const DEFAULT_ENTITY_INDEX_POLICY: EntityIndexPolicy = EntityIndexPolicy {
+    cap: 10_000,
+    strict: false,
+};
+
+/// Bounds the per-entity event index so a hot entity cannot outgrow the
+/// ledger entry size limit. By default `cap` is the size of one index
+/// segment and a full segment rotates into the next one. In `strict` mode
+/// `cap` is the entity's total and further events for it are rejected.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntityIndexPolicy {
+    pub cap: u32,
+    pub strict: bool,
+}
+
+/// How an event's payload is encoded. `Json` payloads are stored in
+/// `event_data`; `Binary` payloads are stored as raw bytes in `binary_data`
+/// and decoded by the client.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataEncoding {
+    Json,
+    Binary,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct AuditEvent {
@@ -18,24 +74,95 @@ pub struct AuditEvent {
     pub primary_entity_id: String,
     pub secondary_entity_id: Option<String>,
     pub event_data: String,
+    pub binary_data: Bytes,
+    pub data_encoding: DataEncoding,
     pub tx_hash: BytesN<32>,
+    pub seq: u64,
+    /// Ledger the event was recorded in; with `seq` it orders events that
+    /// share a timestamp.
+    pub ledger_seq: u32,
+    /// Earlier event this one follows from, e.g. the issuance a reversal
+    /// undoes.
+    pub caused_by: Option<BytesN<32>>,
+    /// Public getters return `event_data` as the hex sha256 of its bytes;
+    /// allowlisted readers see it through `get_event_restricted`.
+    pub restricted: bool,
+}
+
+/// Tamper-evident summary of one UTC day: `digest` is the sha256 of the
+/// day's event ids, concatenated in recording order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyDigest {
+    pub day: u64,
+    pub event_count: u32,
+    pub digest: BytesN<32>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    NotInitialized = 1,
+    InvalidRetentionPolicy = 2,
+    PruningDisabled = 3,
+    EntityIndexFull = 4,
+    InvalidEntityIndexPolicy = 5,
+    Paused = 6,
+    UnknownCause = 7,
+    TooManyEntities = 8,
+    DayNotOver = 9,
+    DayAlreadyFinalized = 10,
+    DayExpired = 11,
+    UnknownEventType = 12,
 }
 
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Admin,
     AuthorizedEmitters,
+    EmitterList,
     Events(BytesN<32>),
-    EntityIndex(String),
+    EntityIndex(BytesN<32>),
+    EntityIndexSegment(BytesN<32>, u32),
+    EntitySegmentCount(BytesN<32>),
+    EntitySegmentLens(BytesN<32>),
+    EntityIndexPolicy,
     TypeTimeIndex((String, u64)),
     ContractIndex(Address),
     RetentionPeriod,
+    RetentionPolicy,
     ActiveDays,
     AllEventsIndex(u64),
     TotalEventCount,
     TotalEventBytes,
+    EventSeq,
+    SeqIndex(u64),
+    MinRetainedSeq,
+    Paused,
+    CausedEvents(BytesN<32>),
+    DailyDigest(u64),
+    Readers,
+    EventTypes,
+}
+
+// The variant names are part of the stored keys and must match `DataKey`'s,
+// so they keep their shared prefix.
+#[allow(clippy::enum_variant_names)]
+mod legacy {
+    use soroban_sdk::{contracttype, String};
+
+    /// Entity index keys as written before entity ids were hashed. They
+    /// are only read, and then removed, by `migrate_entity_index`.
+    #[derive(Clone)]
+    #[contracttype]
+    pub enum LegacyDataKey {
+        EntityIndex(String),
+        EntityIndexSegment(String, u32),
+        EntitySegmentCount(String),
+        EntitySegmentLens(String),
+    }
 }
+pub use legacy::LegacyDataKey;
 
 #[contractevent]
 #[derive(Clone, Debug, PartialEq)]
@@ -45,16 +172,60 @@ pub struct PruningEvent {
     pub timestamp: u64,
 }
 
+#[contractevent]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmittersUpdatedEvent {
+    pub emitters: Vec<Address>,
+    pub authorized: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReaderUpdatedEvent {
+    pub reader: Address,
+    pub allowed: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventTypeUpdatedEvent {
+    pub event_type: String,
+    pub allowed: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PauseEvent {
+    pub paused: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayFinalizedEvent {
+    pub day: u64,
+    pub event_count: u32,
+    pub digest: BytesN<32>,
+    pub timestamp: u64,
+}
+
 #[contract]
 pub struct AuditTrailContract;
 
 #[contractimpl]
 impl AuditTrailContract {
-    pub fn initialize(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
+    pub fn initialize(env: Env, admin: Address, policy: RetentionPolicy) {
+        if access_control::has_admin(&env) {
             panic!("Already initialized");
         }
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        Self::validate_policy(&env, &policy);
+        access_control::set_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::RetentionPolicy, &policy);
 
         let empty_emitters: Map<Address, bool> = Map::new(&env);
         env.storage()
@@ -71,15 +242,50 @@ impl AuditTrailContract {
         Self::extend_instance_ttl(&env);
     }
 
+    pub fn is_initialized(env: Env) -> bool {
+        access_control::has_admin(&env)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        Self::require_initialized(&env);
+        access_control::get_admin(&env).unwrap()
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        access_control::get_pending_admin(&env)
+    }
+
+    /// Nominate `new_admin`. The role moves only when they call
+    /// `accept_admin`.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
+        access_control::propose_admin(&env, &new_admin).unwrap();
+        Self::extend_instance_ttl(&env);
+    }
+
+    pub fn accept_admin(env: Env) {
+        Self::require_initialized(&env);
+        access_control::accept_admin(&env).unwrap();
+        Self::extend_instance_ttl(&env);
+    }
+
+    pub fn cancel_admin_transfer(env: Env) {
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
+        access_control::cancel_admin_transfer(&env).unwrap();
+    }
+
     pub fn authorize_emitter(env: Env, emitter: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
 
         let mut emitters: Map<Address, bool> = env
             .storage()
             .instance()
             .get(&DataKey::AuthorizedEmitters)
             .unwrap();
+        Self::track_emitters(&env, &emitters, &Vec::from_array(&env, [emitter.clone()]));
         emitters.set(emitter.clone(), true);
         env.storage()
             .instance()
@@ -88,14 +294,15 @@ impl AuditTrailContract {
     }
 
     pub fn revoke_emitter(env: Env, emitter: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
 
         let mut emitters: Map<Address, bool> = env
             .storage()
             .instance()
             .get(&DataKey::AuthorizedEmitters)
             .unwrap();
+        Self::track_emitters(&env, &emitters, &Vec::from_array(&env, [emitter.clone()]));
         emitters.set(emitter.clone(), false);
         env.storage()
             .instance()
@@ -103,7 +310,16 @@ impl AuditTrailContract {
         Self::extend_instance_ttl(&env);
     }
 
+    pub fn authorize_emitters(env: Env, emitters: Vec<Address>) {
+        Self::set_emitters(&env, emitters, true);
+    }
+
+    pub fn revoke_emitters(env: Env, emitters: Vec<Address>) {
+        Self::set_emitters(&env, emitters, false);
+    }
+
     pub fn is_authorized(env: Env, emitter: Address) -> bool {
+        Self::require_initialized(&env);
         let emitters: Map<Address, bool> = env
             .storage()
             .instance()
@@ -112,6 +328,81 @@ impl AuditTrailContract {
         emitters.get(emitter).unwrap_or(false)
     }
 
+    /// Every emitter that has ever been authorized or revoked, in the order it
+    /// was first seen, with its current authorization flag.
+    pub fn list_emitters(env: Env) -> Vec<(Address, bool)> {
+        Self::require_initialized(&env);
+        let emitters: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AuthorizedEmitters)
+            .unwrap();
+        let emitter_list: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmitterList)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut listed = Vec::new(&env);
+        for emitter in emitter_list.iter() {
+            let authorized = emitters.get(emitter.clone()).unwrap_or(false);
+            listed.push_back((emitter, authorized));
+        }
+        listed
+    }
+
+    /// Allow `reader` to see restricted payloads through
+    /// `get_event_restricted`.
+    pub fn add_reader(env: Env, reader: Address) {
+        Self::set_reader(&env, reader, true);
+    }
+
+    pub fn remove_reader(env: Env, reader: Address) {
+        Self::set_reader(&env, reader, false);
+    }
+
+    pub fn is_reader(env: Env, reader: Address) -> bool {
+        let readers: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Readers)
+            .unwrap_or_else(|| Map::new(&env));
+        readers.get(reader).unwrap_or(false)
+    }
+
+    /// Add `event_type` to the whitelist. Once the whitelist has an entry,
+    /// events of any other type are rejected with `UnknownEventType`; while
+    /// it is empty every type is accepted.
+    pub fn add_event_type(env: Env, event_type: String) {
+        Self::set_event_type(&env, event_type, true);
+    }
+
+    /// Remove `event_type` from the whitelist. Events already recorded
+    /// under it are kept. Removing the last entry accepts every type again.
+    pub fn remove_event_type(env: Env, event_type: String) {
+        Self::set_event_type(&env, event_type, false);
+    }
+
+    /// The whitelisted event types, empty when every type is accepted.
+    pub fn list_event_types(env: Env) -> Vec<String> {
+        let event_types: Map<String, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventTypes)
+            .unwrap_or_else(|| Map::new(&env));
+        event_types.keys()
+    }
+
+    /// Whether events of `event_type` can be recorded right now.
+    pub fn is_event_type_allowed(env: Env, event_type: String) -> bool {
+        let event_types: Map<String, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventTypes)
+            .unwrap_or_else(|| Map::new(&env));
+        event_types.is_empty() || event_types.contains_key(event_type)
+    }
+
     #[allow(unused_variables)]
     pub fn record_event(
         env: Env,
@@ -133,6 +424,147 @@ impl AuditTrailContract {
         event_data: String,
         tx_hash: BytesN<32>,
     ) -> BytesN<32> {
+        Self::record_event_internal(
+            env.clone(),
+            emitter,
+            event_type,
+            primary_entity_id,
+            secondary_entity_id,
+            event_data,
+            Bytes::new(&env),
+            DataEncoding::Json,
+            tx_hash,
+            None,
+            false,
+        )
+    }
+
+    /// Same as `record_event_auth`, for payloads such as beneficiary
+    /// details that only allowlisted readers may see in full.
+    pub fn record_event_restricted(
+        env: Env,
+        emitter: Address,
+        event_type: String,
+        primary_entity_id: String,
+        secondary_entity_id: Option<String>,
+        event_data: String,
+        tx_hash: BytesN<32>,
+    ) -> BytesN<32> {
+        Self::record_event_internal(
+            env.clone(),
+            emitter,
+            event_type,
+            primary_entity_id,
+            secondary_entity_id,
+            event_data,
+            Bytes::new(&env),
+            DataEncoding::Json,
+            tx_hash,
+            None,
+            true,
+        )
+    }
+
+    /// Same as `record_event_auth`, linking the new event to the earlier
+    /// event `caused_by`, which must exist. `get_caused_events` lists the
+    /// events linked to a given event.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_event_with_cause(
+        env: Env,
+        emitter: Address,
+        event_type: String,
+        primary_entity_id: String,
+        secondary_entity_id: Option<String>,
+        event_data: String,
+        tx_hash: BytesN<32>,
+        caused_by: Option<BytesN<32>>,
+    ) -> BytesN<32> {
+        Self::record_event_internal(
+            env.clone(),
+            emitter,
+            event_type,
+            primary_entity_id,
+            secondary_entity_id,
+            event_data,
+            Bytes::new(&env),
+            DataEncoding::Json,
+            tx_hash,
+            caused_by,
+            false,
+        )
+    }
+
+    /// Same as `record_event_auth`, with a compact binary payload in place
+    /// of a JSON string. The payload counts against
+    /// `MAX_EVENT_PAYLOAD_SIZE` like a JSON one.
+    pub fn record_event_bytes(
+        env: Env,
+        emitter: Address,
+        event_type: String,
+        primary_entity_id: String,
+        secondary_entity_id: Option<String>,
+        event_data: Bytes,
+        tx_hash: BytesN<32>,
+    ) -> BytesN<32> {
+        Self::record_event_internal(
+            env.clone(),
+            emitter,
+            event_type,
+            primary_entity_id,
+            secondary_entity_id,
+            String::from_str(&env, ""),
+            event_data,
+            DataEncoding::Binary,
+            tx_hash,
+            None,
+            false,
+        )
+    }
+
+    /// The `event_id` that recording an event with `tx_hash` in the current
+    /// ledger would return, without writing anything. Ids hash only the
+    /// transaction hash and ledger timestamp, not the event sequence, so the
+    /// preview is exact as long as the event is recorded at the same
+    /// timestamp, whatever is recorded in between. Fails like recording
+    /// would for an unauthorized emitter.
+    pub fn preview_event_id(env: Env, emitter: Address, tx_hash: BytesN<32>) -> BytesN<32> {
+        if !Self::is_authorized(env.clone(), emitter) {
+            panic!("Emitter not authorized");
+        }
+        Self::compute_event_id(&env, &tx_hash, env.ledger().timestamp())
+    }
+
+    /// The payload of `event_id` as raw bytes, with the encoding needed to
+    /// decode it. JSON payloads are returned as their UTF-8 bytes.
+    pub fn get_event_data(env: Env, event_id: BytesN<32>) -> Option<(DataEncoding, Bytes)> {
+        let event = Self::get_event(env, event_id)?;
+        let data = match event.data_encoding {
+            DataEncoding::Json => event.event_data.to_bytes(),
+            DataEncoding::Binary => event.binary_data,
+        };
+        Some((event.data_encoding, data))
+    }
+
+    // Only the field matching `data_encoding` holds the payload; the other
+    // is left empty.
+    #[allow(clippy::too_many_arguments)]
+    fn record_event_internal(
+        env: Env,
+        emitter: Address,
+        event_type: String,
+        primary_entity_id: String,
+        secondary_entity_id: Option<String>,
+        event_data: String,
+        binary_data: Bytes,
+        data_encoding: DataEncoding,
+        tx_hash: BytesN<32>,
+        caused_by: Option<BytesN<32>>,
+        restricted: bool,
+    ) -> BytesN<32> {
+        Self::require_initialized(&env);
+        if Self::is_paused(env.clone()) {
+            panic_with_error!(&env, ContractError::Paused);
+        }
         emitter.require_auth();
 
         let emitters: Map<Address, bool> = env
@@ -143,22 +575,34 @@ impl AuditTrailContract {
         if !emitters.get(emitter.clone()).unwrap_or(false) {
             panic!("Emitter not authorized");
         }
+        if !Self::is_event_type_allowed(env.clone(), event_type.clone()) {
+            panic_with_error!(&env, ContractError::UnknownEventType);
+        }
 
-        let payload_bytes = event_data.len();
+        let payload_bytes = event_data.len() + binary_data.len();
         if payload_bytes > MAX_EVENT_PAYLOAD_SIZE {
             panic!(
                 "Event payload exceeds maximum allowed size of {} bytes",
                 MAX_EVENT_PAYLOAD_SIZE
             );
         }
+        if let Some(cause) = &caused_by {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Events(cause.clone()))
+            {
+                panic_with_error!(&env, ContractError::UnknownCause);
+            }
+        }
 
         let timestamp = env.ledger().timestamp();
+        let ledger_seq = env.ledger().sequence();
 
-        let mut hash_payload = Bytes::new(&env);
-        hash_payload.append(&Bytes::from_slice(&env, &tx_hash.to_array()));
-        hash_payload.append(&Bytes::from_slice(&env, &timestamp.to_be_bytes()));
+        let event_id = Self::compute_event_id(&env, &tx_hash, timestamp);
 
-        let event_id: BytesN<32> = env.crypto().sha256(&hash_payload).into();
+        let seq = Self::current_seq(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::EventSeq, &seq);
 
         let event_size = 32 + 8 
             + event_type.len() as u64 
@@ -166,7 +610,9 @@ impl AuditTrailContract {
             + primary_entity_id.len() as u64 
             + secondary_entity_id.as_ref().map(|s| s.len() as u64).unwrap_or(0) 
             + event_data.len() as u64 
-            + 32;
+            + binary_data.len() as u64
+            + 32
+            + 4;
 
         let event = AuditEvent {
             event_id: event_id.clone(),
@@ -176,7 +622,13 @@ impl AuditTrailContract {
             primary_entity_id: primary_entity_id.clone(),
             secondary_entity_id: secondary_entity_id.clone(),
             event_data,
+            binary_data,
+            data_encoding,
             tx_hash,
+            seq,
+            ledger_seq,
+            caused_by: caused_by.clone(),
+            restricted,
         };
 
         let event_key = DataKey::Events(event_id.clone());
@@ -186,15 +638,24 @@ impl AuditTrailContract {
         
         Self::extend_key_ttl(&env, &event_key, timestamp);
 
-        let entity_key = DataKey::EntityIndex(primary_entity_id.clone());
-        let mut entity_events: Vec<BytesN<32>> = env
-            .storage()
-            .persistent()
-            .get(&entity_key)
-            .unwrap_or_else(|| Vec::new(&env));
-        entity_events.push_back(event_id.clone());
-        env.storage().persistent().set(&entity_key, &entity_events);
-        Self::extend_key_ttl(&env, &entity_key, timestamp);
+        let seq_key = DataKey::SeqIndex(seq);
+        env.storage().persistent().set(&seq_key, &event_id);
+        Self::extend_key_ttl(&env, &seq_key, timestamp);
+
+        let entity = Self::entity_hash(&env, &primary_entity_id);
+        Self::index_entity_event(&env, &entity, &event_id, timestamp);
+
+        if let Some(cause) = caused_by {
+            let caused_key = DataKey::CausedEvents(cause);
+            let mut caused_events: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&caused_key)
+                .unwrap_or_else(|| Vec::new(&env));
+            caused_events.push_back(event_id.clone());
+            env.storage().persistent().set(&caused_key, &caused_events);
+            Self::extend_key_ttl(&env, &caused_key, timestamp);
+        }
 
         let day_timestamp = timestamp / 86400 * 86400;
         let type_time_key = DataKey::TypeTimeIndex((event_type.clone(), day_timestamp));
@@ -277,70 +738,257 @@ impl AuditTrailContract {
             .get::<DataKey, AuditEvent>(&key)
         {
             Self::extend_key_ttl(&env, &key, event.timestamp);
-            Some(event)
+            Some(Self::redact(&env, event))
         } else {
             None
         }
     }
 
-    pub fn get_events_by_entity(env: Env, entity_id: String) -> Vec<AuditEvent> {
-        let entity_key = DataKey::EntityIndex(entity_id);
-        let event_ids: Vec<BytesN<32>> = env
+    /// `get_event` for an allowlisted reader, with restricted payloads in
+    /// full. Other callers get the same redacted event as `get_event`.
+    pub fn get_event_restricted(
+        env: Env,
+        reader: Address,
+        event_id: BytesN<32>,
+    ) -> Option<AuditEvent> {
+        reader.require_auth();
+        let key = DataKey::Events(event_id);
+        let event = env
             .storage()
             .persistent()
-            .get(&entity_key)
-            .unwrap_or_else(|| Vec::new(&env));
+            .get::<DataKey, AuditEvent>(&key)?;
+        Self::extend_key_ttl(&env, &key, event.timestamp);
+        if Self::is_reader(env.clone(), reader) {
+            Some(event)
+        } else {
+            Some(Self::redact(&env, event))
+        }
+    }
+
+    /// Entity ids are matched ignoring ASCII case, so "Project-123" and
+    /// "project-123" name the same entity.
+    pub fn get_events_by_entity(env: Env, entity_id: String) -> Vec<AuditEvent> {
+        Self::get_events_by_entity_paged(env, entity_id, 0, u32::MAX)
+    }
+
+    /// Number of indexed events for `entity_id`, across all index segments.
+    pub fn count_events_by_entity(env: Env, entity_id: String) -> u32 {
+        let entity = Self::entity_hash(&env, &entity_id);
+        Self::entity_segment_lens(&env, &entity).iter().sum()
+    }
+
+    pub fn get_events_by_entity_paged(
+        env: Env,
+        entity_id: String,
+        start: u32,
+        limit: u32,
+    ) -> Vec<AuditEvent> {
+        let entity = Self::entity_hash(&env, &entity_id);
         let mut events = Vec::new(&env);
-        for id in event_ids.iter() {
+        for (segment, id) in Self::entity_event_ids(&env, &entity, start, limit).iter() {
             if let Some(e) = env
                 .storage()
                 .persistent()
                 .get::<DataKey, AuditEvent>(&DataKey::Events(id.clone()))
             {
+                let entity_key = Self::entity_segment_key(&entity, segment);
                 Self::extend_key_ttl(&env, &DataKey::Events(id.clone()), e.timestamp);
                 Self::extend_key_ttl(&env, &entity_key, e.timestamp);
-                events.push_back(e);
+                events.push_back(Self::redact(&env, e));
             }
         }
         events
     }
 
-    pub fn get_events_by_entity_paged(
+    /// Returns up to `limit` events recorded for `entity_id` after the `after`
+    /// cursor, plus the cursor for the next page (the last returned event_id).
+    /// Pages follow recording order, i.e. ascending `(ledger_seq, seq)`.
+    /// A cursor whose event has been pruned restarts from the oldest retained
+    /// event; an empty page returns `None` so callers keep their last cursor.
+    pub fn get_events_by_entity_cursor(
         env: Env,
         entity_id: String,
+        after: Option<BytesN<32>>,
+        limit: u32,
+    ) -> (Vec<AuditEvent>, Option<BytesN<32>>) {
+        let entity = Self::entity_hash(&env, &entity_id);
+        let mut events = Vec::new(&env);
+
+        let start = match after {
+            Some(cursor) => Self::entity_event_position(&env, &entity, &cursor)
+                .map(|i| i + 1)
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        let mut next_cursor = None;
+        for (segment, id) in Self::entity_event_ids(&env, &entity, start, limit).iter() {
+            if let Some(e) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, AuditEvent>(&DataKey::Events(id.clone()))
+            {
+                let entity_key = Self::entity_segment_key(&entity, segment);
+                Self::extend_key_ttl(&env, &DataKey::Events(id.clone()), e.timestamp);
+                Self::extend_key_ttl(&env, &entity_key, e.timestamp);
+                events.push_back(Self::redact(&env, e));
+            }
+            next_cursor = Some(id);
+        }
+        (events, next_cursor)
+    }
+
+    /// Timeline of a single credit: the events recorded with `token_id` as
+    /// their primary entity (mint, transfer, retirement, ...), oldest first.
+    /// The entity index is appended in recording order, so the slice is
+    /// ascending by `(ledger_seq, seq)` and stable across calls.
+    pub fn history_of(env: Env, token_id: String, start: u32, limit: u32) -> Vec<AuditEvent> {
+        Self::get_events_by_entity_paged(env, token_id, start, limit)
+    }
+
+    /// Events recorded for any of `entity_ids`, newest first by
+    /// `(timestamp, ledger_seq, seq)`, skipping the first `start`. An event
+    /// reachable from more than one listed entity is returned once. Accepts
+    /// at most `MAX_QUERY_ENTITIES` ids; `limit` is clamped to
+    /// `MAX_QUERY_LIMIT`.
+    pub fn get_events_for_entities(
+        env: Env,
+        entity_ids: Vec<String>,
         start: u32,
         limit: u32,
     ) -> Vec<AuditEvent> {
-        let entity_key = DataKey::EntityIndex(entity_id);
+        if entity_ids.len() > MAX_QUERY_ENTITIES {
+            panic_with_error!(&env, ContractError::TooManyEntities);
+        }
+        let limit = limit.min(MAX_QUERY_LIMIT);
+        let wanted = start.saturating_add(limit);
+
+        // Indexes are in recording order, so only the newest `wanted` ids of
+        // each can reach the page. Walk them from the end, keeping every
+        // entity's next candidate in `heads`.
+        let mut tails = Vec::new(&env);
+        let mut heads = Vec::new(&env);
+        for entity_id in entity_ids.iter() {
+            let entity = Self::entity_hash(&env, &entity_id);
+            let count: u32 = Self::entity_segment_lens(&env, &entity).iter().sum();
+            let ids = Self::entity_event_ids(&env, &entity, count.saturating_sub(wanted), wanted);
+            let mut pos = ids.len();
+            heads.push_back(Self::previous_entity_event(&env, &ids, &mut pos));
+            tails.push_back((entity, ids, pos));
+        }
+
+        let mut events = Vec::new(&env);
+        let mut seen: Map<BytesN<32>, bool> = Map::new(&env);
+        let mut skipped = 0;
+        while events.len() < limit {
+            let mut newest: Option<(u32, (u64, u32, u64))> = None;
+            for (i, head) in heads.iter().enumerate() {
+                if let Some((_, e)) = head {
+                    let key = (e.timestamp, e.ledger_seq, e.seq);
+                    if newest.is_none_or(|(_, best)| key > best) {
+                        newest = Some((i as u32, key));
+                    }
+                }
+            }
+            let Some((i, _)) = newest else {
+                break;
+            };
+
+            let (segment, e) = heads.get(i).unwrap().unwrap();
+            let (entity, ids, mut pos) = tails.get(i).unwrap();
+            heads.set(i, Self::previous_entity_event(&env, &ids, &mut pos));
+            tails.set(i, (entity.clone(), ids, pos));
+
+            if seen.contains_key(e.event_id.clone()) {
+                continue;
+            }
+            seen.set(e.event_id.clone(), true);
+            if skipped < start {
+                skipped += 1;
+                continue;
+            }
+
+            let entity_key = Self::entity_segment_key(&entity, segment);
+            Self::extend_key_ttl(&env, &DataKey::Events(e.event_id.clone()), e.timestamp);
+            Self::extend_key_ttl(&env, &entity_key, e.timestamp);
+            events.push_back(Self::redact(&env, e));
+        }
+        events
+    }
+
+    /// Events recorded with `caused_by` set to `event_id`, in recording
+    /// order. The other direction is the `caused_by` field of each event.
+    pub fn get_caused_events(
+        env: Env,
+        event_id: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<AuditEvent> {
+        let caused_key = DataKey::CausedEvents(event_id);
         let event_ids: Vec<BytesN<32>> = env
             .storage()
             .persistent()
-            .get(&entity_key)
+            .get(&caused_key)
             .unwrap_or_else(|| Vec::new(&env));
         let mut events = Vec::new(&env);
-
-        let total = event_ids.len();
-        if start >= total {
-            return events;
-        }
-
-        let end = core::cmp::min(start + limit, total);
-
-        for i in start..end {
+        let end = start.saturating_add(limit).min(event_ids.len());
+        for i in start.min(end)..end {
             let id = event_ids.get(i).unwrap();
             if let Some(e) = env
                 .storage()
                 .persistent()
                 .get::<DataKey, AuditEvent>(&DataKey::Events(id.clone()))
             {
-                Self::extend_key_ttl(&env, &DataKey::Events(id.clone()), e.timestamp);
-                Self::extend_key_ttl(&env, &entity_key, e.timestamp);
-                events.push_back(e);
+                Self::extend_key_ttl(&env, &DataKey::Events(id), e.timestamp);
+                Self::extend_key_ttl(&env, &caused_key, e.timestamp);
+                events.push_back(Self::redact(&env, e));
             }
         }
         events
     }
 
+    /// Returns up to `limit` events with a sequence number greater than `seq`,
+    /// in recording order. Consumers pass the last `seq` they processed.
+    pub fn get_events_since(env: Env, seq: u64, limit: u32) -> Vec<AuditEvent> {
+        let mut events = Vec::new(&env);
+        let current = Self::current_seq(env.clone());
+        let min_retained: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinRetainedSeq)
+            .unwrap_or(1);
+
+        let mut next = core::cmp::max(seq.saturating_add(1), min_retained);
+        while next <= current && events.len() < limit {
+            let seq_key = DataKey::SeqIndex(next);
+            if let Some(id) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, BytesN<32>>(&seq_key)
+            {
+                if let Some(e) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, AuditEvent>(&DataKey::Events(id.clone()))
+                {
+                    Self::extend_key_ttl(&env, &DataKey::Events(id), e.timestamp);
+                    Self::extend_key_ttl(&env, &seq_key, e.timestamp);
+                    events.push_back(Self::redact(&env, e));
+                }
+            }
+            next += 1;
+        }
+        events
+    }
+
+    /// Returns the sequence number of the most recently recorded event.
+    pub fn current_seq(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventSeq)
+            .unwrap_or(0)
+    }
+
     pub fn get_events_by_type_and_time(
         env: Env,
         event_type: String,
@@ -362,12 +1010,108 @@ impl AuditTrailContract {
             {
                 Self::extend_key_ttl(&env, &DataKey::Events(id.clone()), e.timestamp);
                 Self::extend_key_ttl(&env, &type_time_key, e.timestamp);
-                events.push_back(e);
+                events.push_back(Self::redact(&env, e));
             }
         }
         events
     }
 
+    /// Ids of the `event_type` events recorded on the day containing
+    /// `day_timestamp`, in recording order, split into chunks of
+    /// `DAY_CHUNK_SIZE`. Reads only the day's index, never the events, so
+    /// archivers can mirror a day cheaply. Chunks past the end are empty.
+    pub fn get_day_event_ids(
+        env: Env,
+        event_type: String,
+        day_timestamp: u64,
+        chunk: u32,
+    ) -> Vec<BytesN<32>> {
+        let day = day_timestamp / 86400 * 86400;
+        let type_time_key = DataKey::TypeTimeIndex((event_type, day));
+        let event_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&type_time_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        let start = chunk.saturating_mul(DAY_CHUNK_SIZE);
+        if start >= event_ids.len() {
+            return Vec::new(&env);
+        }
+        Self::extend_key_ttl(&env, &type_time_key, day);
+        let end = start.saturating_add(DAY_CHUNK_SIZE).min(event_ids.len());
+        event_ids.slice(start..end)
+    }
+
+    /// Number of `get_day_event_ids` chunks for the day; the last one may be
+    /// partial.
+    pub fn get_day_chunk_count(env: Env, event_type: String, day_timestamp: u64) -> u32 {
+        let day = day_timestamp / 86400 * 86400;
+        let event_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TypeTimeIndex((event_type, day)))
+            .unwrap_or_else(|| Vec::new(&env));
+        event_ids.len().div_ceil(DAY_CHUNK_SIZE)
+    }
+
+    /// Seals the day containing `day_timestamp` with a `DailyDigest` of its
+    /// event ids. Anyone may call it once the day has fully passed; each day
+    /// is finalized once, and only while its events are still retained.
+    pub fn finalize_day(env: Env, day_timestamp: u64) -> DailyDigest {
+        let day = day_timestamp / 86400 * 86400;
+        let now = env.ledger().timestamp();
+        if now < day + 86400 {
+            panic_with_error!(&env, ContractError::DayNotOver);
+        }
+        if day + Self::get_retention_period_internal(&env) < now {
+            panic_with_error!(&env, ContractError::DayExpired);
+        }
+        let digest_key = DataKey::DailyDigest(day);
+        if env.storage().persistent().has(&digest_key) {
+            panic_with_error!(&env, ContractError::DayAlreadyFinalized);
+        }
+
+        let event_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllEventsIndex(day))
+            .unwrap_or_else(|| Vec::new(&env));
+        let daily = DailyDigest {
+            day,
+            event_count: event_ids.len(),
+            digest: Self::digest_event_ids(&env, &event_ids),
+        };
+        env.storage().persistent().set(&digest_key, &daily);
+        Self::extend_key_ttl(&env, &digest_key, now);
+
+        DayFinalizedEvent {
+            day,
+            event_count: daily.event_count,
+            digest: daily.digest.clone(),
+            timestamp: now,
+        }
+        .publish(&env);
+        daily
+    }
+
+    pub fn get_daily_digest(env: Env, day_timestamp: u64) -> Option<DailyDigest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DailyDigest(day_timestamp / 86400 * 86400))
+    }
+
+    /// Whether `event_ids`, in recording order, are exactly the events sealed
+    /// in the day's digest. `false` if the day has not been finalized.
+    pub fn verify_day(env: Env, day_timestamp: u64, event_ids: Vec<BytesN<32>>) -> bool {
+        match Self::get_daily_digest(env.clone(), day_timestamp) {
+            Some(daily) => {
+                daily.event_count == event_ids.len()
+                    && daily.digest == Self::digest_event_ids(&env, &event_ids)
+            }
+            None => false,
+        }
+    }
+
     pub fn get_events_by_contract(env: Env, emitter: Address) -> Vec<AuditEvent> {
         let contract_key = DataKey::ContractIndex(emitter);
         let event_ids: Vec<BytesN<32>> = env
@@ -384,15 +1128,15 @@ impl AuditTrailContract {
             {
                 Self::extend_key_ttl(&env, &DataKey::Events(id.clone()), e.timestamp);
                 Self::extend_key_ttl(&env, &contract_key, e.timestamp);
-                events.push_back(e);
+                events.push_back(Self::redact(&env, e));
             }
         }
         events
     }
 
     pub fn set_retention_period(env: Env, period_secs: u64) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
         env.storage().instance().set(&DataKey::RetentionPeriod, &period_secs);
         Self::extend_instance_ttl(&env);
     }
@@ -401,9 +1145,132 @@ impl AuditTrailContract {
         Self::get_retention_period_internal(&env)
     }
 
+    pub fn update_retention_policy(env: Env, policy: RetentionPolicy) {
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
+        Self::validate_policy(&env, &policy);
+        env.storage()
+            .instance()
+            .set(&DataKey::RetentionPolicy, &policy);
+        Self::extend_instance_ttl(&env);
+    }
+
+    pub fn get_retention_policy(env: Env) -> RetentionPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::RetentionPolicy)
+            .unwrap_or(DEFAULT_RETENTION_POLICY)
+    }
+
+    /// Applies to events recorded after the change; existing segments are
+    /// not split or merged.
+    pub fn set_entity_index_policy(env: Env, policy: EntityIndexPolicy) {
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
+        if policy.cap == 0 {
+            panic_with_error!(&env, ContractError::InvalidEntityIndexPolicy);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EntityIndexPolicy, &policy);
+        Self::extend_instance_ttl(&env);
+    }
+
+    pub fn get_entity_index_policy(env: Env) -> EntityIndexPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::EntityIndexPolicy)
+            .unwrap_or(DEFAULT_ENTITY_INDEX_POLICY)
+    }
+
+    /// Moves the index `entity_id` had before entity ids were hashed into
+    /// its hashed index, so events recorded before the upgrade are found by
+    /// the entity queries again. Ids are merged with those indexed since in
+    /// recording order, and ids of pruned events are dropped. Each legacy
+    /// spelling is a separate entry, e.g. "Project-123" and "project-123"
+    /// are migrated one call each. Returns the number of ids moved.
+    pub fn migrate_entity_index(env: Env, entity_id: String) -> u32 {
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
+
+        let count_key = LegacyDataKey::EntitySegmentCount(entity_id.clone());
+        let segments: u32 = env.storage().persistent().get(&count_key).unwrap_or(1);
+        let mut legacy_ids = Vec::new(&env);
+        for segment in 0..segments {
+            let key = if segment == 0 {
+                LegacyDataKey::EntityIndex(entity_id.clone())
+            } else {
+                LegacyDataKey::EntityIndexSegment(entity_id.clone(), segment)
+            };
+            if let Some(ids) = env
+                .storage()
+                .persistent()
+                .get::<LegacyDataKey, Vec<BytesN<32>>>(&key)
+            {
+                legacy_ids.append(&ids);
+                env.storage().persistent().remove(&key);
+            }
+        }
+        env.storage().persistent().remove(&count_key);
+        env.storage()
+            .persistent()
+            .remove(&LegacyDataKey::EntitySegmentLens(entity_id.clone()));
+
+        let legacy = Self::stored_events_by_seq(&env, legacy_ids.iter());
+        if legacy.is_empty() {
+            return 0;
+        }
+        let entity = Self::entity_hash(&env, &entity_id);
+        let indexed = Self::stored_events_by_seq(
+            &env,
+            Self::entity_event_ids(&env, &entity, 0, u32::MAX)
+                .iter()
+                .map(|(_, id)| id),
+        );
+
+        // Both lists are in recording order; merge them on `seq`.
+        let mut merged = Vec::new(&env);
+        let (mut i, mut j) = (0, 0);
+        while i < legacy.len() || j < indexed.len() {
+            let take_legacy = match (legacy.get(i), indexed.get(j)) {
+                (Some(a), Some(b)) => a.0 < b.0,
+                (a, _) => a.is_some(),
+            };
+            if take_legacy {
+                merged.push_back(legacy.get_unchecked(i));
+                i += 1;
+            } else {
+                merged.push_back(indexed.get_unchecked(j));
+                j += 1;
+            }
+        }
+        Self::rewrite_entity_index(&env, &entity, &merged);
+        legacy.len()
+    }
+
+    /// Stop accepting new events, e.g. while indexes are rebuilt. Queries
+    /// and admin calls keep working.
+    pub fn pause(env: Env) {
+        Self::set_paused(&env, true);
+    }
+
+    pub fn unpause(env: Env) {
+        Self::set_paused(&env, false);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
     pub fn prune_old_events(env: Env) -> u32 {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        Self::require_initialized(&env);
+        access_control::require_admin_auth(&env).unwrap();
+        if !Self::get_retention_policy(env.clone()).allow_pruning {
+            panic_with_error!(&env, ContractError::PruningDisabled);
+        }
 
         let retention_period = Self::get_retention_period_internal(&env);
         let current_time = env.ledger().timestamp();
@@ -417,6 +1284,11 @@ impl AuditTrailContract {
         let mut new_active_days = Vec::new(&env);
         let mut pruned_count: u32 = 0;
         let mut pruned_bytes: u64 = 0;
+        let mut min_retained_seq: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinRetainedSeq)
+            .unwrap_or(1);
 
         for day in active_days.iter() {
             if day + retention_period < current_time {
@@ -432,21 +1304,8 @@ impl AuditTrailContract {
                             .persistent()
                             .get::<DataKey, AuditEvent>(&DataKey::Events(event_id.clone()))
                         {
-                            let entity_key = DataKey::EntityIndex(event.primary_entity_id.clone());
-                            if let Some(mut entity_events) = env
-                                .storage()
-                                .persistent()
-                                .get::<DataKey, Vec<BytesN<32>>>(&entity_key)
-                            {
-                                if let Some(idx) = entity_events.first_index_of(&event_id) {
-                                    entity_events.remove(idx);
-                                    if entity_events.is_empty() {
-                                        env.storage().persistent().remove(&entity_key);
-                                    } else {
-                                        env.storage().persistent().set(&entity_key, &entity_events);
-                                    }
-                                }
-                            }
+                            let entity = Self::entity_hash(&env, &event.primary_entity_id);
+                            Self::unindex_entity_event(&env, &entity, &event_id);
 
                             let type_time_key = DataKey::TypeTimeIndex((event.event_type.clone(), day));
                             if let Some(mut type_time_events) = env
@@ -480,17 +1339,48 @@ impl AuditTrailContract {
                                 }
                             }
 
+                            if let Some(cause) = event.caused_by.clone() {
+                                let caused_key = DataKey::CausedEvents(cause);
+                                if let Some(mut caused_events) = env
+                                    .storage()
+                                    .persistent()
+                                    .get::<DataKey, Vec<BytesN<32>>>(&caused_key)
+                                {
+                                    if let Some(idx) = caused_events.first_index_of(&event_id) {
+                                        caused_events.remove(idx);
+                                        if caused_events.is_empty() {
+                                            env.storage().persistent().remove(&caused_key);
+                                        } else {
+                                            env.storage()
+                                                .persistent()
+                                                .set(&caused_key, &caused_events);
+                                        }
+                                    }
+                                }
+                            }
+
                             let event_size = 32 + 8 
                                 + event.event_type.len() as u64 
                                 + 32 
                                 + event.primary_entity_id.len() as u64 
                                 + event.secondary_entity_id.as_ref().map(|s| s.len() as u64).unwrap_or(0) 
                                 + event.event_data.len() as u64 
-                                + 32;
+                                + event.binary_data.len() as u64
+                                + 32
+                                + 4;
 
                             pruned_bytes += event_size;
                             pruned_count += 1;
 
+                            // Events are pruned a whole day at a time, oldest first, so
+                            // the pruned sequence numbers always form a prefix.
+                            env.storage()
+                                .persistent()
+                                .remove(&DataKey::SeqIndex(event.seq));
+                            if event.seq >= min_retained_seq {
+                                min_retained_seq = event.seq + 1;
+                            }
+
                             env.storage().persistent().remove(&DataKey::Events(event_id));
                         }
                     }
@@ -504,6 +1394,10 @@ impl AuditTrailContract {
         env.storage().instance().set(&DataKey::ActiveDays, &new_active_days);
 
         if pruned_count > 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::MinRetainedSeq, &min_retained_seq);
+
             let total_count: u32 = env
                 .storage()
                 .instance()
@@ -554,24 +1448,428 @@ impl AuditTrailContract {
             .unwrap_or(90 * 86400)
     }
 
+    fn digest_event_ids(env: &Env, event_ids: &Vec<BytesN<32>>) -> BytesN<32> {
+        let mut payload = Bytes::new(env);
+        for id in event_ids.iter() {
+            payload.append(&Bytes::from_slice(env, &id.to_array()));
+        }
+        env.crypto().sha256(&payload).into()
+    }
+
     fn extend_key_ttl(env: &Env, key: &DataKey, timestamp: u64) {
+        let policy = Self::get_retention_policy(env.clone());
+        let policy_ttl = match key {
+            DataKey::Events(_) => policy.event_ttl,
+            _ => policy.index_ttl,
+        };
         let retention_period = Self::get_retention_period_internal(env);
         let current_time = env.ledger().timestamp();
         if timestamp + retention_period >= current_time {
             let remaining_seconds = (timestamp + retention_period) - current_time;
             // Convert to ledgers (assume 5s per ledger, round up)
-            let mut remaining_ledgers = ((remaining_seconds + 4) / 5) as u32;
+            let mut remaining_ledgers = remaining_seconds.div_ceil(5) as u32;
             let max_ttl = env.storage().max_ttl();
-            if remaining_ledgers > max_ttl {
-                remaining_ledgers = max_ttl;
-            }
-            let threshold = remaining_ledgers.saturating_sub(17280); // 1 day before limit
+            remaining_ledgers = remaining_ledgers.min(policy_ttl).min(max_ttl);
+            // Bump once within a day of expiry; TTLs shorter than a day are
+            // bumped on every access.
+            let threshold = match remaining_ledgers.checked_sub(17280) {
+                Some(threshold) if threshold > 0 => threshold,
+                _ => remaining_ledgers,
+            };
             env.storage()
                 .persistent()
                 .extend_ttl(key, threshold, remaining_ledgers);
         }
     }
 
+    // Index key for `entity_id`: the sha256 of its ASCII-lowercased bytes,
+    // so ids differing only in case share an index and long ids do not
+    // inflate storage keys. Events keep the id as recorded.
+    fn entity_hash(env: &Env, entity_id: &String) -> BytesN<32> {
+        let mut lowered = Bytes::new(env);
+        for byte in entity_id.to_bytes().iter() {
+            lowered.push_back(byte.to_ascii_lowercase());
+        }
+        env.crypto().sha256(&lowered).into()
+    }
+
+    // Segment 0 is the original `EntityIndex` entry, so indexes written
+    // before segmentation remain readable.
+    fn entity_segment_key(entity: &BytesN<32>, segment: u32) -> DataKey {
+        if segment == 0 {
+            DataKey::EntityIndex(entity.clone())
+        } else {
+            DataKey::EntityIndexSegment(entity.clone(), segment)
+        }
+    }
+
+    fn entity_segment_count(env: &Env, entity: &BytesN<32>) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EntitySegmentCount(entity.clone()))
+            .unwrap_or(1)
+    }
+
+    // Number of ids held by each index segment, so that a page can skip to
+    // its first segment without loading the ones before it. Indexes written
+    // before the lengths were tracked are measured segment by segment.
+    fn entity_segment_lens(env: &Env, entity: &BytesN<32>) -> Vec<u32> {
+        if let Some(lens) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EntitySegmentLens(entity.clone()))
+        {
+            return lens;
+        }
+        let mut lens = Vec::new(env);
+        for segment in 0..Self::entity_segment_count(env, entity) {
+            let segment_ids: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&Self::entity_segment_key(entity, segment))
+                .unwrap_or_else(|| Vec::new(env));
+            lens.push_back(segment_ids.len());
+        }
+        lens
+    }
+
+    fn index_entity_event(env: &Env, entity: &BytesN<32>, event_id: &BytesN<32>, timestamp: u64) {
+        let policy = Self::get_entity_index_policy(env.clone());
+        let mut segment = Self::entity_segment_count(env, entity) - 1;
+        let mut segment_ids: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&Self::entity_segment_key(entity, segment))
+            .unwrap_or_else(|| Vec::new(env));
+
+        if policy.strict {
+            if Self::entity_segment_lens(env, entity).iter().sum::<u32>() >= policy.cap {
+                panic_with_error!(env, ContractError::EntityIndexFull);
+            }
+        } else if segment_ids.len() >= policy.cap {
+            segment += 1;
+            segment_ids = Vec::new(env);
+            let count_key = DataKey::EntitySegmentCount(entity.clone());
+            env.storage().persistent().set(&count_key, &(segment + 1));
+            Self::extend_key_ttl(env, &count_key, timestamp);
+        }
+
+        let entity_key = Self::entity_segment_key(entity, segment);
+        segment_ids.push_back(event_id.clone());
+        env.storage().persistent().set(&entity_key, &segment_ids);
+        Self::extend_key_ttl(env, &entity_key, timestamp);
+
+        let mut lens = Self::entity_segment_lens(env, entity);
+        if segment < lens.len() {
+            lens.set(segment, segment_ids.len());
+        } else {
+            lens.push_back(segment_ids.len());
+        }
+        let lens_key = DataKey::EntitySegmentLens(entity.clone());
+        env.storage().persistent().set(&lens_key, &lens);
+        Self::extend_key_ttl(env, &lens_key, timestamp);
+    }
+
+    // `(seq, event_id, timestamp)` of each id whose event is still stored.
+    fn stored_events_by_seq(
+        env: &Env,
+        ids: impl Iterator<Item = BytesN<32>>,
+    ) -> Vec<(u64, BytesN<32>, u64)> {
+        let mut events = Vec::new(env);
+        for id in ids {
+            if let Some(e) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, AuditEvent>(&DataKey::Events(id.clone()))
+            {
+                events.push_back((e.seq, id, e.timestamp));
+            }
+        }
+        events
+    }
+
+    // Replaces the index of `entity` with `events`, split into segments as
+    // `index_entity_event` would have written them.
+    fn rewrite_entity_index(env: &Env, entity: &BytesN<32>, events: &Vec<(u64, BytesN<32>, u64)>) {
+        let policy = Self::get_entity_index_policy(env.clone());
+        let per_segment = if policy.strict { u32::MAX } else { policy.cap };
+        let old_segments = Self::entity_segment_count(env, entity);
+
+        let mut lens = Vec::new(env);
+        let mut segment_ids = Vec::new(env);
+        let mut latest = 0;
+        for (i, (_, id, timestamp)) in events.iter().enumerate() {
+            segment_ids.push_back(id);
+            latest = latest.max(timestamp);
+            if segment_ids.len() == per_segment || i as u32 + 1 == events.len() {
+                let entity_key = Self::entity_segment_key(entity, lens.len());
+                env.storage().persistent().set(&entity_key, &segment_ids);
+                Self::extend_key_ttl(env, &entity_key, latest);
+                lens.push_back(segment_ids.len());
+                segment_ids = Vec::new(env);
+            }
+        }
+        for segment in lens.len()..old_segments {
+            env.storage()
+                .persistent()
+                .remove(&Self::entity_segment_key(entity, segment));
+        }
+
+        let count_key = DataKey::EntitySegmentCount(entity.clone());
+        env.storage().persistent().set(&count_key, &lens.len());
+        Self::extend_key_ttl(env, &count_key, latest);
+        let lens_key = DataKey::EntitySegmentLens(entity.clone());
+        env.storage().persistent().set(&lens_key, &lens);
+        Self::extend_key_ttl(env, &lens_key, latest);
+    }
+
+    fn unindex_entity_event(env: &Env, entity: &BytesN<32>, event_id: &BytesN<32>) {
+        for segment in 0..Self::entity_segment_count(env, entity) {
+            let entity_key = Self::entity_segment_key(entity, segment);
+            let Some(mut segment_ids) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Vec<BytesN<32>>>(&entity_key)
+            else {
+                continue;
+            };
+            if let Some(idx) = segment_ids.first_index_of(event_id) {
+                segment_ids.remove(idx);
+                if segment_ids.is_empty() {
+                    env.storage().persistent().remove(&entity_key);
+                } else {
+                    env.storage().persistent().set(&entity_key, &segment_ids);
+                }
+
+                let mut lens = Self::entity_segment_lens(env, entity);
+                lens.set(segment, segment_ids.len());
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::EntitySegmentLens(entity.clone()), &lens);
+                return;
+            }
+        }
+    }
+
+    // Up to `limit` event ids for `entity` starting at position `start` of
+    // the combined index, each paired with the segment that holds it.
+    fn entity_event_ids(
+        env: &Env,
+        entity: &BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<(u32, BytesN<32>)> {
+        let mut ids = Vec::new(env);
+        let mut skip = start;
+        for (segment, len) in Self::entity_segment_lens(env, entity).iter().enumerate() {
+            if ids.len() >= limit {
+                break;
+            }
+            if skip >= len {
+                skip -= len;
+                continue;
+            }
+            let segment = segment as u32;
+            let segment_ids: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&Self::entity_segment_key(entity, segment))
+                .unwrap_or_else(|| Vec::new(env));
+            for id in segment_ids.slice(skip..).iter() {
+                if ids.len() >= limit {
+                    break;
+                }
+                ids.push_back((segment, id));
+            }
+            skip = 0;
+        }
+        ids
+    }
+
+    fn entity_event_position(env: &Env, entity: &BytesN<32>, event_id: &BytesN<32>) -> Option<u32> {
+        let mut offset = 0;
+        for segment in 0..Self::entity_segment_count(env, entity) {
+            let segment_ids: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&Self::entity_segment_key(entity, segment))
+                .unwrap_or_else(|| Vec::new(env));
+            if let Some(idx) = segment_ids.first_index_of(event_id) {
+                return Some(offset + idx);
+            }
+            offset += segment_ids.len();
+        }
+        None
+    }
+
+    // Steps `pos` back through `ids` to the next event that is still stored,
+    // returning it with the index segment that holds it.
+    fn previous_entity_event(
+        env: &Env,
+        ids: &Vec<(u32, BytesN<32>)>,
+        pos: &mut u32,
+    ) -> Option<(u32, AuditEvent)> {
+        while *pos > 0 {
+            *pos -= 1;
+            let (segment, id) = ids.get(*pos).unwrap();
+            if let Some(e) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, AuditEvent>(&DataKey::Events(id))
+            {
+                return Some((segment, e));
+            }
+        }
+        None
+    }
+
+    fn set_paused(env: &Env, paused: bool) {
+        Self::require_initialized(env);
+        access_control::require_admin_auth(env).unwrap();
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        Self::extend_instance_ttl(env);
+
+        PauseEvent {
+            paused,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+    }
+
+    fn validate_policy(env: &Env, policy: &RetentionPolicy) {
+        if policy.event_ttl == 0 || policy.index_ttl == 0 {
+            panic_with_error!(env, ContractError::InvalidRetentionPolicy);
+        }
+    }
+
+    fn compute_event_id(env: &Env, tx_hash: &BytesN<32>, timestamp: u64) -> BytesN<32> {
+        let mut hash_payload = Bytes::new(env);
+        hash_payload.append(&Bytes::from_slice(env, &tx_hash.to_array()));
+        hash_payload.append(&Bytes::from_slice(env, &timestamp.to_be_bytes()));
+        env.crypto().sha256(&hash_payload).into()
+    }
+
+    // Calls made before `initialize` fail with `NotInitialized` rather than
+    // a bare storage unwrap.
+    fn require_initialized(env: &Env) {
+        if !access_control::has_admin(env) {
+            panic_with_error!(env, ContractError::NotInitialized);
+        }
+    }
+
+    fn set_emitters(env: &Env, emitters: Vec<Address>, authorized: bool) {
+        Self::require_initialized(env);
+        access_control::require_admin_auth(env).unwrap();
+
+        let mut authorized_emitters: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AuthorizedEmitters)
+            .unwrap();
+        Self::track_emitters(env, &authorized_emitters, &emitters);
+        for emitter in emitters.iter() {
+            authorized_emitters.set(emitter, authorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AuthorizedEmitters, &authorized_emitters);
+        Self::extend_instance_ttl(env);
+
+        EmittersUpdatedEvent {
+            emitters,
+            authorized,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+    }
+
+    fn set_reader(env: &Env, reader: Address, allowed: bool) {
+        Self::require_initialized(env);
+        access_control::require_admin_auth(env).unwrap();
+
+        let mut readers: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Readers)
+            .unwrap_or_else(|| Map::new(env));
+        if allowed {
+            readers.set(reader.clone(), true);
+        } else {
+            readers.remove(reader.clone());
+        }
+        env.storage().instance().set(&DataKey::Readers, &readers);
+        Self::extend_instance_ttl(env);
+
+        ReaderUpdatedEvent {
+            reader,
+            allowed,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+    }
+
+    fn set_event_type(env: &Env, event_type: String, allowed: bool) {
+        Self::require_initialized(env);
+        access_control::require_admin_auth(env).unwrap();
+
+        let mut event_types: Map<String, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::EventTypes)
+            .unwrap_or_else(|| Map::new(env));
+        if allowed {
+            event_types.set(event_type.clone(), true);
+        } else {
+            event_types.remove(event_type.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EventTypes, &event_types);
+        Self::extend_instance_ttl(env);
+
+        EventTypeUpdatedEvent {
+            event_type,
+            allowed,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+    }
+
+    // Restricted payloads are replaced by the hex sha256 of their bytes, so
+    // anyone holding the original data can still match it to the event.
+    fn redact(env: &Env, mut event: AuditEvent) -> AuditEvent {
+        if event.restricted {
+            let digest = env.crypto().sha256(&event.event_data.to_bytes());
+            let mut hex = [0u8; 64];
+            for (i, byte) in digest.to_array().iter().enumerate() {
+                hex[2 * i] = HEX_DIGITS[(byte >> 4) as usize];
+                hex[2 * i + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
+            }
+            event.event_data = String::from_bytes(env, &hex);
+        }
+        event
+    }
+
+    // Append emitters not yet present in the map to the enumeration list.
+    fn track_emitters(env: &Env, known: &Map<Address, bool>, emitters: &Vec<Address>) {
+        let mut emitter_list: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmitterList)
+            .unwrap_or_else(|| Vec::new(env));
+        let len_before = emitter_list.len();
+        for emitter in emitters.iter() {
+            if !known.contains_key(emitter.clone()) && !emitter_list.contains(&emitter) {
+                emitter_list.push_back(emitter);
+            }
+        }
+        if emitter_list.len() != len_before {
+            env.storage()
+                .instance()
+                .set(&DataKey::EmitterList, &emitter_list);
+        }
+    }
+
     fn extend_instance_ttl(env: &Env) {
         // Extend instance storage TTL to at least 30 days (518,400 ledgers)
         // threshold is 29 days (501,120 ledgers)
@@ -1,8 +1,9 @@
 #![cfg(test)]
 
 use super::*;
+use soroban_sdk::testutils::storage::Persistent as _;
 use soroban_sdk::testutils::Ledger;
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, String, Vec};
 
 #[test]
 fn test_initialize_and_auth() {
@@ -13,7 +14,7 @@ fn test_initialize_and_auth() {
     let admin = Address::generate(&env);
     let emitter = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
     env.mock_all_auths();
 
     client.authorize_emitter(&emitter);
@@ -23,6 +24,63 @@ fn test_initialize_and_auth() {
     assert!(!client.is_authorized(&emitter));
 }
 
+#[test]
+fn test_batch_authorize_and_revoke_emitters() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+
+    let mut emitters = Vec::new(&env);
+    for _ in 0..3 {
+        emitters.push_back(Address::generate(&env));
+    }
+    client.authorize_emitters(&emitters);
+    // One admin authorization covers the whole batch
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, admin);
+    for emitter in emitters.iter() {
+        assert!(client.is_authorized(&emitter));
+    }
+
+    let mut revoked = Vec::new(&env);
+    revoked.push_back(emitters.get(0).unwrap());
+    revoked.push_back(emitters.get(2).unwrap());
+    client.revoke_emitters(&revoked);
+    assert!(!client.is_authorized(&emitters.get(0).unwrap()));
+    assert!(client.is_authorized(&emitters.get(1).unwrap()));
+    assert!(!client.is_authorized(&emitters.get(2).unwrap()));
+}
+
+#[test]
+fn test_list_emitters() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    assert!(client.list_emitters().is_empty());
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    client.authorize_emitter(&first);
+    client.authorize_emitters(&Vec::from_array(&env, [second.clone(), first.clone()]));
+    client.revoke_emitter(&second);
+    client.authorize_emitter(&second);
+    client.revoke_emitters(&Vec::from_array(&env, [first.clone(), third.clone()]));
+
+    // Each address is listed once, in first-seen order, with its current flag
+    let expected = Vec::from_array(&env, [(first, false), (second, true), (third, false)]);
+    assert_eq!(client.list_emitters(), expected);
+}
+
 #[test]
 fn test_record_and_query_event() {
     let env = Env::default();
@@ -32,7 +90,7 @@ fn test_record_and_query_event() {
     let admin = Address::generate(&env);
     let emitter = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
     env.mock_all_auths();
     client.authorize_emitter(&emitter);
 
@@ -53,6 +111,7 @@ fn test_record_and_query_event() {
     let stored_event = client.get_event(&event_id).unwrap();
     assert_eq!(stored_event.event_type, event_type);
     assert_eq!(stored_event.primary_entity_id, primary_id);
+    assert_eq!(stored_event.ledger_seq, env.ledger().sequence());
 
     let entity_events = client.get_events_by_entity(&primary_id);
     assert_eq!(entity_events.len(), 1);
@@ -66,6 +125,54 @@ fn test_record_and_query_event() {
     assert_eq!(time_events.len(), 1);
 }
 
+#[test]
+fn test_record_binary_event_data() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let event_type = String::from_str(&env, "TOKEN_MINTED");
+    let primary_id = String::from_str(&env, "project-123");
+    let payload = Bytes::from_slice(&env, &[0x01, 0x00, 0xff, 0x64, 0x00]);
+
+    let binary_id = client.record_event_bytes(
+        &emitter,
+        &event_type,
+        &primary_id,
+        &None,
+        &payload,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    let stored = client.get_event(&binary_id).unwrap();
+    assert_eq!(stored.data_encoding, DataEncoding::Binary);
+    assert_eq!(stored.binary_data, payload);
+    assert_eq!(
+        client.get_event_data(&binary_id),
+        Some((DataEncoding::Binary, payload))
+    );
+
+    let json_id = client.record_event_auth(
+        &emitter,
+        &event_type,
+        &primary_id,
+        &None,
+        &String::from_str(&env, "{}"),
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+    assert_eq!(
+        client.get_event_data(&json_id),
+        Some((DataEncoding::Json, Bytes::from_slice(&env, b"{}")))
+    );
+    assert_eq!(client.get_events_by_entity(&primary_id).len(), 2);
+}
+
 #[test]
 #[should_panic(expected = "Event payload exceeds maximum allowed size")]
 fn test_oversized_event_payload() {
@@ -76,7 +183,7 @@ fn test_oversized_event_payload() {
     let admin = Address::generate(&env);
     let emitter = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
     env.mock_all_auths();
     client.authorize_emitter(&emitter);
 
@@ -106,7 +213,7 @@ fn test_unauthorized_emitter() {
     let admin = Address::generate(&env);
     let emitter = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
     env.mock_all_auths();
 
     let event_type = String::from_str(&env, "TOKEN_MINTED");
@@ -131,7 +238,7 @@ fn test_retention_period_configuration() {
     let client = AuditTrailContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
     env.mock_all_auths();
 
     assert_eq!(client.get_retention_period(), 90 * 86400);
@@ -148,11 +255,184 @@ fn test_unauthorized_set_retention_period() {
     let client = AuditTrailContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
 
     client.set_retention_period(&(30 * 86400));
 }
 
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    client.set_retention_period(&(30 * 86400));
+    assert_eq!(env.auths()[0].0, new_admin);
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_without_proposal() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    client.initialize(&Address::generate(&env), &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+
+    client.accept_admin();
+}
+
+#[test]
+fn test_calls_before_initialize_fail_with_not_initialized() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let emitter = Address::generate(&env);
+    let not_initialized =
+        soroban_sdk::Error::from_contract_error(ContractError::NotInitialized as u32);
+
+    assert!(!client.is_initialized());
+    assert_eq!(client.try_is_authorized(&emitter), Err(Ok(not_initialized)));
+    assert_eq!(
+        client.try_authorize_emitter(&emitter),
+        Err(Ok(not_initialized))
+    );
+    assert_eq!(
+        client.try_record_event_auth(
+            &emitter,
+            &String::from_str(&env, "MINT"),
+            &String::from_str(&env, "PROJ-1"),
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[1u8; 32]),
+        ),
+        Err(Ok(not_initialized))
+    );
+
+    client.initialize(&Address::generate(&env), &DEFAULT_RETENTION_POLICY);
+    assert!(client.is_initialized());
+    assert!(!client.is_authorized(&emitter));
+}
+
+fn event_ttls(env: &Env, contract_id: &Address, event_id: &BytesN<32>, entity: &str) -> (u32, u32) {
+    env.as_contract(contract_id, || {
+        let storage = env.storage().persistent();
+        (
+            storage.get_ttl(&DataKey::Events(event_id.clone())),
+            storage.get_ttl(&DataKey::EntityIndex(AuditTrailContract::entity_hash(
+                env,
+                &String::from_str(env, entity),
+            ))),
+        )
+    })
+}
+
+#[test]
+fn test_retention_policy_sets_write_ttls() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+    let policy = RetentionPolicy {
+        event_ttl: 10_000,
+        index_ttl: 12_000,
+        allow_pruning: true,
+    };
+    client.initialize(&admin, &policy);
+    client.authorize_emitter(&emitter);
+    assert_eq!(client.get_retention_policy(), policy);
+
+    let record = |entity: &str, tx: u8| {
+        client.record_event_auth(
+            &emitter,
+            &String::from_str(&env, "MINT"),
+            &String::from_str(&env, entity),
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[tx; 32]),
+        )
+    };
+
+    let first = record("PROJ-1", 1);
+    assert_eq!(
+        event_ttls(&env, &contract_id, &first, "PROJ-1"),
+        (10_000, 12_000)
+    );
+
+    // Only later writes pick up a new policy
+    client.update_retention_policy(&RetentionPolicy {
+        event_ttl: 8_000,
+        index_ttl: 9_000,
+        allow_pruning: true,
+    });
+    let second = record("PROJ-2", 2);
+    assert_eq!(
+        event_ttls(&env, &contract_id, &second, "PROJ-2"),
+        (8_000, 9_000)
+    );
+    assert_eq!(
+        event_ttls(&env, &contract_id, &first, "PROJ-1"),
+        (10_000, 12_000)
+    );
+}
+
+#[test]
+fn test_retention_policy_validation_and_pruning_switch() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let error = |e: ContractError| soroban_sdk::Error::from_contract_error(e as u32);
+    let admin = Address::generate(&env);
+    let no_pruning = RetentionPolicy {
+        event_ttl: 10_000,
+        index_ttl: 10_000,
+        allow_pruning: false,
+    };
+
+    assert_eq!(
+        client.try_initialize(
+            &admin,
+            &RetentionPolicy {
+                event_ttl: 0,
+                ..no_pruning.clone()
+            }
+        ),
+        Err(Ok(error(ContractError::InvalidRetentionPolicy)))
+    );
+
+    client.initialize(&admin, &no_pruning);
+    assert_eq!(
+        client.try_prune_old_events(),
+        Err(Ok(error(ContractError::PruningDisabled)))
+    );
+
+    client.update_retention_policy(&DEFAULT_RETENTION_POLICY);
+    assert_eq!(client.prune_old_events(), 0);
+}
+
 #[test]
 fn test_pruning_and_compaction() {
     let env = Env::default();
@@ -162,7 +442,7 @@ fn test_pruning_and_compaction() {
     let admin = Address::generate(&env);
     let emitter = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
     env.mock_all_auths();
     client.authorize_emitter(&emitter);
 
@@ -213,3 +493,956 @@ fn test_pruning_and_compaction() {
     assert_eq!(entity_events.len(), 1);
     assert_eq!(entity_events.get(0).unwrap().event_id, event_id_2);
 }
+
+#[test]
+fn test_events_by_entity_cursor_pages() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let event_type = String::from_str(&env, "TOKEN_MINTED");
+    let primary_id = String::from_str(&env, "project-123");
+    let event_data = String::from_str(&env, "{}");
+
+    // Two events share the first ledger and timestamp
+    env.ledger().set_sequence_number(100);
+    let mut event_ids = Vec::new(&env);
+    for i in 0..3u8 {
+        if i == 2 {
+            env.ledger().set_sequence_number(101);
+        }
+        event_ids.push_back(client.record_event_auth(
+            &emitter,
+            &event_type,
+            &primary_id,
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[i; 32]),
+        ));
+    }
+
+    let (first_page, cursor) = client.get_events_by_entity_cursor(&primary_id, &None, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(
+        first_page.get(0).unwrap().event_id,
+        event_ids.get(0).unwrap()
+    );
+    assert_eq!(cursor, Some(event_ids.get(1).unwrap()));
+    assert_eq!(first_page.get(0).unwrap().ledger_seq, 100);
+    assert_eq!(first_page.get(1).unwrap().ledger_seq, 100);
+    assert!(first_page.get(0).unwrap().seq < first_page.get(1).unwrap().seq);
+
+    // Events appended between page reads are picked up without skips or repeats.
+    event_ids.push_back(client.record_event_auth(
+        &emitter,
+        &event_type,
+        &primary_id,
+        &None,
+        &event_data,
+        &BytesN::from_array(&env, &[9; 32]),
+    ));
+
+    let (second_page, cursor) = client.get_events_by_entity_cursor(&primary_id, &cursor, &2);
+    assert_eq!(second_page.len(), 2);
+    assert_eq!(
+        second_page.get(0).unwrap().event_id,
+        event_ids.get(2).unwrap()
+    );
+    assert_eq!(
+        second_page.get(1).unwrap().event_id,
+        event_ids.get(3).unwrap()
+    );
+    assert_eq!(cursor, Some(event_ids.get(3).unwrap()));
+    assert_eq!(second_page.get(0).unwrap().ledger_seq, 101);
+    assert_eq!(second_page.get(1).unwrap().ledger_seq, 101);
+
+    let (empty, end) = client.get_events_by_entity_cursor(&primary_id, &cursor, &2);
+    assert_eq!(empty.len(), 0);
+    assert_eq!(end, None);
+}
+
+#[test]
+fn test_history_of_orders_token_lifecycle() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let token_id = String::from_str(&env, "1");
+    let other_token = String::from_str(&env, "2");
+    let event_data = String::from_str(&env, "{}");
+
+    // Mint and transfer share a ledger and timestamp; another credit's event
+    // lands in between.
+    env.ledger().set_sequence_number(100);
+    let mut steps = Vec::new(&env);
+    for (i, (event_type, entity)) in [
+        ("TOKEN_MINTED", &token_id),
+        ("TOKEN_MINTED", &other_token),
+        ("TOKEN_TRANSFERRED", &token_id),
+        ("TOKEN_RETIRED", &token_id),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if i == 3 {
+            env.ledger().set_sequence_number(101);
+        }
+        let event_id = client.record_event_auth(
+            &emitter,
+            &String::from_str(&env, event_type),
+            entity,
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[i as u8; 32]),
+        );
+        if *entity == token_id {
+            steps.push_back((event_id, String::from_str(&env, event_type)));
+        }
+    }
+
+    let history = client.history_of(&token_id, &0, &10);
+    assert_eq!(history.len(), 3);
+    for (i, (event_id, event_type)) in steps.iter().enumerate() {
+        let event = history.get(i as u32).unwrap();
+        assert_eq!(event.event_id, event_id);
+        assert_eq!(event.event_type, event_type);
+    }
+    assert_eq!(history.get(0).unwrap().ledger_seq, 100);
+    assert_eq!(history.get(1).unwrap().ledger_seq, 100);
+    assert!(history.get(0).unwrap().seq < history.get(1).unwrap().seq);
+    assert_eq!(history.get(2).unwrap().ledger_seq, 101);
+
+    let tail = client.history_of(&token_id, &1, &10);
+    assert_eq!(tail.len(), 2);
+    assert_eq!(tail.get(0).unwrap().event_id, steps.get(1).unwrap().0);
+    assert_eq!(client.history_of(&other_token, &0, &10).len(), 1);
+}
+
+#[test]
+fn test_preview_event_id_matches_recorded_id() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    env.ledger().set_timestamp(1_000);
+    let tx_hash = BytesN::from_array(&env, &[5; 32]);
+    let preview = client.preview_event_id(&emitter, &tx_hash);
+    assert_eq!(client.current_seq(), 0);
+    assert_eq!(client.get_event(&preview), None);
+
+    // An intervening event moves the sequence but not the id
+    client.record_event_auth(
+        &emitter,
+        &String::from_str(&env, "TOKEN_MINTED"),
+        &String::from_str(&env, "1"),
+        &None,
+        &String::from_str(&env, "{}"),
+        &BytesN::from_array(&env, &[6; 32]),
+    );
+    let event_id = client.record_event_auth(
+        &emitter,
+        &String::from_str(&env, "TOKEN_TRANSFERRED"),
+        &String::from_str(&env, "1"),
+        &None,
+        &String::from_str(&env, "{}"),
+        &tx_hash,
+    );
+    assert_eq!(event_id, preview);
+    assert_eq!(client.get_event(&event_id).unwrap().seq, 2);
+
+    // A later ledger timestamp gives a different id
+    env.ledger().set_timestamp(1_001);
+    assert_ne!(client.preview_event_id(&emitter, &tx_hash), preview);
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_preview_event_id(&stranger, &tx_hash).is_err());
+}
+
+#[test]
+fn test_entity_index_rotates_into_segments() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+    client.set_entity_index_policy(&EntityIndexPolicy {
+        cap: 5,
+        strict: false,
+    });
+
+    let event_type = String::from_str(&env, "TOKEN_MINTED");
+    let primary_id = String::from_str(&env, "treasury");
+    let event_data = String::from_str(&env, "{}");
+
+    let mut event_ids = Vec::new(&env);
+    for i in 0..7u8 {
+        event_ids.push_back(client.record_event_auth(
+            &emitter,
+            &event_type,
+            &primary_id,
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[i; 32]),
+        ));
+    }
+
+    // The sixth event opened a second segment
+    let entity = AuditTrailContract::entity_hash(&env, &primary_id);
+    let segment_lens = env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        let first: Vec<BytesN<32>> = storage.get(&DataKey::EntityIndex(entity.clone())).unwrap();
+        let second: Vec<BytesN<32>> = storage
+            .get(&DataKey::EntityIndexSegment(entity.clone(), 1))
+            .unwrap();
+        (first.len(), second.len())
+    });
+    assert_eq!(segment_lens, (5, 2));
+    assert_eq!(client.count_events_by_entity(&primary_id), 7);
+
+    let all = client.get_events_by_entity(&primary_id);
+    assert_eq!(all.len(), 7);
+    for (i, event) in all.iter().enumerate() {
+        assert_eq!(event.event_id, event_ids.get(i as u32).unwrap());
+    }
+
+    let page = client.get_events_by_entity_paged(&primary_id, &3, &3);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(0).unwrap().event_id, event_ids.get(3).unwrap());
+    assert_eq!(page.get(2).unwrap().event_id, event_ids.get(5).unwrap());
+
+    let (first_page, cursor) = client.get_events_by_entity_cursor(&primary_id, &None, &4);
+    assert_eq!(cursor, Some(event_ids.get(3).unwrap()));
+    let (second_page, cursor) = client.get_events_by_entity_cursor(&primary_id, &cursor, &4);
+    assert_eq!(first_page.len() + second_page.len(), 7);
+    assert_eq!(
+        second_page.get(0).unwrap().event_id,
+        event_ids.get(4).unwrap()
+    );
+    assert_eq!(cursor, Some(event_ids.get(6).unwrap()));
+}
+
+#[test]
+fn test_entity_ids_share_an_index_regardless_of_case() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let event_type = String::from_str(&env, "TOKEN_MINTED");
+    let event_data = String::from_str(&env, "{}");
+    let record = |entity_id: &str, seed: u8| {
+        client.record_event_auth(
+            &emitter,
+            &event_type,
+            &String::from_str(&env, entity_id),
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[seed; 32]),
+        )
+    };
+
+    let upper = record("Project-123", 1);
+    let lower = record("project-123", 2);
+    let events = client.get_events_by_entity(&String::from_str(&env, "PROJECT-123"));
+    assert_eq!(events.len(), 2);
+    assert_eq!(events.get(0).unwrap().event_id, upper);
+    assert_eq!(events.get(1).unwrap().event_id, lower);
+    // Events keep the id as it was recorded
+    assert_eq!(
+        events.get(0).unwrap().primary_entity_id,
+        String::from_str(&env, "Project-123")
+    );
+
+    // Move two events into the String-keyed index an older deployment
+    // would have written, one entry per spelling
+    let legacy_upper = record("Legacy-9", 3);
+    let legacy_lower = record("legacy-9", 4);
+    let entity = AuditTrailContract::entity_hash(&env, &String::from_str(&env, "legacy-9"));
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        storage.remove(&DataKey::EntityIndex(entity.clone()));
+        storage.remove(&DataKey::EntitySegmentLens(entity.clone()));
+        storage.set(
+            &LegacyDataKey::EntityIndex(String::from_str(&env, "Legacy-9")),
+            &Vec::from_array(&env, [legacy_upper.clone()]),
+        );
+        storage.set(
+            &LegacyDataKey::EntityIndex(String::from_str(&env, "legacy-9")),
+            &Vec::from_array(&env, [legacy_lower.clone()]),
+        );
+    });
+    let recent = record("LEGACY-9", 5);
+    let legacy_id = String::from_str(&env, "legacy-9");
+    assert_eq!(client.count_events_by_entity(&legacy_id), 1);
+
+    assert_eq!(client.migrate_entity_index(&legacy_id), 1);
+    assert_eq!(
+        client.migrate_entity_index(&String::from_str(&env, "Legacy-9")),
+        1
+    );
+    assert_eq!(client.migrate_entity_index(&legacy_id), 0);
+
+    // Historical events are queryable again, in recording order
+    let events = client.get_events_by_entity(&legacy_id);
+    assert_eq!(events.len(), 3);
+    assert_eq!(events.get(0).unwrap().event_id, legacy_upper);
+    assert_eq!(events.get(1).unwrap().event_id, legacy_lower);
+    assert_eq!(events.get(2).unwrap().event_id, recent);
+}
+
+#[test]
+fn test_entity_pages_skip_earlier_segments_by_length() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+    client.set_entity_index_policy(&EntityIndexPolicy {
+        cap: 4,
+        strict: false,
+    });
+
+    let event_type = String::from_str(&env, "TOKEN_MINTED");
+    let primary_id = String::from_str(&env, "treasury");
+    let event_data = String::from_str(&env, "{}");
+
+    let mut event_ids = Vec::new(&env);
+    for i in 0..10u8 {
+        event_ids.push_back(client.record_event_auth(
+            &emitter,
+            &event_type,
+            &primary_id,
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[i; 32]),
+        ));
+    }
+
+    // Segment lengths are kept alongside the index
+    let lens: Vec<u32> = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EntitySegmentLens(
+                AuditTrailContract::entity_hash(&env, &primary_id),
+            ))
+            .unwrap()
+    });
+    assert_eq!(lens, soroban_sdk::vec![&env, 4, 4, 2]);
+    assert_eq!(client.count_events_by_entity(&primary_id), 10);
+
+    // A page that starts in the last segment
+    let page = client.get_events_by_entity_paged(&primary_id, &9, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().event_id, event_ids.get(9).unwrap());
+
+    // A page that crosses from the second segment into the third
+    let page = client.get_events_by_entity_paged(&primary_id, &6, &3);
+    assert_eq!(page.len(), 3);
+    for (i, event) in page.iter().enumerate() {
+        assert_eq!(event.event_id, event_ids.get(6 + i as u32).unwrap());
+    }
+
+    assert!(client
+        .get_events_by_entity_paged(&primary_id, &10, &5)
+        .is_empty());
+}
+
+#[test]
+fn test_strict_entity_index_cap_rejects_events() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+    client.set_entity_index_policy(&EntityIndexPolicy {
+        cap: 2,
+        strict: true,
+    });
+
+    let record = |entity: &str, tx: u8| {
+        client.try_record_event_auth(
+            &emitter,
+            &String::from_str(&env, "TOKEN_MINTED"),
+            &String::from_str(&env, entity),
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[tx; 32]),
+        )
+    };
+
+    assert!(record("treasury", 1).is_ok());
+    assert!(record("treasury", 2).is_ok());
+    assert_eq!(
+        record("treasury", 3).err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::EntityIndexFull as u32
+        )))
+    );
+    assert!(record("project-123", 4).is_ok());
+    assert_eq!(
+        client.count_events_by_entity(&String::from_str(&env, "treasury")),
+        2
+    );
+}
+
+#[test]
+fn test_pause_blocks_recording() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let primary_id = String::from_str(&env, "project-123");
+    let record = |tx: u8| {
+        client.try_record_event_auth(
+            &emitter,
+            &String::from_str(&env, "TOKEN_MINTED"),
+            &primary_id,
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[tx; 32]),
+        )
+    };
+
+    assert!(record(1).is_ok());
+    client.pause();
+    assert!(client.is_paused());
+    assert_eq!(
+        record(2).err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::Paused as u32
+        )))
+    );
+    assert_eq!(client.get_events_by_entity(&primary_id).len(), 1);
+
+    client.unpause();
+    assert!(!client.is_paused());
+    assert!(record(3).is_ok());
+    assert_eq!(client.count_events_by_entity(&primary_id), 2);
+}
+
+#[test]
+fn test_get_events_since_seq() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+    client.set_retention_period(&86400);
+    assert_eq!(client.current_seq(), 0);
+
+    let event_type = String::from_str(&env, "TOKEN_MINTED");
+    let primary_id = String::from_str(&env, "project-123");
+    let event_data = String::from_str(&env, "{}");
+
+    env.ledger().set_timestamp(0);
+    for i in 0..2u8 {
+        client.record_event_auth(
+            &emitter,
+            &event_type,
+            &primary_id,
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[i; 32]),
+        );
+    }
+    env.ledger().set_timestamp(172800);
+    for i in 2..4u8 {
+        client.record_event_auth(
+            &emitter,
+            &event_type,
+            &primary_id,
+            &None,
+            &event_data,
+            &BytesN::from_array(&env, &[i; 32]),
+        );
+    }
+    assert_eq!(client.current_seq(), 4);
+
+    let since = client.get_events_since(&1, &2);
+    assert_eq!(since.len(), 2);
+    assert_eq!(since.get(0).unwrap().seq, 2);
+    assert_eq!(since.get(1).unwrap().seq, 3);
+
+    let rest = client.get_events_since(&3, &10);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().seq, 4);
+    assert_eq!(client.get_events_since(&4, &10).len(), 0);
+
+    // A consumer behind the retention window resumes at the oldest retained event.
+    env.ledger().set_timestamp(172801);
+    assert_eq!(client.prune_old_events(), 2);
+    let resumed = client.get_events_since(&0, &10);
+    assert_eq!(resumed.len(), 2);
+    assert_eq!(resumed.get(0).unwrap().seq, 3);
+    assert_eq!(client.current_seq(), 4);
+}
+
+#[test]
+fn test_caused_by_links_event_chain() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let primary_id = String::from_str(&env, "project-123");
+    let record = |event_type: &str, tx: u8, caused_by: Option<BytesN<32>>| {
+        client.try_record_event_with_cause(
+            &emitter,
+            &String::from_str(&env, event_type),
+            &primary_id,
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[tx; 32]),
+            &caused_by,
+        )
+    };
+
+    let issuance = record("ISSUANCE", 1, None).unwrap().unwrap();
+    let reversal = record("REVERSAL", 2, Some(issuance.clone()))
+        .unwrap()
+        .unwrap();
+    let correction = record("CORRECTION", 3, Some(reversal.clone()))
+        .unwrap()
+        .unwrap();
+
+    // An unknown cause is rejected and nothing is recorded
+    assert_eq!(
+        record("REVERSAL", 4, Some(BytesN::from_array(&env, &[9; 32]))).err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::UnknownCause as u32
+        )))
+    );
+    assert_eq!(client.count_events_by_entity(&primary_id), 3);
+
+    // Downstream
+    let caused = client.get_caused_events(&issuance, &0, &10);
+    assert_eq!(caused.len(), 1);
+    assert_eq!(caused.get(0).unwrap().event_id, reversal);
+    let caused = client.get_caused_events(&reversal, &0, &10);
+    assert_eq!(caused.len(), 1);
+    assert_eq!(caused.get(0).unwrap().event_id, correction);
+    assert!(client.get_caused_events(&correction, &0, &10).is_empty());
+    assert!(client.get_caused_events(&issuance, &1, &10).is_empty());
+
+    // Upstream
+    let event = client.get_event(&correction).unwrap();
+    assert_eq!(event.caused_by, Some(reversal.clone()));
+    let event = client.get_event(&reversal).unwrap();
+    assert_eq!(event.caused_by, Some(issuance.clone()));
+    assert_eq!(client.get_event(&issuance).unwrap().caused_by, None);
+}
+
+#[test]
+fn test_events_for_entities_merge_newest_first() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let alpha = String::from_str(&env, "project-alpha");
+    let beta = String::from_str(&env, "project-beta");
+    let gamma = String::from_str(&env, "project-gamma");
+
+    // Interleaved across entities: alpha at 100, 400, 700; beta at 200, 500;
+    // gamma at 300, 600, 800.
+    let mut recorded = Vec::new(&env);
+    for (ts, entity) in [
+        (100, &alpha),
+        (200, &beta),
+        (300, &gamma),
+        (400, &alpha),
+        (500, &beta),
+        (600, &gamma),
+        (700, &alpha),
+        (800, &gamma),
+    ] {
+        env.ledger().set_timestamp(ts);
+        recorded.push_back(client.record_event_auth(
+            &emitter,
+            &String::from_str(&env, "ISSUANCE"),
+            entity,
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[ts as u8; 32]),
+        ));
+    }
+
+    // Listing alpha twice reaches its events twice; each is returned once
+    let entities = Vec::from_array(&env, [alpha.clone(), beta, gamma, alpha]);
+    let events = client.get_events_for_entities(&entities, &0, &100);
+    assert_eq!(events.len(), 8);
+    for (i, e) in events.iter().enumerate() {
+        assert_eq!(e.event_id, recorded.get(7 - i as u32).unwrap());
+    }
+
+    // Pages continue the same global order
+    let page = client.get_events_for_entities(&entities, &3, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().timestamp, 500);
+    assert_eq!(page.get(1).unwrap().timestamp, 400);
+    assert!(client
+        .get_events_for_entities(&entities, &8, &10)
+        .is_empty());
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_QUERY_ENTITIES {
+        too_many.push_back(String::from_str(&env, "project-alpha"));
+    }
+    assert_eq!(
+        client.try_get_events_for_entities(&too_many, &0, &10).err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::TooManyEntities as u32
+        )))
+    );
+}
+
+#[test]
+fn test_day_event_ids_page_through_chunks() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let event_type = String::from_str(&env, "ISSUANCE");
+    let mut recorded = Vec::new(&env);
+    for i in 0..250u32 {
+        env.ledger().set_timestamp(86400 + 60 + i as u64);
+        let mut tx = [0u8; 32];
+        tx[..4].copy_from_slice(&i.to_be_bytes());
+        recorded.push_back(client.record_event_auth(
+            &emitter,
+            &event_type,
+            &String::from_str(&env, "project-123"),
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &tx),
+        ));
+    }
+
+    // Any timestamp within the day addresses the same chunks
+    assert_eq!(client.get_day_chunk_count(&event_type, &(86400 * 2 - 1)), 3);
+    let mut paged = Vec::new(&env);
+    for (chunk, expected_len) in [(0u32, 100u32), (1, 100), (2, 50)] {
+        let ids = client.get_day_event_ids(&event_type, &86400, &chunk);
+        assert_eq!(ids.len(), expected_len);
+        paged.append(&ids);
+    }
+    assert_eq!(paged, recorded);
+    assert!(client.get_day_event_ids(&event_type, &86400, &3).is_empty());
+
+    // Other types and days have nothing to export
+    let transfer = String::from_str(&env, "TRANSFER");
+    assert_eq!(client.get_day_chunk_count(&transfer, &86400), 0);
+    assert_eq!(client.get_day_chunk_count(&event_type, &0), 0);
+    assert!(client.get_day_event_ids(&event_type, &0, &0).is_empty());
+}
+
+#[test]
+fn test_finalize_and_verify_daily_digests() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let record = |ts: u64, tx: u8, event_type: &str| {
+        env.ledger().set_timestamp(ts);
+        client.record_event_auth(
+            &emitter,
+            &String::from_str(&env, event_type),
+            &String::from_str(&env, "project-123"),
+            &None,
+            &String::from_str(&env, "{}"),
+            &BytesN::from_array(&env, &[tx; 32]),
+        )
+    };
+
+    // Two event types on day one, one event on day two
+    let day_one = Vec::from_array(
+        &env,
+        [
+            record(100, 1, "ISSUANCE"),
+            record(200, 2, "TRANSFER"),
+            record(300, 3, "ISSUANCE"),
+        ],
+    );
+    let day_two = Vec::from_array(&env, [record(86_400 + 50, 4, "TRANSFER")]);
+
+    assert_eq!(
+        client.try_finalize_day(&86_400).err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::DayNotOver as u32
+        )))
+    );
+
+    env.ledger().set_timestamp(2 * 86_400);
+    let first = client.finalize_day(&0);
+    assert_eq!(first.day, 0);
+    assert_eq!(first.event_count, 3);
+    // Any timestamp within the day names it
+    let second = client.finalize_day(&(86_400 + 7));
+    assert_eq!(second.day, 86_400);
+    assert_eq!(second.event_count, 1);
+    assert_ne!(first.digest, second.digest);
+    assert_eq!(client.get_daily_digest(&86_400), Some(second));
+    assert_eq!(
+        client.try_finalize_day(&0).err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::DayAlreadyFinalized as u32
+        )))
+    );
+
+    assert!(client.verify_day(&0, &day_one));
+    assert!(client.verify_day(&86_400, &day_two));
+
+    // Reordered, missing or foreign ids do not match
+    let mut reordered = day_one.clone();
+    let oldest = reordered.pop_front().unwrap();
+    reordered.push_back(oldest);
+    assert!(!client.verify_day(&0, &reordered));
+    let mut missing = day_one.clone();
+    missing.pop_back();
+    assert!(!client.verify_day(&0, &missing));
+    assert!(!client.verify_day(&0, &day_two));
+    assert!(!client.verify_day(&(2 * 86_400), &Vec::new(&env)));
+}
+
+#[test]
+fn test_restricted_event_data_is_redacted_for_non_readers() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+    let reader = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+    client.add_reader(&reader);
+    assert!(client.is_reader(&reader));
+    assert!(!client.is_reader(&outsider));
+
+    let event_type = String::from_str(&env, "RETIREMENT_CERTIFICATE");
+    let primary_id = String::from_str(&env, "cert-1");
+    let beneficiary = String::from_str(&env, "{\"beneficiary\": \"Jane Doe\"}");
+    let restricted_id = client.record_event_restricted(
+        &emitter,
+        &event_type,
+        &primary_id,
+        &None,
+        &beneficiary,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    let public_data = String::from_str(&env, "{\"amount\": 1}");
+    let public_id = client.record_event_auth(
+        &emitter,
+        &event_type,
+        &primary_id,
+        &None,
+        &public_data,
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+
+    let digest = env.crypto().sha256(&beneficiary.to_bytes()).to_array();
+    let mut hex = [0u8; 64];
+    for (i, byte) in digest.iter().enumerate() {
+        hex[2 * i] = HEX_DIGITS[(byte >> 4) as usize];
+        hex[2 * i + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
+    }
+    let redacted = String::from_bytes(&env, &hex);
+
+    // Public getters and non-readers see the hash of restricted data only
+    let event = client.get_event(&restricted_id).unwrap();
+    assert!(event.restricted);
+    assert_eq!(event.event_data, redacted);
+    let entity_events = client.get_events_by_entity(&primary_id);
+    assert_eq!(entity_events.get(0).unwrap().event_data, redacted);
+    assert_eq!(entity_events.get(1).unwrap().event_data, public_data);
+    assert_eq!(
+        client.get_events_since(&0, &10).get(0).unwrap().event_data,
+        redacted
+    );
+    assert_eq!(
+        client
+            .get_event_restricted(&outsider, &restricted_id)
+            .unwrap()
+            .event_data,
+        redacted
+    );
+
+    // Readers see it in full; unrestricted events are unaffected
+    let event = client
+        .get_event_restricted(&reader, &restricted_id)
+        .unwrap();
+    assert_eq!(event.event_data, beneficiary);
+    assert_eq!(env.auths()[0].0, reader);
+    assert_eq!(
+        client
+            .get_event_restricted(&outsider, &public_id)
+            .unwrap()
+            .event_data,
+        public_data
+    );
+
+    client.remove_reader(&reader);
+    assert!(!client.is_reader(&reader));
+    assert_eq!(
+        client
+            .get_event_restricted(&reader, &restricted_id)
+            .unwrap()
+            .event_data,
+        redacted
+    );
+}
+
+#[test]
+fn test_event_type_whitelist_rejects_unknown_types() {
+    let env = Env::default();
+    let contract_id = env.register(AuditTrailContract, ());
+    let client = AuditTrailContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let emitter = Address::generate(&env);
+    client.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    env.mock_all_auths();
+    client.authorize_emitter(&emitter);
+
+    let certificate = String::from_str(&env, "RETIREMENT_CERTIFICATE");
+    let transfer = String::from_str(&env, "TRANSFER");
+    let typo = String::from_str(&env, "RETIREMNT_CERTIFICATE");
+    let entity = String::from_str(&env, "token-1");
+    let data = String::from_str(&env, "{}");
+
+    // An empty whitelist accepts any type
+    assert!(client.is_event_type_allowed(&typo));
+    client.record_event_auth(
+        &emitter,
+        &typo,
+        &entity,
+        &None,
+        &data,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+
+    client.add_event_type(&certificate);
+    client.add_event_type(&transfer);
+    assert_eq!(client.list_event_types().len(), 2);
+    assert!(client.is_event_type_allowed(&transfer));
+    assert!(!client.is_event_type_allowed(&typo));
+    client.record_event_auth(
+        &emitter,
+        &certificate,
+        &entity,
+        &None,
+        &data,
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+    assert_eq!(
+        client.try_record_event_auth(
+            &emitter,
+            &typo,
+            &entity,
+            &None,
+            &data,
+            &BytesN::from_array(&env, &[3; 32]),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::UnknownEventType as u32
+        )))
+    );
+
+    // Removed types are rejected; removing every type lifts the whitelist
+    client.remove_event_type(&certificate);
+    assert!(!client.is_event_type_allowed(&certificate));
+    client.remove_event_type(&transfer);
+    assert_eq!(client.list_event_types(), Vec::new(&env));
+    client.record_event_auth(
+        &emitter,
+        &typo,
+        &entity,
+        &None,
+        &data,
+        &BytesN::from_array(&env, &[4; 32]),
+    );
+    assert_eq!(client.count_events_by_entity(&entity), 3);
+}
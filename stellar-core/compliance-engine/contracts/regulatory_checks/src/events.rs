@@ -1,5 +1,5 @@
 use crate::{OperationType, JurisdictionRule};
-use soroban_sdk::{contractevent, Address, Bytes, Env, String};
+use soroban_sdk::{contractevent, Address, Bytes, BytesN, Env, String, Symbol};
 
 /// A deterministic hash of a JurisdictionRule used for change detection.
 /// Computed by concatenating all rule fields into a canonical byte representation.
@@ -38,7 +38,7 @@ pub fn compute_rule_hash(env: &Env, rule: &JurisdictionRule) -> Bytes {
 fn append_string(env: &Env, bytes: &mut Bytes, s: &String) {
     let str_bytes = s.to_bytes();
     // Append length prefix
-    let len = str_bytes.len() as u32;
+    let len = str_bytes.len();
     let len_bytes = Bytes::from_array(env, &len.to_be_bytes());
     bytes.append(&len_bytes);
     // Append content
@@ -79,7 +79,49 @@ pub struct RuleDeactivated {
     pub timestamp: u64,
 }
 
+/// Event emitted when a jurisdiction corridor is allowed or blocked
+#[contractevent]
+pub struct CorridorUpdated {
+    pub from_jur: String,
+    pub to_jur: String,
+    pub allowed: bool,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+/// Event emitted by check_and_log when a transfer is rejected.
+/// `rule` is "sanctions" when either party is sanctioned, "kyc" for an
+/// unregistered party, "volume" when the sender's daily limit would be
+/// exceeded, or "corridor" for a blocked corridor, in which case the matched
+/// corridor is included. `rule_version` is the rule set version the
+/// transfer was screened under.
+#[contractevent]
+pub struct TransferRejected {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub token_id: u32,
+    pub project_id: String,
+    pub rule: Symbol,
+    pub corridor_from: Option<String>,
+    pub corridor_to: Option<String>,
+    pub rule_version: u64,
+    pub timestamp: u64,
+}
+
+/// Event emitted when addresses are added to or removed from the sanctions list
+#[contractevent]
+pub struct SanctionsUpdated {
+    pub count: u32,
+    pub added: bool,
+    pub reason_hash: Option<BytesN<32>>,
+    pub expires_at: Option<u64>,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
 /// Emit a RuleAdded event
+#[allow(clippy::too_many_arguments)]
 pub fn emit_rule_added_event(
     env: &Env,
     rule_id: String,
@@ -135,4 +177,71 @@ pub fn emit_rule_deactivated_event(
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
-}
\ No newline at end of file
+}
+
+/// Emit a CorridorUpdated event
+pub fn emit_corridor_updated_event(
+    env: &Env,
+    from_jur: String,
+    to_jur: String,
+    allowed: bool,
+    updated_by: Address,
+) {
+    CorridorUpdated {
+        from_jur,
+        to_jur,
+        allowed,
+        updated_by,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+/// Emit a TransferRejected event
+#[allow(clippy::too_many_arguments)]
+pub fn emit_transfer_rejected_event(
+    env: &Env,
+    from: Address,
+    to: Address,
+    amount: i128,
+    token_id: u32,
+    project_id: String,
+    rule: Symbol,
+    corridor_from: Option<String>,
+    corridor_to: Option<String>,
+    rule_version: u64,
+) {
+    TransferRejected {
+        from,
+        to,
+        amount,
+        token_id,
+        project_id,
+        rule,
+        corridor_from,
+        corridor_to,
+        rule_version,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+/// Emit a SanctionsUpdated event
+pub fn emit_sanctions_updated_event(
+    env: &Env,
+    count: u32,
+    added: bool,
+    reason_hash: Option<BytesN<32>>,
+    expires_at: Option<u64>,
+    updated_by: Address,
+) {
+    SanctionsUpdated {
+        count,
+        added,
+        reason_hash,
+        expires_at,
+        updated_by,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
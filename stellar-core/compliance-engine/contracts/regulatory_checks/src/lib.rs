@@ -1,11 +1,20 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    String, Symbol, Vec,
 };
 
 mod events;
 mod test;
 
+const DAY_SECONDS: u64 = 86_400;
+// Two days of 5s ledgers, so a day bucket outlives the day it counts
+const VOLUME_BUCKET_TTL: u32 = 34_560;
+// Each address is its own ledger entry; larger lists must be split across
+// transactions to stay under the per-transaction write limit.
+pub const MAX_SANCTIONS_BATCH: u32 = 40;
+pub const MAX_HISTORY_PAGE: u32 = 50;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum OperationType {
@@ -47,6 +56,40 @@ pub struct PendingApproval {
     pub approved: bool,
 }
 
+/// Outcome of check_transfer, stamped with the rule version it was decided
+/// under. `rule` names the check that decided it: "sanctions", "kyc",
+/// "volume", "corridor" (with the matched corridor), or "default" when no
+/// corridor matched and nothing blocked the transfer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferDecision {
+    pub allowed: bool,
+    pub rule: Symbol,
+    pub corridor_from: Option<String>,
+    pub corridor_to: Option<String>,
+    pub rule_version: u64,
+}
+
+/// One bump of the rule version: who changed the rules, when, and through
+/// which entry point. The matching RuleAdded, CorridorUpdated, etc. event
+/// carries the details of the change.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RuleChange {
+    pub version: u64,
+    pub change: Symbol,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SanctionEntry {
+    pub reason_hash: BytesN<32>,
+    pub added_at: u64,
+    pub expires_at: Option<u64>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -57,6 +100,15 @@ pub enum DataKey {
     ActiveRuleIds,
     AddressJurisdiction(Address),
     PendingApproval(BytesN<32>),
+    Corridor(String, String), // (from_jur, to_jur) -> allowed
+    RequireKyc,
+    AddressTier(Address),
+    TierLimit(u32),            // tier -> max daily amount
+    DailyVolume(Address, u64), // (address, day) -> amount sent
+    ComplianceOfficer,
+    Sanctioned(Address),
+    RuleVersion,
+    RuleChange(u64), // version -> RuleChange
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -70,6 +122,7 @@ pub enum ContractError {
     ApprovalExpired = 6,
     NoMatchingRule = 7,
     RuleConflict = 8, // New error for logical duplicate/conflict
+    BatchTooLarge = 9,
 }
 
 #[contract]
@@ -162,6 +215,7 @@ impl RegulatoryCheck {
         env.storage()
             .instance()
             .set(&DataKey::ActiveRuleIds, &active_rules);
+        Self::bump_rule_version(&env, "add_rule", &caller);
 
         // Emit RuleAdded event after state changes
         events::emit_rule_added_event(
@@ -220,6 +274,7 @@ impl RegulatoryCheck {
 
         // Store the updated rule
         env.storage().persistent().set(&rule_key, &rule);
+        Self::bump_rule_version(&env, "update_rule", &caller);
 
         // Emit RuleUpdated event after state change
         events::emit_rule_updated_event(
@@ -274,6 +329,7 @@ impl RegulatoryCheck {
         env.storage()
             .instance()
             .set(&DataKey::ActiveRuleIds, &new_rules);
+        Self::bump_rule_version(&env, "deactivate_rule", &caller);
 
         // Emit RuleDeactivated event after state changes
         events::emit_rule_deactivated_event(&env, rule_id, caller);
@@ -414,6 +470,308 @@ impl RegulatoryCheck {
         }
     }
 
+    // ========================================================================
+    // Sanctions
+    // ========================================================================
+
+    /// Set the address allowed to maintain the sanctions list
+    /// Until one is set, the admin holds the compliance role.
+    pub fn set_compliance_officer(
+        env: Env,
+        caller: Address,
+        officer: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ComplianceOfficer, &officer);
+        Ok(())
+    }
+
+    pub fn get_compliance_officer(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::ComplianceOfficer)
+            .unwrap_or_else(|| env.storage().instance().get(&DataKey::Admin).unwrap())
+    }
+
+    /// Add addresses to the sanctions list, optionally expiring at `expires_at`
+    /// Re-adding an address replaces its entry.
+    pub fn add_sanctioned(
+        env: Env,
+        caller: Address,
+        addresses: Vec<Address>,
+        reason_hash: BytesN<32>,
+        expires_at: Option<u64>,
+    ) -> Result<(), ContractError> {
+        Self::require_compliance(&env, &caller, &addresses)?;
+
+        let entry = SanctionEntry {
+            reason_hash: reason_hash.clone(),
+            added_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        for address in addresses.iter() {
+            let key = DataKey::Sanctioned(address);
+            env.storage().persistent().set(&key, &entry);
+        }
+        Self::bump_rule_version(&env, "add_sanctioned", &caller);
+
+        events::emit_sanctions_updated_event(
+            &env,
+            addresses.len(),
+            true,
+            Some(reason_hash),
+            expires_at,
+            caller,
+        );
+
+        Ok(())
+    }
+
+    /// Remove addresses from the sanctions list
+    pub fn remove_sanctioned(
+        env: Env,
+        caller: Address,
+        addresses: Vec<Address>,
+    ) -> Result<(), ContractError> {
+        Self::require_compliance(&env, &caller, &addresses)?;
+
+        for address in addresses.iter() {
+            let key = DataKey::Sanctioned(address);
+            env.storage().persistent().remove(&key);
+        }
+        Self::bump_rule_version(&env, "remove_sanctioned", &caller);
+
+        events::emit_sanctions_updated_event(&env, addresses.len(), false, None, None, caller);
+
+        Ok(())
+    }
+
+    /// Whether an address is currently sanctioned; expired entries do not count
+    pub fn is_sanctioned(env: Env, account: Address) -> bool {
+        match Self::get_sanction(env.clone(), account) {
+            Some(entry) => match entry.expires_at {
+                Some(expires_at) => env.ledger().timestamp() < expires_at,
+                None => true,
+            },
+            None => false,
+        }
+    }
+
+    pub fn get_sanction(env: Env, account: Address) -> Option<SanctionEntry> {
+        let key = DataKey::Sanctioned(account);
+        env.storage().persistent().get(&key)
+    }
+
+    // ========================================================================
+    // Transfer Screening
+    // ========================================================================
+
+    /// Allow transfers from one jurisdiction to another
+    /// Either side may be "ANY"; an exact corridor overrides a wildcard one.
+    pub fn allow_corridor(
+        env: Env,
+        caller: Address,
+        from_jur: String,
+        to_jur: String,
+    ) -> Result<(), ContractError> {
+        Self::set_corridor(env, caller, from_jur, to_jur, true)
+    }
+
+    /// Block transfers from one jurisdiction to another
+    /// Either side may be "ANY"; an exact corridor overrides a wildcard one.
+    pub fn block_corridor(
+        env: Env,
+        caller: Address,
+        from_jur: String,
+        to_jur: String,
+    ) -> Result<(), ContractError> {
+        Self::set_corridor(env, caller, from_jur, to_jur, false)
+    }
+
+    /// Get the configured status of a corridor: allowed, blocked, or unset
+    pub fn get_corridor(env: Env, from_jur: String, to_jur: String) -> Option<bool> {
+        let key = DataKey::Corridor(from_jur, to_jur);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Require both parties to have a registered jurisdiction
+    pub fn set_require_kyc(env: Env, caller: Address, required: bool) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
+
+        if caller != governance {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RequireKyc, &required);
+        Self::bump_rule_version(&env, "set_require_kyc", &caller);
+        Ok(())
+    }
+
+    pub fn get_require_kyc(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::RequireKyc)
+            .unwrap_or(false)
+    }
+
+    /// Screen a transfer against the sanctions list, the corridor table,
+    /// KYC mode, and the sender's daily volume limit
+    /// The decision names the rule that fired and the rule version in force.
+    pub fn check_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        token_id: u32,
+        project_id: String,
+    ) -> TransferDecision {
+        Self::screen_transfer(&env, from, to, amount, token_id, project_id, false)
+    }
+
+    /// Same as check_transfer, but emits a TransferRejected event naming the
+    /// rule that fired when the transfer is rejected
+    pub fn check_and_log(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        token_id: u32,
+        project_id: String,
+    ) -> TransferDecision {
+        Self::screen_transfer(&env, from, to, amount, token_id, project_id, true)
+    }
+
+    // ========================================================================
+    // Rule Versioning
+    // ========================================================================
+
+    /// Current version of the rule set; 0 until the first change
+    /// Every change to jurisdiction rules, corridors, KYC mode, sanctions,
+    /// tiers or tier limits bumps it by one.
+    pub fn get_rule_version(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RuleVersion)
+            .unwrap_or(0)
+    }
+
+    /// Rule changes in version order, skipping the first `start`
+    /// At most MAX_HISTORY_PAGE records are returned per call.
+    pub fn get_rule_history(env: Env, start: u32, limit: u32) -> Vec<RuleChange> {
+        let latest = Self::get_rule_version(env.clone());
+        let first = start as u64 + 1;
+        let last = latest.min(start as u64 + limit.min(MAX_HISTORY_PAGE) as u64);
+
+        let mut history = Vec::new(&env);
+        for version in first..=last {
+            let key = DataKey::RuleChange(version);
+            if let Some(change) = env.storage().persistent().get(&key) {
+                history.push_back(change);
+            }
+        }
+        history
+    }
+
+    // ========================================================================
+    // Volume Limits
+    // ========================================================================
+
+    /// Assign an address to a volume tier (addresses default to tier 0)
+    pub fn set_tier(
+        env: Env,
+        caller: Address,
+        account: Address,
+        tier: u32,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+
+        if caller != admin {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::AddressTier(account), &tier);
+        Self::bump_rule_version(&env, "set_tier", &caller);
+        Ok(())
+    }
+
+    pub fn get_tier(env: Env, account: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AddressTier(account))
+            .unwrap_or(0)
+    }
+
+    /// Set the maximum amount an address in `tier` may send per day
+    /// Tiers without a limit are unrestricted.
+    pub fn set_tier_limit(
+        env: Env,
+        caller: Address,
+        tier: u32,
+        max_daily_amount: i128,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
+
+        if caller != governance {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TierLimit(tier), &max_daily_amount);
+        Self::bump_rule_version(&env, "set_tier_limit", &caller);
+        Ok(())
+    }
+
+    pub fn get_tier_limit(env: Env, tier: u32) -> Option<i128> {
+        env.storage().persistent().get(&DataKey::TierLimit(tier))
+    }
+
+    /// Amount sent by an address so far in the current day
+    pub fn get_daily_volume(env: Env, account: Address) -> i128 {
+        let key = DataKey::DailyVolume(account, Self::current_day(&env));
+        env.storage().temporary().get(&key).unwrap_or(0)
+    }
+
+    /// Count a completed transfer against the sender's daily volume
+    /// Only the carbon asset contract may call this, after the transfer succeeds,
+    /// so rejected attempts never count.
+    pub fn record_transfer(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+        let carbon_asset: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .unwrap();
+        carbon_asset.require_auth();
+
+        let key = DataKey::DailyVolume(from, Self::current_day(&env));
+        let used: i128 = env.storage().temporary().get(&key).unwrap_or(0);
+        env.storage().temporary().set(&key, &(used + amount));
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, VOLUME_BUCKET_TTL, VOLUME_BUCKET_TTL);
+
+        Ok(())
+    }
+
     // ========================================================================
     // Authority Approval
     // ========================================================================
@@ -490,6 +848,165 @@ impl RegulatoryCheck {
     // Helper Functions
     // ========================================================================
 
+    fn set_corridor(
+        env: Env,
+        caller: Address,
+        from_jur: String,
+        to_jur: String,
+        allowed: bool,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
+
+        if caller != governance {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let key = DataKey::Corridor(from_jur.clone(), to_jur.clone());
+        env.storage().persistent().set(&key, &allowed);
+        let change = if allowed {
+            "allow_corridor"
+        } else {
+            "block_corridor"
+        };
+        Self::bump_rule_version(&env, change, &caller);
+
+        events::emit_corridor_updated_event(&env, from_jur, to_jur, allowed, caller);
+
+        Ok(())
+    }
+
+    /// Most specific corridor wins: exact pair, then (from, ANY), (ANY, to), (ANY, ANY).
+    /// Returns the matched corridor and whether it allows the transfer.
+    fn resolve_corridor(
+        env: &Env,
+        source_jur: &String,
+        dest_jur: &String,
+    ) -> Option<(String, String, bool)> {
+        let any = String::from_str(env, "ANY");
+        let candidates = [
+            (source_jur.clone(), dest_jur.clone()),
+            (source_jur.clone(), any.clone()),
+            (any.clone(), dest_jur.clone()),
+            (any.clone(), any),
+        ];
+
+        for (from_jur, to_jur) in candidates {
+            let key = DataKey::Corridor(from_jur.clone(), to_jur.clone());
+            if let Some(allowed) = env.storage().persistent().get::<DataKey, bool>(&key) {
+                return Some((from_jur, to_jur, allowed));
+            }
+        }
+        None
+    }
+
+    fn require_compliance(
+        env: &Env,
+        caller: &Address,
+        addresses: &Vec<Address>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if *caller != Self::get_compliance_officer(env.clone()) {
+            return Err(ContractError::NotAuthorized);
+        }
+        if addresses.len() > MAX_SANCTIONS_BATCH {
+            return Err(ContractError::BatchTooLarge);
+        }
+        Ok(())
+    }
+
+    fn bump_rule_version(env: &Env, change: &str, changed_by: &Address) {
+        let version = Self::get_rule_version(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::RuleVersion, &version);
+
+        let record = RuleChange {
+            version,
+            change: Symbol::new(env, change),
+            changed_by: changed_by.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::RuleChange(version), &record);
+    }
+
+    fn current_day(env: &Env) -> u64 {
+        env.ledger().timestamp() / DAY_SECONDS
+    }
+
+    fn exceeds_daily_limit(env: &Env, from: &Address, amount: i128) -> bool {
+        let tier = Self::get_tier(env.clone(), from.clone());
+        match Self::get_tier_limit(env.clone(), tier) {
+            Some(limit) => Self::get_daily_volume(env.clone(), from.clone()) + amount > limit,
+            None => false,
+        }
+    }
+
+    fn screen_transfer(
+        env: &Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        token_id: u32,
+        project_id: String,
+        log: bool,
+    ) -> TransferDecision {
+        let source_jur = Self::get_address_jurisdiction(env.clone(), from.clone());
+        let dest_jur = Self::get_address_jurisdiction(env.clone(), to.clone());
+
+        // Sanctions take precedence over any corridor that would allow the transfer
+        let decision = match (source_jur, dest_jur) {
+            _ if Self::is_sanctioned(env.clone(), from.clone())
+                || Self::is_sanctioned(env.clone(), to.clone()) =>
+            {
+                Some((symbol_short!("sanctions"), None, None, false))
+            }
+            (Some(source_jur), Some(dest_jur)) => {
+                let corridor = Self::resolve_corridor(env, &source_jur, &dest_jur);
+                corridor.map(|(from, to, ok)| (symbol_short!("corridor"), Some(from), Some(to), ok))
+            }
+            _ if Self::get_require_kyc(env.clone()) => {
+                Some((symbol_short!("kyc"), None, None, false))
+            }
+            _ => None,
+        };
+        // An allowing corridor still answers to the sender's volume limit
+        let (rule, corridor_from, corridor_to, allowed) = match decision {
+            Some((_, _, _, true)) | None if Self::exceeds_daily_limit(env, &from, amount) => {
+                (symbol_short!("volume"), None, None, false)
+            }
+            Some(decision) => decision,
+            None => (symbol_short!("default"), None, None, true),
+        };
+
+        let rule_version = Self::get_rule_version(env.clone());
+        if !allowed && log {
+            events::emit_transfer_rejected_event(
+                env,
+                from,
+                to,
+                amount,
+                token_id,
+                project_id,
+                rule.clone(),
+                corridor_from.clone(),
+                corridor_to.clone(),
+                rule_version,
+            );
+        }
+        TransferDecision {
+            allowed,
+            rule,
+            corridor_from,
+            corridor_to,
+            rule_version,
+        }
+    }
+
     fn rule_matches(
         env: &Env,
         rule: &JurisdictionRule,
@@ -574,4 +1091,4 @@ impl RegulatoryCheck {
             .get(&DataKey::ActiveRuleIds)
             .unwrap_or(Vec::new(&env))
     }
-}
\ No newline at end of file
+}
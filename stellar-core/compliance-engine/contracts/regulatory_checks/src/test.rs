@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String, Bytes};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+};
 
 fn make_rule(
     env: &Env,
@@ -79,6 +82,7 @@ fn test_unique_rule_addition() {
 
 /// Verify that add_rule emits a RuleAdded event with full rule metadata
 #[test]
+#[allow(deprecated)]
 fn test_add_rule_emits_event() {
     let env = Env::default();
     let contract_id = env.register_contract(None, RegulatoryCheck);
@@ -101,6 +105,7 @@ fn test_add_rule_emits_event() {
 
 /// Verify that update_rule emits a RuleUpdated event with old and new hashes
 #[test]
+#[allow(deprecated)]
 fn test_update_rule_emits_event() {
     let env = Env::default();
     let contract_id = env.register_contract(None, RegulatoryCheck);
@@ -131,11 +136,12 @@ fn test_update_rule_emits_event() {
     // Verify the rule was updated
     let stored = client.get_rule(&String::from_str(&env, "R1"));
     assert!(stored.is_some());
-    assert_eq!(stored.unwrap().is_allowed, false);
+    assert!(!stored.unwrap().is_allowed);
 }
 
 /// Verify that deactivate_rule emits a RuleDeactivated event
 #[test]
+#[allow(deprecated)]
 fn test_deactivate_rule_emits_event() {
     let env = Env::default();
     let contract_id = env.register_contract(None, RegulatoryCheck);
@@ -164,6 +170,7 @@ fn test_deactivate_rule_emits_event() {
 
 /// Verify full lifecycle: add → update → deactivate with events
 #[test]
+#[allow(deprecated)]
 fn test_rule_lifecycle_events() {
     let env = Env::default();
     let contract_id = env.register_contract(None, RegulatoryCheck);
@@ -192,7 +199,7 @@ fn test_rule_lifecycle_events() {
     };
     client.update_rule(&governance, &updated);
     let stored = client.get_rule(&String::from_str(&env, "R1")).unwrap();
-    assert_eq!(stored.is_allowed, false);
+    assert!(!stored.is_allowed);
 
     // Step 3: Deactivate rule
     client.deactivate_rule(&governance, &String::from_str(&env, "R1"));
@@ -226,4 +233,501 @@ fn test_compute_rule_hash_different() {
     let hash2 = events::compute_rule_hash(&env, &rule2);
 
     assert_ne!(hash1, hash2, "Hashes should differ for different rules (different rule_id)");
-}
\ No newline at end of file
+}
+
+// ========== Corridor Screening Tests ==========
+
+struct Corridors<'a> {
+    env: Env,
+    client: RegulatoryCheckClient<'a>,
+    admin: Address,
+    governance: Address,
+    asset: Address,
+}
+
+fn setup_corridors<'a>() -> Corridors<'a> {
+    let env = Env::default();
+    let contract_id = env.register(RegulatoryCheck, ());
+    let client = RegulatoryCheckClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let asset = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin, &governance, &asset);
+    Corridors {
+        env,
+        client,
+        admin,
+        governance,
+        asset,
+    }
+}
+
+fn registered(c: &Corridors, jur: &str) -> Address {
+    let account = Address::generate(&c.env);
+    c.client
+        .set_address_jurisdiction(&c.admin, &account, &String::from_str(&c.env, jur));
+    account
+}
+
+fn check(c: &Corridors, from: &Address, to: &Address) -> bool {
+    c.client
+        .check_transfer(from, to, &1, &7, &String::from_str(&c.env, "PROJ-1"))
+        .allowed
+}
+
+/// Exact corridors are directional: blocking US -> CN leaves CN -> US open
+#[test]
+fn test_corridor_matrix_exact_pairs() {
+    let c = setup_corridors();
+    let us = registered(&c, "US");
+    let cn = registered(&c, "CN");
+    let de = registered(&c, "DE");
+    let s = |v: &str| String::from_str(&c.env, v);
+
+    c.client.block_corridor(&c.governance, &s("US"), &s("CN"));
+    c.client.allow_corridor(&c.governance, &s("US"), &s("DE"));
+
+    // (from, to, expected)
+    let matrix = [
+        (&us, &cn, false),
+        (&cn, &us, true),
+        (&us, &de, true),
+        (&de, &us, true),
+        (&cn, &de, true),
+        (&de, &cn, true),
+        (&us, &us, true),
+    ];
+    for (from, to, expected) in matrix.iter() {
+        assert_eq!(check(&c, from, to), *expected);
+    }
+
+    assert_eq!(c.client.get_corridor(&s("US"), &s("CN")), Some(false));
+    assert_eq!(c.client.get_corridor(&s("US"), &s("DE")), Some(true));
+    assert_eq!(c.client.get_corridor(&s("CN"), &s("US")), None);
+}
+
+/// Wildcard corridors apply unless a more specific corridor overrides them
+#[test]
+fn test_corridor_matrix_wildcards() {
+    let c = setup_corridors();
+    let us = registered(&c, "US");
+    let cn = registered(&c, "CN");
+    let de = registered(&c, "DE");
+    let s = |v: &str| String::from_str(&c.env, v);
+
+    // Embargo everything leaving CN except to DE, and everything into US
+    c.client.block_corridor(&c.governance, &s("CN"), &s("ANY"));
+    c.client.allow_corridor(&c.governance, &s("CN"), &s("DE"));
+    c.client.block_corridor(&c.governance, &s("ANY"), &s("US"));
+    c.client.allow_corridor(&c.governance, &s("DE"), &s("US"));
+
+    let matrix = [
+        (&cn, &us, false),
+        (&cn, &de, true),
+        (&cn, &cn, false),
+        (&de, &us, true),
+        (&us, &us, false),
+        (&us, &de, true),
+        (&de, &cn, true),
+    ];
+    for (from, to, expected) in matrix.iter() {
+        assert_eq!(check(&c, from, to), *expected);
+    }
+
+    // Re-allowing an exact corridor lifts the wildcard block for that pair only
+    c.client.allow_corridor(&c.governance, &s("US"), &s("US"));
+    assert!(check(&c, &us, &us));
+    assert!(!check(&c, &cn, &us));
+}
+
+/// Unregistered addresses pass unless require_kyc is on
+#[test]
+fn test_check_transfer_require_kyc() {
+    let c = setup_corridors();
+    let us = registered(&c, "US");
+    let unknown = Address::generate(&c.env);
+
+    assert!(!c.client.get_require_kyc());
+    assert!(check(&c, &us, &unknown));
+    assert!(check(&c, &unknown, &us));
+
+    c.client.set_require_kyc(&c.governance, &true);
+    assert!(c.client.get_require_kyc());
+    assert!(!check(&c, &us, &unknown));
+    assert!(!check(&c, &unknown, &us));
+    assert!(check(&c, &us, &registered(&c, "DE")));
+}
+
+/// Only governance may manage corridors or KYC mode
+#[test]
+fn test_corridor_management_requires_governance() {
+    let c = setup_corridors();
+    let s = |v: &str| String::from_str(&c.env, v);
+
+    let res = c.client.try_block_corridor(&c.admin, &s("US"), &s("CN"));
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+    let res = c.client.try_allow_corridor(&c.admin, &s("US"), &s("CN"));
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+    let res = c.client.try_set_require_kyc(&c.admin, &true);
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+    assert_eq!(c.client.get_corridor(&s("US"), &s("CN")), None);
+}
+
+/// check_and_log returns the same verdict and emits TransferRejected on rejection
+#[test]
+fn test_check_and_log_emits_rejection() {
+    let c = setup_corridors();
+    let us = registered(&c, "US");
+    let cn = registered(&c, "CN");
+    let project = String::from_str(&c.env, "PROJ-1");
+    c.client.block_corridor(
+        &c.governance,
+        &String::from_str(&c.env, "US"),
+        &String::from_str(&c.env, "ANY"),
+    );
+
+    assert!(c.client.check_and_log(&cn, &us, &5, &1, &project).allowed);
+    assert_eq!(c.env.events().all().events().len(), 0);
+
+    assert!(!c.client.check_and_log(&us, &cn, &5, &1, &project).allowed);
+    assert_eq!(c.env.events().all().events().len(), 1);
+
+    // check_transfer never emits
+    assert!(!c.client.check_transfer(&us, &cn, &5, &1, &project).allowed);
+    assert_eq!(c.env.events().all().events().len(), 0);
+}
+
+// ========== Volume Limit Tests ==========
+
+fn check_amount(c: &Corridors, from: &Address, to: &Address, amount: i128) -> bool {
+    c.client
+        .check_transfer(from, to, &amount, &7, &String::from_str(&c.env, "PROJ-1"))
+        .allowed
+}
+
+/// Recorded volume counts against the sender's tier limit until the day rolls over
+#[test]
+fn test_daily_limit_resets_across_day_boundary() {
+    let c = setup_corridors();
+    let sender = registered(&c, "US");
+    let receiver = registered(&c, "DE");
+    c.client.set_tier_limit(&c.governance, &0, &10);
+
+    // 23:00 on day 1
+    c.env.ledger().set_timestamp(86_400 + 82_800);
+    assert!(check_amount(&c, &sender, &receiver, 10));
+    assert!(!check_amount(&c, &sender, &receiver, 11));
+
+    // Checks alone never count; only recorded transfers do
+    assert_eq!(c.client.get_daily_volume(&sender), 0);
+    c.client.record_transfer(&sender, &6);
+    c.client.record_transfer(&sender, &4);
+    assert_eq!(c.client.get_daily_volume(&sender), 10);
+    assert!(!check_amount(&c, &sender, &receiver, 1));
+    assert_eq!(c.client.get_daily_volume(&receiver), 0);
+    assert!(check_amount(&c, &receiver, &sender, 10));
+
+    // Last second of day 1 is still the same bucket
+    c.env.ledger().set_timestamp(2 * 86_400 - 1);
+    assert!(!check_amount(&c, &sender, &receiver, 1));
+
+    // Day 2 starts with a fresh bucket
+    c.env.ledger().set_timestamp(2 * 86_400);
+    assert_eq!(c.client.get_daily_volume(&sender), 0);
+    assert!(check_amount(&c, &sender, &receiver, 10));
+}
+
+/// Upgrading an address's tier mid-day applies the new limit to the volume already sent
+#[test]
+fn test_tier_upgrade_mid_day() {
+    let c = setup_corridors();
+    let sender = registered(&c, "US");
+    let receiver = registered(&c, "DE");
+    c.client.set_tier_limit(&c.governance, &0, &5);
+    c.client.set_tier_limit(&c.governance, &1, &20);
+    c.env.ledger().set_timestamp(86_400 + 43_200);
+
+    c.client.record_transfer(&sender, &5);
+    assert!(!check_amount(&c, &sender, &receiver, 1));
+    assert!(
+        !c.client
+            .check_and_log(
+                &sender,
+                &receiver,
+                &1,
+                &7,
+                &String::from_str(&c.env, "PROJ-1")
+            )
+            .allowed
+    );
+    assert_eq!(c.env.events().all().events().len(), 1);
+
+    c.client.set_tier(&c.admin, &sender, &1);
+    assert_eq!(c.client.get_tier(&sender), 1);
+    assert!(check_amount(&c, &sender, &receiver, 15));
+    assert!(!check_amount(&c, &sender, &receiver, 16));
+
+    // Tiers without a configured limit are unrestricted
+    c.client.set_tier(&c.admin, &sender, &2);
+    assert_eq!(c.client.get_tier_limit(&2), None);
+    assert!(check_amount(&c, &sender, &receiver, 1_000_000));
+}
+
+/// Tier assignment is admin-only, limits are governance-only, and only the
+/// carbon asset contract may record transfers
+#[test]
+fn test_volume_limit_authorization() {
+    let c = setup_corridors();
+    let sender = registered(&c, "US");
+
+    let res = c.client.try_set_tier(&c.governance, &sender, &1);
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+    let res = c.client.try_set_tier_limit(&c.admin, &0, &10);
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+
+    c.env.set_auths(&[]);
+    assert!(c.client.try_record_transfer(&sender, &1).is_err());
+    assert_eq!(c.client.get_daily_volume(&sender), 0);
+
+    c.env.mock_auths(&[MockAuth {
+        address: &c.asset,
+        invoke: &MockAuthInvoke {
+            contract: &c.client.address,
+            fn_name: "record_transfer",
+            args: (sender.clone(), 1i128).into_val(&c.env),
+            sub_invokes: &[],
+        },
+    }]);
+    c.client.record_transfer(&sender, &1);
+    assert_eq!(c.client.get_daily_volume(&sender), 1);
+}
+
+// ========== Sanctions Tests ==========
+
+fn addresses(env: &Env, count: u32) -> Vec<Address> {
+    let mut list = Vec::new(env);
+    for _ in 0..count {
+        list.push_back(Address::generate(env));
+    }
+    list
+}
+
+/// Bulk add and remove honor the batch cap and only touch the listed addresses
+#[test]
+fn test_sanctions_bulk_add_and_remove() {
+    let c = setup_corridors();
+    let reason = BytesN::from_array(&c.env, &[1u8; 32]);
+    let batch = addresses(&c.env, MAX_SANCTIONS_BATCH);
+
+    c.client.add_sanctioned(&c.admin, &batch, &reason, &None);
+    for address in batch.iter() {
+        assert!(c.client.is_sanctioned(&address));
+    }
+    let entry = c.client.get_sanction(&batch.get(0).unwrap()).unwrap();
+    assert_eq!(entry.reason_hash, reason);
+    assert_eq!(entry.expires_at, None);
+
+    let oversized = addresses(&c.env, MAX_SANCTIONS_BATCH + 1);
+    let res = c
+        .client
+        .try_add_sanctioned(&c.admin, &oversized, &reason, &None);
+    assert!(matches!(res, Err(Ok(ContractError::BatchTooLarge))));
+    assert!(!c.client.is_sanctioned(&oversized.get(0).unwrap()));
+    let res = c.client.try_remove_sanctioned(&c.admin, &oversized);
+    assert!(matches!(res, Err(Ok(ContractError::BatchTooLarge))));
+
+    let mut removed = Vec::new(&c.env);
+    removed.push_back(batch.get(0).unwrap());
+    removed.push_back(batch.get(1).unwrap());
+    c.client.remove_sanctioned(&c.admin, &removed);
+    assert!(!c.client.is_sanctioned(&batch.get(0).unwrap()));
+    assert!(!c.client.is_sanctioned(&batch.get(1).unwrap()));
+    assert!(c.client.is_sanctioned(&batch.get(2).unwrap()));
+    assert!(c.client.get_sanction(&batch.get(0).unwrap()).is_none());
+}
+
+/// Entries with an expiry stop applying at expires_at
+#[test]
+fn test_sanctions_expire() {
+    let c = setup_corridors();
+    let sender = registered(&c, "US");
+    let receiver = registered(&c, "DE");
+    let reason = BytesN::from_array(&c.env, &[2u8; 32]);
+    c.env.ledger().set_timestamp(1_000);
+
+    let mut listed = Vec::new(&c.env);
+    listed.push_back(receiver.clone());
+    c.client
+        .add_sanctioned(&c.admin, &listed, &reason, &Some(5_000));
+    assert!(c.client.is_sanctioned(&receiver));
+    assert!(!check(&c, &sender, &receiver));
+    assert!(!check(&c, &receiver, &sender));
+
+    c.env.ledger().set_timestamp(4_999);
+    assert!(c.client.is_sanctioned(&receiver));
+
+    c.env.ledger().set_timestamp(5_000);
+    assert!(!c.client.is_sanctioned(&receiver));
+    assert!(check(&c, &sender, &receiver));
+    assert!(c.client.get_sanction(&receiver).is_some());
+}
+
+/// Sanctions reject transfers even over an explicitly allowed corridor
+#[test]
+fn test_sanctions_take_precedence_over_corridors() {
+    let c = setup_corridors();
+    let sender = registered(&c, "US");
+    let receiver = registered(&c, "DE");
+    let unregistered = Address::generate(&c.env);
+    let s = |v: &str| String::from_str(&c.env, v);
+    c.client.allow_corridor(&c.governance, &s("US"), &s("DE"));
+    assert!(check(&c, &sender, &receiver));
+
+    let mut listed = Vec::new(&c.env);
+    listed.push_back(sender.clone());
+    listed.push_back(unregistered.clone());
+    let reason = BytesN::from_array(&c.env, &[3u8; 32]);
+    c.client.add_sanctioned(&c.admin, &listed, &reason, &None);
+
+    assert!(!check(&c, &sender, &receiver));
+    // Sanctioned addresses are rejected even when KYC mode is off
+    assert!(!check(&c, &receiver, &unregistered));
+    assert!(
+        !c.client
+            .check_and_log(&sender, &receiver, &1, &7, &s("PROJ-1"))
+            .allowed
+    );
+    assert_eq!(c.env.events().all().events().len(), 1);
+}
+
+/// Only the compliance officer (the admin until one is set) maintains the list
+#[test]
+fn test_sanctions_require_compliance_role() {
+    let c = setup_corridors();
+    let officer = Address::generate(&c.env);
+    let reason = BytesN::from_array(&c.env, &[4u8; 32]);
+    let listed = addresses(&c.env, 1);
+
+    assert_eq!(c.client.get_compliance_officer(), c.admin);
+    let res = c
+        .client
+        .try_add_sanctioned(&c.governance, &listed, &reason, &None);
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+
+    c.client.set_compliance_officer(&c.admin, &officer);
+    assert_eq!(c.client.get_compliance_officer(), officer);
+    let res = c
+        .client
+        .try_add_sanctioned(&c.admin, &listed, &reason, &None);
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+
+    c.client.add_sanctioned(&officer, &listed, &reason, &None);
+    assert!(c.client.is_sanctioned(&listed.get(0).unwrap()));
+    let res = c.client.try_remove_sanctioned(&c.governance, &listed);
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+}
+
+// ========== Rule Versioning Tests ==========
+
+/// Every corridor, sanction, tier and rule change bumps the version once and
+/// records who made it
+#[test]
+fn test_rule_changes_bump_version_and_history() {
+    let c = setup_corridors();
+    let account = registered(&c, "US");
+    let s = |v: &str| String::from_str(&c.env, v);
+    let reason = BytesN::from_array(&c.env, &[5u8; 32]);
+
+    // Assigning a jurisdiction is not a rule change
+    assert_eq!(c.client.get_rule_version(), 0);
+    assert!(c.client.get_rule_history(&0, &10).is_empty());
+
+    c.env.ledger().set_timestamp(1_000);
+    c.client.block_corridor(&c.governance, &s("US"), &s("CN"));
+    c.client.allow_corridor(&c.governance, &s("US"), &s("CN"));
+    c.client.set_tier_limit(&c.governance, &1, &100);
+    c.env.ledger().set_timestamp(2_000);
+    c.client.set_tier(&c.admin, &account, &1);
+    c.client
+        .add_sanctioned(&c.admin, &addresses(&c.env, 1), &reason, &None);
+    let rule = make_rule(
+        &c.env,
+        "R1",
+        "US",
+        "ANY",
+        "ANY",
+        OperationType::TRANSFER,
+        true,
+    );
+    c.client.add_rule(&c.governance, &rule);
+    assert_eq!(c.client.get_rule_version(), 6);
+
+    // A rejected change leaves the version alone
+    let res = c.client.try_block_corridor(&c.admin, &s("US"), &s("DE"));
+    assert!(matches!(res, Err(Ok(ContractError::NotAuthorized))));
+    assert_eq!(c.client.get_rule_version(), 6);
+
+    let history = c.client.get_rule_history(&0, &10);
+    assert_eq!(history.len(), 6);
+    let expected = [
+        ("block_corridor", &c.governance, 1_000),
+        ("allow_corridor", &c.governance, 1_000),
+        ("set_tier_limit", &c.governance, 1_000),
+        ("set_tier", &c.admin, 2_000),
+        ("add_sanctioned", &c.admin, 2_000),
+        ("add_rule", &c.governance, 2_000),
+    ];
+    for (i, (change, changed_by, timestamp)) in expected.iter().enumerate() {
+        let record = history.get(i as u32).unwrap();
+        assert_eq!(record.version, i as u64 + 1);
+        assert_eq!(record.change, Symbol::new(&c.env, change));
+        assert_eq!(record.changed_by, **changed_by);
+        assert_eq!(record.timestamp, *timestamp);
+    }
+
+    // Paging past the first records, and past the end
+    let page = c.client.get_rule_history(&4, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().version, 5);
+    assert!(c.client.get_rule_history(&6, &10).is_empty());
+}
+
+/// The decision names the rule that fired and the version it was made under
+#[test]
+fn test_check_transfer_reports_rule_and_version() {
+    let c = setup_corridors();
+    let us = registered(&c, "US");
+    let cn = registered(&c, "CN");
+    let project = String::from_str(&c.env, "PROJ-1");
+    let s = |v: &str| String::from_str(&c.env, v);
+
+    let decision = c.client.check_transfer(&us, &cn, &1, &7, &project);
+    assert!(decision.allowed);
+    assert_eq!(decision.rule, symbol_short!("default"));
+    assert_eq!(decision.rule_version, 0);
+
+    c.client.block_corridor(&c.governance, &s("US"), &s("ANY"));
+    let decision = c.client.check_transfer(&us, &cn, &1, &7, &project);
+    assert!(!decision.allowed);
+    assert_eq!(decision.rule, symbol_short!("corridor"));
+    assert_eq!(decision.corridor_from, Some(s("US")));
+    assert_eq!(decision.corridor_to, Some(s("ANY")));
+    assert_eq!(decision.rule_version, 1);
+
+    c.client.allow_corridor(&c.governance, &s("US"), &s("CN"));
+    let decision = c.client.check_transfer(&us, &cn, &1, &7, &project);
+    assert!(decision.allowed);
+    assert_eq!(decision.rule, symbol_short!("corridor"));
+    assert_eq!(decision.corridor_to, Some(s("CN")));
+    assert_eq!(decision.rule_version, 2);
+
+    // An allowed corridor does not lift the volume limit
+    c.client.set_tier_limit(&c.governance, &0, &0);
+    let decision = c.client.check_and_log(&us, &cn, &1, &7, &project);
+    assert!(!decision.allowed);
+    assert_eq!(decision.rule, symbol_short!("volume"));
+    assert_eq!(decision.corridor_from, None);
+    assert_eq!(decision.rule_version, 3);
+    assert_eq!(c.env.events().all().events().len(), 1);
+}
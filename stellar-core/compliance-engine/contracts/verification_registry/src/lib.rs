@@ -0,0 +1,354 @@
+#![no_std]
+mod test;
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, Address, Bytes, BytesN,
+    Env, IntoVal, Map, String, Symbol, Vec,
+};
+
+/// Longest monitoring period a single attestation may cover, in calendar years.
+pub const MAX_PERIOD_YEARS: u64 = 10;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attestation {
+    pub attestation_id: u64,
+    pub verifier: Address,
+    pub project_id: String,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub report_hash: BytesN<32>,
+    pub verified_amount: i128,
+    pub submitted_at: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    AuthorizedVerifiers,
+    AuditTrail,
+    NextAttestationId,
+    Attestation(u64),
+    ProjectAttestations(String),
+    VerifiedVintage(String, u64),
+    VerifiedAmount(String, u64),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotAuthorized = 2,
+    InvalidPeriod = 3,
+    InvalidAmount = 4,
+    PeriodTooLong = 5,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttestationSubmitted {
+    #[topic]
+    pub project_id: String,
+    pub attestation_id: u64,
+    pub verifier: Address,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub report_hash: BytesN<32>,
+    pub verified_amount: i128,
+}
+
+#[contract]
+pub struct VerificationRegistry;
+
+#[contractimpl]
+impl VerificationRegistry {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        let empty_verifiers: Map<Address, bool> = Map::new(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::AuthorizedVerifiers, &empty_verifiers);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextAttestationId, &1u64);
+        Ok(())
+    }
+
+    pub fn authorize_verifier(env: Env, verifier: Address) {
+        Self::set_verifier(&env, verifier, true);
+    }
+
+    pub fn revoke_verifier(env: Env, verifier: Address) {
+        Self::set_verifier(&env, verifier, false);
+    }
+
+    pub fn is_verifier(env: Env, verifier: Address) -> bool {
+        let verifiers: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AuthorizedVerifiers)
+            .unwrap();
+        verifiers.get(verifier).unwrap_or(false)
+    }
+
+    /// Mirror each attestation into the audit trail. The registry must be an
+    /// authorized emitter there.
+    pub fn set_audit_trail(env: Env, audit_trail: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AuditTrail, &audit_trail);
+    }
+
+    pub fn get_audit_trail(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AuditTrail)
+    }
+
+    /// Record that `verifier` verified `project_id` over `monitoring_period`
+    /// (start inclusive, end exclusive, unix seconds). Every calendar year the
+    /// period overlaps counts as a verified vintage, and `verified_amount` is
+    /// split across those vintages in proportion to the time covered.
+    pub fn submit_attestation(
+        env: Env,
+        verifier: Address,
+        project_id: String,
+        monitoring_period: (u64, u64),
+        report_hash: BytesN<32>,
+        verified_amount: i128,
+    ) -> Result<u64, ContractError> {
+        verifier.require_auth();
+
+        if !Self::is_verifier(env.clone(), verifier.clone()) {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let (period_start, period_end) = monitoring_period;
+        if period_start >= period_end {
+            return Err(ContractError::InvalidPeriod);
+        }
+        if verified_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let first_year = year_of(period_start);
+        let last_year = year_of(period_end - 1);
+        if last_year - first_year >= MAX_PERIOD_YEARS {
+            return Err(ContractError::PeriodTooLong);
+        }
+
+        let attestation_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextAttestationId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextAttestationId, &(attestation_id + 1));
+
+        let attestation = Attestation {
+            attestation_id,
+            verifier: verifier.clone(),
+            project_id: project_id.clone(),
+            period_start,
+            period_end,
+            report_hash: report_hash.clone(),
+            verified_amount,
+            submitted_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestation(attestation_id), &attestation);
+
+        let project_key = DataKey::ProjectAttestations(project_id.clone());
+        let mut attestation_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&project_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        attestation_ids.push_back(attestation_id);
+        env.storage()
+            .persistent()
+            .set(&project_key, &attestation_ids);
+
+        let period_length = (period_end - period_start) as i128;
+        let mut allocated: i128 = 0;
+        for year in first_year..=last_year {
+            let vintage_key = DataKey::VerifiedVintage(project_id.clone(), year);
+            env.storage().persistent().set(&vintage_key, &true);
+
+            // The last vintage takes the rounding remainder so shares sum to the total
+            let share = if year == last_year {
+                verified_amount - allocated
+            } else {
+                let overlap = year_start(year + 1) - period_start.max(year_start(year));
+                verified_amount * overlap as i128 / period_length
+            };
+            allocated += share;
+
+            let amount_key = DataKey::VerifiedAmount(project_id.clone(), year);
+            let total: i128 = env.storage().persistent().get(&amount_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&amount_key, &(total + share));
+        }
+
+        AttestationSubmitted {
+            project_id: project_id.clone(),
+            attestation_id,
+            verifier,
+            period_start,
+            period_end,
+            report_hash: report_hash.clone(),
+            verified_amount,
+        }
+        .publish(&env);
+
+        if let Some(audit_trail) = Self::get_audit_trail(env.clone()) {
+            Self::record_audit_event(
+                &env,
+                &audit_trail,
+                attestation_id,
+                project_id,
+                report_hash,
+                verified_amount,
+            );
+        }
+
+        Ok(attestation_id)
+    }
+
+    pub fn get_attestation(env: Env, attestation_id: u64) -> Option<Attestation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestation(attestation_id))
+    }
+
+    pub fn get_attestations_by_project(env: Env, project_id: String) -> Vec<Attestation> {
+        let attestation_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectAttestations(project_id))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut attestations = Vec::new(&env);
+        for attestation_id in attestation_ids.iter() {
+            if let Some(attestation) = Self::get_attestation(env.clone(), attestation_id) {
+                attestations.push_back(attestation);
+            }
+        }
+        attestations
+    }
+
+    /// Whether any attestation for `project_id` covers part of `vintage_year`.
+    pub fn is_verified(env: Env, project_id: String, vintage_year: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VerifiedVintage(project_id, vintage_year))
+            .unwrap_or(false)
+    }
+
+    /// Total attested amount allocated to `vintage_year` across all attestations.
+    pub fn get_verified_amount(env: Env, project_id: String, vintage_year: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VerifiedAmount(project_id, vintage_year))
+            .unwrap_or(0)
+    }
+
+    fn set_verifier(env: &Env, verifier: Address, authorized: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut verifiers: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AuthorizedVerifiers)
+            .unwrap();
+        verifiers.set(verifier, authorized);
+        env.storage()
+            .instance()
+            .set(&DataKey::AuthorizedVerifiers, &verifiers);
+    }
+
+    fn record_audit_event(
+        env: &Env,
+        audit_trail: &Address,
+        attestation_id: u64,
+        project_id: String,
+        report_hash: BytesN<32>,
+        verified_amount: i128,
+    ) {
+        // The audit trail derives event ids from tx_hash and timestamp, so mix
+        // in the attestation id to keep same-ledger attestations distinct.
+        let mut hash_payload = Bytes::from_slice(env, &report_hash.to_array());
+        hash_payload.append(&Bytes::from_slice(env, &attestation_id.to_be_bytes()));
+        let tx_hash: BytesN<32> = env.crypto().sha256(&hash_payload).into();
+
+        let mut args = Vec::new(env);
+        args.push_back(env.current_contract_address().into_val(env));
+        args.push_back(String::from_str(env, "VERIFICATION_ATTESTED").into_val(env));
+        args.push_back(project_id.into_val(env));
+        args.push_back(None::<String>.into_val(env));
+        args.push_back(amount_to_string(env, verified_amount).into_val(env));
+        args.push_back(tx_hash.into_val(env));
+
+        let symbol = Symbol::new(env, "record_event_auth");
+        env.invoke_contract::<BytesN<32>>(audit_trail, &symbol, args);
+    }
+}
+
+/// Calendar year of a unix timestamp (proleptic Gregorian, UTC).
+fn year_of(timestamp: u64) -> u64 {
+    let days = timestamp / SECONDS_PER_DAY + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400;
+    // Shifted months 10 and 11 are January and February of the following year
+    if shifted_month >= 10 {
+        year + 1
+    } else {
+        year
+    }
+}
+
+/// Unix timestamp of January 1st, 00:00 UTC of `year` (1970 or later).
+fn year_start(year: u64) -> u64 {
+    // Count from March 1st of the previous year, as in year_of
+    let shifted_year = year - 1;
+    let era = shifted_year / 400;
+    let year_of_era = shifted_year - era * 400;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + 306;
+    (era * 146_097 + day_of_era - 719_468) * SECONDS_PER_DAY
+}
+
+fn amount_to_string(env: &Env, amount: i128) -> String {
+    let mut digits = [0u8; 40];
+    let mut start = digits.len();
+    let mut remaining = amount.unsigned_abs();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+    if amount < 0 {
+        start -= 1;
+        digits[start] = b'-';
+    }
+    String::from_bytes(env, &digits[start..])
+}
@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+// 2023-07-01, 2024-01-01, 2024-07-01 and 2025-01-01 00:00 UTC
+const MID_2023: u64 = 1_688_169_600;
+const START_2024: u64 = 1_704_067_200;
+const MID_2024: u64 = 1_719_792_000;
+const START_2025: u64 = 1_735_689_600;
+
+fn setup(env: &Env) -> (VerificationRegistryClient<'_>, Address) {
+    let contract_id = env.register(VerificationRegistry, ());
+    let client = VerificationRegistryClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let verifier = Address::generate(env);
+    client.authorize_verifier(&verifier);
+    (client, verifier)
+}
+
+#[test]
+fn test_initialize_and_verifier_auth() {
+    let env = Env::default();
+    let (client, verifier) = setup(&env);
+
+    assert!(client.is_verifier(&verifier));
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+
+    client.revoke_verifier(&verifier);
+    assert!(!client.is_verifier(&verifier));
+    let result = client.try_submit_attestation(
+        &verifier,
+        &String::from_str(&env, "PROJ-1"),
+        &(MID_2023, MID_2024),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &100,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
+#[test]
+fn test_attestation_marks_overlapping_vintages() {
+    let env = Env::default();
+    let (client, verifier) = setup(&env);
+    let project = String::from_str(&env, "PROJ-1");
+    let other = String::from_str(&env, "PROJ-2");
+
+    let attestation_id = client.submit_attestation(
+        &verifier,
+        &project,
+        &(MID_2023, MID_2024),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &1_500,
+    );
+    assert_eq!(attestation_id, 1);
+    assert!(client.is_verified(&project, &2023));
+    assert!(client.is_verified(&project, &2024));
+    assert!(!client.is_verified(&project, &2022));
+    assert!(!client.is_verified(&project, &2025));
+    assert!(!client.is_verified(&other, &2024));
+
+    // The period end is exclusive: ending on Jan 1 does not verify that year
+    client.submit_attestation(
+        &verifier,
+        &other,
+        &(MID_2023, START_2024),
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &200,
+    );
+    assert!(client.is_verified(&other, &2023));
+    assert!(!client.is_verified(&other, &2024));
+    assert_eq!(client.get_verified_amount(&other, &2023), 200);
+
+    let attestation = client.get_attestation(&attestation_id).unwrap();
+    assert_eq!(attestation.verifier, verifier);
+    assert_eq!(attestation.period_start, MID_2023);
+    assert_eq!(attestation.period_end, MID_2024);
+    assert_eq!(attestation.verified_amount, 1_500);
+    assert_eq!(client.get_attestations_by_project(&project).len(), 1);
+    assert_eq!(client.get_attestations_by_project(&other).len(), 1);
+}
+
+#[test]
+fn test_attestation_validation() {
+    let env = Env::default();
+    let (client, verifier) = setup(&env);
+    let project = String::from_str(&env, "PROJ-1");
+    let report_hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let result =
+        client.try_submit_attestation(&verifier, &project, &(MID_2024, MID_2024), &report_hash, &1);
+    assert_eq!(result, Err(Ok(ContractError::InvalidPeriod)));
+
+    let result =
+        client.try_submit_attestation(&verifier, &project, &(MID_2023, MID_2024), &report_hash, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+
+    let decade = MID_2023 + MAX_PERIOD_YEARS * 366 * 86_400;
+    let result =
+        client.try_submit_attestation(&verifier, &project, &(MID_2023, decade), &report_hash, &1);
+    assert_eq!(result, Err(Ok(ContractError::PeriodTooLong)));
+
+    assert!(client.get_attestations_by_project(&project).is_empty());
+    assert!(!client.is_verified(&project, &2023));
+}
+
+#[test]
+fn test_verified_amount_split_across_vintages() {
+    let env = Env::default();
+    let (client, verifier) = setup(&env);
+    let project = String::from_str(&env, "PROJ-1");
+
+    // 184 of the 366 days fall in 2023, the remaining 182 in 2024
+    client.submit_attestation(
+        &verifier,
+        &project,
+        &(MID_2023, MID_2024),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &3_660,
+    );
+    assert_eq!(client.get_verified_amount(&project, &2023), 1_840);
+    assert_eq!(client.get_verified_amount(&project, &2024), 1_820);
+
+    // Attestations for the same vintage accumulate; rounding never loses units
+    client.submit_attestation(
+        &verifier,
+        &project,
+        &(START_2024, MID_2024 + 86_400),
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &7,
+    );
+    assert_eq!(client.get_verified_amount(&project, &2024), 1_827);
+    assert_eq!(client.get_verified_amount(&project, &2025), 0);
+
+    client.submit_attestation(
+        &verifier,
+        &project,
+        &(START_2025 - 86_400, START_2025 + 86_400),
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &3,
+    );
+    assert_eq!(client.get_verified_amount(&project, &2024), 1_828);
+    assert_eq!(client.get_verified_amount(&project, &2025), 2);
+}
+
+#[test]
+fn test_year_start_matches_year_of() {
+    for year in [1971u64, 2000, 2023, 2024, 2025, 2100] {
+        assert_eq!(year_of(year_start(year)), year);
+        assert_eq!(year_of(year_start(year) - 1), year - 1);
+    }
+    assert_eq!(year_start(1970), 0);
+    assert_eq!(year_start(2024), START_2024);
+    assert_eq!(year_start(2025), START_2025);
+}
+
+#[test]
+fn test_year_of_calendar_boundaries() {
+    assert_eq!(year_of(0), 1970);
+    assert_eq!(year_of(951_782_400), 2000); // 2000-02-29
+    assert_eq!(year_of(START_2024 - 1), 2023);
+    assert_eq!(year_of(START_2024), 2024);
+    assert_eq!(year_of(1_709_164_800), 2024); // 2024-02-29
+    assert_eq!(year_of(START_2025 - 1), 2024);
+    assert_eq!(year_of(START_2025), 2025);
+}
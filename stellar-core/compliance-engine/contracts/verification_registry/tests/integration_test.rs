@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use audit_trail::{AuditTrailContract, AuditTrailContractClient, DEFAULT_RETENTION_POLICY};
+use carbon_asset::errors::ContractError as AssetError;
+use carbon_asset::types::{AssetStatus, CarbonAssetMetadata, StatusReason};
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+use verification_registry::{VerificationRegistry, VerificationRegistryClient};
+
+// 2024-01-01 and 2025-01-01 00:00 UTC
+const START_2024: u64 = 1_704_067_200;
+const START_2025: u64 = 1_735_689_600;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    verifier: Address,
+    asset: CarbonAssetClient<'a>,
+    registry: VerificationRegistryClient<'a>,
+    audit: AuditTrailContractClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let asset_id = env.register(CarbonAsset, ());
+    let registry_id = env.register(VerificationRegistry, ());
+    let audit_id = env.register(AuditTrailContract, ());
+    let asset = CarbonAssetClient::new(&env, &asset_id);
+    let registry = VerificationRegistryClient::new(&env, &registry_id);
+    let audit = AuditTrailContractClient::new(&env, &audit_id);
+
+    asset.initialize(
+        &admin,
+        &String::from_str(&env, "Carbon Asset"),
+        &String::from_str(&env, "C01"),
+        &Address::generate(&env),
+        &String::from_str(&env, "US"),
+    );
+    audit.initialize(&admin, &DEFAULT_RETENTION_POLICY);
+    audit.authorize_emitter(&registry_id);
+
+    registry.initialize(&admin);
+    registry.authorize_verifier(&verifier);
+    registry.set_audit_trail(&audit_id);
+
+    Setup {
+        env,
+        admin,
+        verifier,
+        asset,
+        registry,
+        audit,
+    }
+}
+
+// Mints before the registry is wired in, as for credits issued before
+// attestations were required, then locks the token pending verification.
+fn mint_locked(s: &Setup, project_id: &str, vintage_year: u64) -> u32 {
+    let owner = Address::generate(&s.env);
+    let metadata = CarbonAssetMetadata {
+        project_id: String::from_str(&s.env, project_id),
+        vintage_year,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&s.env, &[7u8; 32]),
+    };
+    let token_id = s.asset.mint(&s.admin, &owner, &metadata);
+    s.asset
+        .set_verification_registry(&s.admin, &s.registry.address);
+    s.asset.set_status(
+        &s.admin,
+        &token_id,
+        &AssetStatus::Locked,
+        &StatusReason::PendingVerification,
+        &None,
+    );
+    token_id
+}
+
+#[test]
+fn test_attestation_unlocks_locked_token() {
+    let s = setup();
+    let token_id = mint_locked(&s, "PROJ-1", 2024);
+    let project = String::from_str(&s.env, "PROJ-1");
+
+    assert_eq!(
+        s.asset.try_unlock_verified(&token_id),
+        Err(Ok(AssetError::NotVerified))
+    );
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Locked);
+
+    s.registry.submit_attestation(
+        &s.verifier,
+        &project,
+        &(START_2024, START_2025),
+        &BytesN::from_array(&s.env, &[9u8; 32]),
+        &1_000,
+    );
+    assert!(s.registry.is_verified(&project, &2024));
+
+    s.asset.unlock_verified(&token_id);
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Issued);
+    assert_eq!(
+        s.asset.try_unlock_verified(&token_id),
+        Err(Ok(AssetError::InvalidStatusTransition))
+    );
+
+    let audit_events = s.audit.get_events_by_entity(&project);
+    assert_eq!(audit_events.len(), 1);
+    let event = audit_events.get(0).unwrap();
+    assert_eq!(
+        event.event_type,
+        String::from_str(&s.env, "VERIFICATION_ATTESTED")
+    );
+    assert_eq!(event.emitting_contract, s.registry.address);
+    assert_eq!(event.event_data, String::from_str(&s.env, "1000"));
+}
+
+#[test]
+fn test_attestation_for_other_vintage_keeps_token_locked() {
+    let s = setup();
+    let token_id = mint_locked(&s, "PROJ-1", 2025);
+
+    s.registry.submit_attestation(
+        &s.verifier,
+        &String::from_str(&s.env, "PROJ-1"),
+        &(START_2024, START_2025),
+        &BytesN::from_array(&s.env, &[9u8; 32]),
+        &1_000,
+    );
+
+    assert_eq!(
+        s.asset.try_unlock_verified(&token_id),
+        Err(Ok(AssetError::NotVerified))
+    );
+    assert_eq!(s.asset.get_status(&token_id), AssetStatus::Locked);
+}
+
+#[test]
+fn test_attestation_without_audit_trail_configured() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let registry_id = env.register(VerificationRegistry, ());
+    let registry = VerificationRegistryClient::new(&env, &registry_id);
+    let verifier = Address::generate(&env);
+    registry.initialize(&Address::generate(&env));
+    registry.authorize_verifier(&verifier);
+
+    assert!(registry.get_audit_trail().is_none());
+    let attestation_id = registry.submit_attestation(
+        &verifier,
+        &String::from_str(&env, "PROJ-1"),
+        &(START_2024, START_2025),
+        &BytesN::from_array(&env, &[9u8; 32]),
+        &1_000,
+    );
+    assert!(registry.get_attestation(&attestation_id).is_some());
+}
+
+#[test]
+fn test_mint_requires_attested_supply() {
+    let s = setup();
+    s.asset
+        .set_verification_registry(&s.admin, &s.registry.address);
+    let owner = Address::generate(&s.env);
+    let metadata = CarbonAssetMetadata {
+        project_id: String::from_str(&s.env, "PROJ-1"),
+        vintage_year: 2024,
+        methodology_id: 1,
+        geo_hash: BytesN::from_array(&s.env, &[7u8; 32]),
+    };
+
+    assert_eq!(
+        s.asset.try_mint(&s.admin, &owner, &metadata),
+        Err(Ok(AssetError::NotVerified))
+    );
+
+    s.registry.submit_attestation(
+        &s.verifier,
+        &metadata.project_id,
+        &(START_2024, START_2025),
+        &BytesN::from_array(&s.env, &[9u8; 32]),
+        &2,
+    );
+    s.asset
+        .mint_batch(&s.admin, &owner, &metadata, &2, &100, &2);
+    assert_eq!(
+        s.asset
+            .get_minted_supply(&metadata.project_id, &metadata.vintage_year),
+        2
+    );
+    assert_eq!(
+        s.asset.try_mint(&s.admin, &owner, &metadata),
+        Err(Ok(AssetError::ExceedsVerifiedAmount))
+    );
+}
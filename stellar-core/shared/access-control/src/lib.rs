@@ -0,0 +1,130 @@
+#![no_std]
+
+// Admin access control shared by the CarbonScribe contracts.
+//
+// The admin and the pending admin live in instance storage under `Admin` and
+// `PendingAdmin`. A unit `#[contracttype]` variant is stored as its name
+// only, so contracts that kept their admin under their own `DataKey::Admin`
+// read the same entry after switching to this module.
+//
+// Handing over the admin role takes two steps: the current admin proposes a
+// new admin with `propose_admin`, and the role only moves once that account
+// calls `accept_admin`. A mistyped address therefore can't lock a contract.
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, Env};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum AccessError {
+    NotInitialized = 1,
+    NotAuthorized = 2,
+    NoPendingAdmin = 3,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum AccessKey {
+    Admin,
+    PendingAdmin,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposedEvent {
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferredEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&AccessKey::Admin)
+}
+
+// Store `admin` without any authorization check. Intended for `initialize`;
+// later changes should go through `propose_admin` and `accept_admin`.
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&AccessKey::Admin, admin);
+}
+
+pub fn get_admin(env: &Env) -> Result<Address, AccessError> {
+    env.storage()
+        .instance()
+        .get(&AccessKey::Admin)
+        .ok_or(AccessError::NotInitialized)
+}
+
+// For entry points that take the acting account as `caller`: `caller` must
+// authorize the call and be the admin.
+pub fn require_admin(env: &Env, caller: &Address) -> Result<(), AccessError> {
+    caller.require_auth();
+    let admin = get_admin(env)?;
+    if *caller != admin {
+        return Err(AccessError::NotAuthorized);
+    }
+    Ok(())
+}
+
+// For entry points without a `caller` argument: the stored admin must
+// authorize the call. Returns the admin.
+pub fn require_admin_auth(env: &Env) -> Result<Address, AccessError> {
+    let admin = get_admin(env)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&AccessKey::PendingAdmin)
+}
+
+// Nominate `new_admin`, replacing any earlier nomination. The caller must
+// already have been checked with `require_admin` or `require_admin_auth`.
+pub fn propose_admin(env: &Env, new_admin: &Address) -> Result<(), AccessError> {
+    let admin = get_admin(env)?;
+    env.storage()
+        .instance()
+        .set(&AccessKey::PendingAdmin, new_admin);
+
+    AdminProposedEvent {
+        admin,
+        pending_admin: new_admin.clone(),
+    }
+    .publish(env);
+    Ok(())
+}
+
+// Complete a transfer. The nominated account must authorize the call.
+// Returns the new admin.
+pub fn accept_admin(env: &Env) -> Result<Address, AccessError> {
+    let new_admin = get_pending_admin(env).ok_or(AccessError::NoPendingAdmin)?;
+    new_admin.require_auth();
+    let previous_admin = get_admin(env)?;
+
+    set_admin(env, &new_admin);
+    env.storage().instance().remove(&AccessKey::PendingAdmin);
+
+    AdminTransferredEvent {
+        previous_admin,
+        new_admin: new_admin.clone(),
+    }
+    .publish(env);
+    Ok(new_admin)
+}
+
+// Withdraw a pending nomination. As with `propose_admin`, the caller must
+// already have been checked.
+pub fn cancel_admin_transfer(env: &Env) -> Result<(), AccessError> {
+    if get_pending_admin(env).is_none() {
+        return Err(AccessError::NoPendingAdmin);
+    }
+    env.storage().instance().remove(&AccessKey::PendingAdmin);
+    Ok(())
+}
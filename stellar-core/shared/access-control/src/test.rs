@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, Events as _};
+use soroban_sdk::{contract, contractimpl, Symbol};
+
+// Exposes the module through a contract so calls run with real auth checks.
+#[contract]
+struct AccessContract;
+
+#[contractimpl]
+impl AccessContract {
+    pub fn init(env: Env, admin: Address) {
+        set_admin(&env, &admin);
+    }
+
+    pub fn admin(env: Env) -> Result<Address, AccessError> {
+        get_admin(&env)
+    }
+
+    pub fn pending(env: Env) -> Option<Address> {
+        get_pending_admin(&env)
+    }
+
+    pub fn guarded(env: Env, caller: Address) -> Result<(), AccessError> {
+        require_admin(&env, &caller)
+    }
+
+    pub fn guarded_auth(env: Env) -> Result<Address, AccessError> {
+        require_admin_auth(&env)
+    }
+
+    pub fn propose(env: Env, caller: Address, new_admin: Address) -> Result<(), AccessError> {
+        require_admin(&env, &caller)?;
+        propose_admin(&env, &new_admin)
+    }
+
+    pub fn accept(env: Env) -> Result<Address, AccessError> {
+        accept_admin(&env)
+    }
+
+    pub fn cancel(env: Env, caller: Address) -> Result<(), AccessError> {
+        require_admin(&env, &caller)?;
+        cancel_admin_transfer(&env)
+    }
+}
+
+fn setup<'a>() -> (Env, Address, AccessContractClient<'a>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = AccessContractClient::new(&env, &env.register(AccessContract, ()));
+    client.init(&admin);
+    (env, admin, client)
+}
+
+#[test]
+fn test_not_initialized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = AccessContractClient::new(&env, &env.register(AccessContract, ()));
+    let caller = Address::generate(&env);
+
+    assert_eq!(client.try_admin(), Err(Ok(AccessError::NotInitialized)));
+    assert_eq!(
+        client.try_guarded(&caller),
+        Err(Ok(AccessError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_guarded_auth(),
+        Err(Ok(AccessError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_require_admin() {
+    let (env, admin, client) = setup();
+    assert_eq!(client.admin(), admin);
+
+    client.guarded(&admin);
+    assert_eq!(
+        client.try_guarded(&Address::generate(&env)),
+        Err(Ok(AccessError::NotAuthorized))
+    );
+
+    assert_eq!(client.guarded_auth(), admin);
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, admin);
+    match &auths[0].1.function {
+        AuthorizedFunction::Contract((contract, function, _)) => {
+            assert_eq!(*contract, client.address);
+            assert_eq!(*function, Symbol::new(&env, "guarded_auth"));
+        }
+        _ => panic!("unexpected authorized function"),
+    }
+}
+
+#[test]
+fn test_two_step_transfer() {
+    let (env, admin, client) = setup();
+    let new_admin = Address::generate(&env);
+
+    client.propose(&admin, &new_admin);
+    assert_eq!(env.events().all().events().len(), 1);
+    assert_eq!(client.pending(), Some(new_admin.clone()));
+    // The current admin keeps the role until the transfer is accepted
+    assert_eq!(client.admin(), admin);
+    assert_eq!(
+        client.try_propose(&new_admin, &new_admin),
+        Err(Ok(AccessError::NotAuthorized))
+    );
+
+    assert_eq!(client.accept(), new_admin);
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(env.events().all().events().len(), 1);
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.pending(), None);
+
+    assert_eq!(
+        client.try_guarded(&admin),
+        Err(Ok(AccessError::NotAuthorized))
+    );
+    client.guarded(&new_admin);
+}
+
+#[test]
+fn test_cancel_transfer() {
+    let (env, admin, client) = setup();
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(client.try_accept(), Err(Ok(AccessError::NoPendingAdmin)));
+    assert_eq!(
+        client.try_cancel(&admin),
+        Err(Ok(AccessError::NoPendingAdmin))
+    );
+
+    client.propose(&admin, &new_admin);
+    client.cancel(&admin);
+    assert_eq!(client.pending(), None);
+    assert_eq!(client.try_accept(), Err(Ok(AccessError::NoPendingAdmin)));
+    assert_eq!(client.admin(), admin);
+}